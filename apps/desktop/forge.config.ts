@@ -452,6 +452,8 @@ const config: ForgeConfig = {
       }`,
       "./models",
       "./assets",
+      // Sample audio for model benchmarks
+      "../../packages/whisper-wrapper/whisper.cpp/samples/jfk.wav",
     ],
     extendInfo: {
       NSMicrophoneUsageDescription:
@@ -0,0 +1,31 @@
+/**
+ * Context windows (input tokens) for providers whose list-models endpoint
+ * doesn't report them. First match wins, so specific patterns come first.
+ */
+const KNOWN_CONTEXT_WINDOWS: [RegExp, number][] = [
+  // OpenAI
+  [/^gpt-5/, 400_000],
+  [/^gpt-4\.1/, 1_047_576],
+  [/^(gpt-4o|chatgpt-4o)/, 128_000],
+  [/^gpt-4-turbo/, 128_000],
+  [/^gpt-4-32k/, 32_768],
+  [/^gpt-4/, 8_192],
+  [/^gpt-3\.5-turbo/, 16_385],
+  [/^o1-mini/, 128_000],
+  [/^o[1-9]/, 200_000],
+  // Anthropic
+  [/^claude-/, 200_000],
+];
+
+export function getKnownContextWindow(modelId: string): number | undefined {
+  return KNOWN_CONTEXT_WINDOWS.find(([pattern]) => pattern.test(modelId))?.[1];
+}
+
+// e.g. 128000 -> "128k", 1048576 -> "1M"
+export function formatContextWindow(tokens: number | undefined): string {
+  if (!tokens) return "Unknown";
+  if (tokens >= 1_000_000) {
+    return `${Math.round(tokens / 100_000) / 10}M`;
+  }
+  return `${Math.floor(tokens / 1000)}k`;
+}
@@ -0,0 +1,83 @@
+// Published list prices in USD, used only to estimate spend in the usage
+// summary. Unknown models are recorded with a null cost rather than guessed.
+
+// Per 1M tokens. Matched by longest prefix of the model id, so dated
+// snapshots ("gpt-4o-mini-2024-07-18") resolve to their family.
+const TOKEN_PRICES: Record<string, { input: number; output: number }> = {
+  "gpt-4o-mini": { input: 0.15, output: 0.6 },
+  "gpt-4o": { input: 2.5, output: 10 },
+  "gpt-4.1-nano": { input: 0.1, output: 0.4 },
+  "gpt-4.1-mini": { input: 0.4, output: 1.6 },
+  "gpt-4.1": { input: 2, output: 8 },
+  "gpt-5-nano": { input: 0.05, output: 0.4 },
+  "gpt-5-mini": { input: 0.25, output: 2 },
+  "gpt-5": { input: 1.25, output: 10 },
+  "claude-3-haiku": { input: 0.25, output: 1.25 },
+  "claude-3-5-haiku": { input: 0.8, output: 4 },
+  "claude-haiku-4-5": { input: 1, output: 5 },
+  "claude-3-5-sonnet": { input: 3, output: 15 },
+  "claude-3-7-sonnet": { input: 3, output: 15 },
+  "claude-sonnet-4": { input: 3, output: 15 },
+  "claude-opus-4": { input: 15, output: 75 },
+  "gemini-1.5-flash": { input: 0.075, output: 0.3 },
+  "gemini-2.0-flash-lite": { input: 0.075, output: 0.3 },
+  "gemini-2.0-flash": { input: 0.1, output: 0.4 },
+  "gemini-2.5-flash-lite": { input: 0.1, output: 0.4 },
+  "gemini-2.5-flash": { input: 0.3, output: 2.5 },
+  "gemini-2.5-pro": { input: 1.25, output: 10 },
+  "text-embedding-3-small": { input: 0.02, output: 0 },
+  "text-embedding-3-large": { input: 0.13, output: 0 },
+  "text-embedding-ada-002": { input: 0.1, output: 0 },
+};
+
+// Per minute of audio, keyed by "<provider>:<apiModelId>"
+const AUDIO_PRICES: Record<string, number> = {
+  "openai:whisper-1": 0.006,
+  "openai:gpt-4o-transcribe": 0.006,
+  "openai:gpt-4o-mini-transcribe": 0.003,
+  "groq:whisper-large-v3": 0.111 / 60,
+  "groq:whisper-large-v3-turbo": 0.04 / 60,
+  "groq:distil-whisper-large-v3-en": 0.02 / 60,
+};
+
+// Providers that run on the user's machine
+const LOCAL_PROVIDERS = new Set(["ollama"]);
+
+/**
+ * Estimated cost of a language model call, or null when the model's price
+ * is unknown. OpenRouter ids ("openai/gpt-4o-mini") are matched without
+ * their vendor prefix.
+ */
+export function estimateTokenCost(
+  provider: string,
+  model: string,
+  promptTokens: number,
+  completionTokens: number,
+): number | null {
+  if (LOCAL_PROVIDERS.has(provider)) {
+    return 0;
+  }
+  const id = model.toLowerCase().replace(/^[^/]+\//, "");
+  const key = Object.keys(TOKEN_PRICES)
+    .filter((prefix) => id.startsWith(prefix))
+    .sort((a, b) => b.length - a.length)[0];
+  if (!key) {
+    return null;
+  }
+  const price = TOKEN_PRICES[key];
+  return (
+    (promptTokens * price.input + completionTokens * price.output) / 1_000_000
+  );
+}
+
+/**
+ * Estimated cost of a cloud transcription request, or null when unknown
+ */
+export function estimateAudioCost(
+  provider: string,
+  model: string,
+  audioSeconds: number,
+): number | null {
+  const perMinute = AUDIO_PRICES[`${provider}:${model}`];
+  return perMinute === undefined ? null : (audioSeconds / 60) * perMinute;
+}
@@ -14,14 +14,26 @@ interface BaseSpeechModel {
   providerIcon: string;
 }
 
+// Engine that runs a local model
+export type LocalRuntime = "whisper" | "parakeet" | "moonshine";
+
+// One file of a model that is downloaded as a folder
+export interface ModelFile {
+  path: string; // Relative to the model folder
+  url: string;
+  size: number; // Approximate size in bytes
+}
+
 // ─── Offline Whisper model (local - requires download) ───
 export interface OfflineWhisperModel extends BaseSpeechModel {
   setup: "offline";
+  runtime?: LocalRuntime; // Defaults to "whisper"
   size: number; // Approximate size in bytes (for UI display only)
   sizeFormatted: string; // Human readable size (e.g., "~39 MB")
   modelSize: string;
   downloadUrl: string;
-  filename: string; // Expected filename after download
+  filename: string; // Expected filename (folder name when `files` is set)
+  files?: ModelFile[]; // Multi-file models, downloaded into `filename`
   checksum?: string; // Optional checksum for validation
 }
 
@@ -41,17 +53,44 @@ export type AvailableSpeechModel =
 export interface DownloadProgress {
   modelId: string;
   progress: number; // 0-100
-  status: "downloading" | "paused" | "cancelling" | "error";
+  status: "queued" | "downloading" | "cancelling";
   bytesDownloaded: number;
   totalBytes: number;
-  error?: string;
-  abortController?: AbortController;
 }
 
-export interface ModelManagerState {
-  activeDownloads: Map<string, DownloadProgress>;
+export interface ModelUpdate {
+  modelId: string;
+  name: string;
+  // "release": this app version ships a new file for a built-in model;
+  // "hub": the file changed in its Hugging Face repository
+  source: "release" | "hub";
+  sizeBytes: number; // Size of the new file
+}
+
+export interface ModeReference {
+  id: string;
+  name: string;
+}
+
+// Why a model wasn't deleted without `force`
+export interface ModelInUseWarning {
+  modelId: string;
+  isDefault: boolean; // It's the selected speech model
+  modes: ModeReference[]; // Modes that use it as their speech model
 }
 
+// A downloaded model that hasn't transcribed anything for a while
+export interface UnusedModel {
+  modelId: string;
+  name: string;
+  lastUsedAt: Date | null; // null if never used since download
+  reclaimableBytes: number; // 0 for files imported in place
+}
+
+export type DeleteModelResult =
+  | { deleted: true; repointedModes: ModeReference[] }
+  | { deleted: false; warning: ModelInUseWarning };
+
 export const AVAILABLE_MODELS: AvailableSpeechModel[] = [
   // ─── Offline Whisper models ───
   {
@@ -248,6 +287,122 @@ export const AVAILABLE_MODELS: AvailableSpeechModel[] = [
     providerIcon: "/icons/models/pc.svg",
   },
 
+  // ─── Offline ONNX models ───
+  {
+    id: "parakeet-tdt-0.6b-v2",
+    name: "Parakeet TDT 0.6B v2",
+    type: "other",
+    runtime: "parakeet",
+    description: "NVIDIA's fast and very accurate English model.",
+    filename: "parakeet-tdt-0.6b-v2",
+    downloadUrl: "https://huggingface.co/istupakov/parakeet-tdt-0.6b-v2-onnx",
+    files: [
+      {
+        path: "nemo128.onnx",
+        url:
+          "https://huggingface.co/istupakov/parakeet-tdt-0.6b-v2-onnx/resolve/main/nemo128.onnx",
+        size: 140 * 1024,
+      },
+      {
+        path: "encoder-model.int8.onnx",
+        url:
+          "https://huggingface.co/istupakov/parakeet-tdt-0.6b-v2-onnx/resolve/main/encoder-model.int8.onnx",
+        size: 652 * 1024 * 1024,
+      },
+      {
+        path: "decoder_joint-model.int8.onnx",
+        url:
+          "https://huggingface.co/istupakov/parakeet-tdt-0.6b-v2-onnx/resolve/main/decoder_joint-model.int8.onnx",
+        size: 9 * 1024 * 1024,
+      },
+      {
+        path: "vocab.txt",
+        url:
+          "https://huggingface.co/istupakov/parakeet-tdt-0.6b-v2-onnx/resolve/main/vocab.txt",
+        size: 9 * 1024,
+      },
+    ],
+    size: 661 * 1024 * 1024,
+    sizeFormatted: "~661 MB",
+    modelSize: "~661 MB",
+    features: [
+      {
+        icon: "rocket",
+        tooltip: "Much faster than Whisper at similar accuracy",
+      },
+      {
+        icon: "award",
+        tooltip: "High accuracy with punctuation",
+      },
+      {
+        icon: "languages",
+        tooltip: "English only",
+      },
+    ],
+    speed: 4.5,
+    accuracy: 4.6,
+    setup: "offline",
+    provider: "NVIDIA",
+    providerIcon: "/icons/models/pc.svg",
+  },
+  {
+    id: "moonshine-base",
+    name: "Moonshine Base",
+    type: "other",
+    runtime: "moonshine",
+    description: "Small English model built for low-latency dictation.",
+    filename: "moonshine-base",
+    downloadUrl: "https://huggingface.co/onnx-community/moonshine-base-ONNX",
+    files: [
+      {
+        path: "onnx/encoder_model.onnx",
+        url:
+          "https://huggingface.co/onnx-community/moonshine-base-ONNX/resolve/main/onnx/encoder_model.onnx",
+        size: 77 * 1024 * 1024,
+      },
+      {
+        path: "onnx/decoder_model_merged.onnx",
+        url:
+          "https://huggingface.co/onnx-community/moonshine-base-ONNX/resolve/main/onnx/decoder_model_merged.onnx",
+        size: 159 * 1024 * 1024,
+      },
+      {
+        path: "tokenizer.json",
+        url:
+          "https://huggingface.co/onnx-community/moonshine-base-ONNX/resolve/main/tokenizer.json",
+        size: 3.6 * 1024 * 1024,
+      },
+      {
+        path: "config.json",
+        url:
+          "https://huggingface.co/onnx-community/moonshine-base-ONNX/resolve/main/config.json",
+        size: 2 * 1024,
+      },
+    ],
+    size: 240 * 1024 * 1024,
+    sizeFormatted: "~240 MB",
+    modelSize: "~240 MB",
+    features: [
+      {
+        icon: "rabbit",
+        tooltip: "Fast even on older CPUs",
+      },
+      {
+        icon: "scale",
+        tooltip: "Small download",
+      },
+      {
+        icon: "languages",
+        tooltip: "English only",
+      },
+    ],
+    speed: 4.8,
+    accuracy: 3.8,
+    setup: "offline",
+    provider: "Useful Sensors",
+    providerIcon: "/icons/models/pc.svg",
+  },
+
   // ─── OpenAI API models ───
   {
     id: "openai-whisper-1",
@@ -463,3 +618,9 @@ export const AVAILABLE_MODELS: AvailableSpeechModel[] = [
     providerIcon: "/icons/models/grok_dark.svg",
   },
 ];
+
+// Models imported from Hugging Face or a file are always whisper.cpp models
+export function getLocalRuntime(modelId: string): LocalRuntime {
+  const model = AVAILABLE_MODELS.find((m) => m.id === modelId);
+  return (model?.setup === "offline" && model.runtime) || "whisper";
+}
@@ -0,0 +1,38 @@
+import type { AvailableSpeechModel } from "./models";
+
+export type PerformancePreset = "fast" | "balanced" | "accurate";
+
+export interface PerformanceProfile {
+  // Score used to pick among downloaded local models (higher wins)
+  rankSpeechModel: (model: AvailableSpeechModel) => number;
+  // VAD probability above which a frame counts as speech; higher is more
+  // aggressive about dropping quiet audio
+  speechThreshold: number;
+  // Silence after speech that closes a chunk; shorter chunks mean less
+  // audio left to transcribe when recording stops
+  maxSilenceMs: number;
+  // false forces LLM formatting off regardless of mode settings
+  formattingAllowed: boolean;
+}
+
+export const PERFORMANCE_PROFILES: Record<PerformancePreset, PerformanceProfile> =
+  {
+    fast: {
+      rankSpeechModel: (model) => model.speed,
+      speechThreshold: 0.3,
+      maxSilenceMs: 1500,
+      formattingAllowed: false,
+    },
+    balanced: {
+      rankSpeechModel: (model) => model.speed + model.accuracy,
+      speechThreshold: 0.2,
+      maxSilenceMs: 3000,
+      formattingAllowed: true,
+    },
+    accurate: {
+      rankSpeechModel: (model) => model.accuracy,
+      speechThreshold: 0.1,
+      maxSilenceMs: 5000,
+      formattingAllowed: true,
+    },
+  };
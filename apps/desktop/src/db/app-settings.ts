@@ -13,7 +13,7 @@
  */
 
 import { eq } from "drizzle-orm";
-import { db } from ".";
+import { db, withTransaction } from ".";
 import {
   appSettings,
   type NewAppSettings,
@@ -21,10 +21,29 @@ import {
   type ModeConfig,
 } from "./schema";
 import { isMacOS } from "../utils/platform";
+import { logger } from "../main/logger";
 
-// Current baseline settings schema version
-// Bump this when adding migrations to migrateSettingsData()
-const CURRENT_SETTINGS_VERSION = 2;
+type SettingsMigration = (data: AppSettingsData) => AppSettingsData;
+
+// Data migrations for the JSON settings blob, oldest first. Entry i upgrades
+// version i + 1 to i + 2; append an entry when renaming or restructuring a
+// field so existing values are carried over instead of lost.
+const SETTINGS_MIGRATIONS: SettingsMigration[] = [
+  // v1 → v2: Add cycleMode default to existing shortcuts
+  (data) =>
+    data.shortcuts && data.shortcuts.cycleMode === undefined
+      ? {
+          ...data,
+          shortcuts: {
+            ...data.shortcuts,
+            cycleMode: getDefaultShortcuts().cycleMode,
+          },
+        }
+      : data,
+];
+
+// Current settings schema version, follows from the migration chain
+const CURRENT_SETTINGS_VERSION = SETTINGS_MIGRATIONS.length + 1;
 
 function createDefaultMode(
   settings: Pick<AppSettingsData, "dictation" | "formatterConfig"> = {},
@@ -126,25 +145,35 @@ export async function getAppSettings(): Promise<AppSettingsData> {
 export async function updateAppSettings(
   newSettings: Partial<AppSettingsData>,
 ): Promise<AppSettingsData> {
-  const currentSettings = await getAppSettings();
+  // Make sure the row exists before reading it back inside the transaction
+  await getAppSettings();
 
-  // Simple shallow merge - each top-level section is replaced entirely
-  const mergedSettings: AppSettingsData = {
-    ...currentSettings,
-    ...newSettings,
-  };
+  // Read and write in one transaction so concurrent updates to different
+  // sections don't overwrite each other
+  return await withTransaction(async (tx) => {
+    const [record] = await tx
+      .select()
+      .from(appSettings)
+      .where(eq(appSettings.id, SETTINGS_ID));
 
-  const now = new Date();
+    // Simple shallow merge - each top-level section is replaced entirely
+    const mergedSettings: AppSettingsData = {
+      ...(record?.data ?? defaultSettings),
+      ...newSettings,
+    };
 
-  await db
-    .update(appSettings)
-    .set({
-      data: mergedSettings,
-      updatedAt: now,
-    })
-    .where(eq(appSettings.id, SETTINGS_ID));
+    const now = new Date();
+
+    await tx
+      .update(appSettings)
+      .set({
+        data: mergedSettings,
+        updatedAt: now,
+      })
+      .where(eq(appSettings.id, SETTINGS_ID));
 
-  return mergedSettings;
+    return mergedSettings;
+  });
 }
 
 // Replace all app settings (complete override)
@@ -208,6 +237,8 @@ async function createDefaultSettings(): Promise<void> {
  * Run data migrations on the JSON settings blob.
  * Called once at startup after DB schema migrations complete.
  * Uses the `version` column to track which migrations have been applied.
+ * A failing step stops the chain at the last good version rather than
+ * discarding the user's settings; it's retried on the next launch.
  */
 export async function migrateSettingsData(): Promise<void> {
   const result = await db
@@ -218,26 +249,39 @@ export async function migrateSettingsData(): Promise<void> {
   if (result.length === 0) return; // No record yet; defaults will be used
 
   const record = result[0];
-  const version = record.version ?? 1;
+  const startVersion = record.version ?? 1;
 
-  if (version >= CURRENT_SETTINGS_VERSION) return; // Already up to date
-
-  const data = { ...record.data };
+  if (startVersion >= CURRENT_SETTINGS_VERSION) {
+    if (startVersion > CURRENT_SETTINGS_VERSION) {
+      // Written by a newer app version; leave it for that version
+      logger.db.warn("Settings are newer than this app version", {
+        version: startVersion,
+        supported: CURRENT_SETTINGS_VERSION,
+      });
+    }
+    return; // Already up to date
+  }
 
-  // v1 → v2: Add cycleMode default to existing shortcuts
-  if (version < 2) {
-    if (data.shortcuts && data.shortcuts.cycleMode === undefined) {
-      data.shortcuts = {
-        ...data.shortcuts,
-        cycleMode: getDefaultShortcuts().cycleMode,
-      };
+  let data = { ...record.data };
+  let version = startVersion;
+  for (; version < CURRENT_SETTINGS_VERSION; version++) {
+    try {
+      data = SETTINGS_MIGRATIONS[version - 1](data);
+    } catch (error) {
+      logger.db.error("Settings migration failed", {
+        fromVersion: version,
+        error,
+      });
+      break;
     }
   }
+  if (version === startVersion) return;
 
   await db
     .update(appSettings)
-    .set({ data, version: CURRENT_SETTINGS_VERSION, updatedAt: new Date() })
+    .set({ data, version, updatedAt: new Date() })
     .where(eq(appSettings.id, SETTINGS_ID));
+  logger.db.info("Migrated settings", { from: startVersion, to: version });
 }
 
 // Export default settings for reference
@@ -0,0 +1,98 @@
+import { desc, eq, gt, isNull, ne, notInArray, or } from "drizzle-orm";
+import { db, withTransaction } from ".";
+import { transcriptionEmbeddings, transcriptions } from "./schema";
+
+// Stored as raw little-endian float32 bytes
+function toBlob(vector: number[]): Buffer {
+  return Buffer.from(new Float32Array(vector).buffer);
+}
+
+function fromBlob(blob: Buffer): Float32Array {
+  // Copy so the view is 4-byte aligned regardless of the driver's buffer
+  return new Float32Array(new Uint8Array(blob).buffer);
+}
+
+// Transcriptions with no embedding for this model, or edited since indexing
+export async function getTranscriptionsNeedingEmbedding(
+  model: string,
+  limit: number,
+) {
+  return await db
+    .select({
+      id: transcriptions.id,
+      text: transcriptions.text,
+      updatedAt: transcriptions.updatedAt,
+    })
+    .from(transcriptions)
+    .leftJoin(
+      transcriptionEmbeddings,
+      eq(transcriptionEmbeddings.transcriptionId, transcriptions.id),
+    )
+    .where(
+      or(
+        isNull(transcriptionEmbeddings.transcriptionId),
+        ne(transcriptionEmbeddings.model, model),
+        gt(transcriptions.updatedAt, transcriptionEmbeddings.createdAt),
+      ),
+    )
+    .orderBy(desc(transcriptions.timestamp))
+    .limit(limit);
+}
+
+// Insert or replace embeddings. An embedding is never dated before the
+// text it was made from, so a transcription whose updatedAt lies in the
+// future (e.g. after a clock change) isn't picked up again on every pass.
+export async function upsertTranscriptionEmbeddings(
+  rows: {
+    transcriptionId: number;
+    model: string;
+    embedding: number[];
+    textUpdatedAt: Date;
+  }[],
+) {
+  if (rows.length === 0) return;
+  const now = new Date();
+  await withTransaction(async (tx) => {
+    for (const row of rows) {
+      const values = {
+        model: row.model,
+        embedding: toBlob(row.embedding),
+        createdAt: row.textUpdatedAt > now ? row.textUpdatedAt : now,
+      };
+      await tx
+        .insert(transcriptionEmbeddings)
+        .values({ transcriptionId: row.transcriptionId, ...values })
+        .onConflictDoUpdate({
+          target: transcriptionEmbeddings.transcriptionId,
+          set: values,
+        });
+    }
+  });
+}
+
+// All vectors for one model, for a brute-force similarity scan
+export async function getTranscriptionEmbeddings(model: string) {
+  const rows = await db
+    .select({
+      transcriptionId: transcriptionEmbeddings.transcriptionId,
+      embedding: transcriptionEmbeddings.embedding,
+    })
+    .from(transcriptionEmbeddings)
+    .where(eq(transcriptionEmbeddings.model, model));
+  return rows.map((row) => ({
+    transcriptionId: row.transcriptionId,
+    embedding: fromBlob(row.embedding),
+  }));
+}
+
+// Drop vectors whose transcription has been deleted
+export async function deleteOrphanedEmbeddings() {
+  await db
+    .delete(transcriptionEmbeddings)
+    .where(
+      notInArray(
+        transcriptionEmbeddings.transcriptionId,
+        db.select({ id: transcriptions.id }).from(transcriptions),
+      ),
+    );
+}
@@ -0,0 +1,167 @@
+import { createClient } from "@libsql/client";
+import { safeStorage } from "electron";
+import { randomBytes } from "node:crypto";
+import * as fs from "node:fs";
+import { logger } from "../main/logger";
+
+/**
+ * At-rest encryption of the database. libsql encrypts with SQLCipher's
+ * AES-256-CBC format when a key is given; the key itself is stored next to
+ * the database, encrypted with the OS keychain (Electron safeStorage).
+ *
+ * The open connection can't be re-keyed, so turning encryption on or off is
+ * staged and applied on the next launch, before the database is opened.
+ */
+
+type EncryptionChange = "encrypt" | "decrypt";
+
+// Cipher libsql uses for `encryptionKey`
+const CIPHER = "aes256cbc";
+
+export const databaseKeyFile = (dbPath: string) => `${dbPath}.key`;
+const pendingKeyFile = (dbPath: string) => `${dbPath}.key.pending`;
+const pendingChangeFile = (dbPath: string) => `${dbPath}.encryption-pending`;
+
+function readKey(file: string): string {
+  return safeStorage.decryptString(fs.readFileSync(file));
+}
+
+function writeKey(file: string, key: string) {
+  fs.writeFileSync(file, safeStorage.encryptString(key), { mode: 0o600 });
+}
+
+export function isDatabaseEncrypted(dbPath: string): boolean {
+  return fs.existsSync(databaseKeyFile(dbPath));
+}
+
+/**
+ * Key to open the database with, or undefined when it's stored in plain text
+ */
+export function getDatabaseKey(dbPath: string): string | undefined {
+  return isDatabaseEncrypted(dbPath)
+    ? readKey(databaseKeyFile(dbPath))
+    : undefined;
+}
+
+export function getPendingEncryptionChange(
+  dbPath: string,
+): EncryptionChange | null {
+  const file = pendingChangeFile(dbPath);
+  if (!fs.existsSync(file)) return null;
+  const change = fs.readFileSync(file, "utf8").trim();
+  return change === "encrypt" || change === "decrypt" ? change : null;
+}
+
+/**
+ * Stage turning encryption on or off. Returns whether a restart is needed
+ * for it to take effect (false when the database is already in that state).
+ */
+export function setDatabaseEncryption(
+  dbPath: string,
+  enabled: boolean,
+): boolean {
+  fs.rmSync(pendingChangeFile(dbPath), { force: true });
+  fs.rmSync(pendingKeyFile(dbPath), { force: true });
+  if (enabled === isDatabaseEncrypted(dbPath)) {
+    return false;
+  }
+
+  if (enabled) {
+    if (!safeStorage.isEncryptionAvailable()) {
+      throw new Error("The system keychain is not available");
+    }
+    writeKey(pendingKeyFile(dbPath), randomBytes(32).toString("hex"));
+  }
+  fs.writeFileSync(
+    pendingChangeFile(dbPath),
+    enabled ? "encrypt" : "decrypt",
+  );
+  return true;
+}
+
+// Files that belong to the database and must move with it
+function removeDatabaseFiles(file: string) {
+  fs.rmSync(file, { force: true });
+  removeJournalFiles(file);
+}
+
+function removeJournalFiles(file: string) {
+  for (const suffix of ["-wal", "-shm"]) {
+    fs.rmSync(`${file}${suffix}`, { force: true });
+  }
+}
+
+function quote(value: string) {
+  return `'${value.replace(/'/g, "''")}'`;
+}
+
+/**
+ * Apply a staged encryption change by exporting the database into a copy
+ * with the new key and swapping it in. On failure the original database is
+ * left untouched and the change stays pending.
+ */
+export async function applyPendingEncryptionChange(
+  dbPath: string,
+): Promise<void> {
+  const change = getPendingEncryptionChange(dbPath);
+  if (!change) return;
+
+  if (!fs.existsSync(dbPath)) {
+    // Nothing to convert; a new database is created with the new key
+    finishEncryptionChange(dbPath, change);
+    return;
+  }
+
+  const sourceKey = getDatabaseKey(dbPath);
+  const targetKey =
+    change === "encrypt" ? readKey(pendingKeyFile(dbPath)) : undefined;
+  const target = `${dbPath}.converting`;
+  removeDatabaseFiles(target);
+  // The cipher is passed as a URI parameter, which wants forward slashes
+  const targetUri = `file:${target.replace(/\\/g, "/")}?cipher=${CIPHER}`;
+
+  const client = createClient({
+    url: `file:${dbPath}`,
+    encryptionKey: sourceKey,
+  });
+  try {
+    await client.execute("PRAGMA wal_checkpoint(TRUNCATE)");
+    await client.execute(
+      `ATTACH DATABASE ${quote(targetUri)} AS converted KEY ${quote(targetKey ?? "")}`,
+    );
+    await client.execute("SELECT sqlcipher_export('converted')");
+    await client.execute("DETACH DATABASE converted");
+  } catch (error) {
+    removeDatabaseFiles(target);
+    throw error;
+  } finally {
+    client.close();
+  }
+
+  // Replace the database in one step so there's always a complete file at
+  // dbPath; the old journal files only go once the new database is in place
+  fs.renameSync(target, dbPath);
+  removeJournalFiles(dbPath);
+  finishEncryptionChange(dbPath, change);
+  logger.db.info(
+    change === "encrypt" ? "Database encrypted" : "Database decrypted",
+  );
+}
+
+function finishEncryptionChange(dbPath: string, change: EncryptionChange) {
+  if (change === "encrypt") {
+    fs.renameSync(pendingKeyFile(dbPath), databaseKeyFile(dbPath));
+  } else {
+    fs.rmSync(databaseKeyFile(dbPath), { force: true });
+  }
+  fs.rmSync(pendingChangeFile(dbPath), { force: true });
+}
+
+/**
+ * Remove the key and any staged change, e.g. when the database is deleted
+ */
+export function removeDatabaseKeys(dbPath: string) {
+  fs.rmSync(databaseKeyFile(dbPath), { force: true });
+  fs.rmSync(pendingKeyFile(dbPath), { force: true });
+  fs.rmSync(pendingChangeFile(dbPath), { force: true });
+}
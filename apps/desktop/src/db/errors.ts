@@ -0,0 +1,36 @@
+/**
+ * Most recent database error, kept for the diagnostics screen
+ */
+
+export interface DatabaseError {
+  message: string;
+  code?: string; // SQLite result code, e.g. "SQLITE_BUSY"
+  source: string; // tRPC path or background task that hit it
+  at: Date;
+}
+
+let lastError: DatabaseError | null = null;
+
+// libsql errors carry the SQLite result code
+export function isDatabaseError(
+  error: unknown,
+): error is Error & { code: string } {
+  return (
+    error instanceof Error &&
+    typeof (error as { code?: unknown }).code === "string" &&
+    (error as { code: string }).code.startsWith("SQLITE_")
+  );
+}
+
+export function recordDatabaseError(error: unknown, source: string): void {
+  lastError = {
+    message: error instanceof Error ? error.message : String(error),
+    code: isDatabaseError(error) ? error.code : undefined,
+    source,
+    at: new Date(),
+  };
+}
+
+export function getLastDatabaseError(): DatabaseError | null {
+  return lastError;
+}
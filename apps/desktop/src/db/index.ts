@@ -1,20 +1,94 @@
+import type { Client } from "@libsql/client";
 import { drizzle } from "drizzle-orm/libsql";
+import { withReplicas } from "drizzle-orm/sqlite-core";
 import { migrate } from "drizzle-orm/libsql/migrator";
 import { app } from "electron";
 import * as path from "path";
 import * as fs from "fs";
 import * as schema from "./schema";
+import {
+  getCustomDataDirectory,
+  getDataDirectory,
+} from "../utils/data-directory";
+import { applyPendingEncryptionChange, getDatabaseKey } from "./encryption";
+import {
+  checkIntegrity,
+  moveCorruptDatabase,
+  salvageDatabase,
+} from "./recovery";
+
+// Get the data directory for storing the database
+export const dbPath =
+  app.isPackaged || getCustomDataDirectory()
+    ? path.join(getDataDirectory(), "grizzo.db")
+    : path.join(process.cwd(), "grizzo.db");
+
+function connect(encryptionKey?: string) {
+  return drizzle({
+    connection: { url: `file:${dbPath}`, encryptionKey },
+    schema: {
+      ...schema,
+    },
+  });
+}
+
+// Separate connection for plain selects
+let reader: ReturnType<typeof connect> | null = null;
+
+/**
+ * Writes and transactions go through one connection and plain selects
+ * through a second, so reads from the UI aren't queued behind a long write.
+ * In WAL mode the reader sees every committed write.
+ */
+function openDatabase(encryptionKey?: string) {
+  reader = connect(encryptionKey);
+  return withReplicas(connect(encryptionKey), [reader]);
+}
+
+function closeClients() {
+  db.$primary.$client.close();
+  reader?.$client.close();
+  reader = null;
+}
+
+// Opened by initializeDatabase, once the encryption key (if any) can be
+// read from the keychain
+export let db: ReturnType<typeof openDatabase>;
 
-// Get the user data directory for storing the database
-export const dbPath = app.isPackaged
-  ? path.join(app.getPath("userData"), "grizzo.db")
-  : path.join(process.cwd(), "grizzo.db");
+export type Transaction = Parameters<Parameters<typeof db.transaction>[0]>[0];
 
-export const db = drizzle(`file:${dbPath}`, {
-  schema: {
-    ...schema,
-  },
-});
+// How long a statement waits on a lock held by another connection before
+// failing with SQLITE_BUSY
+const BUSY_TIMEOUT_MS = 5000;
+
+// journal_mode is stored in the database file; these are per connection
+const CONNECTION_PRAGMAS = [
+  `PRAGMA busy_timeout = ${BUSY_TIMEOUT_MS}`,
+  "PRAGMA synchronous = NORMAL",
+];
+
+async function configureConnection(client: Client, readOnly = false) {
+  for (const pragma of CONNECTION_PRAGMAS) {
+    await client.execute(pragma);
+  }
+  if (readOnly) {
+    await client.execute("PRAGMA query_only = ON");
+  }
+}
+
+/**
+ * Run related writes atomically. The libsql client hands its connection to
+ * the transaction and lazily opens a new one for other queries, so that one
+ * is configured before the callback runs.
+ */
+export async function withTransaction<T>(
+  fn: (tx: Transaction) => Promise<T>,
+): Promise<T> {
+  return await db.transaction(async (tx) => {
+    await configureConnection(db.$primary.$client);
+    return await fn(tx);
+  });
+}
 
 // Initialize database with migrations
 let isInitialized = false;
@@ -28,6 +102,26 @@ export async function initializeDatabase() {
   }
 
   try {
+    try {
+      await applyPendingEncryptionChange(dbPath);
+    } catch (error) {
+      // The database is left as it was; the change is retried next launch
+      logger.db.error("Failed to apply database encryption change:", error);
+    }
+    const encryptionKey = getDatabaseKey(dbPath);
+    db = openDatabase(encryptionKey);
+
+    // A corrupt database is moved aside and salvaged into a fresh one once
+    // that has been migrated
+    let corrupt: { path: string; problems: string[] } | null = null;
+    const problems = await checkIntegrity(db.$primary.$client);
+    if (problems.length > 0) {
+      logger.db.error("Database integrity check failed", { problems });
+      closeClients();
+      corrupt = { path: moveCorruptDatabase(dbPath), problems };
+      db = openDatabase(encryptionKey);
+    }
+
     // Store the connection for later cleanup
     dbConnection = db;
 
@@ -58,15 +152,41 @@ export async function initializeDatabase() {
       throw new Error(`Journal file not found at: ${journalPath}`);
     }
 
+    // WAL lets readers proceed while a long write is in progress
+    await db.$primary.$client.execute("PRAGMA journal_mode = WAL");
+    await configureConnection(db.$primary.$client);
+    await configureConnection(reader!.$client, true);
+
     // Run migrations to ensure database is up to date
-    await migrate(db, {
+    await migrate(db.$primary, {
       migrationsFolder: migrationsPath,
     });
 
+    if (corrupt) {
+      const recovery = await salvageDatabase(
+        db.$primary.$client,
+        corrupt.path,
+        corrupt.problems,
+        encryptionKey,
+      );
+      logger.db.warn("Recovered data from corrupt database", {
+        corruptPath: recovery.corruptPath,
+        restoredTables: recovery.restoredTables,
+        failedTables: recovery.failedTables,
+      });
+    }
+
     // Run JSON settings data migrations (e.g. backfilling new shortcut defaults)
     const { migrateSettingsData } = await import("./app-settings");
     await migrateSettingsData();
 
+    // Previews are computed in TypeScript, so older rows are filled in here
+    const { backfillTranscriptionPreviews } = await import("./transcriptions");
+    const previews = await backfillTranscriptionPreviews();
+    if (previews > 0) {
+      logger.db.info("Backfilled transcription previews", { count: previews });
+    }
+
     logger.db.info(
       "Database initialized and migrations completed successfully",
     );
@@ -84,7 +204,7 @@ export async function initializeDatabase() {
 
 export async function closeDatabase() {
   if (dbConnection) {
-    db.$client.close();
+    closeClients();
     dbConnection = null;
     isInitialized = false;
     dbConnection = null;
@@ -0,0 +1,142 @@
+import { desc, sql } from "drizzle-orm";
+import * as fs from "node:fs";
+import { db, dbPath } from ".";
+import {
+  maintenanceRuns,
+  type MaintenanceRun,
+  type NewMaintenanceRun,
+} from "./schema";
+import { getLastDatabaseError, type DatabaseError } from "./errors";
+import { getDatabaseRecovery } from "./recovery";
+
+// PRAGMA auto_vacuum value for INCREMENTAL
+const AUTO_VACUUM_INCREMENTAL = 2;
+
+async function pragmaValue(name: string): Promise<number> {
+  const row = await db.get<Record<string, number>>(sql.raw(`PRAGMA ${name}`));
+  return Number(row ? Object.values(row)[0] : 0);
+}
+
+// Size of the database file in bytes, excluding the WAL
+export async function getDatabaseSize(): Promise<number> {
+  return (await pragmaValue("page_count")) * (await pragmaValue("page_size"));
+}
+
+/**
+ * Return free pages to the file system. Databases created before
+ * incremental vacuum was switched on need one full VACUUM to convert;
+ * returns whether that happened.
+ */
+export async function vacuumDatabase(): Promise<boolean> {
+  if ((await pragmaValue("auto_vacuum")) === AUTO_VACUUM_INCREMENTAL) {
+    await db.run(sql`PRAGMA incremental_vacuum`);
+    return false;
+  }
+  await db.run(sql`PRAGMA auto_vacuum = INCREMENTAL`);
+  await db.run(sql`VACUUM`);
+  return true;
+}
+
+// Refresh query planner statistics and merge the search index's segments
+export async function analyzeDatabase(): Promise<void> {
+  await db.run(
+    sql`INSERT INTO transcriptions_fts(transcriptions_fts) VALUES ('optimize')`,
+  );
+  await db.run(sql`ANALYZE`);
+}
+
+export interface DatabaseHealth {
+  journalMode: string;
+  pageSize: number;
+  pageCount: number;
+  freelistPages: number; // Free pages a vacuum would return to the disk
+  walBytes: number;
+  lastMaintenance: MaintenanceRun | null;
+  lastError: DatabaseError | null;
+  recoveredAt: Date | null; // When a corrupt database was salvaged this launch
+}
+
+// Numbers for the diagnostics screen, e.g. to explain "the app feels slow"
+export async function getDatabaseHealth(): Promise<DatabaseHealth> {
+  const journal = await db.get<{ journal_mode: string }>(
+    sql`PRAGMA journal_mode`,
+  );
+  let walBytes = 0;
+  try {
+    walBytes = (await fs.promises.stat(`${dbPath}-wal`)).size;
+  } catch {
+    // No WAL file, e.g. right after a truncating checkpoint
+  }
+
+  return {
+    journalMode: journal?.journal_mode ?? "unknown",
+    pageSize: await pragmaValue("page_size"),
+    pageCount: await pragmaValue("page_count"),
+    freelistPages: await pragmaValue("freelist_count"),
+    walBytes,
+    lastMaintenance: await getLastMaintenanceRun(),
+    lastError: getLastDatabaseError(),
+    recoveredAt: getDatabaseRecovery()?.recoveredAt ?? null,
+  };
+}
+
+// Fold the WAL back into the database file and truncate it
+export async function checkpointDatabase(): Promise<void> {
+  await db.run(sql`PRAGMA wal_checkpoint(TRUNCATE)`);
+}
+
+export async function recordMaintenanceRun(data: NewMaintenanceRun) {
+  const result = await db.insert(maintenanceRuns).values(data).returning();
+  return result[0];
+}
+
+export async function getLastMaintenanceRun() {
+  const result = await db
+    .select()
+    .from(maintenanceRuns)
+    .orderBy(desc(maintenanceRuns.startedAt))
+    .limit(1);
+  return result[0] || null;
+}
+
+export interface TableStats {
+  name: string;
+  rows: number;
+  // Pages used by the table and its indexes; null when SQLite was built
+  // without the dbstat table
+  bytes: number | null;
+}
+
+// Row counts and on-disk size of every table, largest first
+export async function getTableStats(): Promise<TableStats[]> {
+  const tables = await db.all<{ name: string }>(
+    sql`SELECT name FROM sqlite_master WHERE type = 'table' AND name NOT LIKE 'sqlite_%' ORDER BY name`,
+  );
+
+  let sizes: Map<string, number> | null = null;
+  try {
+    const rows = await db.all<{ name: string; bytes: number }>(
+      sql`SELECT m.tbl_name AS name, sum(s.pgsize) AS bytes
+          FROM dbstat s JOIN sqlite_master m ON m.name = s.name
+          GROUP BY m.tbl_name`,
+    );
+    sizes = new Map(rows.map((row) => [row.name, Number(row.bytes)]));
+  } catch {
+    // dbstat isn't compiled in
+  }
+
+  const stats: TableStats[] = [];
+  for (const { name } of tables) {
+    const row = await db.get<{ count: number }>(
+      sql`SELECT count(*) AS count FROM ${sql.identifier(name)}`,
+    );
+    stats.push({
+      name,
+      rows: Number(row?.count ?? 0),
+      bytes: sizes ? (sizes.get(name) ?? 0) : null,
+    });
+  }
+  return stats.sort(
+    (a, b) => (b.bytes ?? 0) - (a.bytes ?? 0) || b.rows - a.rows,
+  );
+}
@@ -0,0 +1,2 @@
+ALTER TABLE `transcriptions` ADD `needs_review` integer DEFAULT false NOT NULL;--> statement-breakpoint
+CREATE INDEX `transcriptions_needs_review_idx` ON `transcriptions` (`needs_review`);
\ No newline at end of file
@@ -0,0 +1 @@
+ALTER TABLE `transcriptions` ADD `preview` text;
\ No newline at end of file
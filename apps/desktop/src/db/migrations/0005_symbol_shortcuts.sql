@@ -0,0 +1,36 @@
+CREATE TABLE `symbol_shortcuts` (
+	`id` integer PRIMARY KEY AUTOINCREMENT NOT NULL,
+	`phrase` text NOT NULL,
+	`symbol` text NOT NULL,
+	`language` text,
+	`created_at` integer DEFAULT (unixepoch()) NOT NULL,
+	`updated_at` integer DEFAULT (unixepoch()) NOT NULL
+);
+--> statement-breakpoint
+CREATE UNIQUE INDEX `symbol_shortcuts_phrase_language_idx` ON `symbol_shortcuts` (`phrase`,coalesce("language", ''));--> statement-breakpoint
+INSERT INTO `symbol_shortcuts` (`phrase`, `symbol`, `language`) VALUES
+	('thumbs up emoji', '👍', 'en'),
+	('thumbs down emoji', '👎', 'en'),
+	('smiley face emoji', '🙂', 'en'),
+	('laughing emoji', '😂', 'en'),
+	('heart emoji', '❤️', 'en'),
+	('fire emoji', '🔥', 'en'),
+	('party emoji', '🎉', 'en'),
+	('clapping emoji', '👏', 'en'),
+	('check mark emoji', '✅', 'en'),
+	('em dash', '—', 'en'),
+	('en dash', '–', 'en'),
+	('right arrow symbol', '→', 'en'),
+	('left arrow symbol', '←', 'en'),
+	('degree sign', '°', 'en'),
+	('copyright sign', '©', 'en'),
+	('trademark sign', '™', 'en'),
+	('emoji pulgar arriba', '👍', 'es'),
+	('emoji corazón', '❤️', 'es'),
+	('raya larga', '—', 'es'),
+	('emoji pouce levé', '👍', 'fr'),
+	('emoji cœur', '❤️', 'fr'),
+	('tiret cadratin', '—', 'fr'),
+	('daumen hoch emoji', '👍', 'de'),
+	('herz emoji', '❤️', 'de'),
+	('geviertstrich', '—', 'de');
\ No newline at end of file
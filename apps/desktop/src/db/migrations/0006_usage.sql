@@ -0,0 +1,13 @@
+CREATE TABLE `usage` (
+	`id` integer PRIMARY KEY AUTOINCREMENT NOT NULL,
+	`provider` text NOT NULL,
+	`model` text NOT NULL,
+	`kind` text NOT NULL,
+	`prompt_tokens` integer DEFAULT 0 NOT NULL,
+	`completion_tokens` integer DEFAULT 0 NOT NULL,
+	`audio_seconds` real,
+	`estimated_cost` real,
+	`created_at` integer DEFAULT (unixepoch()) NOT NULL
+);
+--> statement-breakpoint
+CREATE INDEX `usage_created_at_idx` ON `usage` (`created_at`);
\ No newline at end of file
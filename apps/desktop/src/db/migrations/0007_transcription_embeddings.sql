@@ -0,0 +1,6 @@
+CREATE TABLE `transcription_embeddings` (
+	`transcription_id` integer PRIMARY KEY NOT NULL,
+	`model` text NOT NULL,
+	`embedding` blob NOT NULL,
+	`created_at` integer DEFAULT (unixepoch()) NOT NULL
+);
\ No newline at end of file
@@ -0,0 +1,21 @@
+CREATE VIRTUAL TABLE `transcriptions_fts` USING fts5(
+	`text`,
+	content='transcriptions',
+	content_rowid='id',
+	tokenize='trigram remove_diacritics 1'
+);
+--> statement-breakpoint
+CREATE TRIGGER `transcriptions_fts_insert` AFTER INSERT ON `transcriptions` BEGIN
+	INSERT INTO `transcriptions_fts`(rowid, `text`) VALUES (new.`id`, new.`text`);
+END;
+--> statement-breakpoint
+CREATE TRIGGER `transcriptions_fts_delete` AFTER DELETE ON `transcriptions` BEGIN
+	INSERT INTO `transcriptions_fts`(`transcriptions_fts`, rowid, `text`) VALUES ('delete', old.`id`, old.`text`);
+END;
+--> statement-breakpoint
+CREATE TRIGGER `transcriptions_fts_update` AFTER UPDATE OF `text` ON `transcriptions` BEGIN
+	INSERT INTO `transcriptions_fts`(`transcriptions_fts`, rowid, `text`) VALUES ('delete', old.`id`, old.`text`);
+	INSERT INTO `transcriptions_fts`(rowid, `text`) VALUES (new.`id`, new.`text`);
+END;
+--> statement-breakpoint
+INSERT INTO `transcriptions_fts`(`transcriptions_fts`) VALUES ('rebuild');
\ No newline at end of file
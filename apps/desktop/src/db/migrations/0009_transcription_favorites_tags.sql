@@ -0,0 +1,13 @@
+ALTER TABLE `transcriptions` ADD `is_favorite` integer DEFAULT false NOT NULL;--> statement-breakpoint
+CREATE INDEX `transcriptions_is_favorite_idx` ON `transcriptions` (`is_favorite`);--> statement-breakpoint
+CREATE TABLE `transcription_tags` (
+	`transcription_id` integer NOT NULL,
+	`tag` text NOT NULL,
+	`created_at` integer DEFAULT (unixepoch()) NOT NULL,
+	PRIMARY KEY(`transcription_id`, `tag`)
+);
+--> statement-breakpoint
+CREATE INDEX `transcription_tags_tag_idx` ON `transcription_tags` (`tag`);--> statement-breakpoint
+CREATE TRIGGER `transcription_tags_delete` AFTER DELETE ON `transcriptions` BEGIN
+	DELETE FROM `transcription_tags` WHERE `transcription_id` = old.`id`;
+END;
\ No newline at end of file
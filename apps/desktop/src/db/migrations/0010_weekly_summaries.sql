@@ -0,0 +1,10 @@
+CREATE TABLE `weekly_summaries` (
+	`week_start` integer PRIMARY KEY NOT NULL,
+	`transcription_count` integer NOT NULL,
+	`word_count` integer NOT NULL,
+	`duration_seconds` integer NOT NULL,
+	`stats` text NOT NULL,
+	`digest` text,
+	`digest_model` text,
+	`created_at` integer DEFAULT (unixepoch()) NOT NULL
+);
\ No newline at end of file
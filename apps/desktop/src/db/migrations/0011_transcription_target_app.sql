@@ -0,0 +1,3 @@
+ALTER TABLE `transcriptions` ADD `target_app_bundle_id` text;--> statement-breakpoint
+ALTER TABLE `transcriptions` ADD `target_app_name` text;--> statement-breakpoint
+CREATE INDEX `transcriptions_target_app_idx` ON `transcriptions` (`target_app_bundle_id`);
\ No newline at end of file
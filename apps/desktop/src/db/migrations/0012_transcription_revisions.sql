@@ -0,0 +1,11 @@
+CREATE TABLE `transcription_revisions` (
+	`id` integer PRIMARY KEY AUTOINCREMENT NOT NULL,
+	`transcription_id` integer NOT NULL,
+	`text` text NOT NULL,
+	`created_at` integer DEFAULT (unixepoch()) NOT NULL
+);
+--> statement-breakpoint
+CREATE INDEX `transcription_revisions_transcription_idx` ON `transcription_revisions` (`transcription_id`);--> statement-breakpoint
+CREATE TRIGGER `transcription_revisions_delete` AFTER DELETE ON `transcriptions` BEGIN
+	DELETE FROM `transcription_revisions` WHERE `transcription_id` = old.`id`;
+END;
\ No newline at end of file
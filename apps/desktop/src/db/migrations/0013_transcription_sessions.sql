@@ -0,0 +1,2 @@
+ALTER TABLE `transcriptions` ADD `dictation_session_id` integer;--> statement-breakpoint
+CREATE INDEX `transcriptions_dictation_session_idx` ON `transcriptions` (`dictation_session_id`);
\ No newline at end of file
@@ -0,0 +1,2 @@
+ALTER TABLE `transcriptions` ADD `text_hash` text;--> statement-breakpoint
+CREATE INDEX `transcriptions_text_hash_idx` ON `transcriptions` (`text_hash`);
\ No newline at end of file
@@ -0,0 +1 @@
+ALTER TABLE `transcriptions` ADD `raw_text` text;
\ No newline at end of file
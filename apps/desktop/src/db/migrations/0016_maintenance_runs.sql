@@ -0,0 +1,8 @@
+CREATE TABLE `maintenance_runs` (
+	`id` integer PRIMARY KEY AUTOINCREMENT NOT NULL,
+	`started_at` integer NOT NULL,
+	`duration_ms` integer NOT NULL,
+	`full_vacuum` integer DEFAULT false NOT NULL,
+	`size_before` integer NOT NULL,
+	`size_after` integer NOT NULL
+);
\ No newline at end of file
@@ -0,0 +1,4 @@
+CREATE INDEX `transcriptions_timestamp_idx` ON `transcriptions` (`timestamp`,`id`);--> statement-breakpoint
+CREATE INDEX `transcriptions_created_at_idx` ON `transcriptions` (`created_at`);--> statement-breakpoint
+CREATE INDEX `vocabulary_date_added_idx` ON `vocabulary` (`date_added`);--> statement-breakpoint
+CREATE INDEX `vocabulary_usage_count_idx` ON `vocabulary` (`usage_count`);
\ No newline at end of file
@@ -0,0 +1,9 @@
+CREATE TABLE `settings_profiles` (
+	`id` text PRIMARY KEY NOT NULL,
+	`name` text NOT NULL,
+	`data` text NOT NULL,
+	`created_at` integer DEFAULT (unixepoch()) NOT NULL,
+	`updated_at` integer DEFAULT (unixepoch()) NOT NULL
+);
+--> statement-breakpoint
+CREATE UNIQUE INDEX `settings_profiles_name_unique` ON `settings_profiles` (`name`);
\ No newline at end of file
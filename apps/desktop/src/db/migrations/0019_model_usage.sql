@@ -0,0 +1,8 @@
+CREATE TABLE `model_usage` (
+	`model_type` text NOT NULL,
+	`model_id` text NOT NULL,
+	`transcription_count` integer DEFAULT 0 NOT NULL,
+	`audio_seconds` real DEFAULT 0 NOT NULL,
+	`last_used_at` integer NOT NULL,
+	PRIMARY KEY(`model_type`, `model_id`)
+);
\ No newline at end of file
@@ -0,0 +1,50 @@
+import { desc, eq, sql } from "drizzle-orm";
+import { db } from ".";
+import { modelUsage, type ModelUsage } from "./schema";
+import { logger } from "../main/logger";
+
+export type ModelUsageType = ModelUsage["modelType"];
+
+// Count one transcription for a model; never throws so tracking can't
+// break dictation
+export async function recordModelUsage(data: {
+  modelType: ModelUsageType;
+  modelId: string;
+  audioSeconds?: number;
+}) {
+  const audioSeconds =
+    data.audioSeconds !== undefined && Number.isFinite(data.audioSeconds)
+      ? data.audioSeconds
+      : 0;
+  const now = new Date();
+  try {
+    await db
+      .insert(modelUsage)
+      .values({
+        modelType: data.modelType,
+        modelId: data.modelId,
+        transcriptionCount: 1,
+        audioSeconds,
+        lastUsedAt: now,
+      })
+      .onConflictDoUpdate({
+        target: [modelUsage.modelType, modelUsage.modelId],
+        set: {
+          transcriptionCount: sql`${modelUsage.transcriptionCount} + 1`,
+          audioSeconds: sql`${modelUsage.audioSeconds} + ${audioSeconds}`,
+          lastUsedAt: now,
+        },
+      });
+  } catch (error) {
+    logger.db.warn("Failed to record model usage", { error });
+  }
+}
+
+// Totals per model, most used first
+export async function getModelUsageStats(modelType?: ModelUsageType) {
+  return await db
+    .select()
+    .from(modelUsage)
+    .where(modelType ? eq(modelUsage.modelType, modelType) : undefined)
+    .orderBy(desc(modelUsage.transcriptionCount));
+}
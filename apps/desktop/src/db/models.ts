@@ -1,6 +1,8 @@
 import { eq, and, or } from "drizzle-orm";
-import { db } from ".";
+import { db, withTransaction } from ".";
 import { models, type Model, type NewModel } from "./schema";
+import { isImportedModelId } from "../utils/huggingface";
+import { isLocalModelId } from "../utils/local-model-file";
 
 /**
  * Database operations for unified models table
@@ -71,22 +73,29 @@ export async function getDownloadedWhisperModels(): Promise<Model[]> {
  * Create or update a model
  */
 export async function upsertModel(model: NewModel): Promise<void> {
-  // Check if model exists
-  const existing = await getModelById(model.provider, model.id);
-
-  if (existing) {
-    // Update existing model
-    await db
-      .update(models)
-      .set({
-        ...model,
-        updatedAt: new Date(),
-      })
+  await withTransaction(async (tx) => {
+    // Check if model exists
+    const [existing] = await tx
+      .select({ id: models.id })
+      .from(models)
       .where(and(eq(models.provider, model.provider), eq(models.id, model.id)));
-  } else {
-    // Insert new model
-    await db.insert(models).values(model);
-  }
+
+    if (existing) {
+      // Update existing model
+      await tx
+        .update(models)
+        .set({
+          ...model,
+          updatedAt: new Date(),
+        })
+        .where(
+          and(eq(models.provider, model.provider), eq(models.id, model.id)),
+        );
+    } else {
+      // Insert new model
+      await tx.insert(models).values(model);
+    }
+  });
 }
 
 /**
@@ -96,7 +105,7 @@ export async function syncModelsForProvider(
   provider: string,
   newModels: NewModel[],
 ): Promise<void> {
-  await db.transaction(async (tx) => {
+  await withTransaction(async (tx) => {
     // Delete existing models for this provider
     await tx.delete(models).where(eq(models.provider, provider));
 
@@ -172,6 +181,7 @@ export async function syncLocalWhisperModels(
     speed: number;
     accuracy: number;
     filename: string;
+    files?: string[]; // Multi-file models: paths inside the `filename` folder
   }>,
 ): Promise<{ added: number; updated: number; removed: number }> {
   const fs = await import("fs");
@@ -202,23 +212,30 @@ export async function syncLocalWhisperModels(
   // Process each available model
   for (const model of availableModels) {
     const filePath = path.join(modelsDirectory, model.filename);
-    const fileExists = modelFiles.has(model.filename);
+    const fileExists = model.files
+      ? model.files.every((file) => fs.existsSync(path.join(filePath, file)))
+      : modelFiles.has(model.filename);
     const existingRecord = existingModelMap.get(model.id);
 
     if (fileExists) {
-      // File exists on disk
-      const stats = fs.statSync(filePath);
+      // File exists on disk; folder models count all their files
+      const sizeBytes = model.files
+        ? model.files.reduce(
+            (sum, file) => sum + fs.statSync(path.join(filePath, file)).size,
+            0,
+          )
+        : fs.statSync(filePath).size;
 
       if (existingRecord) {
         // Update existing record if needed
         if (
           existingRecord.localPath !== filePath ||
-          existingRecord.sizeBytes !== stats.size
+          existingRecord.sizeBytes !== sizeBytes
         ) {
           await upsertModel({
             ...existingRecord,
             localPath: filePath,
-            sizeBytes: stats.size,
+            sizeBytes,
             downloadedAt: existingRecord.downloadedAt || new Date(),
           });
           updated++;
@@ -236,7 +253,7 @@ export async function syncLocalWhisperModels(
           speed: model.speed,
           accuracy: model.accuracy,
           localPath: filePath,
-          sizeBytes: stats.size,
+          sizeBytes,
           downloadedAt: new Date(),
           context: null,
           originalModel: null,
@@ -257,8 +274,16 @@ export async function syncLocalWhisperModels(
   }
 
   // Remove any remaining records that don't have corresponding available models
-  // (these would be orphaned records)
+  // (these would be orphaned records). Models imported from Hugging Face
+  // or a file aren't in the list; keep them while their file is there.
   for (const [, model] of existingModelMap) {
+    if (
+      (isImportedModelId(model.id) || isLocalModelId(model.id)) &&
+      model.localPath &&
+      fs.existsSync(model.localPath)
+    ) {
+      continue;
+    }
     await removeModel(model.provider, model.id);
     removed++;
   }
@@ -0,0 +1,142 @@
+import type { Client } from "@libsql/client";
+import * as fs from "node:fs";
+import { logger } from "../main/logger";
+
+/**
+ * Startup integrity check and salvage of a corrupt database. The damaged
+ * file is moved aside, a fresh database is migrated in its place, and every
+ * table that can still be read is copied over.
+ */
+
+export interface DatabaseRecovery {
+  recoveredAt: Date;
+  corruptPath: string; // Where the damaged database was moved
+  problems: string[]; // From PRAGMA integrity_check
+  restoredTables: { name: string; rows: number }[];
+  failedTables: string[];
+}
+
+// Cap on the problems integrity_check reports
+const MAX_PROBLEMS = 20;
+
+// Recreated by migrations or triggers rather than copied
+const SKIPPED_TABLES = ["__drizzle_migrations", "transcriptions_fts"];
+
+let lastRecovery: DatabaseRecovery | null = null;
+
+/**
+ * Recovery that happened during this launch, if any
+ */
+export function getDatabaseRecovery(): DatabaseRecovery | null {
+  return lastRecovery;
+}
+
+/**
+ * Problems found by PRAGMA integrity_check; empty when the database is fine
+ */
+export async function checkIntegrity(client: Client): Promise<string[]> {
+  try {
+    const result = await client.execute(
+      `PRAGMA integrity_check(${MAX_PROBLEMS})`,
+    );
+    const problems = result.rows.map((row) => String(row[0]));
+    return problems.length === 1 && problems[0] === "ok" ? [] : problems;
+  } catch (error) {
+    // A badly damaged file can't even be checked
+    return [error instanceof Error ? error.message : String(error)];
+  }
+}
+
+/**
+ * Move the database and its WAL files aside; returns the new path
+ */
+export function moveCorruptDatabase(dbPath: string): string {
+  const corruptPath = `${dbPath}.corrupt-${Date.now()}`;
+  for (const suffix of ["", "-wal", "-shm"]) {
+    if (fs.existsSync(`${dbPath}${suffix}`)) {
+      fs.renameSync(`${dbPath}${suffix}`, `${corruptPath}${suffix}`);
+    }
+  }
+  return corruptPath;
+}
+
+function quoteIdentifier(name: string) {
+  return `"${name.replace(/"/g, '""')}"`;
+}
+
+function quote(value: string) {
+  return `'${value.replace(/'/g, "''")}'`;
+}
+
+async function getColumns(client: Client, table: string, schema: string) {
+  const result = await client.execute({
+    sql: "SELECT name FROM pragma_table_info(?, ?)",
+    args: [table, schema],
+  });
+  return result.rows.map((row) => String(row.name));
+}
+
+/**
+ * Copy what can still be read from the corrupt database into the freshly
+ * migrated one. Only columns both schemas share are copied; a table that
+ * fails to read is skipped and reported.
+ */
+export async function salvageDatabase(
+  client: Client,
+  corruptPath: string,
+  problems: string[],
+  encryptionKey?: string,
+): Promise<DatabaseRecovery> {
+  const recovery: DatabaseRecovery = {
+    recoveredAt: new Date(),
+    corruptPath,
+    problems,
+    restoredTables: [],
+    failedTables: [],
+  };
+
+  const foreignKeys = await client.execute("PRAGMA foreign_keys");
+  await client.execute("PRAGMA foreign_keys = OFF");
+  await client.execute(
+    `ATTACH DATABASE ${quote(corruptPath)} AS damaged` +
+      (encryptionKey ? ` KEY ${quote(encryptionKey)}` : ""),
+  );
+  try {
+    const tables = await client.execute(
+      "SELECT name FROM sqlite_master WHERE type = 'table' AND name NOT LIKE 'sqlite_%' ORDER BY name",
+    );
+    for (const row of tables.rows) {
+      const name = String(row.name);
+      if (SKIPPED_TABLES.some((skipped) => name.startsWith(skipped))) {
+        continue;
+      }
+      try {
+        const sourceColumns = new Set(
+          await getColumns(client, name, "damaged"),
+        );
+        const columns = (await getColumns(client, name, "main"))
+          .filter((column) => sourceColumns.has(column))
+          .map(quoteIdentifier)
+          .join(", ");
+        if (!columns) continue;
+
+        const table = quoteIdentifier(name);
+        const result = await client.execute(
+          `INSERT OR REPLACE INTO main.${table} (${columns}) SELECT ${columns} FROM damaged.${table}`,
+        );
+        recovery.restoredTables.push({ name, rows: result.rowsAffected });
+      } catch (error) {
+        logger.db.warn("Could not salvage table", { table: name, error });
+        recovery.failedTables.push(name);
+      }
+    }
+  } finally {
+    await client.execute("DETACH DATABASE damaged");
+    await client.execute(
+      `PRAGMA foreign_keys = ${Number(foreignKeys.rows[0]?.[0] ?? 0)}`,
+    );
+  }
+
+  lastRecovery = recovery;
+  return recovery;
+}
@@ -4,24 +4,161 @@ import {
   text,
   integer,
   real,
+  blob,
   index,
+  uniqueIndex,
   primaryKey,
 } from "drizzle-orm/sqlite-core";
+import type { NormalizationConfig } from "../types/normalization";
+import type { FormatterConfig } from "../types/formatter";
+import type { EmbeddingConfig } from "../types/embedding";
+import type { ProfanityPolicy } from "../types/profanity";
+import type { PerformancePreset } from "../constants/performance-presets";
 
 // Transcriptions table
-export const transcriptions = sqliteTable("transcriptions", {
-  id: integer("id").primaryKey({ autoIncrement: true }),
-  text: text("text").notNull(),
-  timestamp: integer("timestamp", { mode: "timestamp" })
-    .notNull()
-    .default(sql`(unixepoch())`),
-  language: text("language").default("en"),
-  audioFile: text("audio_file"), // Path to the audio file
-  confidence: real("confidence"), // AI confidence score (0-1)
-  duration: integer("duration"), // Duration in seconds
-  speechModel: text("speech_model"), // Model used for speech recognition
-  formattingModel: text("formatting_model"), // Model used for formatting
-  meta: text("meta", { mode: "json" }), // Additional metadata as JSON
+export const transcriptions = sqliteTable(
+  "transcriptions",
+  {
+    id: integer("id").primaryKey({ autoIncrement: true }),
+    text: text("text").notNull(),
+    // Recognizer output before spoken commands and formatting; null on rows
+    // saved before it was kept
+    rawText: text("raw_text"),
+    preview: text("preview"), // First sentence, cached for list views
+    textHash: text("text_hash"), // Of the normalized text, for duplicate checks
+    timestamp: integer("timestamp", { mode: "timestamp" })
+      .notNull()
+      .default(sql`(unixepoch())`),
+    language: text("language").default("en"),
+    audioFile: text("audio_file"), // Path to the audio file
+    confidence: real("confidence"), // AI confidence score (0-1)
+    duration: integer("duration"), // Duration in seconds
+    speechModel: text("speech_model"), // Model used for speech recognition
+    formattingModel: text("formatting_model"), // Model used for formatting
+    meta: text("meta", { mode: "json" }), // Additional metadata as JSON
+    needsReview: integer("needs_review", { mode: "boolean" })
+      .notNull()
+      .default(false), // Flagged by quality heuristics; cleared on edit/approve
+    isFavorite: integer("is_favorite", { mode: "boolean" })
+      .notNull()
+      .default(false), // Starred by the user; exempt from history retention
+    targetAppBundleId: text("target_app_bundle_id"), // App focused when the text was inserted
+    targetAppName: text("target_app_name"),
+    // Id of the first transcription of the dictation session this belongs
+    // to; null when it started the session
+    dictationSessionId: integer("dictation_session_id"),
+    createdAt: integer("created_at", { mode: "timestamp" })
+      .notNull()
+      .default(sql`(unixepoch())`),
+    updatedAt: integer("updated_at", { mode: "timestamp" })
+      .notNull()
+      .default(sql`(unixepoch())`),
+  },
+  (table) => [
+    // List views sort by timestamp with id as the tie-breaker
+    index("transcriptions_timestamp_idx").on(table.timestamp, table.id),
+    index("transcriptions_created_at_idx").on(table.createdAt),
+    index("transcriptions_needs_review_idx").on(table.needsReview),
+    index("transcriptions_is_favorite_idx").on(table.isFavorite),
+    index("transcriptions_target_app_idx").on(table.targetAppBundleId),
+    index("transcriptions_dictation_session_idx").on(table.dictationSessionId),
+    index("transcriptions_text_hash_idx").on(table.textHash),
+  ],
+);
+
+// User-defined tags on transcriptions. Rows are removed with their
+// transcription by a trigger.
+export const transcriptionTags = sqliteTable(
+  "transcription_tags",
+  {
+    transcriptionId: integer("transcription_id").notNull(),
+    tag: text("tag").notNull(),
+    createdAt: integer("created_at", { mode: "timestamp" })
+      .notNull()
+      .default(sql`(unixepoch())`),
+  },
+  (table) => [
+    primaryKey({ columns: [table.transcriptionId, table.tag] }),
+    index("transcription_tags_tag_idx").on(table.tag),
+  ],
+);
+
+// Previous texts of a transcription, saved whenever the text is changed.
+// Rows are removed with their transcription by a trigger.
+export const transcriptionRevisions = sqliteTable(
+  "transcription_revisions",
+  {
+    id: integer("id").primaryKey({ autoIncrement: true }),
+    transcriptionId: integer("transcription_id").notNull(),
+    text: text("text").notNull(), // The text before the change
+    createdAt: integer("created_at", { mode: "timestamp" })
+      .notNull()
+      .default(sql`(unixepoch())`),
+  },
+  (table) => [
+    index("transcription_revisions_transcription_idx").on(
+      table.transcriptionId,
+    ),
+  ],
+);
+
+// Vocabulary table
+export const vocabulary = sqliteTable(
+  "vocabulary",
+  {
+    id: integer("id").primaryKey({ autoIncrement: true }),
+    word: text("word").notNull().unique(),
+    replacementWord: text("replacement_word"),
+    isReplacement: integer("is_replacement", { mode: "boolean" }).default(
+      false,
+    ),
+    dateAdded: integer("date_added", { mode: "timestamp" })
+      .notNull()
+      .default(sql`(unixepoch())`),
+    usageCount: integer("usage_count").default(0), // How many times this word appeared in transcriptions
+    createdAt: integer("created_at", { mode: "timestamp" })
+      .notNull()
+      .default(sql`(unixepoch())`),
+    updatedAt: integer("updated_at", { mode: "timestamp" })
+      .notNull()
+      .default(sql`(unixepoch())`),
+  },
+  (table) => [
+    index("vocabulary_date_added_idx").on(table.dateAdded),
+    index("vocabulary_usage_count_idx").on(table.usageCount),
+  ],
+);
+
+// Spoken phrase → emoji/symbol shortcuts ("thumbs up emoji" → 👍)
+export const symbolShortcuts = sqliteTable(
+  "symbol_shortcuts",
+  {
+    id: integer("id").primaryKey({ autoIncrement: true }),
+    phrase: text("phrase").notNull(), // Stored lowercase
+    symbol: text("symbol").notNull(),
+    language: text("language"), // null = applies to every language
+    createdAt: integer("created_at", { mode: "timestamp" })
+      .notNull()
+      .default(sql`(unixepoch())`),
+    updatedAt: integer("updated_at", { mode: "timestamp" })
+      .notNull()
+      .default(sql`(unixepoch())`),
+  },
+  (table) => [
+    // coalesce so a phrase can only have one language-neutral shortcut;
+    // SQLite treats NULLs as distinct in unique indexes
+    uniqueIndex("symbol_shortcuts_phrase_language_idx").on(
+      table.phrase,
+      sql`coalesce(${table.language}, '')`,
+    ),
+  ],
+);
+
+// App settings table with typed JSON
+export const appSettings = sqliteTable("app_settings", {
+  id: integer("id").primaryKey(),
+  data: text("data", { mode: "json" }).$type<AppSettingsData>().notNull(),
+  version: integer("version").notNull().default(1), // Settings schema version
   createdAt: integer("created_at", { mode: "timestamp" })
     .notNull()
     .default(sql`(unixepoch())`),
@@ -30,16 +167,13 @@ export const transcriptions = sqliteTable("transcriptions", {
     .default(sql`(unixepoch())`),
 });
 
-// Vocabulary table
-export const vocabulary = sqliteTable("vocabulary", {
-  id: integer("id").primaryKey({ autoIncrement: true }),
-  word: text("word").notNull().unique(),
-  replacementWord: text("replacement_word"),
-  isReplacement: integer("is_replacement", { mode: "boolean" }).default(false),
-  dateAdded: integer("date_added", { mode: "timestamp" })
-    .notNull()
-    .default(sql`(unixepoch())`),
-  usageCount: integer("usage_count").default(0), // How many times this word appeared in transcriptions
+// Named sets of modes, providers and shortcuts the user can switch
+// between. The active profile's values live in app_settings; its row here
+// is refreshed when switching away from it.
+export const settingsProfiles = sqliteTable("settings_profiles", {
+  id: text("id").primaryKey(),
+  name: text("name").notNull().unique(),
+  data: text("data", { mode: "json" }).$type<ProfileSettings>().notNull(),
   createdAt: integer("created_at", { mode: "timestamp" })
     .notNull()
     .default(sql`(unixepoch())`),
@@ -48,19 +182,79 @@ export const vocabulary = sqliteTable("vocabulary", {
     .default(sql`(unixepoch())`),
 });
 
-// App settings table with typed JSON
-export const appSettings = sqliteTable("app_settings", {
-  id: integer("id").primaryKey(),
-  data: text("data", { mode: "json" }).$type<AppSettingsData>().notNull(),
-  version: integer("version").notNull().default(1), // Settings schema version
+// Vector per transcription for semantic search; rebuilt when the text or
+// the embedding model changes
+export const transcriptionEmbeddings = sqliteTable("transcription_embeddings", {
+  transcriptionId: integer("transcription_id").primaryKey(),
+  model: text("model").notNull(), // "<provider>:<model>"
+  embedding: blob("embedding", { mode: "buffer" }).notNull(), // Float32 LE
   createdAt: integer("created_at", { mode: "timestamp" })
     .notNull()
     .default(sql`(unixepoch())`),
-  updatedAt: integer("updated_at", { mode: "timestamp" })
+});
+
+// One row per billable provider call (LLM, embedding, cloud transcription)
+export const usage = sqliteTable(
+  "usage",
+  {
+    id: integer("id").primaryKey({ autoIncrement: true }),
+    provider: text("provider").notNull(), // e.g. "openai", "openrouter", "groq"
+    model: text("model").notNull(),
+    kind: text("kind", {
+      enum: ["formatting", "generation", "transcription", "embedding"],
+    }).notNull(),
+    promptTokens: integer("prompt_tokens").notNull().default(0),
+    completionTokens: integer("completion_tokens").notNull().default(0),
+    audioSeconds: real("audio_seconds"), // Transcription calls only
+    estimatedCost: real("estimated_cost"), // USD; null when the price is unknown
+    createdAt: integer("created_at", { mode: "timestamp" })
+      .notNull()
+      .default(sql`(unixepoch())`),
+  },
+  (table) => [index("usage_created_at_idx").on(table.createdAt)],
+);
+
+// Running totals per speech and formatting model, local or cloud
+export const modelUsage = sqliteTable(
+  "model_usage",
+  {
+    modelType: text("model_type", {
+      enum: ["speech", "formatting"],
+    }).notNull(),
+    modelId: text("model_id").notNull(),
+    transcriptionCount: integer("transcription_count").notNull().default(0),
+    audioSeconds: real("audio_seconds").notNull().default(0),
+    lastUsedAt: integer("last_used_at", { mode: "timestamp" }).notNull(),
+  },
+  (table) => [primaryKey({ columns: [table.modelType, table.modelId] })],
+);
+
+// Weekly dictation digest, keyed by the local Monday the week starts on
+export const weeklySummaries = sqliteTable("weekly_summaries", {
+  weekStart: integer("week_start", { mode: "timestamp" }).primaryKey(),
+  transcriptionCount: integer("transcription_count").notNull(),
+  wordCount: integer("word_count").notNull(),
+  durationSeconds: integer("duration_seconds").notNull(),
+  stats: text("stats", { mode: "json" }).notNull(), // Per-model/language/day breakdowns
+  digest: text("digest"), // Language model write-up; null when not generated
+  digestModel: text("digest_model"),
+  createdAt: integer("created_at", { mode: "timestamp" })
     .notNull()
     .default(sql`(unixepoch())`),
 });
 
+// Database housekeeping passes (vacuum, ANALYZE), newest last
+export const maintenanceRuns = sqliteTable("maintenance_runs", {
+  id: integer("id").primaryKey({ autoIncrement: true }),
+  startedAt: integer("started_at", { mode: "timestamp" }).notNull(),
+  durationMs: integer("duration_ms").notNull(),
+  fullVacuum: integer("full_vacuum", { mode: "boolean" })
+    .notNull()
+    .default(false), // One-off VACUUM that switched on incremental vacuum
+  sizeBefore: integer("size_before").notNull(), // Database size in bytes
+  sizeAfter: integer("size_after").notNull(),
+});
+
 // Unified models table for all model types (Whisper, Language)
 export const models = sqliteTable(
   "models",
@@ -79,7 +273,7 @@ export const models = sqliteTable(
     // Local model fields (only for downloaded Whisper models)
     localPath: text("local_path"), // Where file is stored on disk
     sizeBytes: integer("size_bytes"), // Actual file size in bytes
-    checksum: text("checksum"), // SHA-1 hash for verification
+    checksum: text("checksum"), // SHA-1 (built-in) or SHA-256 (imported) hash
     downloadedAt: integer("downloaded_at", { mode: "timestamp" }),
 
     // Remote model fields (OpenRouter/Ollama)
@@ -115,25 +309,28 @@ export interface ModeConfig {
     autoDetectEnabled: boolean;
     selectedLanguage: string;
   };
-  formatterConfig: {
-    enabled: boolean;
-    modelId?: string;
-    fallbackModelId?: string;
-  };
+  formatterConfig: FormatterConfig;
   customInstructions?: string; // Free-text injected into formatter system prompt
   speechModelId?: string; // Per-mode speech model override; undefined = use current speech selection
   appBindings?: string[]; // Bundle identifiers for auto-switch (e.g. ["com.apple.mail"])
+  spokenCommandsEnabled?: boolean; // Interpret "new line", "comma", "delete that" etc. before formatting
+  normalization?: NormalizationConfig; // Number/date/currency normalization; undefined = off
+  voiceEditingEnabled?: boolean; // "replace X with Y" / "make it more formal" edits the previous dictation
+  messageSplitting?: {
+    enabled: boolean;
+    maxLength: number; // Characters per message
+    delayMs: number; // Pause between sending messages
+  }; // Send long dictation as several chat messages
+  profanityPolicy?: ProfanityPolicy; // undefined = keep
+  vocabularyCorrectionEnabled?: boolean; // LLM pass fixing misheard vocabulary terms only
   createdAt: string; // ISO 8601
   updatedAt: string; // ISO 8601
 }
 
 // Define the shape of our settings JSON
 export interface AppSettingsData {
-  formatterConfig?: {
-    enabled: boolean;
-    modelId?: string; // Formatting model selection (language model ID)
-    fallbackModelId?: string; // Last formatting model for auto-restore
-  };
+  formatterConfig?: FormatterConfig; // Formatting model selection (language model IDs)
+  embeddingConfig?: EmbeddingConfig; // Model used for semantic search
   ui?: {
     theme: "light" | "dark" | "system";
   };
@@ -145,6 +342,7 @@ export interface AppSettingsData {
     enableTimestamps: boolean;
     preloadWhisperModel?: boolean;
     useGPU?: boolean; // undefined = platform default (ON for Apple Silicon, OFF otherwise)
+    performancePreset?: PerformancePreset; // undefined = balanced
   };
   recording?: {
     defaultFormat: "wav" | "mp3" | "flac";
@@ -153,6 +351,33 @@ export interface AppSettingsData {
     silenceThreshold: number;
     maxRecordingDuration: number;
     preferredMicrophoneName?: string;
+    fileNameTemplate?: string; // undefined = "{date}_{time}_{mode}"
+    watchdogTimeoutMs?: number; // Stuck-session recovery threshold
+  };
+  history?: {
+    retentionDays?: number; // Delete transcriptions older than this
+    maxItems?: number; // Keep only the newest N transcriptions
+    audioRetentionDays?: number; // Delete recordings older than this, keeping the text
+    duplicatePolicy?: "keep" | "flag" | "skip"; // Repeat of a recent dictation; undefined = flag
+  };
+  weeklyDigest?: {
+    enabled: boolean; // Generate a summary after each week ends
+    useLanguageModel?: boolean; // Add a written digest using the formatter model
+    notify?: boolean; // Show a system notification when it's ready
+  };
+  maintenance?: {
+    enabled?: boolean; // Automatic vacuum/ANALYZE passes; undefined = on
+    frequency?: "daily" | "weekly"; // undefined = daily
+    hour?: number; // Local hour (0-23) passes start from; undefined = any time
+    onlyWhenIdle?: boolean; // Wait until the user is away; undefined = true
+  };
+  modelCleanup?: {
+    enabled?: boolean; // Offer to delete models that go unused; undefined = off
+    unusedDays?: number; // Days without a transcription; undefined = 30
+  };
+  modelDownloads?: {
+    mirrors?: string[]; // Hosts serving huggingface.co paths, tried in order
+    fallbackToOrigin?: boolean; // Try huggingface.co last; undefined = true
   };
   shortcuts?: {
     pushToTalk?: string[];
@@ -199,6 +424,19 @@ export interface AppSettingsData {
   telemetry?: {
     enabled?: boolean;
   };
+  remoteControl?: {
+    enabled: boolean;
+    port: number;
+    token: string; // Bearer token required on every request
+    allowLan?: boolean; // Bind on all interfaces instead of loopback only
+  };
+  proxy?: {
+    enabled: boolean;
+    url: string; // e.g. "http://proxy.corp:8080"
+    username?: string;
+    password?: string;
+    bypass?: string[]; // Hosts that skip the proxy, e.g. "*.internal"
+  };
   auth?: {
     isAuthenticated: boolean;
     idToken: string | null;
@@ -215,6 +453,9 @@ export interface AppSettingsData {
     items: ModeConfig[];
     activeModeId: string; // Must match an item's id
   };
+  profiles?: {
+    activeProfileId: string; // Unset until the first profile is created
+  };
   onboarding?: {
     completedVersion: number;
     completedAt: string; // ISO 8601 timestamp
@@ -224,12 +465,38 @@ export interface AppSettingsData {
   };
 }
 
+// Settings sections that belong to a profile
+export const PROFILE_SECTIONS = [
+  "modes",
+  "modelProvidersConfig",
+  "shortcuts",
+] as const;
+
+export type ProfileSettings = Pick<
+  AppSettingsData,
+  (typeof PROFILE_SECTIONS)[number]
+>;
+
 // Export types for TypeScript
 export type Transcription = typeof transcriptions.$inferSelect;
 export type NewTranscription = typeof transcriptions.$inferInsert;
+export type TranscriptionTag = typeof transcriptionTags.$inferSelect;
+export type TranscriptionRevision = typeof transcriptionRevisions.$inferSelect;
 export type Vocabulary = typeof vocabulary.$inferSelect;
 export type NewVocabulary = typeof vocabulary.$inferInsert;
+export type SymbolShortcut = typeof symbolShortcuts.$inferSelect;
+export type NewSymbolShortcut = typeof symbolShortcuts.$inferInsert;
+export type TranscriptionEmbedding =
+  typeof transcriptionEmbeddings.$inferSelect;
+export type Usage = typeof usage.$inferSelect;
+export type NewUsage = typeof usage.$inferInsert;
+export type WeeklySummary = typeof weeklySummaries.$inferSelect;
+export type NewWeeklySummary = typeof weeklySummaries.$inferInsert;
+export type MaintenanceRun = typeof maintenanceRuns.$inferSelect;
+export type NewMaintenanceRun = typeof maintenanceRuns.$inferInsert;
+export type ModelUsage = typeof modelUsage.$inferSelect;
 export type Model = typeof models.$inferSelect;
 export type NewModel = typeof models.$inferInsert;
+export type SettingsProfile = typeof settingsProfiles.$inferSelect;
 export type AppSettings = typeof appSettings.$inferSelect;
 export type NewAppSettings = typeof appSettings.$inferInsert;
@@ -0,0 +1,55 @@
+import { asc, eq } from "drizzle-orm";
+import { db } from ".";
+import { settingsProfiles, type ProfileSettings } from "./schema";
+
+export async function getSettingsProfiles() {
+  return await db
+    .select()
+    .from(settingsProfiles)
+    .orderBy(asc(settingsProfiles.createdAt), asc(settingsProfiles.name));
+}
+
+export async function getSettingsProfile(id: string) {
+  const result = await db
+    .select()
+    .from(settingsProfiles)
+    .where(eq(settingsProfiles.id, id));
+  return result[0] || null;
+}
+
+export async function getSettingsProfileByName(name: string) {
+  const result = await db
+    .select()
+    .from(settingsProfiles)
+    .where(eq(settingsProfiles.name, name));
+  return result[0] || null;
+}
+
+export async function createSettingsProfile(data: {
+  id: string;
+  name: string;
+  data: ProfileSettings;
+}) {
+  const now = new Date();
+  const result = await db
+    .insert(settingsProfiles)
+    .values({ ...data, createdAt: now, updatedAt: now })
+    .returning();
+  return result[0];
+}
+
+export async function updateSettingsProfile(
+  id: string,
+  data: Partial<{ name: string; data: ProfileSettings }>,
+) {
+  const result = await db
+    .update(settingsProfiles)
+    .set({ ...data, updatedAt: new Date() })
+    .where(eq(settingsProfiles.id, id))
+    .returning();
+  return result[0] || null;
+}
+
+export async function deleteSettingsProfile(id: string) {
+  await db.delete(settingsProfiles).where(eq(settingsProfiles.id, id));
+}
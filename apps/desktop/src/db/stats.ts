@@ -0,0 +1,117 @@
+import { and, desc, gt, gte, lt, sql, type SQL } from "drizzle-orm";
+import { db } from ".";
+import { transcriptions } from "./schema";
+
+const DAY_MS = 24 * 60 * 60 * 1000;
+
+// Whitespace-separated word count. Line breaks count as spaces;
+// runs of spaces overcount slightly, which is fine for statistics.
+const wordCount = sql<number>`case when trim(${transcriptions.text}) = '' then 0 else length(replace(replace(trim(${transcriptions.text}), char(13), ''), char(10), ' ')) - length(replace(replace(replace(trim(${transcriptions.text}), char(10), ''), char(13), ''), ' ', '')) + 1 end`;
+
+const localDay = sql<string>`strftime('%Y-%m-%d', ${transcriptions.timestamp}, 'unixepoch', 'localtime')`;
+
+function formatDay(date: Date): string {
+  const pad = (value: number) => value.toString().padStart(2, "0");
+  return `${date.getFullYear()}-${pad(date.getMonth() + 1)}-${pad(date.getDate())}`;
+}
+
+function dayNumber(day: string): number {
+  return Math.round(Date.parse(`${day}T00:00:00Z`) / DAY_MS);
+}
+
+/**
+ * Current and longest runs of consecutive days with at least one
+ * dictation. The current streak survives until the end of today, so it
+ * counts from yesterday when nothing has been dictated yet today.
+ */
+export function computeStreaks(days: string[], today: string) {
+  const numbers = [...new Set(days.map(dayNumber))].sort((a, b) => a - b);
+  let longest = 0;
+  let run = 0;
+  for (let i = 0; i < numbers.length; i++) {
+    run = i > 0 && numbers[i] === numbers[i - 1] + 1 ? run + 1 : 1;
+    longest = Math.max(longest, run);
+  }
+
+  const last = numbers[numbers.length - 1];
+  const todayNumber = dayNumber(today);
+  const current = last === todayNumber || last === todayNumber - 1 ? run : 0;
+  return { current, longest };
+}
+
+// Dictation statistics over an optional timestamp range (to is exclusive)
+export async function getDictationStats(options: { from?: Date; to?: Date }) {
+  const { from, to } = options;
+  const conditions: SQL[] = [];
+  if (from) conditions.push(gte(transcriptions.timestamp, from));
+  if (to) conditions.push(lt(transcriptions.timestamp, to));
+  const where = conditions.length > 0 ? and(...conditions) : undefined;
+
+  const [totals] = await db
+    .select({
+      transcriptions: sql<number>`count(*)`,
+      words: sql<number>`coalesce(sum(${wordCount}), 0)`,
+      durationSeconds: sql<number>`coalesce(sum(${transcriptions.duration}), 0)`,
+    })
+    .from(transcriptions)
+    .where(where);
+
+  // WPM only over rows with a known duration, so their words and time match
+  const [timed] = await db
+    .select({
+      words: sql<number>`coalesce(sum(${wordCount}), 0)`,
+      durationSeconds: sql<number>`coalesce(sum(${transcriptions.duration}), 0)`,
+    })
+    .from(transcriptions)
+    .where(and(where, gt(transcriptions.duration, 0)));
+
+  const daily = await db
+    .select({
+      day: localDay,
+      transcriptions: sql<number>`count(*)`,
+      words: sql<number>`coalesce(sum(${wordCount}), 0)`,
+    })
+    .from(transcriptions)
+    .where(where)
+    .groupBy(localDay)
+    .orderBy(localDay);
+
+  const byModel = await db
+    .select({
+      speechModel: transcriptions.speechModel,
+      transcriptions: sql<number>`count(*)`,
+      words: sql<number>`coalesce(sum(${wordCount}), 0)`,
+    })
+    .from(transcriptions)
+    .where(where)
+    .groupBy(transcriptions.speechModel)
+    .orderBy(desc(sql`count(*)`));
+
+  const byLanguage = await db
+    .select({
+      language: transcriptions.language,
+      transcriptions: sql<number>`count(*)`,
+      words: sql<number>`coalesce(sum(${wordCount}), 0)`,
+    })
+    .from(transcriptions)
+    .where(where)
+    .groupBy(transcriptions.language)
+    .orderBy(desc(sql`count(*)`));
+
+  return {
+    totals: {
+      ...totals,
+      averageWpm:
+        timed.durationSeconds > 0
+          ? timed.words / (timed.durationSeconds / 60)
+          : null,
+    },
+    streaks: computeStreaks(
+      daily.map((row) => row.day),
+      formatDay(new Date()),
+    ),
+    daily,
+    byModel,
+    byLanguage,
+  };
+}
@@ -0,0 +1,74 @@
+import { eq, asc, or, isNull } from "drizzle-orm";
+import { db } from ".";
+import {
+  symbolShortcuts,
+  type SymbolShortcut,
+  type NewSymbolShortcut,
+} from "./schema";
+
+// Create a new symbol shortcut
+export async function createSymbolShortcut(
+  data: Omit<NewSymbolShortcut, "id" | "createdAt" | "updatedAt">,
+) {
+  const now = new Date();
+
+  const result = await db
+    .insert(symbolShortcuts)
+    .values({
+      ...data,
+      phrase: data.phrase.trim().toLowerCase(),
+      createdAt: now,
+      updatedAt: now,
+    })
+    .returning();
+  return result[0];
+}
+
+// Get symbol shortcuts, optionally for one language (plus language-neutral ones)
+export async function getSymbolShortcuts(language?: string) {
+  const query = db.select().from(symbolShortcuts);
+  if (language) {
+    return await query
+      .where(
+        or(
+          eq(symbolShortcuts.language, language),
+          isNull(symbolShortcuts.language),
+        ),
+      )
+      .orderBy(asc(symbolShortcuts.phrase));
+  }
+  return await query.orderBy(
+    asc(symbolShortcuts.language),
+    asc(symbolShortcuts.phrase),
+  );
+}
+
+// Update symbol shortcut
+export async function updateSymbolShortcut(
+  id: number,
+  data: Partial<Omit<SymbolShortcut, "id" | "createdAt">>,
+) {
+  const result = await db
+    .update(symbolShortcuts)
+    .set({
+      ...data,
+      ...(data.phrase !== undefined && {
+        phrase: data.phrase.trim().toLowerCase(),
+      }),
+      updatedAt: new Date(),
+    })
+    .where(eq(symbolShortcuts.id, id))
+    .returning();
+
+  return result[0] || null;
+}
+
+// Delete symbol shortcut
+export async function deleteSymbolShortcut(id: number) {
+  const result = await db
+    .delete(symbolShortcuts)
+    .where(eq(symbolShortcuts.id, id))
+    .returning();
+
+  return result[0] || null;
+}
@@ -9,15 +9,62 @@ import {
   lte,
   sql,
   like,
+  getTableColumns,
+  isNotNull,
+  isNull,
+  inArray,
+  notInArray,
+  gt,
+  lt,
+  or,
+  type SQL,
 } from "drizzle-orm";
-import { db } from ".";
+import { createHash } from "node:crypto";
+import { db, withTransaction } from ".";
 import {
   transcriptions,
+  transcriptionTags,
+  transcriptionRevisions,
   type Transcription,
   type NewTranscription,
 } from "./schema";
 
-// Create a new transcription
+const PREVIEW_MAX_LENGTH = 120;
+
+// Short single-line summary shown in list views: the first sentence, clipped
+export function buildTranscriptionPreview(text: string): string {
+  const normalized = text.replace(/\s+/g, " ").trim();
+  const sentenceEnd = normalized.search(/[.!?。！？](\s|$)/);
+  const firstSentence =
+    sentenceEnd === -1 ? normalized : normalized.slice(0, sentenceEnd + 1);
+  if (firstSentence.length <= PREVIEW_MAX_LENGTH) {
+    return firstSentence;
+  }
+  const clipped = firstSentence.slice(0, PREVIEW_MAX_LENGTH - 1);
+  const lastSpace = clipped.lastIndexOf(" ");
+  return `${lastSpace > PREVIEW_MAX_LENGTH / 2 ? clipped.slice(0, lastSpace) : clipped}…`;
+}
+
+// Hash of the text with case, punctuation and spacing ignored, so the same
+// words dictated twice match. Null for text without words.
+export function hashTranscriptionText(text: string): string | null {
+  const words = text
+    .normalize("NFKC")
+    .toLowerCase()
+    .match(/[\p{L}\p{N}]+/gu);
+  return words
+    ? createHash("sha1").update(words.join(" ")).digest("hex")
+    : null;
+}
+
+// Dictations into the same app at most this far apart form one session
+const SESSION_GAP_MS = 5 * 60 * 1000;
+
+// A session is identified by the id of its first transcription
+const sessionKey = sql<number>`coalesce(${transcriptions.dictationSessionId}, ${transcriptions.id})`;
+
+// Create a new transcription, joining the latest one's dictation session
+// when it went into the same app shortly before
 export async function createTranscription(
   data: Omit<NewTranscription, "id" | "createdAt" | "updatedAt">,
 ) {
@@ -25,59 +72,263 @@ export async function createTranscription(
 
   const newTranscription: NewTranscription = {
     ...data,
+    preview: buildTranscriptionPreview(data.text),
+    textHash: hashTranscriptionText(data.text),
     timestamp: data.timestamp || now,
     createdAt: now,
     updatedAt: now,
   };
 
-  const result = await db
-    .insert(transcriptions)
-    .values(newTranscription)
-    .returning();
-  return result[0];
+  return await withTransaction(async (tx) => {
+    const [latest] = await tx
+      .select({
+        id: transcriptions.id,
+        timestamp: transcriptions.timestamp,
+        targetAppBundleId: transcriptions.targetAppBundleId,
+        dictationSessionId: transcriptions.dictationSessionId,
+      })
+      .from(transcriptions)
+      .orderBy(desc(transcriptions.timestamp), desc(transcriptions.id))
+      .limit(1);
+    const timestamp = newTranscription.timestamp!;
+    if (
+      latest &&
+      newTranscription.dictationSessionId === undefined &&
+      (latest.targetAppBundleId ?? null) ===
+        (newTranscription.targetAppBundleId ?? null) &&
+      timestamp.getTime() - latest.timestamp.getTime() <= SESSION_GAP_MS &&
+      timestamp >= latest.timestamp
+    ) {
+      newTranscription.dictationSessionId =
+        latest.dictationSessionId ?? latest.id;
+    }
+
+    const result = await tx
+      .insert(transcriptions)
+      .values(newTranscription)
+      .returning();
+    return result[0];
+  });
+}
+
+const PREVIEW_BACKFILL_BATCH = 500;
+
+// Fill in the preview of rows saved before the column existed, so list views
+// get the same first-sentence preview as new dictations
+export async function backfillTranscriptionPreviews(): Promise<number> {
+  let filled = 0;
+  for (;;) {
+    const rows = await db
+      .select({ id: transcriptions.id, text: transcriptions.text })
+      .from(transcriptions)
+      .where(isNull(transcriptions.preview))
+      .limit(PREVIEW_BACKFILL_BATCH);
+    if (rows.length === 0) return filled;
+
+    await withTransaction(async (tx) => {
+      for (const row of rows) {
+        await tx
+          .update(transcriptions)
+          .set({ preview: buildTranscriptionPreview(row.text) })
+          .where(eq(transcriptions.id, row.id));
+      }
+    });
+    filled += rows.length;
+  }
+}
+
+export type TranscriptionField = keyof Transcription;
+
+// Column selection for list queries. `id` is always included. Rows are typed
+// as full transcriptions, so callers passing `fields` must only read those.
+function selectTranscriptionColumns(fields?: TranscriptionField[]) {
+  const columns = getTableColumns(transcriptions);
+  if (!fields) {
+    return columns;
+  }
+  const picked: Partial<typeof columns> = { id: columns.id };
+  for (const field of fields) {
+    picked[field] = columns[field] as never;
+  }
+  return picked as typeof columns;
+}
+
+// Opaque keyset cursor: "<sort column in unix seconds>:<id>" of the last row
+function encodeCursor(sortValue: Date, id: number): string {
+  return `${Math.floor(sortValue.getTime() / 1000)}:${id}`;
+}
+
+function decodeCursor(cursor: string): { seconds: number; id: number } | null {
+  const match = cursor.match(/^(-?\d+):(\d+)$/);
+  return match ? { seconds: Number(match[1]), id: Number(match[2]) } : null;
+}
+
+export interface TranscriptionFilters {
+  search?: string;
+  from?: Date; // Inclusive, on timestamp
+  to?: Date; // Exclusive, on timestamp
+  language?: string;
+  speechModel?: string;
+  favorite?: boolean;
+  tag?: string;
+  targetAppBundleId?: string;
+  sessionId?: number; // From getDictationSessions
+}
+
+function transcriptionFilterConditions(
+  filters: TranscriptionFilters,
+): SQL | undefined {
+  const {
+    search,
+    from,
+    to,
+    language,
+    speechModel,
+    favorite,
+    tag,
+    targetAppBundleId,
+    sessionId,
+  } = filters;
+  const conditions: SQL[] = [];
+  if (search) {
+    conditions.push(
+      sql`${transcriptions.text} LIKE ${`%${search}%`} COLLATE NOCASE`,
+    );
+  }
+  if (from) conditions.push(gte(transcriptions.timestamp, from));
+  if (to) conditions.push(lt(transcriptions.timestamp, to));
+  if (language) conditions.push(eq(transcriptions.language, language));
+  if (speechModel) {
+    conditions.push(eq(transcriptions.speechModel, speechModel));
+  }
+  if (favorite !== undefined) {
+    conditions.push(eq(transcriptions.isFavorite, favorite));
+  }
+  if (targetAppBundleId) {
+    conditions.push(eq(transcriptions.targetAppBundleId, targetAppBundleId));
+  }
+  if (sessionId !== undefined) {
+    conditions.push(sql`${sessionKey} = ${sessionId}`);
+  }
+  if (tag) {
+    conditions.push(
+      inArray(
+        transcriptions.id,
+        db
+          .select({ id: transcriptionTags.transcriptionId })
+          .from(transcriptionTags)
+          .where(eq(transcriptionTags.tag, tag)),
+      ),
+    );
+  }
+  return conditions.length > 0 ? and(...conditions) : undefined;
 }
 
-// Get all transcriptions with pagination and sorting
+// Get a page of transcriptions with sorting and pagination metadata.
+// Pass `cursor` (the previous page's nextCursor) instead of `offset` for
+// stable paging through large histories while new rows are being added.
 export async function getTranscriptions(
-  options: {
+  options: TranscriptionFilters & {
     limit?: number;
     offset?: number;
+    cursor?: string;
     sortBy?: "timestamp" | "createdAt";
     sortOrder?: "asc" | "desc";
-    search?: string;
+    fields?: TranscriptionField[];
   } = {},
 ) {
   const {
     limit = 50,
     offset = 0,
+    cursor,
     sortBy = "timestamp",
     sortOrder = "desc",
-    search,
+    fields,
+    ...filters
   } = options;
+  // The sort column is needed for the next cursor even if not requested
+  const columns = selectTranscriptionColumns(
+    fields ? [...fields, sortBy] : undefined,
+  );
+
+  // The window total is computed over every match before the cursor
+  // filter and limit apply, so one query yields both the page and the count
+  const matching = db
+    .select({
+      ...columns,
+      total: sql<number>`count(*) over ()`.as("total"),
+    })
+    .from(transcriptions)
+    .where(transcriptionFilterConditions(filters))
+    .as("matching");
 
-  // Build query with conditional where clause
-  const sortColumn =
-    sortBy === "timestamp"
-      ? transcriptions.timestamp
-      : transcriptions.createdAt;
+  const sortColumn = matching[sortBy];
   const orderFn = sortOrder === "asc" ? asc : desc;
+  const position = cursor ? decodeCursor(cursor) : null;
+  if (cursor && !position) {
+    throw new Error("Invalid cursor");
+  }
 
-  if (search) {
-    return await db
-      .select()
-      .from(transcriptions)
-      .where(sql`${transcriptions.text} LIKE ${`%${search}%`} COLLATE NOCASE`)
-      .orderBy(orderFn(sortColumn))
-      .limit(limit)
-      .offset(offset);
-  } else {
-    return await db
-      .select()
-      .from(transcriptions)
-      .orderBy(orderFn(sortColumn))
-      .limit(limit)
-      .offset(offset);
+  // Rows strictly after the cursor in sort order, id breaking ties
+  let afterCursor: SQL | undefined;
+  if (position) {
+    const compare = sortOrder === "asc" ? gt : lt;
+    const value = new Date(position.seconds * 1000);
+    afterCursor = or(
+      compare(sortColumn, value),
+      and(eq(sortColumn, value), compare(matching.id, position.id)),
+    );
   }
+
+  const rows = await db
+    .select()
+    .from(matching)
+    .where(afterCursor)
+    .orderBy(orderFn(sortColumn), orderFn(matching.id))
+    .limit(limit + 1)
+    .offset(position ? 0 : offset);
+
+  const hasMore = rows.length > limit;
+  const last = rows[Math.min(rows.length, limit) - 1];
+  const nextCursor =
+    hasMore && last ? encodeCursor(last[sortBy], last.id) : undefined;
+  const items = rows.slice(0, limit).map(({ total: _total, ...row }) => {
+    if (fields && !fields.includes(sortBy)) {
+      delete (row as Partial<typeof row>)[sortBy];
+    }
+    return row;
+  });
+  const total =
+    rows.length > 0 ? rows[0].total : await getTranscriptionsCount(filters);
+
+  return { items, total, hasMore, nextCursor };
+}
+
+// Get every transcription matching the filters, oldest first
+export async function getAllTranscriptions(filters: TranscriptionFilters = {}) {
+  return await db
+    .select()
+    .from(transcriptions)
+    .where(transcriptionFilterConditions(filters))
+    .orderBy(asc(transcriptions.timestamp), asc(transcriptions.id));
+}
+
+// Latest transcription since `since` with the same words as `text`
+export async function findRecentDuplicate(text: string, since: Date) {
+  const textHash = hashTranscriptionText(text);
+  if (!textHash) return null;
+  const result = await db
+    .select()
+    .from(transcriptions)
+    .where(
+      and(
+        eq(transcriptions.textHash, textHash),
+        gte(transcriptions.timestamp, since),
+      ),
+    )
+    .orderBy(desc(transcriptions.timestamp), desc(transcriptions.id))
+    .limit(1);
+  return result[0] || null;
 }
 
 // Get transcription by ID
@@ -89,25 +340,167 @@ export async function getTranscriptionById(id: number) {
   return result[0] || null;
 }
 
-// Update transcription
+// Get the most recent transcription
+export async function getLatestTranscription() {
+  const result = await db
+    .select()
+    .from(transcriptions)
+    .orderBy(desc(transcriptions.timestamp), desc(transcriptions.id))
+    .limit(1);
+  return result[0] || null;
+}
+
+// Update transcription. A changed text is kept as a revision first.
 export async function updateTranscription(
   id: number,
   data: Partial<Omit<Transcription, "id" | "createdAt">>,
 ) {
+  const now = new Date();
   const updateData = {
     ...data,
-    updatedAt: new Date(),
+    ...(data.text !== undefined && {
+      preview: buildTranscriptionPreview(data.text),
+      textHash: hashTranscriptionText(data.text),
+    }),
+    updatedAt: now,
   };
 
+  return await withTransaction(async (tx) => {
+    if (data.text !== undefined) {
+      const [current] = await tx
+        .select({ text: transcriptions.text })
+        .from(transcriptions)
+        .where(eq(transcriptions.id, id));
+      if (current && current.text !== data.text) {
+        await tx.insert(transcriptionRevisions).values({
+          transcriptionId: id,
+          text: current.text,
+          createdAt: now,
+        });
+      }
+    }
+
+    const result = await tx
+      .update(transcriptions)
+      .set(updateData)
+      .where(eq(transcriptions.id, id))
+      .returning();
+    return result[0] || null;
+  });
+}
+
+// Earlier texts of a transcription, newest first
+export async function getTranscriptionRevisions(transcriptionId: number) {
+  return await db
+    .select()
+    .from(transcriptionRevisions)
+    .where(eq(transcriptionRevisions.transcriptionId, transcriptionId))
+    .orderBy(desc(transcriptionRevisions.id));
+}
+
+// Restore a revision's text. The text being replaced becomes a revision
+// itself, so reverting can be undone.
+export async function revertToRevision(revisionId: number) {
+  const [revision] = await db
+    .select()
+    .from(transcriptionRevisions)
+    .where(eq(transcriptionRevisions.id, revisionId));
+  if (!revision) {
+    return null;
+  }
+  return await updateTranscription(revision.transcriptionId, {
+    text: revision.text,
+  });
+}
+
+// Star or unstar a transcription. Not an edit, so updatedAt is untouched.
+export async function setTranscriptionFavorite(
+  id: number,
+  isFavorite: boolean,
+) {
   const result = await db
     .update(transcriptions)
-    .set(updateData)
+    .set({ isFavorite })
     .where(eq(transcriptions.id, id))
     .returning();
-
   return result[0] || null;
 }
 
+// Get a transcription's tags, alphabetically
+export async function getTranscriptionTags(id: number): Promise<string[]> {
+  const rows = await db
+    .select({ tag: transcriptionTags.tag })
+    .from(transcriptionTags)
+    .where(eq(transcriptionTags.transcriptionId, id))
+    .orderBy(asc(transcriptionTags.tag));
+  return rows.map((row) => row.tag);
+}
+
+// Replace a transcription's tags. Tags are trimmed and deduplicated.
+export async function setTranscriptionTags(id: number, tags: string[]) {
+  const unique = [...new Set(tags.map((tag) => tag.trim()).filter(Boolean))];
+  await withTransaction(async (tx) => {
+    await tx
+      .delete(transcriptionTags)
+      .where(eq(transcriptionTags.transcriptionId, id));
+    if (unique.length > 0) {
+      await tx
+        .insert(transcriptionTags)
+        .values(unique.map((tag) => ({ transcriptionId: id, tag })));
+    }
+  });
+  return unique.sort();
+}
+
+// Every tag in use with how many transcriptions carry it
+export async function getAllTags() {
+  return await db
+    .select({ tag: transcriptionTags.tag, count: count() })
+    .from(transcriptionTags)
+    .groupBy(transcriptionTags.tag)
+    .orderBy(asc(transcriptionTags.tag));
+}
+
+// Apps dictated into, most used first
+export async function getTargetApps() {
+  return await db
+    .select({
+      bundleId: sql<string>`${transcriptions.targetAppBundleId}`,
+      name: sql<string | null>`max(${transcriptions.targetAppName})`,
+      count: count(),
+    })
+    .from(transcriptions)
+    .where(isNotNull(transcriptions.targetAppBundleId))
+    .groupBy(transcriptions.targetAppBundleId)
+    .orderBy(desc(count()));
+}
+
+// Dictation sessions, most recent first. Rows created before sessions
+// were tracked each count as their own session.
+export async function getDictationSessions(
+  options: { limit?: number; offset?: number } = {},
+) {
+  const { limit = 50, offset = 0 } = options;
+  return await db
+    .select({
+      sessionId: sessionKey,
+      targetAppBundleId: transcriptions.targetAppBundleId,
+      targetAppName: sql<string | null>`max(${transcriptions.targetAppName})`,
+      startedAt: sql<Date>`min(${transcriptions.timestamp})`.mapWith(
+        transcriptions.timestamp,
+      ),
+      endedAt: sql<Date>`max(${transcriptions.timestamp})`.mapWith(
+        transcriptions.timestamp,
+      ),
+      count: count(),
+    })
+    .from(transcriptions)
+    .groupBy(sessionKey, transcriptions.targetAppBundleId)
+    .orderBy(desc(sql`max(${transcriptions.timestamp})`), desc(sessionKey))
+    .limit(limit)
+    .offset(offset);
+}
+
 // Delete transcription
 export async function deleteTranscription(id: number) {
   const result = await db
@@ -118,18 +511,81 @@ export async function deleteTranscription(id: number) {
   return result[0] || null;
 }
 
-// Get transcriptions count
-export async function getTranscriptionsCount(search?: string) {
-  if (search) {
-    const result = await db
-      .select({ count: count() })
+// Delete several transcriptions, returning the removed rows' audio files
+export async function deleteTranscriptions(ids: number[]) {
+  if (ids.length === 0) return [];
+  return await db
+    .delete(transcriptions)
+    .where(inArray(transcriptions.id, ids))
+    .returning({ id: transcriptions.id, audioFile: transcriptions.audioFile });
+}
+
+// IDs of transcriptions outside the retention policy: older than
+// `olderThan`, or beyond the newest `keepLatest` rows. Favorites are never
+// returned and don't count towards `keepLatest`.
+export async function getExpiredTranscriptionIds(options: {
+  olderThan?: Date;
+  keepLatest?: number;
+  limit: number;
+}) {
+  const { olderThan, keepLatest, limit } = options;
+  const conditions: SQL[] = [];
+  if (olderThan) {
+    conditions.push(lt(transcriptions.timestamp, olderThan));
+  }
+  if (keepLatest !== undefined) {
+    const newest = db
+      .select({ id: transcriptions.id })
       .from(transcriptions)
-      .where(sql`${transcriptions.text} LIKE ${`%${search}%`} COLLATE NOCASE`);
-    return result[0]?.count || 0;
-  } else {
-    const result = await db.select({ count: count() }).from(transcriptions);
-    return result[0]?.count || 0;
+      .where(eq(transcriptions.isFavorite, false))
+      .orderBy(desc(transcriptions.timestamp), desc(transcriptions.id))
+      .limit(keepLatest);
+    conditions.push(notInArray(transcriptions.id, newest));
   }
+  if (conditions.length === 0) return [];
+
+  const rows = await db
+    .select({ id: transcriptions.id })
+    .from(transcriptions)
+    .where(and(eq(transcriptions.isFavorite, false), or(...conditions)))
+    .orderBy(asc(transcriptions.timestamp))
+    .limit(limit);
+  return rows.map((row) => row.id);
+}
+
+// Clear the audio file of up to `limit` transcriptions recorded before
+// `olderThan`, returning the paths that were detached
+export async function detachExpiredAudioFiles(olderThan: Date, limit: number) {
+  return await withTransaction(async (tx) => {
+    const rows = await tx
+      .select({ id: transcriptions.id, audioFile: transcriptions.audioFile })
+      .from(transcriptions)
+      .where(
+        and(
+          isNotNull(transcriptions.audioFile),
+          lt(transcriptions.timestamp, olderThan),
+        ),
+      )
+      .limit(limit);
+    if (rows.length === 0) return [];
+
+    await tx
+      .update(transcriptions)
+      .set({ audioFile: null })
+      .where(inArray(transcriptions.id, rows.map((row) => row.id)));
+    return rows.map((row) => ({ id: row.id, audioFile: row.audioFile! }));
+  });
+}
+
+// Get transcriptions count
+export async function getTranscriptionsCount(
+  filters: TranscriptionFilters = {},
+) {
+  const result = await db
+    .select({ count: count() })
+    .from(transcriptions)
+    .where(transcriptionFilterConditions(filters));
+  return result[0]?.count || 0;
 }
 
 // Get transcriptions by date range
@@ -158,12 +614,170 @@ export async function getTranscriptionsByLanguage(language: string) {
     .orderBy(desc(transcriptions.timestamp));
 }
 
-// Search transcriptions
-export async function searchTranscriptions(searchTerm: string, limit = 20) {
+// Get transcriptions by ID (order not preserved)
+export async function getTranscriptionsByIds(ids: number[]) {
+  if (ids.length === 0) return [];
   return await db
     .select()
     .from(transcriptions)
-    .where(sql`${transcriptions.text} LIKE ${`%${searchTerm}%`} COLLATE NOCASE`)
-    .orderBy(desc(transcriptions.timestamp))
+    .where(inArray(transcriptions.id, ids));
+}
+
+// The trigram index can only match terms of at least three characters
+const MIN_INDEXED_TERM_LENGTH = 3;
+// Characters of context either side of the first hit in scanned results
+const EXCERPT_CONTEXT = 40;
+
+// Private-use characters marking hits in snippet(). Speech models don't
+// produce them, so the excerpt can be escaped before they become <mark>.
+const HIT_START = "\uE000";
+const HIT_END = "\uE001";
+
+function searchTerms(searchTerm: string): string[] {
+  return searchTerm.match(/[\p{L}\p{N}_]+/gu) ?? [];
+}
+
+// LIKE pattern for text containing `term`; `_` is the only wildcard a term
+// can contain
+function containsPattern(term: string): string {
+  return `%${term.replace(/_/g, "\\_")}%`;
+}
+
+function escapeHtml(text: string): string {
+  return text.replace(/[&<>"']/g, (char) => `&#${char.charCodeAt(0)};`);
+}
+
+function markIndexedHits(snippet: string): string {
+  return escapeHtml(snippet)
+    .replaceAll(HIT_START, "<mark>")
+    .replaceAll(HIT_END, "</mark>");
+}
+
+// Excerpt around the first hit with every hit marked, for results found
+// without the index
+function markScannedHits(text: string, terms: string[]): string {
+  const lower = text.toLowerCase();
+  const hits = terms
+    .map((term) => lower.indexOf(term.toLowerCase()))
+    .filter((index) => index >= 0);
+  const first = hits.length > 0 ? Math.min(...hits) : 0;
+  const start = Math.max(0, first - EXCERPT_CONTEXT);
+  const end = Math.min(text.length, first + EXCERPT_CONTEXT * 2);
+  const excerpt = text.slice(start, end);
+
+  // Terms are word characters only, so they're safe in a pattern
+  const parts = excerpt.split(new RegExp(`(${terms.join("|")})`, "giu"));
+  const html = parts
+    .map((part, i) =>
+      i % 2 ? `<mark>${escapeHtml(part)}</mark>` : escapeHtml(part),
+    )
+    .join("");
+  return `${start > 0 ? "…" : ""}${html}${end < text.length ? "…" : ""}`;
+}
+
+// Search transcriptions, best matches first. `snippet` is an HTML-escaped
+// excerpt with hits wrapped in <mark></mark>. The index ignores accents, so
+// "cafe" finds "café". Terms too short for the trigram index (common for
+// Chinese and Japanese words) fall back to a scan, newest first, which
+// matches accents exactly.
+export async function searchTranscriptions(searchTerm: string, limit = 20) {
+  const terms = searchTerms(searchTerm);
+  if (terms.length === 0) {
+    return [];
+  }
+
+  if (terms.some((term) => [...term].length < MIN_INDEXED_TERM_LENGTH)) {
+    const rows = await db
+      .select()
+      .from(transcriptions)
+      .where(
+        and(
+          ...terms.map(
+            (term) =>
+              sql`${transcriptions.text} LIKE ${containsPattern(term)} ESCAPE '\\'`,
+          ),
+        ),
+      )
+      .orderBy(desc(transcriptions.timestamp))
+      .limit(limit);
+    return rows.map((row) => ({
+      ...row,
+      snippet: markScannedHits(row.text, terms),
+      rank: 0,
+    }));
+  }
+
+  // Quoting keeps FTS syntax characters in the input inert
+  const query = terms.map((term) => `"${term}"`).join(" ");
+  const rows = await db
+    .select({
+      ...getTableColumns(transcriptions),
+      snippet: sql<string>`snippet(transcriptions_fts, 0, ${HIT_START}, ${HIT_END}, '…', 32)`,
+      rank: sql<number>`bm25(transcriptions_fts)`,
+    })
+    .from(transcriptions)
+    .innerJoin(
+      sql`transcriptions_fts`,
+      sql`transcriptions_fts.rowid = ${transcriptions.id}`,
+    )
+    .where(sql`transcriptions_fts MATCH ${query}`)
+    .orderBy(sql`bm25(transcriptions_fts)`, desc(transcriptions.timestamp))
     .limit(limit);
+  return rows.map((row) => ({
+    ...row,
+    snippet: markIndexedHits(row.snippet),
+  }));
+}
+
+// Get transcriptions flagged for review (oldest first so the queue drains in order)
+export async function getReviewQueue(
+  options: { limit?: number; offset?: number } = {},
+) {
+  const { limit = 50, offset = 0 } = options;
+
+  return await db
+    .select()
+    .from(transcriptions)
+    .where(eq(transcriptions.needsReview, true))
+    .orderBy(asc(transcriptions.timestamp))
+    .limit(limit)
+    .offset(offset);
+}
+
+// Get the audio file paths that are still referenced by a transcription
+export async function getReferencedAudioFiles(): Promise<Set<string>> {
+  const rows = await db
+    .select({ audioFile: transcriptions.audioFile })
+    .from(transcriptions)
+    .where(isNotNull(transcriptions.audioFile));
+  return new Set(rows.map((row) => row.audioFile!));
+}
+
+// Transcriptions that reference an audio file
+export async function getTranscriptionAudioFiles() {
+  const rows = await db
+    .select({ id: transcriptions.id, audioFile: transcriptions.audioFile })
+    .from(transcriptions)
+    .where(isNotNull(transcriptions.audioFile));
+  return rows.map((row) => ({ id: row.id, audioFile: row.audioFile! }));
+}
+
+// Forget the audio file of these transcriptions; returns rows updated
+export async function clearTranscriptionAudioFiles(ids: number[]) {
+  if (ids.length === 0) return 0;
+  const result = await db
+    .update(transcriptions)
+    .set({ audioFile: null })
+    .where(inArray(transcriptions.id, ids))
+    .returning({ id: transcriptions.id });
+  return result.length;
+}
+
+// Get number of transcriptions awaiting review
+export async function getReviewQueueCount() {
+  const result = await db
+    .select({ count: count() })
+    .from(transcriptions)
+    .where(eq(transcriptions.needsReview, true));
+  return result[0]?.count || 0;
 }
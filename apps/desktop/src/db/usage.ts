@@ -0,0 +1,97 @@
+import { and, desc, gte, lt, sql } from "drizzle-orm";
+import { db } from ".";
+import { usage, type NewUsage } from "./schema";
+import { logger } from "../main/logger";
+import {
+  estimateAudioCost,
+  estimateTokenCost,
+} from "../constants/model-pricing";
+
+export type UsageKind = NewUsage["kind"];
+
+// The AI SDK reports NaN when the API response omits usage
+function tokenCount(value: number | undefined): number {
+  return value !== undefined && Number.isFinite(value) ? value : 0;
+}
+
+// Record a language model call; never throws so tracking can't break dictation
+export async function recordTokenUsage(data: {
+  provider: string;
+  model: string;
+  kind: UsageKind;
+  promptTokens?: number;
+  completionTokens?: number;
+}) {
+  const promptTokens = tokenCount(data.promptTokens);
+  const completionTokens = tokenCount(data.completionTokens);
+  try {
+    await db.insert(usage).values({
+      provider: data.provider,
+      model: data.model,
+      kind: data.kind,
+      promptTokens,
+      completionTokens,
+      estimatedCost: estimateTokenCost(
+        data.provider,
+        data.model,
+        promptTokens,
+        completionTokens,
+      ),
+      createdAt: new Date(),
+    });
+  } catch (error) {
+    logger.db.warn("Failed to record token usage", { error });
+  }
+}
+
+// Record a cloud transcription request; never throws
+export async function recordTranscriptionUsage(data: {
+  provider: string;
+  model: string;
+  audioSeconds: number;
+}) {
+  try {
+    await db.insert(usage).values({
+      provider: data.provider,
+      model: data.model,
+      kind: "transcription",
+      audioSeconds: data.audioSeconds,
+      estimatedCost: estimateAudioCost(
+        data.provider,
+        data.model,
+        data.audioSeconds,
+      ),
+      createdAt: new Date(),
+    });
+  } catch (error) {
+    logger.db.warn("Failed to record transcription usage", { error });
+  }
+}
+
+// Usage totals grouped by local calendar day, provider and model
+export async function getUsageSummary(options: { from?: Date; to?: Date }) {
+  const { from, to } = options;
+  const day = sql<string>`strftime('%Y-%m-%d', ${usage.createdAt}, 'unixepoch', 'localtime')`;
+
+  const conditions = [];
+  if (from) conditions.push(gte(usage.createdAt, from));
+  if (to) conditions.push(lt(usage.createdAt, to));
+
+  return await db
+    .select({
+      day,
+      provider: usage.provider,
+      model: usage.model,
+      requests: sql<number>`count(*)`,
+      promptTokens: sql<number>`coalesce(sum(${usage.promptTokens}), 0)`,
+      completionTokens: sql<number>`coalesce(sum(${usage.completionTokens}), 0)`,
+      audioSeconds: sql<number>`coalesce(sum(${usage.audioSeconds}), 0)`,
+      estimatedCost: sql<number>`coalesce(sum(${usage.estimatedCost}), 0)`,
+      // Rows whose model has no known price; the cost is a lower bound
+      unpricedRequests: sql<number>`sum(case when ${usage.estimatedCost} is null then 1 else 0 end)`,
+    })
+    .from(usage)
+    .where(conditions.length > 0 ? and(...conditions) : undefined)
+    .groupBy(day, usage.provider, usage.model)
+    .orderBy(desc(day), usage.provider, usage.model);
+}
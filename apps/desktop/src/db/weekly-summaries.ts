@@ -0,0 +1,31 @@
+import { desc, eq } from "drizzle-orm";
+import { db } from ".";
+import { weeklySummaries, type NewWeeklySummary } from "./schema";
+
+// Insert or replace the summary for a week
+export async function upsertWeeklySummary(data: NewWeeklySummary) {
+  const { weekStart: _weekStart, ...update } = data;
+  const result = await db
+    .insert(weeklySummaries)
+    .values(data)
+    .onConflictDoUpdate({ target: weeklySummaries.weekStart, set: update })
+    .returning();
+  return result[0];
+}
+
+export async function getWeeklySummary(weekStart: Date) {
+  const result = await db
+    .select()
+    .from(weeklySummaries)
+    .where(eq(weeklySummaries.weekStart, weekStart));
+  return result[0] || null;
+}
+
+// Most recent weeks first
+export async function getWeeklySummaries(limit = 12) {
+  return await db
+    .select()
+    .from(weeklySummaries)
+    .orderBy(desc(weeklySummaries.weekStart))
+    .limit(limit);
+}
@@ -35,7 +35,7 @@ log.transports.file.format =
   "[{y}-{m}-{d} {h}:{i}:{s}.{ms}] [{level}] [{scope}] {text}";
 
 // Set custom log file path
-const logPath = isDev
+export const logPath = isDev
   ? path.join(app.getPath("userData"), "logs", "grizzo-dev.log")
   : path.join(app.getPath("logs"), "grizzo.log");
 
@@ -1,11 +1,14 @@
-import { ipcMain, app } from "electron";
+import { ipcMain } from "electron";
 import { EventEmitter } from "node:events";
 import { Mutex } from "async-mutex";
 import { logger, logPerformance } from "../logger";
 import type { ServiceManager } from "@/main/managers/service-manager";
 import type { RecordingState } from "../../types/recording";
+import type { FinalizedTranscription } from "../../pipeline/core/pipeline-types";
+import { MIN_MESSAGE_GAP_MS } from "../../pipeline/processing/message-splitting";
 import type { ShortcutManager } from "./shortcut-manager";
 import { StreamingWavWriter } from "../../utils/streaming-wav-writer";
+import { getRecordingsDirectory } from "../../utils/data-directory";
 import * as fs from "node:fs";
 import * as path from "node:path";
 
@@ -20,6 +23,13 @@ export type TerminationCode =
 const QUICK_PRESS_THRESHOLD = 500;
 const NO_AUDIO_TIMEOUT = 5000;
 const STUCK_STATE_TIMEOUT = 10000;
+const WATCHDOG_INTERVAL = 5000;
+export const DEFAULT_WATCHDOG_TIMEOUT = 60000;
+// Finalization (flush, formatting with its fallback, vocabulary correction)
+// can outlast the watchdog timeout on slow machines without being stuck
+export const FINALIZE_WATCHDOG_TIMEOUT = 5 * 60 * 1000;
+
+export type RecoveryAction = "finalized" | "discarded";
 
 /**
  * Manages recording state and coordinates audio recording across the application
@@ -32,6 +42,8 @@ const STUCK_STATE_TIMEOUT = 10000;
  * - Mutex serializes lifecycle operations (doStart, endRecording)
  * - Audio chunks accumulated in memory, file written only at the end
  * - Single terminationCode field determines final action in handleFinalChunk
+ * - A watchdog recovers sessions stuck outside IDLE (audio stopped arriving,
+ *   final chunk never sent, finalization hung) so dictation isn't blocked
  */
 export class RecordingManager extends EventEmitter {
   // Core state
@@ -47,9 +59,18 @@ export class RecordingManager extends EventEmitter {
   private noAudioTimer: NodeJS.Timeout | null = null;
   private stuckStateTimer: NodeJS.Timeout | null = null;
 
+  // Watchdog
+  private watchdogTimer: NodeJS.Timeout | null = null;
+  private watchdogTimeoutMs = DEFAULT_WATCHDOG_TIMEOUT;
+  private stateEnteredAt = 0;
+  private lastChunkAt = 0;
+  private finalizingSessionId: string | null = null;
+  private finalizingStartedAt = 0;
+
   // Session state
   private currentSessionId: string | null = null;
   private initPromise: Promise<void> | null = null;
+  private stopContextRefresh: Promise<void> | null = null;
   private firstChunkReceived: boolean = false;
 
   // In-memory audio buffer - written to file only in handleFinalChunk
@@ -95,6 +116,12 @@ export class RecordingManager extends EventEmitter {
   private setState(newState: RecordingState): void {
     const oldState = this.recordingState;
     this.recordingState = newState;
+    this.stateEnteredAt = Date.now();
+    if (newState === "idle") {
+      this.stopWatchdog();
+    } else {
+      this.startWatchdog();
+    }
 
     logger.audio.info("Recording state changed", {
       oldState,
@@ -282,6 +309,11 @@ export class RecordingManager extends EventEmitter {
       const nativeBridge = this.serviceManager.getService("nativeBridge");
       nativeBridge.refreshAccessibilityContext();
 
+      const settingsService = this.serviceManager.getService("settingsService");
+      this.watchdogTimeoutMs =
+        (await settingsService.getRecordingSettings())?.watchdogTimeoutMs ??
+        DEFAULT_WATCHDOG_TIMEOUT;
+
       // AWAIT mute to ensure it completes before mutex releases
       await nativeBridge.call("muteSystemAudio", {});
     } catch (error) {
@@ -327,6 +359,11 @@ export class RecordingManager extends EventEmitter {
       // Restore audio after state change (can happen while final chunk is in flight)
       try {
         const nativeBridge = this.serviceManager.getService("nativeBridge");
+        // Capture the app focused at stop time; finalization resolves the
+        // per-app mode from it
+        if (!code) {
+          this.stopContextRefresh = nativeBridge.refreshAccessibilityContext();
+        }
         await nativeBridge.call("restoreSystemAudio", {});
       } catch (error) {
         logger.main.warn("Failed to restore system audio", { error });
@@ -346,9 +383,12 @@ export class RecordingManager extends EventEmitter {
 
       // Safety timeout for stuck state
       this.stuckStateTimer = setTimeout(() => {
-        if (this.recordingState === "stopping") {
-          logger.audio.warn("No final chunk received, forcing idle");
-          this.forceIdle();
+        if (
+          this.recordingState === "stopping" &&
+          !this.isFinalizing(this.currentSessionId)
+        ) {
+          logger.audio.warn("No final chunk received, recovering session");
+          this.recoverStuckSession("stopping");
         }
       }, STUCK_STATE_TIMEOUT);
     });
@@ -374,6 +414,8 @@ export class RecordingManager extends EventEmitter {
       return;
     }
 
+    this.lastChunkAt = Date.now();
+
     // Wait for async init to complete
     if (this.initPromise) {
       await this.initPromise;
@@ -452,9 +494,13 @@ export class RecordingManager extends EventEmitter {
       this.stuckStateTimer = null;
     }
 
-    if (this.recordingState !== "stopping") {
+    if (
+      this.recordingState !== "stopping" ||
+      this.isFinalizing(this.currentSessionId)
+    ) {
       logger.audio.debug("Unexpected state in handleFinalChunk", {
         state: this.recordingState,
+        sessionId: this.currentSessionId,
       });
       return;
     }
@@ -524,8 +570,13 @@ export class RecordingManager extends EventEmitter {
     }
 
     // NORMAL - get transcription and paste
-    let result = "";
+    let result: FinalizedTranscription = { text: "" };
+    this.finalizingSessionId = sessionId;
+    this.finalizingStartedAt = Date.now();
     try {
+      await this.stopContextRefresh;
+      this.stopContextRefresh = null;
+
       const transcriptionService = this.serviceManager.getService(
         "transcriptionService",
       );
@@ -537,15 +588,38 @@ export class RecordingManager extends EventEmitter {
       });
     } catch (error) {
       logger.audio.error("Failed to get final transcription", { error });
+    } finally {
+      if (this.finalizingSessionId === sessionId) {
+        this.finalizingSessionId = null;
+      }
+    }
+
+    // The watchdog gave up on this session while it was finalizing; the
+    // transcription is saved, but don't paste into whatever is focused now
+    if (this.currentSessionId !== sessionId) {
+      logger.audio.warn("Session recovered during finalization, not pasting", {
+        sessionId,
+      });
+      return;
     }
 
     logPerformance("streaming transcription complete", Date.now(), {
       sessionId,
-      resultLength: result?.length || 0,
+      resultLength: result.text.length,
     });
 
-    if (result) {
-      await this.pasteTranscription(result);
+    if (result.messages && result.messages.length > 1) {
+      await this.pasteMessages(
+        result.messages,
+        result.messageDelayMs ?? MIN_MESSAGE_GAP_MS,
+      );
+    } else if (result.replaceLength !== undefined) {
+      // Voice edit of the previous dictation
+      if (result.text || result.replaceLength > 0) {
+        await this.pasteTranscription(result.text, result.replaceLength);
+      }
+    } else if (result.text) {
+      await this.pasteTranscription(result.text);
     } else {
       // Check for empty transcript notification
       const sessionDurationMs =
@@ -644,9 +718,99 @@ export class RecordingManager extends EventEmitter {
     this.setState("idle");
   }
 
+  // ═══════════════════════════════════════════════════════════════════
+  // WATCHDOG
+  // ═══════════════════════════════════════════════════════════════════
+
+  private isFinalizing(sessionId: string | null): boolean {
+    return sessionId !== null && this.finalizingSessionId === sessionId;
+  }
+
+  private startWatchdog(): void {
+    if (this.watchdogTimer) return;
+    this.watchdogTimer = setInterval(() => {
+      this.checkWatchdog().catch((error) => {
+        logger.audio.error("Recording watchdog check failed", { error });
+      });
+    }, WATCHDOG_INTERVAL);
+  }
+
+  private stopWatchdog(): void {
+    if (this.watchdogTimer) {
+      clearInterval(this.watchdogTimer);
+      this.watchdogTimer = null;
+    }
+  }
+
+  private async checkWatchdog(): Promise<void> {
+    const now = Date.now();
+    const timeout = this.watchdogTimeoutMs;
+    const state = this.recordingState;
+
+    // A session that's being finalized gets its own, longer budget
+    if (
+      this.isFinalizing(this.currentSessionId) &&
+      now - this.finalizingStartedAt <=
+        Math.max(timeout, FINALIZE_WATCHDOG_TIMEOUT)
+    ) {
+      return;
+    }
+
+    const stuck =
+      state === "recording"
+        ? // Audio stopped arriving (renderer crashed or worklet died); the
+          // no-audio timer covers sessions that never got a first chunk
+          this.firstChunkReceived && now - this.lastChunkAt > timeout
+        : state !== "idle" && now - this.stateEnteredAt > timeout;
+
+    if (stuck) {
+      logger.audio.warn("Recording watchdog fired", {
+        state,
+        sessionId: this.currentSessionId,
+        timeoutMs: timeout,
+      });
+      await this.recoverStuckSession(state);
+    }
+  }
+
+  /**
+   * Get out of a stuck session: transcribe the buffered audio when this was
+   * a normal stop that simply never got its final chunk, otherwise discard.
+   */
+  private async recoverStuckSession(state: RecordingState): Promise<void> {
+    const sessionId = this.currentSessionId;
+    const canFinalize =
+      !this.isFinalizing(sessionId) &&
+      state !== "starting" &&
+      (!this.terminationCode || this.terminationCode === "dismissed") &&
+      this.audioChunks.length > 0;
+
+    let action: RecoveryAction;
+    if (canFinalize) {
+      if (this.recordingState === "recording") {
+        await this.endRecording();
+      }
+      // Proceed as if the final chunk had arrived
+      await this.handleFinalChunk();
+      action = "finalized";
+    } else {
+      await this.forceIdle();
+      action = "discarded";
+    }
+
+    logger.audio.warn("Recovered stuck recording session", {
+      sessionId,
+      state,
+      action,
+    });
+    this.emit("recording-recovered", { sessionId, state, action });
+    this.emit("widget-notification", { type: "recording_recovered" });
+  }
+
   private resetSessionState(): void {
     this.currentSessionId = null;
     this.initPromise = null;
+    this.stopContextRefresh = null;
     this.firstChunkReceived = false;
     this.recordingInitiatedAt = null;
     this.recordingMode = "idle";
@@ -659,7 +823,7 @@ export class RecordingManager extends EventEmitter {
    * Create audio file for recording session
    */
   private async createAudioFile(sessionId: string): Promise<string> {
-    const audioDir = path.join(app.getPath("temp"), "grizzo-audio");
+    const audioDir = getRecordingsDirectory();
     await fs.promises.mkdir(audioDir, { recursive: true });
 
     const timestamp = new Date().toISOString().replace(/[:.]/g, "-");
@@ -673,8 +837,14 @@ export class RecordingManager extends EventEmitter {
     return filePath;
   }
 
-  private async pasteTranscription(transcription: string): Promise<void> {
-    if (!transcription || typeof transcription !== "string") {
+  private async pasteTranscription(
+    transcription: string,
+    replaceLength?: number,
+  ): Promise<void> {
+    if (
+      typeof transcription !== "string" ||
+      (!transcription && !replaceLength)
+    ) {
       logger.main.warn("Invalid transcription, not pasting");
       return;
     }
@@ -684,11 +854,13 @@ export class RecordingManager extends EventEmitter {
 
       logger.main.info("Pasting transcription to active application", {
         textLength: transcription.length,
+        replaceLength,
       });
 
       if (nativeBridge) {
         nativeBridge.call("pasteText", {
           transcript: transcription,
+          replaceLength,
         });
       }
     } catch (error) {
@@ -699,6 +871,43 @@ export class RecordingManager extends EventEmitter {
     }
   }
 
+  /**
+   * Send each message with Return in between; the last one is left in the
+   * input so the user can review it before sending
+   */
+  private async pasteMessages(
+    messages: string[],
+    delayMs: number,
+  ): Promise<void> {
+    const gapMs = Math.max(delayMs, MIN_MESSAGE_GAP_MS);
+    try {
+      const nativeBridge = this.serviceManager.getService("nativeBridge");
+      if (!nativeBridge) {
+        return;
+      }
+
+      logger.main.info("Pasting transcription as separate messages", {
+        messageCount: messages.length,
+        delayMs: gapMs,
+      });
+
+      for (let i = 0; i < messages.length; i++) {
+        const isLast = i === messages.length - 1;
+        await nativeBridge.call("pasteText", {
+          transcript: messages[i],
+          submit: !isLast,
+        });
+        if (!isLast) {
+          await new Promise((resolve) => setTimeout(resolve, gapMs));
+        }
+      }
+    } catch (error) {
+      logger.main.warn("Failed to paste messages", {
+        error: error instanceof Error ? error.message : String(error),
+      });
+    }
+  }
+
   // ═══════════════════════════════════════════════════════════════════
   // IPC HANDLERS
   // ═══════════════════════════════════════════════════════════════════
@@ -753,6 +962,7 @@ export class RecordingManager extends EventEmitter {
   // Clean up resources
   async cleanup(): Promise<void> {
     this.clearTimers();
+    this.stopWatchdog();
 
     // Stop recording if active
     if (this.recordingState === "recording") {
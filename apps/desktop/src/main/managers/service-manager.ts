@@ -13,6 +13,13 @@ import { TelemetryService } from "../../services/telemetry-service";
 import { AuthService } from "../../services/auth-service";
 import { OnboardingService } from "../../services/onboarding-service";
 import { InstalledAppsService } from "../../services/installed-apps-service";
+import { RemoteControlService } from "../../services/remote-control-service";
+import { SearchService } from "../../services/search-service";
+import { RetentionService } from "../../services/retention-service";
+import { DigestService } from "../../services/digest-service";
+import { MaintenanceService } from "../../services/maintenance-service";
+import { runStartupRepair } from "../../utils/startup-repair";
+import { applyProxySettings } from "../../utils/proxy";
 
 /**
  * Service map for type-safe service access
@@ -31,6 +38,11 @@ export interface ServiceMap {
   windowManager: WindowManager;
   onboardingService: OnboardingService;
   installedAppsService: InstalledAppsService;
+  remoteControlService: RemoteControlService;
+  searchService: SearchService;
+  retentionService: RetentionService;
+  digestService: DigestService;
+  maintenanceService: MaintenanceService;
 }
 
 /**
@@ -54,6 +66,11 @@ export class ServiceManager {
   private recordingManager: RecordingManager | null = null;
   private shortcutManager: ShortcutManager | null = null;
   private windowManager: WindowManager | null = null;
+  private remoteControlService: RemoteControlService | null = null;
+  private searchService: SearchService | null = null;
+  private retentionService: RetentionService | null = null;
+  private digestService: DigestService | null = null;
+  private maintenanceService: MaintenanceService | null = null;
 
   async initialize(): Promise<void> {
     if (this.isInitialized) {
@@ -65,16 +82,23 @@ export class ServiceManager {
 
     try {
       this.initializeSettingsService();
+      await this.initializeProxy();
       this.initializeInstalledAppsService();
       this.initializeAuthService();
       await this.initializeTelemetryService();
       await this.initializeModelServices();
+      await runStartupRepair(this.settingsService!, this.modelService!);
       await this.initializeOnboardingService();
       this.initializePlatformServices();
       await this.initializeVADService();
       await this.initializeAIServices();
       this.initializeRecordingManager();
       await this.initializeShortcutManager();
+      await this.initializeRemoteControlService();
+      this.initializeSearchService();
+      this.initializeRetentionService();
+      this.initializeDigestService();
+      this.initializeMaintenanceService();
       this.initializeAutoUpdater();
 
       this.isInitialized = true;
@@ -97,6 +121,25 @@ export class ServiceManager {
     logger.main.info("Settings service initialized");
   }
 
+  // Before any service makes outbound requests
+  private async initializeProxy(): Promise<void> {
+    const settingsService = this.settingsService!;
+    const apply = async () => {
+      await applyProxySettings(await settingsService.getProxySettings());
+    };
+    settingsService.on("proxy-changed", () => {
+      apply().catch((error) => {
+        logger.main.error("Failed to apply proxy settings", { error });
+      });
+    });
+    try {
+      await apply();
+    } catch (error) {
+      // Don't throw - fall back to a direct connection
+      logger.main.error("Failed to apply proxy settings:", error);
+    }
+  }
+
   private initializeInstalledAppsService(): void {
     this.installedAppsService = new InstalledAppsService();
     logger.main.info("Installed apps service initialized");
@@ -302,6 +345,56 @@ export class ServiceManager {
     logger.main.info("Shortcut manager initialized");
   }
 
+  private async initializeRemoteControlService(): Promise<void> {
+    if (!this.recordingManager || !this.settingsService) {
+      throw new Error(
+        "RecordingManager and SettingsService must be initialized first",
+      );
+    }
+    this.remoteControlService = new RemoteControlService(
+      this.settingsService,
+      this.recordingManager,
+    );
+    try {
+      await this.remoteControlService.initialize();
+      logger.main.info("Remote control service initialized");
+    } catch (error) {
+      // Don't throw - remote control is optional
+      logger.main.error("Failed to initialize remote control service:", error);
+    }
+  }
+
+  private initializeSearchService(): void {
+    this.searchService = new SearchService(this.settingsService!);
+    // Indexes in the background; doesn't delay startup
+    this.searchService.initialize();
+    logger.main.info("Search service initialized");
+  }
+
+  private initializeRetentionService(): void {
+    this.retentionService = new RetentionService(this.settingsService!);
+    this.retentionService.initialize();
+    logger.main.info("Retention service initialized");
+  }
+
+  private initializeDigestService(): void {
+    this.digestService = new DigestService(
+      this.settingsService!,
+      this.transcriptionService,
+    );
+    this.digestService.initialize();
+    logger.main.info("Digest service initialized");
+  }
+
+  private initializeMaintenanceService(): void {
+    this.maintenanceService = new MaintenanceService(
+      this.settingsService!,
+      this.recordingManager!,
+    );
+    this.maintenanceService.initialize();
+    logger.main.info("Maintenance service initialized");
+  }
+
   private initializeAutoUpdater(): void {
     this.autoUpdaterService = new AutoUpdaterService();
   }
@@ -331,12 +424,24 @@ export class ServiceManager {
       windowManager: this.windowManager!,
       onboardingService: this.onboardingService!,
       installedAppsService: this.installedAppsService!,
+      remoteControlService: this.remoteControlService!,
+      searchService: this.searchService!,
+      retentionService: this.retentionService!,
+      digestService: this.digestService!,
+      maintenanceService: this.maintenanceService!,
     };
 
     return services[serviceName];
   }
 
   async cleanup(): Promise<void> {
+    this.retentionService?.stop();
+    this.digestService?.stop();
+    this.maintenanceService?.stop();
+    if (this.remoteControlService) {
+      logger.main.info("Stopping remote control server...");
+      await this.remoteControlService.stop();
+    }
     if (this.shortcutManager) {
       logger.main.info("Cleaning up shortcut manager...");
       this.shortcutManager.cleanup();
@@ -347,7 +452,7 @@ export class ServiceManager {
     }
     if (this.modelService) {
       logger.main.info("Cleaning up model downloads...");
-      this.modelService.cleanup();
+      await this.modelService.cleanup();
     }
 
     if (this.vadService) {
@@ -1,5 +1,6 @@
 import {
   app,
+  clipboard,
   Tray,
   Menu,
   nativeImage,
@@ -12,6 +13,7 @@ import { logger } from "../logger";
 import type { WindowManager } from "../core/window-manager";
 import type { SettingsService } from "../../services/settings-service";
 import { isMacOS, isWindows } from "../../utils/platform";
+import { getLatestTranscription } from "../../db/transcriptions";
 
 interface TrayAudioInputDevice {
   label: string;
@@ -169,6 +171,19 @@ export class TrayManager {
           }
         },
       },
+      {
+        label: "Copy Last Transcription",
+        click: async () => {
+          try {
+            const latest = await getLatestTranscription();
+            if (latest) {
+              clipboard.writeText(latest.text);
+            }
+          } catch (error) {
+            logger.main.error("Failed to copy last transcription", { error });
+          }
+        },
+      },
       { type: "separator" as const },
       microphoneSection,
       modeSection,
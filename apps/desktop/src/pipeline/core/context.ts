@@ -11,10 +11,14 @@ export interface PipelineContext {
 
 import { GetAccessibilityContextResult } from "@amical/types";
 import type { FormatterConfig } from "../../types/formatter";
+import type { NormalizationConfig } from "../../types/normalization";
+import type { PerformancePreset } from "../../constants/performance-presets";
+import type { ModeConfig } from "../../db/schema";
 
 export interface SharedPipelineData {
   vocabulary: string[]; // Custom vocab
   replacements: Map<string, string>; // Custom replacements
+  symbolShortcuts: Map<string, string>; // Spoken phrase → emoji/symbol
   userPreferences: {
     language?: string; // Optional - undefined means auto-detect
     formattingStyle: "formal" | "casual" | "technical";
@@ -24,10 +28,21 @@ export interface SharedPipelineData {
     duration?: number;
   };
   accessibilityContext: GetAccessibilityContextResult | null;
-  // Mode settings snapshot (resolved at session start)
+  // Mode settings snapshot (resolved at session start, output settings
+  // re-resolved at stop for the focused app)
+  modeId?: string;
+  modeName?: string;
   formatter?: FormatterConfig;
-  speechModelId?: string;
+  speechModelId?: string; // Mode's model or the global one; fixed per session
   customInstructions?: string;
+  spokenCommandsEnabled?: boolean;
+  normalization?: NormalizationConfig;
+  voiceEditingEnabled?: boolean;
+  messageSplitting?: ModeConfig["messageSplitting"];
+  profanityPolicy?: ModeConfig["profanityPolicy"];
+  vocabularyCorrectionEnabled?: boolean;
+  // Global speed/accuracy trade-off (resolved at session start)
+  performancePreset?: PerformancePreset;
 }
 
 /**
@@ -39,6 +54,7 @@ export function createDefaultContext(sessionId: string): PipelineContext {
     sharedData: {
       vocabulary: [],
       replacements: new Map(),
+      symbolShortcuts: new Map(),
       userPreferences: {
         language: "en",
         formattingStyle: "formal",
@@ -16,6 +16,22 @@ export interface TranscribeContext {
   aggregatedTranscription?: string;
   language?: string;
   formattingEnabled?: boolean;
+  chunking?: ChunkingOptions;
+  // Called for each transcribed chunk when the provider reports how sure it
+  // is of the text (0-1)
+  reportConfidence?: (text: string, confidence: number) => void;
+}
+
+// One chunk of speech as transcribed by a local runtime
+export interface ChunkTranscription {
+  text: string;
+  confidence?: number; // 0-1, when the runtime reports one
+}
+
+// Speech detection/chunking tuning from the performance preset
+export interface ChunkingOptions {
+  speechThreshold: number; // VAD probability above which a frame is speech
+  maxSilenceMs: number; // Silence after speech that triggers transcription
 }
 
 // Transcription input parameters
@@ -35,7 +51,9 @@ export interface FormatParams {
     previousChunk?: string;
     aggregatedTranscription?: string;
     customInstructions?: string;
+    presetId?: string;
   };
+  abortSignal?: AbortSignal; // Aborted when the formatting timeout elapses
 }
 
 // Transcription provider interface
@@ -46,10 +64,19 @@ export interface TranscriptionProvider {
   reset(): void; // Clear internal buffers without transcribing
 }
 
+// Free-form completion (summaries etc.) outside the formatter prompt
+export interface GenerateParams {
+  system: string;
+  prompt: string;
+  maxTokens?: number;
+  abortSignal?: AbortSignal;
+}
+
 // Formatting provider interface
 export interface FormattingProvider {
   readonly name: string;
   format(params: FormatParams): Promise<string>;
+  generate(params: GenerateParams): Promise<string>;
 }
 
 // Pipeline execution result
@@ -79,6 +106,17 @@ export interface StreamingSession {
   recordingStartedAt?: number; // When user pressed record button (from RecordingManager)
   recordingStoppedAt?: number; // When user released record button (from RecordingManager)
   finalizationStartedAt?: number; // When finalizeSession() was called
+  audioSamples: number; // 16 kHz samples received, for the recording length
+  // Provider-reported confidence, summed and weighted by text length
+  confidence: { sum: number; weight: number };
+}
+
+// Result of finalizing a session, ready to be inserted into the active app
+export interface FinalizedTranscription {
+  text: string;
+  replaceLength?: number; // Characters before the cursor to delete first (voice edits)
+  messages?: string[]; // Set when the text should be sent as several chat messages
+  messageDelayMs?: number;
 }
 
 // Simple pipeline configuration
@@ -6,6 +6,7 @@
 export type {
   TranscriptionProvider,
   FormattingProvider,
+  GenerateParams,
   PipelineResult,
   PipelineConfig,
   StreamingPipelineContext,
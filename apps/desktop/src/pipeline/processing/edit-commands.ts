@@ -0,0 +1,112 @@
+/**
+ * Follow-up voice edits applied to the previous dictation.
+ * A dictation that consists solely of an edit command ("replace Tuesday with
+ * Wednesday", "make it more formal") rewrites the last result instead of
+ * being inserted as new text. Grammar currently covers English only.
+ */
+
+export type EditCommand =
+  | { type: "replace"; target: string; replacement: string }
+  | { type: "rewrite"; instruction: string };
+
+const REPLACE_PATTERN = /^(?:replace|change)\s+(.+?)\s+(?:with|to)\s+(.+)$/i;
+const REWRITE_PATTERNS = [
+  /^(?:make\s+(?:it|that)\s+.+)$/i,
+  /^(?:rewrite|rephrase)\s+(?:it|that)(?:\s+.+)?$/i,
+];
+
+function stripUtterance(text: string): string {
+  return text
+    .trim()
+    .replace(/[.!?]+$/, "")
+    .trim();
+}
+
+function stripQuotes(text: string): string {
+  return text.replace(/^["'“”‘’]+|["'“”‘’,]+$/g, "").trim();
+}
+
+/**
+ * Recognize an edit command spanning the whole utterance.
+ */
+export function parseEditCommand(
+  text: string,
+  language?: string,
+): EditCommand | null {
+  if (language && !language.startsWith("en")) {
+    return null;
+  }
+
+  const utterance = stripUtterance(text);
+  if (!utterance) {
+    return null;
+  }
+
+  const replace = utterance.match(REPLACE_PATTERN);
+  if (replace) {
+    const target = stripQuotes(replace[1]);
+    const replacement = stripQuotes(replace[2]);
+    if (target && replacement) {
+      return { type: "replace", target, replacement };
+    }
+  }
+
+  if (REWRITE_PATTERNS.some((pattern) => pattern.test(utterance))) {
+    return { type: "rewrite", instruction: utterance };
+  }
+
+  return null;
+}
+
+/**
+ * Replace the last occurrence of `target` (case-insensitive, whole words).
+ * Returns null when the target does not appear in the text.
+ */
+export function applyReplaceCommand(
+  text: string,
+  target: string,
+  replacement: string,
+): string | null {
+  const escaped = target.replace(/[.*+?^${}()|[\]\\]/g, "\\$&");
+  const regex = new RegExp(
+    `(?<![\\p{L}\\p{N}])${escaped}(?![\\p{L}\\p{N}])`,
+    "giu",
+  );
+  const matches = [...text.matchAll(regex)];
+  const last = matches[matches.length - 1];
+  if (!last) {
+    return null;
+  }
+  return (
+    text.slice(0, last.index) +
+    replacement +
+    text.slice(last.index! + last[0].length)
+  );
+}
+
+/**
+ * Minimal keystroke diff between the previously inserted text and its edit.
+ * Counts in grapheme clusters because each backspace removes one.
+ */
+export function diffForReinjection(
+  previous: string,
+  next: string,
+): { replaceLength: number; insertText: string } {
+  const segmenter = new Intl.Segmenter(undefined, { granularity: "grapheme" });
+  const before = Array.from(segmenter.segment(previous), (s) => s.segment);
+  const after = Array.from(segmenter.segment(next), (s) => s.segment);
+
+  let prefix = 0;
+  while (
+    prefix < before.length &&
+    prefix < after.length &&
+    before[prefix] === after[prefix]
+  ) {
+    prefix++;
+  }
+
+  return {
+    replaceLength: before.length - prefix,
+    insertText: after.slice(prefix).join(""),
+  };
+}
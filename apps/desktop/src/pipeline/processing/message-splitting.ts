@@ -0,0 +1,94 @@
+/**
+ * Split long dictation into chat-sized messages. Paragraph breaks are kept as
+ * message boundaries; paragraphs over the limit are packed sentence by
+ * sentence, and sentences over the limit are wrapped at word boundaries.
+ */
+
+/**
+ * Shortest pause between messages. The paste helper returns once the key
+ * events are posted, before the target app has read the clipboard, so a
+ * shorter gap can paste a message twice or drop one.
+ */
+export const MIN_MESSAGE_GAP_MS = 150;
+
+// Chinese and Japanese sentences run together without a space
+const NO_SPACE_BEFORE_NEXT =
+  /[\p{Script=Han}\p{Script=Hiragana}\p{Script=Katakana}\u3000-\u303f\uff00-\uffef]$/u;
+
+function splitSentences(paragraph: string): string[] {
+  const segmenter = new Intl.Segmenter(undefined, { granularity: "sentence" });
+  return Array.from(segmenter.segment(paragraph), (s) =>
+    s.segment.trim(),
+  ).filter((sentence) => sentence.length > 0);
+}
+
+function wrapWords(sentence: string, maxLength: number): string[] {
+  const lines: string[] = [];
+  let current = "";
+  for (const word of sentence.split(/\s+/)) {
+    const candidate = current ? `${current} ${word}` : word;
+    if (candidate.length <= maxLength) {
+      current = candidate;
+      continue;
+    }
+    if (current) {
+      lines.push(current);
+    }
+    // A single word longer than the limit is cut as-is
+    let rest = word;
+    while (rest.length > maxLength) {
+      lines.push(rest.slice(0, maxLength));
+      rest = rest.slice(maxLength);
+    }
+    current = rest;
+  }
+  if (current) {
+    lines.push(current);
+  }
+  return lines;
+}
+
+function packSentences(sentences: string[], maxLength: number): string[] {
+  const messages: string[] = [];
+  let current = "";
+  for (const sentence of sentences) {
+    const pieces =
+      sentence.length > maxLength ? wrapWords(sentence, maxLength) : [sentence];
+    for (const piece of pieces) {
+      const separator = NO_SPACE_BEFORE_NEXT.test(current) ? "" : " ";
+      const candidate = current ? `${current}${separator}${piece}` : piece;
+      if (candidate.length <= maxLength) {
+        current = candidate;
+      } else {
+        messages.push(current);
+        current = piece;
+      }
+    }
+  }
+  if (current) {
+    messages.push(current);
+  }
+  return messages;
+}
+
+/**
+ * Returns the text as a single message when it already fits.
+ */
+export function splitIntoMessages(text: string, maxLength: number): string[] {
+  const trimmed = text.trim();
+  if (trimmed.length <= maxLength) {
+    return trimmed ? [trimmed] : [];
+  }
+
+  const messages: string[] = [];
+  for (const paragraph of trimmed.split(/\n\s*\n/)) {
+    const clean = paragraph.trim();
+    if (!clean) continue;
+    if (clean.length <= maxLength) {
+      messages.push(clean);
+    } else {
+      messages.push(...packSentences(splitSentences(clean), maxLength));
+    }
+  }
+  return messages;
+}
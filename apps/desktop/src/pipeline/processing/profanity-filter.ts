@@ -0,0 +1,57 @@
+import type { ProfanityPolicy } from "../../types/profanity";
+
+/**
+ * Client-side profanity handling. None of the supported speech providers
+ * (whisper.cpp, OpenAI, Groq, xAI) expose a profanity flag, so the mode's
+ * policy is applied to the final text instead.
+ */
+
+// Word stems; each matches the stem plus common suffixes ("fucking", "shits")
+const PROFANITY_STEMS = [
+  "fuck",
+  "motherfuck",
+  "shit",
+  "bullshit",
+  "bitch",
+  "bastard",
+  "asshole",
+  "dickhead",
+  "cunt",
+  "cock",
+  "piss",
+  "wanker",
+  "twat",
+  "bollocks",
+  "douchebag",
+  "damn",
+  "goddamn",
+  "crap",
+];
+
+const PROFANITY_REGEX = new RegExp(
+  `\\b(?:${PROFANITY_STEMS.join("|")})(?:s|es|ed|er|ers|ing|in'|y)?\\b`,
+  "gi",
+);
+
+export function applyProfanityPolicy(
+  text: string,
+  policy: ProfanityPolicy | undefined,
+): string {
+  if (!policy || policy === "keep" || !text) {
+    return text;
+  }
+
+  if (policy === "mask") {
+    // Keep the first letter so the sentence stays readable: "f***"
+    return text.replace(
+      PROFANITY_REGEX,
+      (word) => word[0] + "*".repeat(word.length - 1),
+    );
+  }
+
+  return text
+    .replace(PROFANITY_REGEX, "")
+    .replace(/[ \t]{2,}/g, " ")
+    .replace(/[ \t]+([.,!?;:])/g, "$1")
+    .replace(/^[ \t]+|[ \t]+$/gm, "");
+}
@@ -0,0 +1,113 @@
+/**
+ * Heuristics for flagging transcriptions that likely need a human look.
+ * Only whisper models report a confidence score, so the other checks act as
+ * a WER proxy: they look for the failure shapes Whisper typically produces
+ * (empty output on real speech, repetition loops, stock hallucinations).
+ */
+
+export type ReviewReason =
+  | "low_confidence"
+  | "empty_output"
+  | "low_word_rate"
+  | "repetition"
+  | "hallucination_phrase"
+  | "duplicate"; // Set by the caller, not by these heuristics
+
+export interface ReviewAssessment {
+  needsReview: boolean;
+  reasons: ReviewReason[];
+}
+
+// Phrases Whisper emits on silence or noise when they make up the whole output
+const HALLUCINATION_PHRASES = new Set([
+  "thank you",
+  "thank you for watching",
+  "thanks for watching",
+  "please subscribe",
+  "subtitles by the amara org community",
+  "you",
+]);
+
+const MIN_AUDIO_SECONDS_FOR_RATE_CHECK = 3;
+const MIN_WORDS_PER_SECOND = 0.3;
+const MIN_WORDS_FOR_REPETITION_CHECK = 12;
+const MIN_UNIQUE_WORD_RATIO = 0.3;
+const MAX_TRIGRAM_REPEATS = 4;
+
+function tokenize(text: string): string[] {
+  return text
+    .toLowerCase()
+    .split(/[^\p{L}\p{N}']+/u)
+    .filter((word) => word.length > 0);
+}
+
+function hasRepetitionLoop(words: string[]): boolean {
+  if (words.length < MIN_WORDS_FOR_REPETITION_CHECK) {
+    return false;
+  }
+
+  const uniqueRatio = new Set(words).size / words.length;
+  if (uniqueRatio < MIN_UNIQUE_WORD_RATIO) {
+    return true;
+  }
+
+  const trigramCounts = new Map<string, number>();
+  for (let i = 0; i + 2 < words.length; i++) {
+    const trigram = `${words[i]} ${words[i + 1]} ${words[i + 2]}`;
+    const next = (trigramCounts.get(trigram) ?? 0) + 1;
+    if (next >= MAX_TRIGRAM_REPEATS) {
+      return true;
+    }
+    trigramCounts.set(trigram, next);
+  }
+  return false;
+}
+
+/**
+ * Decide whether a finished transcription should go into the review queue.
+ */
+export function assessTranscriptionQuality(options: {
+  text: string;
+  confidence?: number | null;
+  audioDurationSeconds?: number;
+  confidenceThreshold?: number;
+}): ReviewAssessment {
+  const { text, confidence, audioDurationSeconds, confidenceThreshold } =
+    options;
+  const reasons: ReviewReason[] = [];
+  const words = tokenize(text);
+
+  if (
+    confidence !== undefined &&
+    confidence !== null &&
+    confidenceThreshold !== undefined &&
+    confidence < confidenceThreshold
+  ) {
+    reasons.push("low_confidence");
+  }
+
+  const hasSubstantialAudio =
+    audioDurationSeconds !== undefined &&
+    audioDurationSeconds >= MIN_AUDIO_SECONDS_FOR_RATE_CHECK;
+
+  if (words.length === 0) {
+    if (hasSubstantialAudio) {
+      reasons.push("empty_output");
+    }
+  } else {
+    if (
+      hasSubstantialAudio &&
+      words.length / audioDurationSeconds! < MIN_WORDS_PER_SECOND
+    ) {
+      reasons.push("low_word_rate");
+    }
+    if (hasRepetitionLoop(words)) {
+      reasons.push("repetition");
+    }
+    if (HALLUCINATION_PHRASES.has(words.join(" "))) {
+      reasons.push("hallucination_phrase");
+    }
+  }
+
+  return { needsReview: reasons.length > 0, reasons };
+}
@@ -0,0 +1,342 @@
+/**
+ * Rule-based interpreter for spoken punctuation and dictation commands.
+ * Runs on the raw transcription before the LLM formatter so that phrases like
+ * "new line", "comma" or "delete that" become text operations even when
+ * formatting is disabled.
+ */
+
+type SpokenCommand =
+  | { type: "punctuation"; value: string } // Attaches to the previous word
+  | { type: "open"; value: string } // Attaches to the next word
+  | { type: "close"; value: string } // Attaches to the previous word, no sentence end
+  | { type: "newline"; value: string }
+  | { type: "delete" }; // Removes the previous sentence
+
+type CommandTable = Record<string, SpokenCommand>;
+
+const EN_COMMANDS: CommandTable = {
+  "new paragraph": { type: "newline", value: "\n\n" },
+  "new line": { type: "newline", value: "\n" },
+  "next line": { type: "newline", value: "\n" },
+  period: { type: "punctuation", value: "." },
+  "full stop": { type: "punctuation", value: "." },
+  comma: { type: "punctuation", value: "," },
+  "question mark": { type: "punctuation", value: "?" },
+  "exclamation mark": { type: "punctuation", value: "!" },
+  "exclamation point": { type: "punctuation", value: "!" },
+  colon: { type: "punctuation", value: ":" },
+  semicolon: { type: "punctuation", value: ";" },
+  ellipsis: { type: "punctuation", value: "..." },
+  "open quote": { type: "open", value: '"' },
+  "close quote": { type: "close", value: '"' },
+  "open parenthesis": { type: "open", value: "(" },
+  "close parenthesis": { type: "close", value: ")" },
+  "delete that": { type: "delete" },
+  "scratch that": { type: "delete" },
+};
+
+const ES_COMMANDS: CommandTable = {
+  "nuevo párrafo": { type: "newline", value: "\n\n" },
+  "nueva línea": { type: "newline", value: "\n" },
+  "punto y coma": { type: "punctuation", value: ";" },
+  punto: { type: "punctuation", value: "." },
+  coma: { type: "punctuation", value: "," },
+  "signo de interrogación": { type: "punctuation", value: "?" },
+  "signo de exclamación": { type: "punctuation", value: "!" },
+  "dos puntos": { type: "punctuation", value: ":" },
+  "abrir comillas": { type: "open", value: '"' },
+  "cerrar comillas": { type: "close", value: '"' },
+  "abrir paréntesis": { type: "open", value: "(" },
+  "cerrar paréntesis": { type: "close", value: ")" },
+  "borra eso": { type: "delete" },
+  "borrar eso": { type: "delete" },
+};
+
+const FR_COMMANDS: CommandTable = {
+  "nouveau paragraphe": { type: "newline", value: "\n\n" },
+  "nouvelle ligne": { type: "newline", value: "\n" },
+  "à la ligne": { type: "newline", value: "\n" },
+  "point virgule": { type: "punctuation", value: ";" },
+  "point d'interrogation": { type: "punctuation", value: "?" },
+  "point d'exclamation": { type: "punctuation", value: "!" },
+  "deux points": { type: "punctuation", value: ":" },
+  point: { type: "punctuation", value: "." },
+  virgule: { type: "punctuation", value: "," },
+  "ouvrir les guillemets": { type: "open", value: '"' },
+  "fermer les guillemets": { type: "close", value: '"' },
+  "ouvrir la parenthèse": { type: "open", value: "(" },
+  "fermer la parenthèse": { type: "close", value: ")" },
+  "efface ça": { type: "delete" },
+  "supprime ça": { type: "delete" },
+};
+
+const DE_COMMANDS: CommandTable = {
+  "neuer absatz": { type: "newline", value: "\n\n" },
+  "neue zeile": { type: "newline", value: "\n" },
+  punkt: { type: "punctuation", value: "." },
+  komma: { type: "punctuation", value: "," },
+  fragezeichen: { type: "punctuation", value: "?" },
+  ausrufezeichen: { type: "punctuation", value: "!" },
+  doppelpunkt: { type: "punctuation", value: ":" },
+  semikolon: { type: "punctuation", value: ";" },
+  "anführungszeichen auf": { type: "open", value: '"' },
+  "anführungszeichen zu": { type: "close", value: '"' },
+  "klammer auf": { type: "open", value: "(" },
+  "klammer zu": { type: "close", value: ")" },
+  "lösch das": { type: "delete" },
+  "das löschen": { type: "delete" },
+};
+
+const COMMAND_TABLES: Record<string, CommandTable> = {
+  en: EN_COMMANDS,
+  es: ES_COMMANDS,
+  fr: FR_COMMANDS,
+  de: DE_COMMANDS,
+};
+
+// Words after which a sentence-ending command word is an ordinary noun
+// ("the period", "un punto", "le point", "um Punkt acht")
+const DETERMINERS: Record<string, Set<string>> = {
+  en: new Set([
+    "the",
+    "a",
+    "an",
+    "this",
+    "that",
+    "each",
+    "every",
+    "any",
+    "no",
+    "one",
+    "which",
+    "what",
+    "whole",
+    "same",
+  ]),
+  es: new Set([
+    "el",
+    "un",
+    "este",
+    "ese",
+    "aquel",
+    "cada",
+    "ningún",
+    "mismo",
+    "en",
+    "al",
+    "del",
+  ]),
+  fr: new Set([
+    "le",
+    "un",
+    "ce",
+    "cet",
+    "du",
+    "au",
+    "chaque",
+    "quel",
+    "mon",
+    "ton",
+    "son",
+    "notre",
+    "votre",
+    "leur",
+    "même",
+  ]),
+  de: new Set([
+    "der",
+    "die",
+    "das",
+    "den",
+    "dem",
+    "des",
+    "ein",
+    "einen",
+    "einem",
+    "eines",
+    "jeder",
+    "jeden",
+    "jedem",
+    "dieser",
+    "diesen",
+    "diesem",
+    "kein",
+    "keinen",
+    "zum",
+    "am",
+    "um",
+  ]),
+};
+
+const SENTENCE_TERMINATORS = /[.!?\n]/;
+
+const compiledPatterns = new Map<string, RegExp>();
+
+/**
+ * Resolve the command table for a language code.
+ * Auto-detect (undefined) and unsupported languages fall back to English.
+ */
+function getCommandTable(language?: string): { key: string; table: CommandTable } {
+  const key = language && COMMAND_TABLES[language] ? language : "en";
+  return { key, table: COMMAND_TABLES[key] };
+}
+
+function getCommandPattern(key: string, table: CommandTable): RegExp {
+  const cached = compiledPatterns.get(key);
+  if (cached) {
+    return cached;
+  }
+
+  // Longest phrases first so "punto y coma" wins over "punto"
+  const alternatives = Object.keys(table)
+    .sort((a, b) => b.length - a.length)
+    .map((phrase) =>
+      phrase
+        .replace(/[.*+?^${}()|[\]\\]/g, "\\$&")
+        .replace(/\s+/g, "[\\s,]+"),
+    );
+
+  // Whisper often punctuates the command itself ("Comma," / "New line."),
+  // so swallow a single trailing punctuation mark along with the phrase.
+  const pattern = new RegExp(
+    `(?<![\\p{L}\\p{N}])(${alternatives.join("|")})(?![\\p{L}\\p{N}])[.,!?;:]?`,
+    "giu",
+  );
+  compiledPatterns.set(key, pattern);
+  return pattern;
+}
+
+function normalizePhrase(phrase: string): string {
+  return phrase.toLowerCase().replace(/[\s,]+/g, " ");
+}
+
+/**
+ * Whether a one-word sentence-ending command ("period", "punto", "point",
+ * "Punkt") is an ordinary word here: it follows an article or preposition,
+ * or runs on into lowercase text with at most a comma between. A spoken stop
+ * makes Whisper end the sentence or capitalize what follows, so "the trial
+ * period ends" and "grace period, however" are prose.
+ */
+function isOrdinaryWord(
+  text: string,
+  match: RegExpMatchArray,
+  key: string,
+): boolean {
+  const phrase = match[1];
+  if (/[\s,]/.test(phrase)) {
+    return false;
+  }
+  const previous = text
+    .slice(0, match.index)
+    .match(/(\p{L}+)[ \t]*$/u)?.[1]
+    ?.toLowerCase();
+  if (previous && DETERMINERS[key].has(previous)) {
+    return true;
+  }
+  const swallowed = match[0].slice(phrase.length);
+  const following = text.slice(match.index! + match[0].length);
+  return (
+    (swallowed === "" || swallowed === ",") &&
+    /^[ \t]+\p{Ll}/u.test(following)
+  );
+}
+
+/**
+ * Remove the most recent sentence from the output buffer.
+ */
+function deleteLastSentence(output: string): string {
+  const trimmed = output.trimEnd();
+  // Skip the terminator that closes the sentence being deleted
+  const searchArea = trimmed.slice(0, -1);
+  for (let i = searchArea.length - 1; i >= 0; i--) {
+    if (SENTENCE_TERMINATORS.test(searchArea[i])) {
+      return trimmed.slice(0, i + 1);
+    }
+  }
+  return "";
+}
+
+function capitalizeFirstLetter(text: string): string {
+  return text.replace(/^(\s*)(\p{Ll})/u, (_, space: string, letter: string) =>
+    space + letter.toUpperCase(),
+  );
+}
+
+/**
+ * Convert spoken punctuation and dictation commands into text operations.
+ */
+export function applySpokenCommands(text: string, language?: string): string {
+  if (!text.trim()) {
+    return text;
+  }
+
+  const { key, table } = getCommandTable(language);
+  const pattern = getCommandPattern(key, table);
+
+  let output = "";
+  let lastIndex = 0;
+  let stripLeadingSpace = false;
+  let capitalizeNext = false;
+
+  const appendSegment = (segment: string) => {
+    let next = segment;
+    if (stripLeadingSpace) {
+      next = next.replace(/^[ \t]+/, "");
+    }
+    if (capitalizeNext && /\p{L}/u.test(next)) {
+      next = capitalizeFirstLetter(next);
+      capitalizeNext = false;
+    }
+    if (next.length > 0) {
+      stripLeadingSpace = false;
+    }
+    output += next;
+  };
+
+  for (const match of text.matchAll(pattern)) {
+    appendSegment(text.slice(lastIndex, match.index));
+    lastIndex = match.index! + match[0].length;
+
+    const command = table[normalizePhrase(match[1])];
+    if (
+      !command ||
+      (command.type === "punctuation" &&
+        /[.!?]$/.test(command.value) &&
+        isOrdinaryWord(text, match, key))
+    ) {
+      appendSegment(match[0]);
+      continue;
+    }
+
+    switch (command.type) {
+      case "punctuation":
+        output = output.replace(/[ \t,]+$/, "") + command.value;
+        capitalizeNext = /[.!?]$/.test(command.value);
+        break;
+      case "close":
+        output = output.replace(/[ \t]+$/, "") + command.value;
+        break;
+      case "open":
+        if (output.length > 0 && !/\s$/.test(output)) {
+          output += " ";
+        }
+        output += command.value;
+        stripLeadingSpace = true;
+        break;
+      case "newline":
+        output = output.replace(/[ \t]+$/, "") + command.value;
+        stripLeadingSpace = true;
+        capitalizeNext = true;
+        break;
+      case "delete":
+        output = deleteLastSentence(output);
+        stripLeadingSpace = output.length === 0;
+        capitalizeNext = output.length === 0 || /[.!?\n]$/.test(output);
+        break;
+    }
+  }
+
+  appendSegment(text.slice(lastIndex));
+
+  return output.replace(/[ \t]+\n/g, "\n");
+}
@@ -0,0 +1,36 @@
+/**
+ * Replace spoken emoji/symbol phrases ("thumbs up emoji", "em dash") with the
+ * character itself. Phrases come from the user-editable symbol_shortcuts
+ * table and match case-insensitively on whole words, tolerating hyphens or
+ * extra spaces between the words ("thumbs-up emoji").
+ */
+
+function phrasePattern(phrase: string): string {
+  return phrase
+    .trim()
+    .split(/[\s-]+/)
+    .map((word) => word.replace(/[.*+?^${}()|[\]\\]/g, "\\$&"))
+    .join("[\\s-]+");
+}
+
+export function applySymbolShortcuts(
+  text: string,
+  shortcuts: Map<string, string>,
+): string {
+  if (shortcuts.size === 0 || !text) {
+    return text;
+  }
+
+  // Longest phrase first so "thumbs up emoji" wins over "thumbs up"
+  const phrases = [...shortcuts.keys()].sort((a, b) => b.length - a.length);
+
+  let result = text;
+  for (const phrase of phrases) {
+    const regex = new RegExp(
+      `(?<![\\p{L}\\p{N}])${phrasePattern(phrase)}(?![\\p{L}\\p{N}])`,
+      "giu",
+    );
+    result = result.replace(regex, () => shortcuts.get(phrase)!);
+  }
+  return result;
+}
@@ -0,0 +1,528 @@
+/**
+ * Deterministic normalization of numbers, dates and currency amounts.
+ * Runs without an LLM so dictated documents come out consistently formatted
+ * even when the formatter is disabled. Rules currently cover English only.
+ */
+
+import type {
+  DateFormat,
+  NormalizationConfig,
+} from "../../types/normalization";
+
+const UNITS: Record<string, number> = {
+  zero: 0,
+  one: 1,
+  two: 2,
+  three: 3,
+  four: 4,
+  five: 5,
+  six: 6,
+  seven: 7,
+  eight: 8,
+  nine: 9,
+};
+
+const TEENS: Record<string, number> = {
+  ten: 10,
+  eleven: 11,
+  twelve: 12,
+  thirteen: 13,
+  fourteen: 14,
+  fifteen: 15,
+  sixteen: 16,
+  seventeen: 17,
+  eighteen: 18,
+  nineteen: 19,
+};
+
+const TENS: Record<string, number> = {
+  twenty: 20,
+  thirty: 30,
+  forty: 40,
+  fifty: 50,
+  sixty: 60,
+  seventy: 70,
+  eighty: 80,
+  ninety: 90,
+};
+
+const SCALES: Record<string, number> = {
+  thousand: 1_000,
+  million: 1_000_000,
+  billion: 1_000_000_000,
+};
+
+const ORDINALS: Record<string, number> = {
+  first: 1,
+  second: 2,
+  third: 3,
+  fourth: 4,
+  fifth: 5,
+  sixth: 6,
+  seventh: 7,
+  eighth: 8,
+  ninth: 9,
+  tenth: 10,
+  eleventh: 11,
+  twelfth: 12,
+  thirteenth: 13,
+  fourteenth: 14,
+  fifteenth: 15,
+  sixteenth: 16,
+  seventeenth: 17,
+  eighteenth: 18,
+  nineteenth: 19,
+  twentieth: 20,
+  thirtieth: 30,
+};
+
+const MONTHS = [
+  "January",
+  "February",
+  "March",
+  "April",
+  "May",
+  "June",
+  "July",
+  "August",
+  "September",
+  "October",
+  "November",
+  "December",
+];
+
+const CURRENCY_SYMBOLS: Record<string, string> = {
+  dollar: "$",
+  dollars: "$",
+  bucks: "$",
+  euro: "€",
+  euros: "€",
+  pound: "£",
+  pounds: "£",
+};
+
+type NumberCategory = "unit" | "teen" | "tens" | "hundred" | "scale";
+
+interface ParsedNumber {
+  value: number;
+  start: number;
+  end: number;
+  decimal?: string;
+  twoDigit: boolean; // Bare 10-99 group, candidate for "nineteen ninety" years
+  isYear?: boolean;
+}
+
+const byLengthDesc = (a: string, b: string) => b.length - a.length;
+
+const NUMBER_WORDS = [
+  ...Object.keys(UNITS),
+  ...Object.keys(TEENS),
+  ...Object.keys(TENS),
+  "hundred",
+  ...Object.keys(SCALES),
+]
+  .sort(byLengthDesc)
+  .join("|");
+
+// A run of number words, allowing "and"/"point" connectors and a leading "a"
+// ("a hundred"). Validation happens in parseNumberRun, not in the regex.
+const NUMBER_RUN = `(?:\\ba[\\s-]+(?=(?:hundred|thousand|million|billion)\\b))?\\b(?:${NUMBER_WORDS})\\b(?:[\\s-]+(?:and|point|${NUMBER_WORDS})\\b)*`;
+
+const AMOUNT = `\\d+(?:\\.\\d+)?|${NUMBER_RUN}`;
+
+const ORDINAL_DAY = `(?:(?:twenty|thirty)[\\s-]+)?(?:${Object.keys(ORDINALS).sort(byLengthDesc).join("|")})`;
+const CARDINAL_DAY = `(?:(?:twenty|thirty)[\\s-]+)?(?:${[...Object.keys(UNITS), ...Object.keys(TEENS), "twenty", "thirty"].sort(byLengthDesc).join("|")})`;
+const DAY = `\\d{1,2}(?:st|nd|rd|th)?|${ORDINAL_DAY}|${CARDINAL_DAY}`;
+const MONTH = MONTHS.join("|");
+const YEAR = `\\d{4}|${NUMBER_RUN}`;
+
+const MONTH_DAY_PATTERN = new RegExp(
+  `\\b(${MONTH})\\s+(?:the\\s+)?(${DAY})\\b(?:(,?\\s+)(${YEAR}))?`,
+  "gi",
+);
+const DAY_OF_MONTH_PATTERN = new RegExp(
+  `\\b(?:the\\s+)?(${DAY})\\s+of\\s+(${MONTH})\\b(?:(,?\\s+)(${YEAR}))?`,
+  "gi",
+);
+const CURRENCY_PATTERN = new RegExp(
+  `(${AMOUNT})\\s+(dollars?|bucks|euros?|pounds?)\\b(?:\\s+and\\s+(${AMOUNT})\\s+cents?\\b)?`,
+  "gi",
+);
+const PERCENT_PATTERN = new RegExp(`(${AMOUNT})\\s+percent\\b`, "gi");
+const NUMBER_RUN_PATTERN = new RegExp(NUMBER_RUN, "gi");
+const SINGLE_DIGIT_PATTERN = /(?<![\d.,:/$€£%-])\b(\d)\b(?![.,:/]\d|\d|\s*%)/g;
+
+function categorize(word: string): NumberCategory | null {
+  if (word in UNITS) return "unit";
+  if (word in TEENS) return "teen";
+  if (word in TENS) return "tens";
+  if (word === "hundred") return "hundred";
+  if (word in SCALES) return "scale";
+  return null;
+}
+
+function wordValue(word: string): number {
+  return UNITS[word] ?? TEENS[word] ?? TENS[word] ?? SCALES[word] ?? 100;
+}
+
+function canFollow(
+  last: NumberCategory | null,
+  next: NumberCategory,
+  hasHundred: boolean,
+): boolean {
+  switch (next) {
+    case "unit":
+      return last !== "unit" && last !== "teen";
+    case "teen":
+    case "tens":
+      return last === null || last === "hundred" || last === "scale";
+    case "hundred":
+      return (last === "unit" || last === "teen") && !hasHundred;
+    case "scale":
+      return last !== null && last !== "scale";
+  }
+}
+
+/**
+ * Parse the longest valid number starting at token `start`.
+ */
+function parseNumberAt(
+  words: string[],
+  start: number,
+): { value: number; next: number; twoDigit: boolean } | null {
+  let total = 0;
+  let current = 0;
+  let last: NumberCategory | null = null;
+  let firstCategory: NumberCategory | null = null;
+  let lastScale = Infinity;
+  let hasHundred = false;
+  let consumed = start;
+  let i = start;
+
+  if (words[i] === "a") {
+    const following = words[i + 1] ? categorize(words[i + 1]) : null;
+    if (following !== "hundred" && following !== "scale") {
+      return null;
+    }
+    current = 1;
+    last = "unit";
+    firstCategory = "unit";
+    i++;
+  }
+
+  while (i < words.length) {
+    const word = words[i];
+    if (word === "and") {
+      const following = words[i + 1] ? categorize(words[i + 1]) : null;
+      if (
+        (last === "hundred" || last === "scale") &&
+        (following === "unit" || following === "teen" || following === "tens")
+      ) {
+        i++;
+        continue;
+      }
+      break;
+    }
+
+    const category = categorize(word);
+    if (!category || !canFollow(last, category, hasHundred)) {
+      break;
+    }
+
+    const value = wordValue(word);
+    if (category === "hundred") {
+      current *= 100;
+      hasHundred = true;
+    } else if (category === "scale") {
+      if (value >= lastScale) break;
+      total += current * value;
+      current = 0;
+      lastScale = value;
+      hasHundred = false;
+    } else {
+      current += value;
+    }
+
+    firstCategory ??= category;
+    last = category;
+    i++;
+    consumed = i;
+  }
+
+  if (consumed === start) {
+    return null;
+  }
+
+  const value = total + current;
+  return {
+    value,
+    next: consumed,
+    twoDigit:
+      total === 0 &&
+      !hasHundred &&
+      (firstCategory === "teen" || firstCategory === "tens"),
+  };
+}
+
+/**
+ * Split a run of number words into individual numbers.
+ * "twenty five and three point five" → [25, 3.5]
+ */
+function parseNumberRun(run: string): ParsedNumber[] {
+  const tokens = [...run.matchAll(/[a-z]+/gi)].map((match) => ({
+    word: match[0].toLowerCase(),
+    start: match.index!,
+    end: match.index! + match[0].length,
+  }));
+  const words = tokens.map((token) => token.word);
+  const results: ParsedNumber[] = [];
+
+  let i = 0;
+  while (i < tokens.length) {
+    const parsed = parseNumberAt(words, i);
+    if (!parsed) {
+      i++;
+      continue;
+    }
+
+    let next = parsed.next;
+    let decimal: string | undefined;
+    if (words[next] === "point" && words[next + 1] in UNITS) {
+      decimal = "";
+      next++;
+      while (next < words.length && words[next] in UNITS) {
+        decimal += UNITS[words[next]];
+        next++;
+      }
+    }
+
+    results.push({
+      value: parsed.value,
+      start: tokens[i].start,
+      end: tokens[next - 1].end,
+      decimal,
+      twoDigit: parsed.twoDigit && decimal === undefined,
+    });
+    i = next;
+  }
+
+  // "nineteen ninety nine" / "twenty twenty four" are spoken years
+  const merged: ParsedNumber[] = [];
+  for (const current of results) {
+    const previous = merged[merged.length - 1];
+    if (
+      previous?.twoDigit &&
+      current.twoDigit &&
+      /^\s+$/.test(run.slice(previous.end, current.start))
+    ) {
+      merged[merged.length - 1] = {
+        value: previous.value * 100 + current.value,
+        start: previous.start,
+        end: current.end,
+        twoDigit: false,
+        isYear: true,
+      };
+    } else {
+      merged.push(current);
+    }
+  }
+
+  return merged;
+}
+
+/**
+ * Parse a whole amount (digits or words) into a single number.
+ */
+function parseAmount(text: string): number | null {
+  if (/^\d+(?:\.\d+)?$/.test(text)) {
+    return Number(text);
+  }
+  const parsed = parseNumberRun(text);
+  if (
+    parsed.length !== 1 ||
+    parsed[0].start !== 0 ||
+    parsed[0].end !== text.length
+  ) {
+    return null;
+  }
+  const [number] = parsed;
+  return number.decimal !== undefined
+    ? Number(`${number.value}.${number.decimal}`)
+    : number.value;
+}
+
+function formatNumber(value: number): string {
+  return value >= 10_000 ? value.toLocaleString("en-US") : String(value);
+}
+
+function parseDay(text: string): number | null {
+  const digits = text.match(/^(\d{1,2})(?:st|nd|rd|th)?$/i);
+  let day: number | null;
+  if (digits) {
+    day = Number(digits[1]);
+  } else {
+    const words = text.toLowerCase().split(/[\s-]+/);
+    const last = words[words.length - 1];
+    if (last in ORDINALS) {
+      const tens = words.length > 1 ? (TENS[words[0]] ?? 0) : 0;
+      day = tens + ORDINALS[last];
+    } else {
+      day = parseAmount(text.toLowerCase());
+    }
+  }
+  return day !== null && Number.isInteger(day) && day >= 1 && day <= 31
+    ? day
+    : null;
+}
+
+function parseYear(text: string): number | null {
+  const year = parseAmount(text.toLowerCase());
+  return year !== null && Number.isInteger(year) && year >= 1000 && year <= 2999
+    ? year
+    : null;
+}
+
+function formatDate(
+  month: number,
+  day: number,
+  year: number | null,
+  format: DateFormat,
+): string {
+  const mm = String(month).padStart(2, "0");
+  const dd = String(day).padStart(2, "0");
+  switch (format) {
+    case "iso":
+      if (year !== null) return `${year}-${mm}-${dd}`;
+      break;
+    case "us":
+      return year !== null ? `${mm}/${dd}/${year}` : `${mm}/${dd}`;
+    case "eu":
+      return year !== null ? `${dd}/${mm}/${year}` : `${dd}/${mm}`;
+  }
+  const name = MONTHS[month - 1];
+  return year !== null ? `${name} ${day}, ${year}` : `${name} ${day}`;
+}
+
+function normalizeDates(text: string, format: DateFormat): string {
+  const replaceDate = (
+    match: string,
+    monthText: string,
+    dayText: string,
+    separator: string | undefined,
+    yearText: string | undefined,
+  ): string => {
+    // Month names must be capitalized to avoid "you may second that"
+    if (monthText[0] !== monthText[0].toUpperCase()) {
+      return match;
+    }
+    const month =
+      MONTHS.findIndex((m) => m.toLowerCase() === monthText.toLowerCase()) + 1;
+    const day = parseDay(dayText);
+    if (!month || day === null) {
+      return match;
+    }
+    const year = yearText ? parseYear(yearText) : null;
+    const formatted = formatDate(month, day, year, format);
+    // Keep an unparseable trailing phrase as-is rather than dropping it
+    return yearText && year === null
+      ? formatted + separator + yearText
+      : formatted;
+  };
+
+  return text
+    .replace(MONTH_DAY_PATTERN, (match, month, day, separator, year) =>
+      replaceDate(match, month, day, separator, year),
+    )
+    .replace(DAY_OF_MONTH_PATTERN, (match, day, month, separator, year) =>
+      replaceDate(match, month, day, separator, year),
+    );
+}
+
+function normalizeCurrency(text: string): string {
+  return text.replace(
+    CURRENCY_PATTERN,
+    (match, amountText: string, unit: string, centsText?: string) => {
+      const amount = parseAmount(amountText.toLowerCase());
+      if (amount === null) {
+        return match;
+      }
+      const symbol = CURRENCY_SYMBOLS[unit.toLowerCase()];
+      if (centsText !== undefined) {
+        const cents = parseAmount(centsText.toLowerCase());
+        if (cents === null || !Number.isInteger(cents) || cents > 99) {
+          return match;
+        }
+        return `${symbol}${formatNumber(Math.trunc(amount))}.${String(cents).padStart(2, "0")}`;
+      }
+      return `${symbol}${Number.isInteger(amount) ? formatNumber(amount) : amount.toFixed(2)}`;
+    },
+  );
+}
+
+function normalizePercentages(text: string): string {
+  return text.replace(PERCENT_PATTERN, (match, amountText: string) => {
+    const amount = parseAmount(amountText.toLowerCase());
+    return amount === null ? match : `${amount}%`;
+  });
+}
+
+function wordsToDigits(text: string): string {
+  return text.replace(NUMBER_RUN_PATTERN, (run) => {
+    let output = "";
+    let position = 0;
+    for (const number of parseNumberRun(run)) {
+      const original = run.slice(number.start, number.end);
+      let rendered = original;
+      if (number.isYear) {
+        rendered = String(number.value);
+      } else if (number.decimal !== undefined) {
+        rendered = `${number.value}.${number.decimal}`;
+      } else if (number.value >= 10) {
+        // AP style: spell out zero through nine
+        rendered = formatNumber(number.value);
+      }
+      output += run.slice(position, number.start) + rendered;
+      position = number.end;
+    }
+    return output + run.slice(position);
+  });
+}
+
+function digitsToWords(text: string): string {
+  const names = Object.keys(UNITS);
+  return text.replace(SINGLE_DIGIT_PATTERN, (_, digit: string) => {
+    return names[Number(digit)];
+  });
+}
+
+/**
+ * Apply the configured normalization rules to transcription output.
+ * Non-English languages are returned untouched.
+ */
+export function normalizeText(
+  text: string,
+  config: NormalizationConfig,
+  language?: string,
+): string {
+  if (!config.enabled || !text.trim()) {
+    return text;
+  }
+  if (language && !language.startsWith("en")) {
+    return text;
+  }
+
+  let result = text;
+  if (config.dateFormat !== "off") {
+    result = normalizeDates(result, config.dateFormat);
+  }
+  if (config.currency) {
+    result = normalizeCurrency(result);
+  }
+  if (config.numbers === "digits") {
+    result = normalizePercentages(result);
+    result = wordsToDigits(result);
+  } else if (config.numbers === "words") {
+    result = digitsToWords(result);
+  }
+  return result;
+}
@@ -0,0 +1,161 @@
+/**
+ * LLM pass that fixes misrecognised vocabulary terms, plus the diff check
+ * that makes sure the model did nothing else.
+ *
+ * The model's output is never used as-is: it's diffed word by word against
+ * the original and each changed span must turn a short run of words into
+ * exactly one vocabulary term, keeping surrounding punctuation. If any span
+ * fails, the whole correction is rejected. Accepted spans are spliced into
+ * the original so its whitespace and line breaks survive.
+ */
+
+// LCS table is O(n·m); longer dictations skip the pass
+export const MAX_CORRECTION_WORDS = 1500;
+// A term can be misheard as a few words ("cooper netties" → "Kubernetes")
+const MAX_REPLACED_WORDS = 4;
+
+export const VOCABULARY_CORRECTION_SYSTEM_PROMPT = `You fix speech recognition errors in a transcript.
+The user has a list of terms (names, jargon, product names) that the recognizer often gets wrong.
+Where the transcript contains a misrecognition of one of these terms (a similar-sounding word or words), replace it with the term exactly as written in the list.
+Change nothing else: do not rephrase, fix grammar, punctuation or capitalization, or add or remove words.
+If nothing needs fixing, return the transcript unchanged.
+Return only the transcript inside <corrected_text></corrected_text> tags.`;
+
+export function buildVocabularyCorrectionPrompt(
+  text: string,
+  vocabulary: string[],
+): string {
+  const terms = vocabulary.map((term) => `- ${term}`).join("\n");
+  return `Terms:\n${terms}\n\n<transcript>\n${text}\n</transcript>`;
+}
+
+export function parseVocabularyCorrectionResponse(response: string): string {
+  const match = response.match(/<corrected_text>([\s\S]*?)<\/corrected_text>/);
+  return (match ? match[1] : response).trim();
+}
+
+interface Token {
+  word: string;
+  trailing: string; // Whitespace after the word
+}
+
+function tokenize(text: string): { leading: string; tokens: Token[] } {
+  const leading = text.match(/^\s*/)?.[0] ?? "";
+  const tokens: Token[] = [];
+  for (const match of text.matchAll(/(\S+)(\s*)/g)) {
+    tokens.push({ word: match[1], trailing: match[2] });
+  }
+  return { leading, tokens };
+}
+
+function splitPunctuation(phrase: string) {
+  const match = phrase.match(/^([\p{P}\p{S}]*)(.*?)([\p{P}\p{S}]*)$/u);
+  return {
+    lead: match?.[1] ?? "",
+    core: match?.[2] ?? phrase,
+    trail: match?.[3] ?? "",
+  };
+}
+
+interface Hunk {
+  from: number; // Range in the original words
+  to: number;
+  insert: string[]; // Words from the corrected text
+}
+
+// Word-level diff via longest common subsequence
+function diffWords(a: string[], b: string[]): Hunk[] {
+  const n = a.length;
+  const m = b.length;
+  const width = m + 1;
+  const lcs = new Int32Array((n + 1) * width);
+  for (let i = n - 1; i >= 0; i--) {
+    for (let j = m - 1; j >= 0; j--) {
+      lcs[i * width + j] =
+        a[i] === b[j]
+          ? lcs[(i + 1) * width + j + 1] + 1
+          : Math.max(lcs[(i + 1) * width + j], lcs[i * width + j + 1]);
+    }
+  }
+
+  const hunks: Hunk[] = [];
+  let current: Hunk | null = null;
+  let i = 0;
+  let j = 0;
+  while (i < n || j < m) {
+    if (i < n && j < m && a[i] === b[j]) {
+      if (current) hunks.push(current);
+      current = null;
+      i++;
+      j++;
+      continue;
+    }
+    current ??= { from: i, to: i, insert: [] };
+    if (
+      j < m &&
+      (i === n || lcs[i * width + j + 1] >= lcs[(i + 1) * width + j])
+    ) {
+      current.insert.push(b[j]);
+      j++;
+    } else {
+      i++;
+      current.to = i;
+    }
+  }
+  if (current) hunks.push(current);
+  return hunks;
+}
+
+/**
+ * Validate the model's correction against the original and splice the
+ * accepted term replacements in. Returns null when the model changed
+ * anything besides vocabulary terms.
+ */
+export function applyVocabularyCorrection(
+  original: string,
+  corrected: string,
+  vocabulary: string[],
+): { text: string; corrections: number } | null {
+  const { leading, tokens } = tokenize(original);
+  const correctedWords = tokenize(corrected).tokens.map((t) => t.word);
+  const hunks = diffWords(tokens.map((t) => t.word), correctedWords);
+  if (hunks.length === 0) {
+    return { text: original, corrections: 0 };
+  }
+
+  const terms = new Set(vocabulary);
+  for (const hunk of hunks) {
+    const removed = tokens.slice(hunk.from, hunk.to).map((t) => t.word);
+    if (
+      removed.length === 0 ||
+      removed.length > MAX_REPLACED_WORDS ||
+      hunk.insert.length === 0
+    ) {
+      return null;
+    }
+    const before = splitPunctuation(removed.join(" "));
+    const after = splitPunctuation(hunk.insert.join(" "));
+    if (
+      !terms.has(after.core) ||
+      after.core === before.core ||
+      after.lead !== before.lead ||
+      after.trail !== before.trail
+    ) {
+      return null;
+    }
+  }
+
+  let text = leading;
+  let index = 0;
+  for (const hunk of hunks) {
+    for (; index < hunk.from; index++) {
+      text += tokens[index].word + tokens[index].trailing;
+    }
+    text += hunk.insert.join(" ") + tokens[hunk.to - 1].trailing;
+    index = hunk.to;
+  }
+  for (; index < tokens.length; index++) {
+    text += tokens[index].word + tokens[index].trailing;
+  }
+  return { text, corrections: hunks.length };
+}
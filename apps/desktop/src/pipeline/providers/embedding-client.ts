@@ -0,0 +1,61 @@
+import { createOpenAI } from "@ai-sdk/openai";
+import { embedMany } from "ai";
+import { OllamaClient } from "./ollama-client";
+import { recordTokenUsage } from "../../db/usage";
+import type { EmbeddingConfig } from "../../types/embedding";
+import type { AppSettingsData } from "../../db/schema";
+
+export interface EmbeddingClient {
+  // "<provider>:<model>"; vectors from different models aren't comparable
+  readonly modelKey: string;
+  embed(texts: string[], signal?: AbortSignal): Promise<number[][]>;
+}
+
+/**
+ * Build a client for the configured embedding model. Throws when the
+ * provider it points at has no credentials/URL configured.
+ */
+export function createEmbeddingClient(
+  config: EmbeddingConfig,
+  providers: AppSettingsData["modelProvidersConfig"],
+): EmbeddingClient {
+  const modelKey = `${config.provider}:${config.model}`;
+
+  switch (config.provider) {
+    case "ollama": {
+      const url = providers?.ollama?.url;
+      if (!url) {
+        throw new Error("Ollama is not configured");
+      }
+      const client = new OllamaClient(url);
+      return {
+        modelKey,
+        embed: (texts, signal) => client.embed(config.model, texts, signal),
+      };
+    }
+    case "openai": {
+      const apiKey = providers?.openAI?.apiKey;
+      if (!apiKey) {
+        throw new Error("OpenAI API key is not configured");
+      }
+      const openai = createOpenAI({ apiKey });
+      return {
+        modelKey,
+        embed: async (texts, signal) => {
+          const { embeddings, usage } = await embedMany({
+            model: openai.embedding(config.model),
+            values: texts,
+            abortSignal: signal,
+          });
+          void recordTokenUsage({
+            provider: "openai",
+            model: config.model,
+            kind: "embedding",
+            promptTokens: usage.tokens,
+          });
+          return embeddings;
+        },
+      };
+    }
+  }
+}
@@ -1,13 +1,54 @@
-import { FormattingProvider, FormatParams } from "../../core/pipeline-types";
+import {
+  FormattingProvider,
+  FormatParams,
+  GenerateParams,
+} from "../../core/pipeline-types";
 import { logger } from "../../../main/logger";
-import { createAnthropic } from "@ai-sdk/anthropic";
-import { constructFormatterPrompt } from "./formatter-prompt";
-import { generateText } from "ai";
+import { recordTokenUsage } from "../../../db/usage";
+import { createAnthropic, type AnthropicProvider } from "@ai-sdk/anthropic";
+import {
+  constructFormatterPrompt,
+  DEFAULT_GENERATE_MAX_TOKENS,
+} from "./formatter-prompt";
+import { APICallError, generateText } from "ai";
+
+// Output budget scales with input; formatting rarely grows the text much
+const MIN_MAX_TOKENS = 1024;
+const MAX_MAX_TOKENS = 8192;
+
+/**
+ * Map Messages API failures to something actionable in the logs.
+ * See https://docs.anthropic.com/en/api/errors
+ */
+function describeAnthropicError(error: unknown): string {
+  if (!APICallError.isInstance(error)) {
+    return error instanceof Error ? error.message : String(error);
+  }
+  switch (error.statusCode) {
+    case 400:
+      return `Invalid request: ${error.message}`;
+    case 401:
+      return "Invalid Anthropic API key";
+    case 403:
+      return "API key lacks permission for this model";
+    case 404:
+      return "Model not found";
+    case 413:
+      return "Transcription too long for a single request";
+    case 429:
+      return "Rate limited by Anthropic";
+    case 500:
+    case 529:
+      return "Anthropic API is overloaded or unavailable";
+    default:
+      return `Anthropic API error ${error.statusCode ?? ""}: ${error.message}`;
+  }
+}
 
 export class AnthropicFormatter implements FormattingProvider {
   readonly name = "anthropic";
 
-  private provider: any;
+  private provider: AnthropicProvider;
   private model: string;
 
   constructor(apiKey: string, model: string) {
@@ -30,20 +71,33 @@ export class AnthropicFormatter implements FormattingProvider {
         userPrompt,
       });
 
-      const { text: aiResponse } = await generateText({
+      // ~4 chars per token; double it for headroom and the XML wrapper
+      const maxTokens = Math.min(
+        MAX_MAX_TOKENS,
+        Math.max(MIN_MAX_TOKENS, Math.ceil(userPrompt.length / 2)),
+      );
+
+      const { text: aiResponse, usage } = await generateText({
         model: this.provider(this.model),
+        system: systemPrompt,
         messages: [
-          {
-            role: "system",
-            content: systemPrompt,
-          },
           {
             role: "user",
             content: userPrompt,
           },
         ],
         temperature: 0.1,
-        maxTokens: 2000,
+        maxTokens,
+        maxRetries: 1,
+        abortSignal: params.abortSignal,
+      });
+
+      void recordTokenUsage({
+        provider: this.name,
+        model: this.model,
+        kind: "formatting",
+        promptTokens: usage.promptTokens,
+        completionTokens: usage.completionTokens,
       });
 
       logger.pipeline.debug("Formatting raw response", {
@@ -64,8 +118,32 @@ export class AnthropicFormatter implements FormattingProvider {
 
       return formattedText;
     } catch (error) {
-      logger.pipeline.error("Formatting failed:", error);
-      return params.text;
+      logger.pipeline.error("Formatting failed:", {
+        provider: this.name,
+        model: this.model,
+        reason: describeAnthropicError(error),
+      });
+      throw error;
     }
   }
+
+  async generate(params: GenerateParams): Promise<string> {
+    const { text, usage } = await generateText({
+      model: this.provider(this.model),
+      system: params.system,
+      prompt: params.prompt,
+      temperature: 0.3,
+      maxTokens: params.maxTokens ?? DEFAULT_GENERATE_MAX_TOKENS,
+      maxRetries: 1,
+      abortSignal: params.abortSignal,
+    });
+    void recordTokenUsage({
+      provider: this.name,
+      model: this.model,
+      kind: "generation",
+      promptTokens: usage.promptTokens,
+      completionTokens: usage.completionTokens,
+    });
+    return text;
+  }
 }
@@ -0,0 +1,87 @@
+/**
+ * Built-in tone/style presets. A preset replaces the app-type rules that
+ * constructFormatterPrompt would otherwise pick from the focused app, so the
+ * output style stays the same regardless of where the text is inserted.
+ */
+
+export const FORMATTER_PRESET_IDS = [
+  "email",
+  "chat",
+  "bullet-notes",
+  "code-comment",
+  "professional",
+] as const;
+
+export type FormatterPresetId = (typeof FORMATTER_PRESET_IDS)[number];
+
+export interface FormatterPreset {
+  id: FormatterPresetId;
+  name: string;
+  description: string;
+  instructions: string[];
+}
+
+export const FORMATTER_PRESETS: FormatterPreset[] = [
+  {
+    id: "email",
+    name: "Email",
+    description: "Greeting, short paragraphs and a closing.",
+    instructions: [
+      "Structure the text as an email body: greeting, paragraphs, closing",
+      "Start a new paragraph for each topic or request",
+      "Keep a friendly but professional tone",
+      "Do not invent a subject line, recipient names or a signature",
+    ],
+  },
+  {
+    id: "chat",
+    name: "Chat",
+    description: "Casual, concise messages for Slack, Teams and friends.",
+    instructions: [
+      "Keep the conversational tone and informal wording",
+      "Prefer short sentences; avoid formal greetings and sign-offs",
+      "Do not add headings or bullet points unless they were dictated",
+      "Keep emoji and emoticons that are present",
+    ],
+  },
+  {
+    id: "bullet-notes",
+    name: "Bullet notes",
+    description: "Condensed bullet points, one idea per line.",
+    instructions: [
+      "Rewrite the text as a bulleted list using '- ' markers",
+      "Put one idea, fact or action item per bullet",
+      "Drop filler and connective phrases, but keep every fact",
+      "Use nested bullets for details that belong to a previous point",
+      "Start action items with a verb",
+    ],
+  },
+  {
+    id: "code-comment",
+    name: "Code comment",
+    description: "Terse technical prose suitable for code comments.",
+    instructions: [
+      "Write terse, technical sentences suitable for a code comment",
+      "Keep identifiers, file names and symbols exactly as spoken, formatted in backticks",
+      "Do not add comment markers such as // or #",
+      "Avoid first person and conversational phrasing",
+    ],
+  },
+  {
+    id: "professional",
+    name: "Professional",
+    description: "Polished, formal wording for documents and reports.",
+    instructions: [
+      "Use a polished, formal register",
+      "Replace slang and casual phrasing with neutral professional wording",
+      "Use complete sentences and well-formed paragraphs",
+      "Do not add content, opinions or claims that were not dictated",
+    ],
+  },
+];
+
+export function getFormatterPreset(
+  id: string | undefined,
+): FormatterPreset | undefined {
+  return id ? FORMATTER_PRESETS.find((preset) => preset.id === id) : undefined;
+}
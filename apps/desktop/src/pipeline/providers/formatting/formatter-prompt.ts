@@ -1,5 +1,9 @@
 import { FormatParams } from "../../core/pipeline-types";
 import { GetAccessibilityContextResult } from "@amical/types";
+import { getFormatterPreset } from "./formatter-presets";
+
+// Output budget for generate() calls that don't set one
+export const DEFAULT_GENERATE_MAX_TOKENS = 1000;
 
 // Base system prompt
 const SYSTEM_PROMPT = `You are a professional text formatter. Your task is to format transcribed text to be clear, readable, and properly structured.`;
@@ -119,16 +123,18 @@ const URL_PATTERNS: Partial<Record<AppType, RegExp[]>> = {
 export function constructFormatterPrompt(context: FormatParams["context"]): {
   systemPrompt: string;
 } {
-  const { accessibilityContext, vocabulary, customInstructions } = context;
+  const { accessibilityContext, vocabulary, customInstructions, presetId } =
+    context;
 
-  // Detect application type
-  const applicationType = detectApplicationType(accessibilityContext);
+  // A selected preset overrides the rules detected from the focused app
+  const preset = getFormatterPreset(presetId);
+  const styleRules =
+    preset?.instructions ??
+    APPLICATION_TYPE_RULES[detectApplicationType(accessibilityContext)] ??
+    [];
 
   // Build instructions array
-  const instructions = [
-    ...BASE_INSTRUCTIONS,
-    ...(APPLICATION_TYPE_RULES[applicationType] || []),
-  ];
+  const instructions = [...BASE_INSTRUCTIONS, ...styleRules];
 
   // Build prompt parts
   const parts = [SYSTEM_PROMPT];
@@ -1,14 +1,27 @@
-import { FormattingProvider, FormatParams } from "../../core/pipeline-types";
+import {
+  FormattingProvider,
+  FormatParams,
+  GenerateParams,
+} from "../../core/pipeline-types";
 import { logger } from "../../../main/logger";
-import { constructFormatterPrompt } from "./formatter-prompt";
+import { recordTokenUsage } from "../../../db/usage";
+import {
+  constructFormatterPrompt,
+  DEFAULT_GENERATE_MAX_TOKENS,
+} from "./formatter-prompt";
+import { OllamaClient } from "../ollama-client";
 
 export class OllamaFormatter implements FormattingProvider {
   readonly name = "ollama";
 
+  private client: OllamaClient;
+
   constructor(
-    private ollamaUrl: string,
+    ollamaUrl: string,
     private model: string,
-  ) {}
+  ) {
+    this.client = new OllamaClient(ollamaUrl);
+  }
 
   async format(params: FormatParams): Promise<string> {
     try {
@@ -24,30 +37,26 @@ export class OllamaFormatter implements FormattingProvider {
       });
 
       // Use Ollama's chat endpoint for system/user message structure
-      const response = await fetch(`${this.ollamaUrl}/api/chat`, {
-        method: "POST",
-        headers: { "Content-Type": "application/json" },
-        body: JSON.stringify({
-          model: this.model,
-          messages: [
-            { role: "system", content: systemPrompt },
-            { role: "user", content: text },
-          ],
-          stream: false,
-          options: {
-            temperature: 0.1, // Low temperature for consistent formatting
-            num_predict: 2000,
-          },
-        }),
+      const aiResponse = await this.client.chat({
+        model: this.model,
+        messages: [
+          { role: "system", content: systemPrompt },
+          { role: "user", content: text },
+        ],
+        options: {
+          temperature: 0.1, // Low temperature for consistent formatting
+          num_predict: 2000,
+        },
+        signal: params.abortSignal,
+        onUsage: (usage) =>
+          void recordTokenUsage({
+            provider: this.name,
+            model: this.model,
+            kind: "formatting",
+            ...usage,
+          }),
       });
 
-      if (!response.ok) {
-        throw new Error(`Ollama API error: ${response.status}`);
-      }
-
-      const data = await response.json();
-      const aiResponse = data.message?.content ?? "";
-
       logger.pipeline.debug("Formatting raw response", {
         model: this.model,
         rawResponse: aiResponse,
@@ -68,8 +77,29 @@ export class OllamaFormatter implements FormattingProvider {
       return formattedText;
     } catch (error) {
       logger.pipeline.error("Formatting failed:", error);
-      // Return original text if formatting fails - simple fallback
-      return params.text;
+      throw error;
     }
   }
+
+  async generate(params: GenerateParams): Promise<string> {
+    return await this.client.chat({
+      model: this.model,
+      messages: [
+        { role: "system", content: params.system },
+        { role: "user", content: params.prompt },
+      ],
+      options: {
+        temperature: 0.3,
+        num_predict: params.maxTokens ?? DEFAULT_GENERATE_MAX_TOKENS,
+      },
+      signal: params.abortSignal,
+      onUsage: (usage) =>
+        void recordTokenUsage({
+          provider: this.name,
+          model: this.model,
+          kind: "generation",
+          ...usage,
+        }),
+    });
+  }
 }
@@ -1,7 +1,15 @@
-import { FormattingProvider, FormatParams } from "../../core/pipeline-types";
+import {
+  FormattingProvider,
+  FormatParams,
+  GenerateParams,
+} from "../../core/pipeline-types";
 import { logger } from "../../../main/logger";
+import { recordTokenUsage } from "../../../db/usage";
 import { createOpenAI } from "@ai-sdk/openai";
-import { constructFormatterPrompt } from "./formatter-prompt";
+import {
+  constructFormatterPrompt,
+  DEFAULT_GENERATE_MAX_TOKENS,
+} from "./formatter-prompt";
 import { generateText } from "ai";
 
 export class OpenAIFormatter implements FormattingProvider {
@@ -30,7 +38,7 @@ export class OpenAIFormatter implements FormattingProvider {
         userPrompt,
       });
 
-      const { text: aiResponse } = await generateText({
+      const { text: aiResponse, usage } = await generateText({
         model: this.provider(this.model),
         messages: [
           {
@@ -44,6 +52,15 @@ export class OpenAIFormatter implements FormattingProvider {
         ],
         temperature: 0.1,
         maxTokens: 2000,
+        abortSignal: params.abortSignal,
+      });
+
+      void recordTokenUsage({
+        provider: this.name,
+        model: this.model,
+        kind: "formatting",
+        promptTokens: usage.promptTokens,
+        completionTokens: usage.completionTokens,
       });
 
       logger.pipeline.debug("Formatting raw response", {
@@ -65,7 +82,26 @@ export class OpenAIFormatter implements FormattingProvider {
       return formattedText;
     } catch (error) {
       logger.pipeline.error("Formatting failed:", error);
-      return params.text;
+      throw error;
     }
   }
+
+  async generate(params: GenerateParams): Promise<string> {
+    const { text, usage } = await generateText({
+      model: this.provider(this.model),
+      system: params.system,
+      prompt: params.prompt,
+      temperature: 0.3,
+      maxTokens: params.maxTokens ?? DEFAULT_GENERATE_MAX_TOKENS,
+      abortSignal: params.abortSignal,
+    });
+    void recordTokenUsage({
+      provider: this.name,
+      model: this.model,
+      kind: "generation",
+      promptTokens: usage.promptTokens,
+      completionTokens: usage.completionTokens,
+    });
+    return text;
+  }
 }
@@ -1,20 +1,39 @@
-import { FormattingProvider, FormatParams } from "../../core/pipeline-types";
+import {
+  FormattingProvider,
+  FormatParams,
+  GenerateParams,
+} from "../../core/pipeline-types";
 import { logger } from "../../../main/logger";
-import { createOpenRouter } from "@openrouter/ai-sdk-provider";
-import { constructFormatterPrompt } from "./formatter-prompt";
+import { recordTokenUsage } from "../../../db/usage";
+import {
+  createOpenRouter,
+  type OpenRouterProvider as OpenRouterClient,
+} from "@openrouter/ai-sdk-provider";
+import {
+  constructFormatterPrompt,
+  DEFAULT_GENERATE_MAX_TOKENS,
+} from "./formatter-prompt";
+import { getUserAgent } from "../../../utils/http-client";
 
 import { generateText } from "ai";
 
+// App attribution headers, see https://openrouter.ai/docs/api-reference/overview#headers
+const OPENROUTER_HEADERS = {
+  "HTTP-Referer": "https://amical.ai",
+  "X-Title": "Grizzo",
+};
+
 export class OpenRouterProvider implements FormattingProvider {
   readonly name = "openrouter";
 
-  private provider: any;
+  private provider: OpenRouterClient;
   private model: string;
 
   constructor(apiKey: string, model: string) {
     // Configure OpenRouter provider
     this.provider = createOpenRouter({
       apiKey: apiKey,
+      headers: { ...OPENROUTER_HEADERS, "User-Agent": getUserAgent() },
     });
 
     this.model = model;
@@ -37,8 +56,12 @@ export class OpenRouterProvider implements FormattingProvider {
         userPrompt,
       });
 
-      const { text: aiResponse } = await generateText({
-        model: this.provider(this.model),
+      const { text: aiResponse, usage } = await generateText({
+        // Dictation is interactive, so let OpenRouter route to the
+        // lowest-latency upstream serving this model slug
+        model: this.provider(this.model, {
+          extraBody: { provider: { sort: "latency", allow_fallbacks: true } },
+        }),
         messages: [
           {
             role: "system",
@@ -51,6 +74,15 @@ export class OpenRouterProvider implements FormattingProvider {
         ],
         temperature: 0.1, // Low temperature for consistent formatting
         maxTokens: 2000,
+        abortSignal: params.abortSignal,
+      });
+
+      void recordTokenUsage({
+        provider: this.name,
+        model: this.model,
+        kind: "formatting",
+        promptTokens: usage.promptTokens,
+        completionTokens: usage.completionTokens,
       });
 
       logger.pipeline.debug("Formatting raw response", {
@@ -73,8 +105,28 @@ export class OpenRouterProvider implements FormattingProvider {
       return formattedText;
     } catch (error) {
       logger.pipeline.error("Formatting failed:", error);
-      // Return original text if formatting fails - simple fallback
-      return params.text;
+      throw error;
     }
   }
+
+  async generate(params: GenerateParams): Promise<string> {
+    const { text, usage } = await generateText({
+      model: this.provider(this.model, {
+        extraBody: { provider: { sort: "latency", allow_fallbacks: true } },
+      }),
+      system: params.system,
+      prompt: params.prompt,
+      temperature: 0.3,
+      maxTokens: params.maxTokens ?? DEFAULT_GENERATE_MAX_TOKENS,
+      abortSignal: params.abortSignal,
+    });
+    void recordTokenUsage({
+      provider: this.name,
+      model: this.model,
+      kind: "generation",
+      promptTokens: usage.promptTokens,
+      completionTokens: usage.completionTokens,
+    });
+    return text;
+  }
 }
@@ -0,0 +1,178 @@
+import type { OllamaModel, OllamaResponse } from "../../types/providers";
+import { getUserAgent } from "../../utils/http-client";
+
+const DEFAULT_TIMEOUT_MS = 60_000;
+
+export interface OllamaChatMessage {
+  role: "system" | "user" | "assistant";
+  content: string;
+}
+
+export interface OllamaChatRequest {
+  model: string;
+  messages: OllamaChatMessage[];
+  options?: {
+    temperature?: number;
+    num_predict?: number;
+    num_ctx?: number;
+  };
+  keepAlive?: string; // e.g. "5m"; how long Ollama keeps the model loaded
+  signal?: AbortSignal;
+  // Called with the token counts Ollama reports for a non-streaming chat
+  onUsage?: (usage: { promptTokens: number; completionTokens: number }) => void;
+}
+
+export class OllamaError extends Error {
+  constructor(
+    message: string,
+    readonly status?: number,
+  ) {
+    super(message);
+    this.name = "OllamaError";
+  }
+}
+
+/**
+ * Minimal client for a local Ollama server (https://github.com/ollama/ollama/blob/main/docs/api.md).
+ * Every request is bounded by `timeoutMs`; callers can also pass their own
+ * AbortSignal to cancel earlier.
+ */
+export class OllamaClient {
+  private baseUrl: string;
+  private timeoutMs: number;
+
+  constructor(baseUrl: string, options?: { timeoutMs?: number }) {
+    this.baseUrl = baseUrl.replace(/\/+$/, "");
+    this.timeoutMs = options?.timeoutMs ?? DEFAULT_TIMEOUT_MS;
+  }
+
+  /**
+   * Get the server version; doubles as a connectivity check
+   */
+  async version(signal?: AbortSignal): Promise<string> {
+    const data = await this.request<{ version: string }>("/api/version", {
+      method: "GET",
+      signal,
+    });
+    return data.version;
+  }
+
+  /**
+   * List locally installed models
+   */
+  async listModels(signal?: AbortSignal): Promise<OllamaModel[]> {
+    const data = await this.request<OllamaResponse>("/api/tags", {
+      method: "GET",
+      signal,
+    });
+    return data.models ?? [];
+  }
+
+  /**
+   * Non-streaming chat completion; returns the assistant message content
+   */
+  async chat(request: OllamaChatRequest): Promise<string> {
+    const data = await this.request<{
+      message?: { content?: string };
+      prompt_eval_count?: number;
+      eval_count?: number;
+    }>("/api/chat", {
+      method: "POST",
+      body: this.chatBody(request),
+      signal: request.signal,
+    });
+    request.onUsage?.({
+      promptTokens: data.prompt_eval_count ?? 0,
+      completionTokens: data.eval_count ?? 0,
+    });
+    return data.message?.content ?? "";
+  }
+
+  /**
+   * Compute embeddings for one or more inputs
+   */
+  async embed(
+    model: string,
+    input: string | string[],
+    signal?: AbortSignal,
+  ): Promise<number[][]> {
+    const data = await this.request<{ embeddings: number[][] }>(
+      "/api/embed",
+      {
+        method: "POST",
+        body: JSON.stringify({ model, input }),
+        signal,
+      },
+    );
+    return data.embeddings;
+  }
+
+  private chatBody(request: OllamaChatRequest): string {
+    return JSON.stringify({
+      model: request.model,
+      messages: request.messages,
+      stream: false,
+      options: request.options,
+      keep_alive: request.keepAlive,
+    });
+  }
+
+  private async request<T>(
+    path: string,
+    init: { method: string; body?: string; signal?: AbortSignal },
+  ): Promise<T> {
+    const response = await this.send(path, init);
+    return (await response.json()) as T;
+  }
+
+  private async send(
+    path: string,
+    init: { method: string; body?: string; signal?: AbortSignal },
+  ): Promise<Response> {
+    const timeoutSignal = AbortSignal.timeout(this.timeoutMs);
+    const signal = init.signal
+      ? AbortSignal.any([init.signal, timeoutSignal])
+      : timeoutSignal;
+
+    let response: Response;
+    try {
+      response = await fetch(`${this.baseUrl}${path}`, {
+        method: init.method,
+        headers: {
+          "Content-Type": "application/json",
+          "User-Agent": getUserAgent(),
+        },
+        body: init.body,
+        signal,
+      });
+    } catch (error) {
+      if (timeoutSignal.aborted) {
+        throw new OllamaError(
+          `Ollama request timed out after ${this.timeoutMs}ms`,
+        );
+      }
+      if (init.signal?.aborted) {
+        throw error;
+      }
+      throw new OllamaError(
+        `Failed to connect to Ollama at ${this.baseUrl}. Make sure Ollama is running.`,
+      );
+    }
+
+    if (!response.ok) {
+      // Ollama reports failures as { "error": "..." }
+      const detail = await response
+        .json()
+        .then((body: { error?: string }) => body.error)
+        .catch(() => undefined);
+      throw new OllamaError(
+        detail
+          ? `Ollama API error ${response.status}: ${detail}`
+          : `Ollama API error: ${response.status} ${response.statusText}`,
+        response.status,
+      );
+    }
+
+    return response;
+  }
+}
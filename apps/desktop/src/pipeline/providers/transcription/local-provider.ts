@@ -0,0 +1,257 @@
+import {
+  TranscriptionProvider,
+  TranscribeParams,
+  TranscribeContext,
+  ChunkTranscription,
+} from "../../core/pipeline-types";
+import { logger } from "../../../main/logger";
+
+/**
+ * Base for on-device speech models. Frames are buffered until a long enough
+ * pause (or too much audio), then the whole chunk is handed to the runtime.
+ */
+export abstract class LocalTranscriptionProvider
+  implements TranscriptionProvider
+{
+  abstract readonly name: string;
+
+  // Frame aggregation state
+  private frameBuffer: Float32Array[] = [];
+  private frameBufferSpeechProbabilities: number[] = []; // Track speech probabilities for each frame
+  private currentSilenceFrameCount = 0;
+  private lastSpeechTimestamp = 0;
+
+  // Configuration
+  private readonly TRIM_TRAILING_AND_LEADING_SILENCE = false;
+  private readonly FRAME_SIZE = 512; // 32ms at 16kHz
+  private readonly MIN_SPEECH_DURATION_MS = 500; // Minimum speech duration to transcribe
+  private readonly MAX_SILENCE_DURATION_MS = 3000; // Max silence before cutting
+  private readonly SAMPLE_RATE = 16000;
+  private readonly SPEECH_PROBABILITY_THRESHOLD = 0.2; // Threshold for speech detection
+  private readonly IGNORE_FULLY_SILENT_CHUNKS = true;
+
+  /**
+   * Load the model into memory so the first chunk isn't delayed
+   */
+  abstract preloadModel(): Promise<void>;
+
+  // Whether the model is in memory, ready for the next chunk
+  abstract isModelLoaded(): boolean;
+
+  abstract dispose(): Promise<void>;
+
+  /**
+   * Transcribe one aggregated chunk of 16 kHz mono audio
+   */
+  protected abstract transcribeChunk(
+    audio: Float32Array,
+    context: TranscribeContext,
+  ): Promise<ChunkTranscription>;
+
+  /**
+   * Process an audio chunk - buffers and conditionally transcribes
+   */
+  async transcribe(params: TranscribeParams): Promise<string> {
+    await this.preloadModel();
+
+    const { audioData, speechProbability = 1, context } = params;
+
+    // Add frame to buffer with speech probability
+    this.frameBuffer.push(audioData);
+    this.frameBufferSpeechProbabilities.push(speechProbability);
+
+    // Consider it speech if probability is above threshold
+    const isSpeech =
+      speechProbability >
+      (context.chunking?.speechThreshold ?? this.SPEECH_PROBABILITY_THRESHOLD);
+
+    logger.transcription.debug(
+      `Frame received - SpeechProb: ${speechProbability.toFixed(3)}, Buffer size: ${this.frameBuffer.length}, Silence count: ${this.currentSilenceFrameCount}`,
+    );
+
+    // Handle speech/silence logic
+    if (isSpeech) {
+      this.currentSilenceFrameCount = 0;
+      this.lastSpeechTimestamp = Date.now();
+    } else {
+      this.currentSilenceFrameCount++;
+    }
+
+    // Only transcribe if speech/silence patterns indicate we should
+    if (!this.shouldTranscribe(context.chunking?.maxSilenceMs)) {
+      return "";
+    }
+
+    return this.doTranscription(context);
+  }
+
+  /**
+   * Flush any buffered audio and return transcription
+   * Called at the end of a recording session
+   */
+  async flush(context: TranscribeContext): Promise<string> {
+    if (this.frameBuffer.length === 0) {
+      return "";
+    }
+
+    await this.preloadModel();
+    return this.doTranscription(context);
+  }
+
+  /**
+   * Shared transcription logic - aggregates buffer, calls the runtime,
+   * clears state. Assumes preloadModel() was already called by caller
+   */
+  private async doTranscription(context: TranscribeContext): Promise<string> {
+    try {
+      const isAllSilent = this.isAllSilent();
+
+      // Aggregate buffered frames
+      const aggregatedAudio = this.aggregateFrames();
+
+      // Clear buffers immediately after aggregation
+      this.reset();
+
+      if (isAllSilent && this.IGNORE_FULLY_SILENT_CHUNKS) {
+        logger.transcription.debug("Skipping transcription - all silent");
+        return "";
+      }
+
+      logger.transcription.debug(
+        `Starting transcription of ${aggregatedAudio.length} samples (${((aggregatedAudio.length / this.SAMPLE_RATE) * 1000).toFixed(0)}ms)`,
+      );
+
+      const { text, confidence } = await this.transcribeChunk(
+        aggregatedAudio,
+        context,
+      );
+      if (confidence !== undefined && text.trim()) {
+        context.reportConfidence?.(text, confidence);
+      }
+
+      logger.transcription.debug(
+        `Transcription completed, length: ${text.length}`,
+      );
+
+      return text;
+    } catch (error) {
+      logger.transcription.error("Transcription failed:", error);
+      throw new Error(`Transcription failed: ${error}`);
+    }
+  }
+
+  /**
+   * Clear internal buffers without transcribing
+   * Called when cancelling a session to prevent audio bleed
+   */
+  reset(): void {
+    this.frameBuffer = [];
+    this.frameBufferSpeechProbabilities = [];
+    this.currentSilenceFrameCount = 0;
+  }
+
+  private shouldTranscribe(
+    maxSilenceMs = this.MAX_SILENCE_DURATION_MS,
+  ): boolean {
+    // Transcribe if:
+    // 1. We have significant silence after speech
+    // 2. Buffer is getting too large
+
+    const bufferDurationMs =
+      ((this.frameBuffer.length * this.FRAME_SIZE) / this.SAMPLE_RATE) * 1000;
+    const silenceDurationMs =
+      ((this.currentSilenceFrameCount * this.FRAME_SIZE) / this.SAMPLE_RATE) *
+      1000;
+
+    // If we have speech and then significant silence, transcribe
+    if (
+      this.frameBuffer.length > 0 &&
+      silenceDurationMs > maxSilenceMs
+    ) {
+      logger.transcription.debug(
+        `Transcribing due to ${silenceDurationMs}ms of silence`,
+      );
+      return true;
+    }
+
+    // If buffer is too large (e.g., 30 seconds), transcribe anyway
+    if (bufferDurationMs > 30000) {
+      logger.transcription.debug(
+        `Transcribing due to buffer size: ${bufferDurationMs}ms`,
+      );
+      return true;
+    }
+
+    logger.transcription.debug("Not transcribing", {
+      bufferDurationMs,
+      silenceDurationMs,
+      frameBufferLength: this.frameBuffer.length,
+      silenceFrameCount: this.currentSilenceFrameCount,
+    });
+
+    return false;
+  }
+
+  private aggregateFrames(): Float32Array {
+    // Calculate total size
+    const totalLength = this.frameBuffer.reduce(
+      (sum, frame) => sum + frame.length,
+      0,
+    );
+    let aggregated = new Float32Array(totalLength);
+
+    // Copy all frames into single array
+    let offset = 0;
+    for (const frame of this.frameBuffer) {
+      aggregated.set(frame, offset);
+      offset += frame.length;
+    }
+
+    // Trim silence from beginning and end
+    aggregated = this.TRIM_TRAILING_AND_LEADING_SILENCE
+      ? this.trimSilence(aggregated)
+      : aggregated;
+
+    return aggregated;
+  }
+
+  private isAllSilent = () => {
+    const bufferDurationMs =
+      ((this.frameBuffer.length * this.FRAME_SIZE) / this.SAMPLE_RATE) * 1000;
+    const silenceDurationMs =
+      ((this.currentSilenceFrameCount * this.FRAME_SIZE) / this.SAMPLE_RATE) *
+      1000;
+
+    return bufferDurationMs === silenceDurationMs;
+  };
+
+  private trimSilence(
+    audio: Float32Array<ArrayBuffer>,
+  ): Float32Array<ArrayBuffer> {
+    // Find first speech frame (probability > threshold)
+    let startIdx = 0;
+    for (let i = 0; i < this.frameBufferSpeechProbabilities.length; i++) {
+      if (
+        this.frameBufferSpeechProbabilities[i] >
+        this.SPEECH_PROBABILITY_THRESHOLD
+      ) {
+        startIdx = i * this.FRAME_SIZE;
+        break;
+      }
+    }
+
+    // Find last speech frame (probability > threshold)
+    let endIdx = audio.length;
+    for (let i = this.frameBufferSpeechProbabilities.length - 1; i >= 0; i--) {
+      if (
+        this.frameBufferSpeechProbabilities[i] >
+        this.SPEECH_PROBABILITY_THRESHOLD
+      ) {
+        endIdx = (i + 1) * this.FRAME_SIZE;
+        break;
+      }
+    }
+
+    return audio.slice(startIdx, Math.min(endIdx, audio.length));
+  }
+}
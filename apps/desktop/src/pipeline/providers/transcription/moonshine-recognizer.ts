@@ -0,0 +1,156 @@
+import * as fs from "fs";
+import * as path from "path";
+import {
+  argmax,
+  type OrtInferenceSession,
+  type OrtModule,
+  type OrtTensor,
+  type SpeechRecognizer,
+} from "./onnx-recognizer";
+
+// Moonshine emits roughly this many tokens per second of speech; used to
+// stop runaway decoding
+const MAX_TOKENS_PER_SECOND = 6.5;
+const SAMPLE_RATE = 16000;
+
+interface MoonshineConfig {
+  layers: number;
+  kvHeads: number;
+  headDim: number;
+  startTokenId: number;
+  eosTokenId: number;
+}
+
+/**
+ * Useful Sensors' Moonshine, using the Hugging Face ONNX export (encoder
+ * plus a merged decoder with a key/value cache), decoded greedily
+ */
+export class MoonshineRecognizer implements SpeechRecognizer {
+  private constructor(
+    private ort: OrtModule,
+    private encoder: OrtInferenceSession,
+    private decoder: OrtInferenceSession,
+    private config: MoonshineConfig,
+    private tokens: string[],
+    private specialIds: Set<number>,
+  ) {}
+
+  static async load(
+    ort: OrtModule,
+    modelDir: string,
+  ): Promise<MoonshineRecognizer> {
+    const config = JSON.parse(
+      fs.readFileSync(path.join(modelDir, "config.json"), "utf8"),
+    );
+    const heads = config.decoder_num_attention_heads;
+    const tokenizer = JSON.parse(
+      fs.readFileSync(path.join(modelDir, "tokenizer.json"), "utf8"),
+    );
+
+    const tokens: string[] = [];
+    for (const [token, id] of Object.entries<number>(tokenizer.model.vocab)) {
+      tokens[id] = token;
+    }
+    const specialIds = new Set<number>();
+    for (const added of tokenizer.added_tokens ?? []) {
+      tokens[added.id] = added.content;
+      if (added.special) specialIds.add(added.id);
+    }
+
+    const [encoder, decoder] = await Promise.all(
+      ["onnx/encoder_model.onnx", "onnx/decoder_model_merged.onnx"].map(
+        (file) => ort.InferenceSession.create(path.join(modelDir, file)),
+      ),
+    );
+
+    return new MoonshineRecognizer(
+      ort,
+      encoder,
+      decoder,
+      {
+        layers: config.decoder_num_hidden_layers,
+        kvHeads: config.decoder_num_key_value_heads ?? heads,
+        headDim: config.hidden_size / heads,
+        startTokenId: config.decoder_start_token_id ?? 1,
+        eosTokenId: config.eos_token_id ?? 2,
+      },
+      tokens,
+      specialIds,
+    );
+  }
+
+  async transcribe(audio: Float32Array): Promise<string> {
+    const { Tensor } = this.ort;
+    const { layers, kvHeads, headDim, startTokenId, eosTokenId } = this.config;
+
+    const { last_hidden_state } = await this.encoder.run({
+      input_values: new Tensor("float32", audio, [1, audio.length]),
+    });
+
+    // Empty caches for the first step, which fills the cross-attention ones
+    const cacheShape = [1, kvHeads, 0, headDim];
+    const emptyCache = new Tensor("float32", new Float32Array(0), cacheShape);
+    const cache: Record<string, OrtTensor> = {};
+    for (let i = 0; i < layers; i++) {
+      for (const kind of ["decoder", "encoder"]) {
+        cache[`past_key_values.${i}.${kind}.key`] = emptyCache;
+        cache[`past_key_values.${i}.${kind}.value`] = emptyCache;
+      }
+    }
+
+    const maxTokens = Math.ceil(
+      (audio.length / SAMPLE_RATE) * MAX_TOKENS_PER_SECOND,
+    );
+    const output: number[] = [];
+    let token = startTokenId;
+    for (let step = 0; step < maxTokens; step++) {
+      const inputIds = BigInt64Array.of(BigInt(token));
+      const useCache = Uint8Array.of(step > 0 ? 1 : 0);
+      const result = await this.decoder.run({
+        input_ids: new Tensor("int64", inputIds, [1, 1]),
+        encoder_hidden_states: last_hidden_state,
+        use_cache_branch: new Tensor("bool", useCache, [1]),
+        ...cache,
+      });
+
+      const logits = result.logits.data as Float32Array;
+      const vocabSize = result.logits.dims[2];
+      token = argmax(logits, logits.length - vocabSize, logits.length);
+      if (token === eosTokenId) break;
+      output.push(token);
+
+      for (let i = 0; i < layers; i++) {
+        // Cross-attention caches only come out of the first step
+        const kinds = step === 0 ? ["decoder", "encoder"] : ["decoder"];
+        for (const kind of kinds) {
+          for (const part of ["key", "value"]) {
+            cache[`past_key_values.${i}.${kind}.${part}`] =
+              result[`present.${i}.${kind}.${part}`];
+          }
+        }
+      }
+    }
+
+    return this.detokenize(output);
+  }
+
+  // SentencePiece-style tokens: "▁" marks a space, <0xNN> is a raw byte
+  private detokenize(ids: number[]): string {
+    const bytes: number[] = [];
+    for (const id of ids) {
+      if (this.specialIds.has(id)) continue;
+      const token = this.tokens[id] ?? "";
+      const byte = /^<0x([0-9A-Fa-f]{2})>$/.exec(token);
+      if (byte) {
+        bytes.push(parseInt(byte[1], 16));
+      } else {
+        bytes.push(...Buffer.from(token.replace(/▁/g, " "), "utf8"));
+      }
+    }
+    return Buffer.from(bytes).toString("utf8").trim();
+  }
+
+  async dispose(): Promise<void> {
+    await Promise.all([this.encoder.release(), this.decoder.release()]);
+  }
+}
@@ -0,0 +1,25 @@
+export type OrtModule = typeof import("onnxruntime-node");
+export type OrtInferenceSession = import("onnxruntime-node").InferenceSession;
+export type OrtTensor = import("onnxruntime-node").Tensor;
+
+/**
+ * A speech model running in-process on onnxruntime
+ */
+export interface SpeechRecognizer {
+  // 16 kHz mono samples in [-1, 1]
+  transcribe(audio: Float32Array): Promise<string>;
+  dispose(): Promise<void>;
+}
+
+// Index of the largest value in data[start, end), relative to start
+export function argmax(
+  data: Float32Array,
+  start: number,
+  end: number,
+): number {
+  let best = start;
+  for (let i = start + 1; i < end; i++) {
+    if (data[i] > data[best]) best = i;
+  }
+  return best - start;
+}
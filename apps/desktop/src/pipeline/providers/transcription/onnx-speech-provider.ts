@@ -0,0 +1,94 @@
+import { logger } from "../../../main/logger";
+import { ModelService } from "../../../services/model-service";
+import type { LocalRuntime } from "../../../constants/models";
+import type { ChunkTranscription } from "../../core/pipeline-types";
+import { LocalTranscriptionProvider } from "./local-provider";
+import type { SpeechRecognizer } from "./onnx-recognizer";
+import { ParakeetRecognizer } from "./parakeet-recognizer";
+import { MoonshineRecognizer } from "./moonshine-recognizer";
+
+export type OnnxRuntime = Exclude<LocalRuntime, "whisper">;
+
+/**
+ * Local models that run on onnxruntime in the main process instead of the
+ * whisper.cpp worker. They're English-only and take no prompt, so the
+ * vocabulary and language from the context are ignored.
+ */
+export class OnnxSpeechProvider extends LocalTranscriptionProvider {
+  readonly name: string;
+
+  private recognizer: Promise<SpeechRecognizer> | null = null;
+  private loaded = false;
+
+  constructor(
+    private modelService: ModelService,
+    private modelId: string,
+    private runtime: OnnxRuntime,
+  ) {
+    super();
+    this.name = `${runtime}-local`;
+  }
+
+  async preloadModel(): Promise<void> {
+    await this.getRecognizer();
+  }
+
+  isModelLoaded(): boolean {
+    return this.loaded;
+  }
+
+  protected async transcribeChunk(
+    audio: Float32Array,
+  ): Promise<ChunkTranscription> {
+    const recognizer = await this.getRecognizer();
+    return { text: await recognizer.transcribe(audio) };
+  }
+
+  private getRecognizer(): Promise<SpeechRecognizer> {
+    this.recognizer ??= this.loadRecognizer().catch((error) => {
+      // Let the next chunk try again
+      this.recognizer = null;
+      throw error;
+    });
+    return this.recognizer;
+  }
+
+  private async loadRecognizer(): Promise<SpeechRecognizer> {
+    const downloadedModels = await this.modelService.getValidDownloadedModels();
+    const modelDir = downloadedModels[this.modelId]?.localPath;
+    if (!modelDir) {
+      throw new Error(
+        `Model ${this.modelId} is not downloaded. Please download it first.`,
+      );
+    }
+
+    logger.transcription.info(`Loading ${this.runtime} model`, { modelDir });
+    try {
+      const ort = await import("onnxruntime-node");
+      const recognizer =
+        this.runtime === "parakeet"
+          ? await ParakeetRecognizer.load(ort, modelDir)
+          : await MoonshineRecognizer.load(ort, modelDir);
+      this.loaded = true;
+      return recognizer;
+    } catch (error) {
+      logger.transcription.error(`Failed to load ${this.runtime}:`, error);
+      throw new Error(`Failed to load ${this.runtime} model: ${error}`);
+    }
+  }
+
+  async dispose(): Promise<void> {
+    const recognizer = this.recognizer;
+    this.recognizer = null;
+    this.loaded = false;
+    this.reset();
+
+    if (recognizer) {
+      try {
+        await (await recognizer).dispose();
+      } catch (error) {
+        logger.transcription.warn("Error disposing recognizer:", error);
+      }
+    }
+  }
+}
@@ -4,8 +4,29 @@ import {
   TranscribeContext,
 } from "../../core/pipeline-types";
 import { logger } from "../../../main/logger";
+import { recordTranscriptionUsage } from "../../../db/usage";
 import { convertRawToWav } from "../../../utils/audio-converter";
 
+interface VerboseSegment {
+  text: string;
+  avg_logprob: number;
+}
+
+// Probability-scale confidence from verbose_json segments, weighted by how
+// much text each segment produced
+function segmentConfidence(segments: VerboseSegment[]): number | undefined {
+  let sum = 0;
+  let weight = 0;
+  for (const segment of segments) {
+    const length = segment.text.trim().length;
+    if (typeof segment.avg_logprob === "number" && length > 0) {
+      sum += Math.exp(segment.avg_logprob) * length;
+      weight += length;
+    }
+  }
+  return weight > 0 ? sum / weight : undefined;
+}
+
 export class OpenAITranscriptionProvider implements TranscriptionProvider {
   readonly name: string;
 
@@ -52,7 +73,9 @@ export class OpenAITranscriptionProvider implements TranscriptionProvider {
     this.frameBufferSpeechProbabilities.push(speechProbability);
 
     // Consider it speech if probability is above threshold
-    const isSpeech = speechProbability > this.SPEECH_PROBABILITY_THRESHOLD;
+    const isSpeech =
+      speechProbability >
+      (context.chunking?.speechThreshold ?? this.SPEECH_PROBABILITY_THRESHOLD);
 
     if (isSpeech) {
       this.currentSilenceFrameCount = 0;
@@ -62,7 +85,7 @@ export class OpenAITranscriptionProvider implements TranscriptionProvider {
     }
 
     // Only transcribe if speech/silence patterns indicate we should
-    if (!this.shouldTranscribe()) {
+    if (!this.shouldTranscribe(context.chunking?.maxSilenceMs)) {
       return "";
     }
 
@@ -82,7 +105,9 @@ export class OpenAITranscriptionProvider implements TranscriptionProvider {
     this.currentSilenceFrameCount = 0;
   }
 
-  private shouldTranscribe(): boolean {
+  private shouldTranscribe(
+    maxSilenceMs = this.MAX_SILENCE_DURATION_MS,
+  ): boolean {
     const bufferDurationMs =
       ((this.frameBuffer.length * this.FRAME_SIZE) / this.SAMPLE_RATE) * 1000;
     const silenceDurationMs =
@@ -92,7 +117,7 @@ export class OpenAITranscriptionProvider implements TranscriptionProvider {
     // If we have speech and then significant silence, transcribe
     if (
       this.frameBuffer.length > 0 &&
-      silenceDurationMs > this.MAX_SILENCE_DURATION_MS
+      silenceDurationMs > maxSilenceMs
     ) {
       return true;
     }
@@ -159,6 +184,11 @@ export class OpenAITranscriptionProvider implements TranscriptionProvider {
       );
       formData.append("file", audioBlob, "audio.wav");
       formData.append("model", this.apiModelId);
+      // Only whisper models return per-segment log-probabilities
+      const verbose = this.apiModelId.includes("whisper");
+      if (verbose) {
+        formData.append("response_format", "verbose_json");
+      }
 
       // Set language if specified and not "auto"
       if (language && language !== "auto") {
@@ -203,6 +233,18 @@ export class OpenAITranscriptionProvider implements TranscriptionProvider {
 
       const result = await response.json();
       const text = result.text || "";
+      if (verbose && Array.isArray(result.segments) && text.trim()) {
+        const confidence = segmentConfidence(result.segments);
+        if (confidence !== undefined) {
+          context.reportConfidence?.(text, confidence);
+        }
+      }
+
+      void recordTranscriptionUsage({
+        provider: this.name.replace(/-transcription$/, ""),
+        model: this.apiModelId,
+        audioSeconds: aggregatedAudio.length / this.SAMPLE_RATE,
+      });
 
       logger.transcription.debug(
         `[${this.name}] Transcription completed, length: ${text.length}`,
@@ -0,0 +1,163 @@
+import * as fs from "fs";
+import * as path from "path";
+import {
+  argmax,
+  type OrtInferenceSession,
+  type OrtModule,
+  type OrtTensor,
+  type SpeechRecognizer,
+} from "./onnx-recognizer";
+
+// Prediction network LSTM state: [layers, batch, hidden]
+const STATE_SHAPE = [2, 1, 640];
+// Token-and-duration transducer: the joint also predicts how many encoder
+// frames to skip after each step
+const DURATIONS = [0, 1, 2, 3, 4];
+const MAX_TOKENS_PER_STEP = 10;
+
+/**
+ * NVIDIA Parakeet TDT exported to ONNX (preprocessor, encoder and
+ * decoder/joint), decoded greedily
+ */
+export class ParakeetRecognizer implements SpeechRecognizer {
+  private constructor(
+    private ort: OrtModule,
+    private preprocessor: OrtInferenceSession,
+    private encoder: OrtInferenceSession,
+    private decoderJoint: OrtInferenceSession,
+    private vocab: string[],
+    private blankId: number,
+  ) {}
+
+  static async load(
+    ort: OrtModule,
+    modelDir: string,
+  ): Promise<ParakeetRecognizer> {
+    const vocab: string[] = [];
+    const lines = fs
+      .readFileSync(path.join(modelDir, "vocab.txt"), "utf8")
+      .split("\n");
+    for (const line of lines) {
+      const [token, id] = line.trim().split(" ");
+      if (token && id !== undefined) {
+        vocab[Number(id)] = token;
+      }
+    }
+    const blankId = vocab.indexOf("<blk>");
+
+    const [preprocessor, encoder, decoderJoint] = await Promise.all(
+      [
+        "nemo128.onnx",
+        "encoder-model.int8.onnx",
+        "decoder_joint-model.int8.onnx",
+      ].map((file) => ort.InferenceSession.create(path.join(modelDir, file))),
+    );
+
+    return new ParakeetRecognizer(
+      ort,
+      preprocessor,
+      encoder,
+      decoderJoint,
+      vocab,
+      blankId === -1 ? vocab.length - 1 : blankId,
+    );
+  }
+
+  async transcribe(audio: Float32Array): Promise<string> {
+    const { Tensor } = this.ort;
+
+    const { features, features_lens } = await this.preprocessor.run({
+      waveforms: new Tensor("float32", audio, [1, audio.length]),
+      waveforms_lens: new Tensor(
+        "int64",
+        BigInt64Array.of(BigInt(audio.length)),
+        [1],
+      ),
+    });
+    const { outputs, encoded_lengths } = await this.encoder.run({
+      audio_signal: features,
+      length: features_lens,
+    });
+
+    // [1, dim, frames]
+    const [, dim, frames] = outputs.dims;
+    const encoded = outputs.data as Float32Array;
+    const frameCount = Math.min(
+      frames,
+      Number((encoded_lengths.data as BigInt64Array)[0]),
+    );
+
+    const tokens = await this.decode(encoded, dim, frames, frameCount);
+    return tokens
+      .map((id) => this.vocab[id] ?? "")
+      .join("")
+      .replace(/▁/g, " ")
+      .trim();
+  }
+
+  private async decode(
+    encoded: Float32Array,
+    dim: number,
+    frames: number,
+    frameCount: number,
+  ): Promise<number[]> {
+    const { Tensor } = this.ort;
+    const stateSize = STATE_SHAPE.reduce((a, b) => a * b, 1);
+    let states: [OrtTensor, OrtTensor] = [
+      new Tensor("float32", new Float32Array(stateSize), STATE_SHAPE),
+      new Tensor("float32", new Float32Array(stateSize), STATE_SHAPE),
+    ];
+
+    const tokens: number[] = [];
+    const frame = new Float32Array(dim);
+    let emitted = 0;
+    let t = 0;
+    while (t < frameCount) {
+      for (let d = 0; d < dim; d++) {
+        frame[d] = encoded[d * frames + t];
+      }
+
+      const result = await this.decoderJoint.run({
+        encoder_outputs: new Tensor("float32", frame, [1, dim, 1]),
+        targets: new Tensor(
+          "int32",
+          Int32Array.of(tokens.at(-1) ?? this.blankId),
+          [1, 1],
+        ),
+        target_length: new Tensor("int32", Int32Array.of(1), [1]),
+        input_states_1: states[0],
+        input_states_2: states[1],
+      });
+
+      const logits = result.outputs.data as Float32Array;
+      const vocabSize = this.vocab.length;
+      const token = argmax(logits, 0, vocabSize);
+      const step = DURATIONS[argmax(logits, vocabSize, logits.length)];
+
+      if (token !== this.blankId) {
+        // The prediction network only advances on emitted tokens
+        states = [result.output_states_1, result.output_states_2];
+        tokens.push(token);
+        emitted++;
+      }
+
+      if (step > 0) {
+        t += step;
+        emitted = 0;
+      } else if (token === this.blankId || emitted === MAX_TOKENS_PER_STEP) {
+        t++;
+        emitted = 0;
+      }
+    }
+
+    return tokens;
+  }
+
+  async dispose(): Promise<void> {
+    await Promise.all([
+      this.preprocessor.release(),
+      this.encoder.release(),
+      this.decoderJoint.release(),
+    ]);
+  }
+}
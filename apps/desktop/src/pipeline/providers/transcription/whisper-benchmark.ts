@@ -0,0 +1,143 @@
+import * as fs from "fs";
+import * as path from "path";
+import { app } from "electron";
+import { logger } from "../../../main/logger";
+import { SimpleForkWrapper } from "./simple-fork-wrapper";
+import { getNodeBinaryPath, getWhisperWorkerPath } from "./whisper-provider";
+
+const SAMPLE_RATE = 16000;
+
+export interface ModelBenchmarkResult {
+  modelId: string;
+  useGPU: boolean;
+  audioDurationMs: number;
+  loadTimeMs: number;
+  transcribeTimeMs: number;
+  // Transcription time divided by audio length; below 1 is faster than realtime
+  realtimeFactor: number;
+  // Resident memory of the worker with the model loaded. GPU memory isn't
+  // always included.
+  memoryBytes: number;
+  text: string;
+}
+
+// The 11 second sample that ships with whisper.cpp
+function getBenchmarkSamplePath(): string {
+  return app.isPackaged
+    ? path.join(process.resourcesPath, "jfk.wav")
+    : path.join(
+        app.getAppPath(),
+        "../../packages/whisper-wrapper/whisper.cpp/samples/jfk.wav",
+      );
+}
+
+/**
+ * Decode a 16 kHz 16-bit PCM WAV file into mono samples in [-1, 1]
+ */
+function readWavSamples(filePath: string): Float32Array {
+  const buffer = fs.readFileSync(filePath);
+  if (
+    buffer.toString("ascii", 0, 4) !== "RIFF" ||
+    buffer.toString("ascii", 8, 12) !== "WAVE"
+  ) {
+    throw new Error(`Not a WAV file: ${filePath}`);
+  }
+
+  let channels = 0;
+  let offset = 12;
+  while (offset + 8 <= buffer.length) {
+    const chunkId = buffer.toString("ascii", offset, offset + 4);
+    const chunkSize = buffer.readUInt32LE(offset + 4);
+    const body = offset + 8;
+
+    if (chunkId === "fmt ") {
+      const format = buffer.readUInt16LE(body);
+      channels = buffer.readUInt16LE(body + 2);
+      const sampleRate = buffer.readUInt32LE(body + 4);
+      const bitsPerSample = buffer.readUInt16LE(body + 14);
+      if (
+        format !== 1 ||
+        bitsPerSample !== 16 ||
+        sampleRate !== SAMPLE_RATE
+      ) {
+        throw new Error("Benchmark sample must be 16 kHz 16-bit PCM");
+      }
+    } else if (chunkId === "data") {
+      if (!channels) break;
+      const frames = Math.floor(chunkSize / (2 * channels));
+      const samples = new Float32Array(frames);
+      for (let i = 0; i < frames; i++) {
+        let sum = 0;
+        for (let c = 0; c < channels; c++) {
+          sum += buffer.readInt16LE(body + (i * channels + c) * 2);
+        }
+        samples[i] = sum / channels / 32768;
+      }
+      return samples;
+    }
+
+    // Chunks are padded to an even length
+    offset = body + chunkSize + (chunkSize % 2);
+  }
+
+  throw new Error(`No audio data in ${filePath}`);
+}
+
+/**
+ * Transcribe the bundled sample with a model in a separate worker, so the
+ * measurement doesn't disturb (or include) the model used for dictation
+ */
+export async function benchmarkWhisperModel(
+  modelId: string,
+  modelPath: string,
+  useGPU: boolean,
+): Promise<ModelBenchmarkResult> {
+  const audio = readWavSamples(getBenchmarkSamplePath());
+  const worker = new SimpleForkWrapper(
+    getWhisperWorkerPath(),
+    getNodeBinaryPath(),
+    { WHISPER_USE_GPU: useGPU ? "1" : "0" },
+  );
+
+  try {
+    await worker.initialize();
+
+    const loadStart = performance.now();
+    await worker.exec("initializeModel", [modelPath]);
+    const loadTimeMs = performance.now() - loadStart;
+
+    const transcribeStart = performance.now();
+    const { text } = await worker.exec<{ text: string }>("transcribeAudio", [
+      audio,
+      {
+        language: "en",
+        initial_prompt: "",
+        suppress_blank: true,
+        suppress_non_speech_tokens: true,
+        no_timestamps: false,
+      },
+    ]);
+    const transcribeTimeMs = performance.now() - transcribeStart;
+    const memoryBytes = await worker.exec<number>("getMemoryUsage", []);
+
+    const audioDurationMs = (audio.length / SAMPLE_RATE) * 1000;
+    const result: ModelBenchmarkResult = {
+      modelId,
+      useGPU,
+      audioDurationMs,
+      loadTimeMs: Math.round(loadTimeMs),
+      transcribeTimeMs: Math.round(transcribeTimeMs),
+      realtimeFactor: transcribeTimeMs / audioDurationMs,
+      memoryBytes,
+      text: text.trim(),
+    };
+    logger.transcription.info("Model benchmark finished", {
+      ...result,
+      text: undefined,
+    });
+    return result;
+  } finally {
+    // Killing the worker frees the model
+    await worker.terminate();
+  }
+}
@@ -65,7 +65,7 @@ const methods = {
       suppress_non_speech_tokens: boolean;
       no_timestamps: boolean;
     },
-  ): Promise<string> {
+  ): Promise<{ text: string; confidence?: number }> {
     if (!whisperInstance) {
       throw new Error("Whisper instance is not initialized");
     }
@@ -80,11 +80,15 @@ const methods = {
       aggregatedAudio = padded;
     }
 
-    const { result } = await whisperInstance.transcribe(
-      aggregatedAudio,
-      options,
-    );
-    const transcription = await result;
+    // "detail" adds each segment's mean token probability
+    const { result } = await whisperInstance.transcribe(aggregatedAudio, {
+      ...options,
+      format: "detail",
+    });
+    const transcription = (await result) as Array<{
+      text: string;
+      confidence?: number;
+    }>;
 
     logger.transcription.debug(
       `Transcription segments: ${Array.isArray(transcription) ? transcription.length : "?"}`,
@@ -95,9 +99,22 @@ const methods = {
       );
     }
 
-    return transcription
-      .map((segment: { text: string }) => segment.text)
-      .join("");
+    // Weight each segment's confidence by how much text it produced
+    let confidenceSum = 0;
+    let confidenceWeight = 0;
+    for (const segment of transcription) {
+      const weight = segment.text.trim().length;
+      if (typeof segment.confidence === "number" && weight > 0) {
+        confidenceSum += segment.confidence * weight;
+        confidenceWeight += weight;
+      }
+    }
+
+    return {
+      text: transcription.map((segment) => segment.text).join(""),
+      confidence:
+        confidenceWeight > 0 ? confidenceSum / confidenceWeight : undefined,
+    };
   },
 
   async dispose(): Promise<void> {
@@ -111,6 +128,11 @@ const methods = {
   getBindingInfo(): { path: string; type: string } | null {
     return getLoadedBindingInfo();
   },
+
+  // Resident memory of this process, which holds the loaded model
+  getMemoryUsage(): number {
+    return process.memoryUsage().rss;
+  },
 };
 
 // Handle messages from parent process
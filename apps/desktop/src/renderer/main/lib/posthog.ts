@@ -35,12 +35,13 @@ function setTelemetryEnabled(enabled: boolean): void {
 export function usePostHog() {
   const { data: config } = api.settings.getTelemetryConfig.useQuery();
 
-  // Initialize PostHog when config is available
+  // Initialize PostHog once config is available and the user has telemetry
+  // on; init alone already contacts the host
   useEffect(() => {
-    if (config?.apiKey) {
+    if (config?.apiKey && config.enabled) {
       initPostHog(config.apiKey, config.host, config.machineId);
     }
-  }, [config?.apiKey, config?.host, config?.machineId]);
+  }, [config?.apiKey, config?.host, config?.machineId, config?.enabled]);
 
   // Sync opt-in/opt-out state when enabled changes
   useEffect(() => {
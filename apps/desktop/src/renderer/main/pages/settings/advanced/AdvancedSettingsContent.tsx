@@ -21,7 +21,15 @@ import {
   AlertDialogTitle,
   AlertDialogTrigger,
 } from "@/components/ui/alert-dialog";
+import {
+  Select,
+  SelectContent,
+  SelectItem,
+  SelectTrigger,
+  SelectValue,
+} from "@/components/ui/select";
 import { api } from "@/trpc/react";
+import { getAppError } from "@/types/app-error";
 import { toast } from "sonner";
 import { cn } from "@/lib/utils";
 
@@ -39,12 +47,17 @@ export function AdvancedSettingsContent({
   const [useGPU, setUseGPU] = useState(isAppleSilicon);
   const [preloadWhisperModel, setPreloadWhisperModel] = useState(true);
   const [isResetting, setIsResetting] = useState(false);
+  const [fileNameTemplate, setFileNameTemplate] = useState("");
 
   const settingsQuery = api.settings.getSettings.useQuery();
   const telemetryQuery = api.settings.getTelemetrySettings.useQuery();
   const dataPathQuery = api.settings.getDataPath.useQuery();
   const logFilePathQuery = api.settings.getLogFilePath.useQuery();
   const machineIdQuery = api.settings.getMachineId.useQuery();
+  const remoteControlQuery = api.settings.getRemoteControlSettings.useQuery();
+  const fileNameTemplateQuery =
+    api.settings.getAudioFileNameTemplate.useQuery();
+  const performancePresetQuery = api.settings.getPerformancePreset.useQuery();
   const utils = api.useUtils();
 
   const updateTranscriptionSettingsMutation =
@@ -72,6 +85,54 @@ export function AdvancedSettingsContent({
       },
     });
 
+  const updateRemoteControlMutation =
+    api.settings.updateRemoteControlSettings.useMutation({
+      onSuccess: () => {
+        utils.settings.getRemoteControlSettings.invalidate();
+        toast.success("Remote control settings updated");
+      },
+      onError: (error) => {
+        console.error("Failed to update remote control settings:", error);
+        toast.error("Failed to update remote control settings.");
+      },
+    });
+
+  const regenerateRemoteControlTokenMutation =
+    api.settings.regenerateRemoteControlToken.useMutation({
+      onSuccess: () => {
+        utils.settings.getRemoteControlSettings.invalidate();
+        toast.success("New access token generated");
+      },
+      onError: () => {
+        toast.error("Failed to generate a new token");
+      },
+    });
+
+  const setPerformancePresetMutation =
+    api.settings.setPerformancePreset.useMutation({
+      onSuccess: () => {
+        utils.settings.getPerformancePreset.invalidate();
+        utils.models.getSelectedModel.invalidate();
+        toast.success("Performance preset applied");
+      },
+      onError: (error) => {
+        console.error("Failed to apply performance preset:", error);
+        toast.error("Failed to apply performance preset");
+      },
+    });
+
+  const setFileNameTemplateMutation =
+    api.settings.setAudioFileNameTemplate.useMutation({
+      onSuccess: () => {
+        utils.settings.getAudioFileNameTemplate.invalidate();
+        toast.success("Recording file name template updated");
+      },
+      onError: (error) => {
+        console.error("Failed to update file name template:", error);
+        toast.error("Invalid file name template");
+      },
+    });
+
   const resetAppMutation = api.settings.resetApp.useMutation({
     onMutate: () => {
       setIsResetting(true);
@@ -83,6 +144,16 @@ export function AdvancedSettingsContent({
     onError: (error) => {
       setIsResetting(false);
       console.error("Failed to reset app:", error);
+      if (getAppError(error)?.details?.backupFailed) {
+        // Nothing was deleted; only go ahead if the user accepts the risk
+        const resetAnyway = window.confirm(
+          "Your data couldn't be backed up before the reset, so it couldn't be restored afterwards. Reset without a backup?",
+        );
+        if (resetAnyway) {
+          resetAppMutation.mutate({ withoutBackup: true });
+        }
+        return;
+      }
       toast.error("Failed to reset app. Please try again.");
     },
   });
@@ -110,6 +181,12 @@ export function AdvancedSettingsContent({
     }
   }, [settingsQuery.data]);
 
+  useEffect(() => {
+    if (fileNameTemplateQuery.data) {
+      setFileNameTemplate(fileNameTemplateQuery.data.template);
+    }
+  }, [fileNameTemplateQuery.data]);
+
   const relaunchAppMutation = api.settings.relaunchApp.useMutation();
 
   const handleUseGPUChange = (checked: boolean) => {
@@ -154,6 +231,19 @@ export function AdvancedSettingsContent({
     // window.electronAPI.openExternal("https://amical.ai/docs/telemetry");
   };
 
+  const handleFileNameTemplateBlur = () => {
+    if (fileNameTemplate.trim() !== fileNameTemplateQuery.data?.template) {
+      setFileNameTemplateMutation.mutate({ template: fileNameTemplate });
+    }
+  };
+
+  const handleCopyRemoteControlToken = async () => {
+    if (remoteControlQuery.data?.token) {
+      await navigator.clipboard.writeText(remoteControlQuery.data.token);
+      toast.success("Access token copied to clipboard");
+    }
+  };
+
   const handleCopyMachineId = async () => {
     if (machineIdQuery.data) {
       await navigator.clipboard.writeText(machineIdQuery.data);
@@ -214,6 +304,38 @@ export function AdvancedSettingsContent({
 
           <Separator />
 
+          <div className="flex items-center justify-between">
+            <div className="space-y-1">
+              <Label className="text-base font-medium text-foreground">
+                Performance
+              </Label>
+              <p className="text-xs text-muted-foreground">
+                Fast picks the quickest downloaded model and skips AI
+                formatting; Accurate picks the most accurate model and waits
+                longer before cutting audio.
+              </p>
+            </div>
+            <Select
+              value={performancePresetQuery.data ?? "balanced"}
+              onValueChange={(preset) =>
+                setPerformancePresetMutation.mutate({
+                  preset: preset as "fast" | "balanced" | "accurate",
+                })
+              }
+            >
+              <SelectTrigger className="w-32">
+                <SelectValue />
+              </SelectTrigger>
+              <SelectContent>
+                <SelectItem value="fast">Fast</SelectItem>
+                <SelectItem value="balanced">Balanced</SelectItem>
+                <SelectItem value="accurate">Accurate</SelectItem>
+              </SelectContent>
+            </Select>
+          </div>
+
+          <Separator />
+
           <div className="flex items-center justify-between">
             <div className="space-y-1">
               <Label
@@ -258,6 +380,114 @@ export function AdvancedSettingsContent({
 
           <Separator />
 
+          <div className="flex items-center justify-between">
+            <div className="space-y-1">
+              <Label
+                htmlFor="remote-control"
+                className="text-base font-medium text-foreground"
+              >
+                Remote Control
+              </Label>
+              <p className="text-xs text-muted-foreground">
+                Start and stop recording over HTTP, e.g. from home automation.
+                Requests need the access token below.
+              </p>
+            </div>
+            <Switch
+              id="remote-control"
+              checked={remoteControlQuery.data?.enabled ?? false}
+              onCheckedChange={(checked) =>
+                updateRemoteControlMutation.mutate({ enabled: checked })
+              }
+            />
+          </div>
+
+          {remoteControlQuery.data?.enabled && (
+            <div className="space-y-3 pl-4 border-l-2 border-muted">
+              <div className="flex items-center justify-between">
+                <div className="space-y-1">
+                  <Label className="text-sm font-medium text-foreground">
+                    Endpoint
+                  </Label>
+                  <p className="text-xs text-muted-foreground font-mono">
+                    POST http://
+                    {remoteControlQuery.data.allowLan ? "<this-ip>" : "127.0.0.1"}
+                    :{remoteControlQuery.data.port}/record/start?mode=&lt;name&gt;
+                  </p>
+                </div>
+              </div>
+              <div className="flex items-center justify-between">
+                <div className="space-y-1">
+                  <Label
+                    htmlFor="remote-control-lan"
+                    className="text-sm font-medium text-foreground"
+                  >
+                    Allow other devices
+                  </Label>
+                  <p className="text-xs text-muted-foreground">
+                    Listen on the local network instead of this computer only
+                  </p>
+                </div>
+                <Switch
+                  id="remote-control-lan"
+                  checked={remoteControlQuery.data.allowLan}
+                  onCheckedChange={(checked) =>
+                    updateRemoteControlMutation.mutate({ allowLan: checked })
+                  }
+                />
+              </div>
+              <div className="flex gap-2">
+                <Input
+                  id="remote-control-token"
+                  type="password"
+                  value={remoteControlQuery.data.token}
+                  disabled
+                  className="cursor-default flex-1 font-mono text-xs"
+                />
+                <Button
+                  variant="outline"
+                  onClick={handleCopyRemoteControlToken}
+                  disabled={!remoteControlQuery.data.token}
+                >
+                  Copy
+                </Button>
+                <Button
+                  variant="outline"
+                  onClick={() => regenerateRemoteControlTokenMutation.mutate()}
+                  disabled={regenerateRemoteControlTokenMutation.isPending}
+                >
+                  Regenerate
+                </Button>
+              </div>
+            </div>
+          )}
+
+          <Separator />
+
+          <div className="space-y-2">
+            <Label
+              htmlFor="file-name-template"
+              className="text-base font-medium text-foreground"
+            >
+              Recording File Names
+            </Label>
+            <Input
+              id="file-name-template"
+              value={fileNameTemplate}
+              onChange={(e) => setFileNameTemplate(e.target.value)}
+              onBlur={handleFileNameTemplateBlur}
+              placeholder={fileNameTemplateQuery.data?.defaultTemplate}
+              className="font-mono text-xs"
+            />
+            <p className="text-xs text-muted-foreground">
+              Available: {"{date}"}, {"{time}"}, {"{mode}"}, {"{app}"},{" "}
+              {"{words}"} (the start of the transcript), {"{id}"}. Leave empty
+              for the default.
+            </p>
+          </div>
+
+          <Separator />
+
           <div className="space-y-2">
             <Label
               htmlFor="data-location"
@@ -5,10 +5,10 @@ import { AccordionContent, AccordionItem, AccordionTrigger } from "@/components/
 import { Avatar, AvatarFallback, AvatarImage } from "@/components/ui/avatar";
 import { Badge } from "@/components/ui/badge";
 import { Button } from "@/components/ui/button";
-import { Download, Loader2, Square, Trash2 } from "lucide-react";
+import { Download, Loader2, RefreshCw, Square, Trash2 } from "lucide-react";
 import { toast } from "sonner";
 import { api } from "@/trpc/react";
-import type { DownloadProgress } from "@/constants/models";
+import type { DownloadProgress, ModelInUseWarning } from "@/constants/models";
 
 const PROVIDER_NAME = "Whisper (Offline)";
 const PROVIDER_ICON = "icons/models/pc.svg";
@@ -21,12 +21,16 @@ export default function OfflineWhisperAccordion() {
   >({});
   const [modelToDelete, setModelToDelete] = useState<string | null>(null);
   const [showDeleteId, setShowDeleteId] = useState<string | null>(null);
+  const [deleteWarning, setDeleteWarning] = useState<ModelInUseWarning | null>(
+    null,
+  );
 
   const utils = api.useUtils();
 
   const availableModelsQuery = api.models.getAvailableModels.useQuery();
   const downloadedModelsQuery = api.models.getDownloadedModels.useQuery();
   const activeDownloadsQuery = api.models.getActiveDownloads.useQuery();
+  const availableUpdatesQuery = api.models.getAvailableUpdates.useQuery();
 
   const downloadModelMutation = api.models.downloadModel.useMutation({
     onSuccess: () => {
@@ -39,6 +43,17 @@ export default function OfflineWhisperAccordion() {
     },
   });
 
+  const updateModelMutation = api.models.updateModel.useMutation({
+    onSuccess: () => {
+      utils.models.getDownloadedModels.invalidate();
+      utils.models.getAvailableUpdates.invalidate();
+    },
+    onError: (error) => {
+      console.error("Failed to update model:", error);
+      toast.error(`Failed to update model: ${error.message}`);
+    },
+  });
+
   const cancelDownloadMutation = api.models.cancelDownload.useMutation({
     onSuccess: () => {
       utils.models.getActiveDownloads.invalidate();
@@ -50,16 +65,28 @@ export default function OfflineWhisperAccordion() {
   });
 
   const deleteModelMutation = api.models.deleteModel.useMutation({
-    onSuccess: () => {
+    onSuccess: (result) => {
+      // In use: keep the prompt open and ask again with the details
+      if (!result.deleted) {
+        setDeleteWarning(result.warning);
+        return;
+      }
       utils.models.getDownloadedModels.invalidate();
+      if (result.repointedModes.length > 0) {
+        toast.info(
+          `${result.repointedModes.map((mode) => mode.name).join(", ")} now use the default speech model`,
+        );
+      }
       setModelToDelete(null);
       setShowDeleteId(null);
+      setDeleteWarning(null);
     },
     onError: (error) => {
       console.error("Failed to delete model:", error);
       toast.error("Failed to delete model");
       setModelToDelete(null);
       setShowDeleteId(null);
+      setDeleteWarning(null);
     },
   });
 
@@ -112,6 +139,15 @@ export default function OfflineWhisperAccordion() {
     },
   });
 
+  api.models.onUpdateAvailable.useSubscription(undefined, {
+    onData: () => {
+      utils.models.getAvailableUpdates.invalidate();
+    },
+    onError: (error) => {
+      console.error("Update available subscription error:", error);
+    },
+  });
+
   api.models.onDownloadCancelled.useSubscription(undefined, {
     onData: ({ modelId }) => {
       setDownloadProgress((prev) => {
@@ -132,6 +168,9 @@ export default function OfflineWhisperAccordion() {
   }, [availableModelsQuery.data]);
 
   const downloadedModels = downloadedModelsQuery.data ?? {};
+  const updatableModelIds = new Set(
+    (availableUpdatesQuery.data ?? []).map((update) => update.modelId),
+  );
   const isLoading =
     availableModelsQuery.isLoading || downloadedModelsQuery.isLoading;
 
@@ -154,12 +193,16 @@ export default function OfflineWhisperAccordion() {
   const handleDelete = async (modelId: string) => {
     setModelToDelete(modelId);
     setShowDeleteId(modelId);
+    setDeleteWarning(null);
   };
 
   const confirmDelete = async () => {
     if (!modelToDelete) return;
     try {
-      await deleteModelMutation.mutateAsync({ modelId: modelToDelete });
+      await deleteModelMutation.mutateAsync({
+        modelId: modelToDelete,
+        force: deleteWarning?.modelId === modelToDelete,
+      });
     } catch (err) {
       console.error("Failed to delete model:", err);
     }
@@ -168,6 +211,7 @@ export default function OfflineWhisperAccordion() {
   const cancelDelete = () => {
     setModelToDelete(null);
     setShowDeleteId(null);
+    setDeleteWarning(null);
   };
 
   return (
@@ -234,6 +278,12 @@ export default function OfflineWhisperAccordion() {
               const isDownloaded = !!downloadedModels[model.id];
               const progress = downloadProgress[model.id];
               const isDownloading = progress?.status === "downloading";
+              const isQueued = progress?.status === "queued";
+              const canUpdate =
+                isDownloaded &&
+                updatableModelIds.has(model.id) &&
+                !isDownloading &&
+                !isQueued;
               const progressValue =
                 typeof progress?.progress === "number"
                   ? Math.round(progress.progress)
@@ -256,7 +306,12 @@ export default function OfflineWhisperAccordion() {
                         {progressValue}%
                       </span>
                     )}
-                    {!isDownloaded && !isDownloading && (
+                    {isQueued && (
+                      <span className="text-xs text-muted-foreground">
+                        Queued
+                      </span>
+                    )}
+                    {!isDownloaded && !isDownloading && !isQueued && (
                       <Button
                         size="icon"
                         variant="ghost"
@@ -267,7 +322,20 @@ export default function OfflineWhisperAccordion() {
                         <Download className="h-4 w-4" />
                       </Button>
                     )}
-                    {isDownloading && (
+                    {canUpdate && (
+                      <Button
+                        size="icon"
+                        variant="ghost"
+                        onClick={() =>
+                          updateModelMutation.mutate({ modelId: model.id })
+                        }
+                        aria-label="Update model"
+                        title="A new version of this model is available"
+                      >
+                        <RefreshCw className="h-4 w-4" />
+                      </Button>
+                    )}
+                    {(isDownloading || isQueued) && (
                       <Button
                         size="icon"
                         variant="ghost"
@@ -301,14 +369,24 @@ export default function OfflineWhisperAccordion() {
           <div className="mt-3 rounded-md border border-destructive/40 bg-destructive/5 p-3 text-xs">
             <div className="flex flex-wrap items-center justify-between gap-2">
               <span className="text-destructive">
-                Delete this model? You can re-download it anytime.
+                {deleteWarning
+                  ? [
+                      deleteWarning.isDefault &&
+                        "This is your default speech model.",
+                      deleteWarning.modes.length > 0 &&
+                        `Used by ${deleteWarning.modes.map((mode) => mode.name).join(", ")}; they'll switch to the default model.`,
+                      "Delete anyway?",
+                    ]
+                      .filter(Boolean)
+                      .join(" ")
+                  : "Delete this model? You can re-download it anytime."}
               </span>
               <div className="flex items-center gap-2">
                 <Button size="sm" variant="outline" onClick={cancelDelete}>
                   Cancel
                 </Button>
                 <Button size="sm" variant="destructive" onClick={confirmDelete}>
-                  Delete
+                  {deleteWarning ? "Delete anyway" : "Delete"}
                 </Button>
               </div>
             </div>
@@ -112,9 +112,8 @@ export function useFormattingSettings(): UseFormattingSettingsReturn {
   const handleFormattingEnabledChange = useCallback(
     (enabled: boolean) => {
       const nextConfig: FormatterConfig = {
+        ...formatterConfig,
         enabled,
-        modelId: formatterConfig?.modelId,
-        fallbackModelId: formatterConfig?.fallbackModelId,
       };
       setFormatterConfigMutation.mutate(nextConfig);
     },
@@ -135,9 +134,13 @@ export function useFormattingSettings(): UseFormattingSettingsReturn {
       }
 
       const nextConfig: FormatterConfig = {
+        ...formatterConfig,
         enabled: formatterConfig?.enabled ?? false,
         modelId,
-        fallbackModelId: modelId,
+        fallbackModelId:
+          formatterConfig?.fallbackModelId === modelId
+            ? undefined
+            : formatterConfig?.fallbackModelId,
       };
 
       setFormatterConfigMutation.mutate(nextConfig);
@@ -322,7 +322,7 @@ export default function HistorySettingsPage() {
     },
   });
 
-  const transcriptions = transcriptionsQuery.data || [];
+  const transcriptions = transcriptionsQuery.data?.items ?? [];
 
   function handleCopy(text: string) {
     navigator.clipboard.writeText(text);
@@ -0,0 +1,58 @@
+import { useState } from "react";
+import { Button } from "@/components/ui/button";
+import { Label } from "@/components/ui/label";
+import { Textarea } from "@/components/ui/textarea";
+import { api } from "@/trpc/react";
+
+interface FormattingPreviewProps {
+  modeId: string;
+}
+
+export function FormattingPreview({ modeId }: FormattingPreviewProps) {
+  const [sample, setSample] = useState("");
+  const previewMutation = api.formatter.preview.useMutation();
+
+  return (
+    <div>
+      <Label className="text-sm font-medium text-foreground mb-2 block">
+        Try it
+      </Label>
+      <Textarea
+        placeholder="Paste or type some raw dictation to see how this mode formats it."
+        value={sample}
+        onChange={(e) => setSample(e.target.value)}
+        className="min-h-20 text-sm"
+      />
+      <div className="flex items-center justify-between mt-2">
+        <p className="text-xs text-muted-foreground">
+          Uses saved settings; nothing is stored.
+        </p>
+        <Button
+          type="button"
+          variant="outline"
+          size="sm"
+          disabled={!sample.trim() || previewMutation.isPending}
+          onClick={() => previewMutation.mutate({ text: sample, modeId })}
+        >
+          {previewMutation.isPending ? "Formatting..." : "Preview"}
+        </Button>
+      </div>
+      {previewMutation.error && (
+        <p className="mt-2 text-xs text-destructive">
+          {previewMutation.error.message}
+        </p>
+      )}
+      {previewMutation.data && (
+        <div className="mt-2 rounded-md border bg-muted/40 p-3">
+          <p className="whitespace-pre-wrap text-sm">
+            {previewMutation.data.text}
+          </p>
+          <p className="mt-2 text-xs text-muted-foreground">
+            {previewMutation.data.model} ·{" "}
+            {Math.round(previewMutation.data.duration)} ms
+          </p>
+        </div>
+      )}
+    </div>
+  );
+}
@@ -6,6 +6,13 @@ import { Label } from "@/components/ui/label";
 import { Switch } from "@/components/ui/switch";
 import { Separator } from "@/components/ui/separator";
 import { Combobox } from "@/components/ui/combobox";
+import {
+  Select,
+  SelectContent,
+  SelectItem,
+  SelectTrigger,
+  SelectValue,
+} from "@/components/ui/select";
 import {
   Collapsible,
   CollapsibleContent,
@@ -38,10 +45,18 @@ import { Check, ChevronDown, Plus, X } from "lucide-react";
 import { Link } from "@tanstack/react-router";
 import { cn } from "@/lib/utils";
 import { AVAILABLE_LANGUAGES } from "@/constants/languages";
+import type {
+  DateFormat,
+  NormalizationConfig,
+  NumberStyle,
+} from "@/types/normalization";
+import type { ProfanityPolicy } from "@/types/profanity";
+import { MIN_MESSAGE_GAP_MS } from "@/pipeline/processing/message-splitting";
 import { api } from "@/trpc/react";
 import { toast } from "sonner";
 import type { ModeConfig } from "@/db/schema";
 import { CustomInstructionsEditor } from "./CustomInstructionsEditor";
+import { FormattingPreview } from "./FormattingPreview";
 import type { ComboboxOption } from "@/components/ui/combobox";
 
 interface ModeCardProps {
@@ -133,6 +148,24 @@ const getAppFallback = (label: string) => {
   return `${words[0][0]}${words[1][0]}`.toUpperCase();
 };
 
+const DEFAULT_NORMALIZATION: NormalizationConfig = {
+  enabled: false,
+  numbers: "digits",
+  dateFormat: "long",
+  currency: true,
+};
+
+const DEFAULT_MESSAGE_SPLITTING: NonNullable<ModeConfig["messageSplitting"]> =
+  {
+    enabled: false,
+    maxLength: 500,
+    delayMs: 400,
+  };
+
+// Sentinel combobox value for "no fallback model"
+const NO_FALLBACK_MODEL = "__none__";
+// Sentinel select value for "style detected from the focused app"
+const AUTO_PRESET = "__auto__";
 
 export function ModeCard({
   mode,
@@ -215,6 +248,9 @@ export function ModeCard({
   });
   const speechModels = speechModelsQuery.data || [];
 
+  const presetsQuery = api.formatter.getPresets.useQuery();
+  const presets = presetsQuery.data || [];
+
   const formattingOptions = useMemo<ComboboxOption[]>(() => {
     const languageOptions = languageModels.map((model) => ({
       value: model.id,
@@ -224,6 +260,15 @@ export function ModeCard({
 
     return languageOptions;
   }, [languageModels]);
+  const fallbackOptions = useMemo<ComboboxOption[]>(
+    () => [
+      { value: NO_FALLBACK_MODEL, label: "No fallback" },
+      ...formattingOptions.filter(
+        (option) => option.value !== mode.formatterConfig.modelId,
+      ),
+    ],
+    [formattingOptions, mode.formatterConfig.modelId],
+  );
   const hasFormattingOptions = formattingOptions.length > 0;
   const isFormattingOptionsLoading =
     (languageModelsQuery.isLoading || languageModelsQuery.isFetching) &&
@@ -366,9 +411,8 @@ export function ModeCard({
       updateModeMutation.mutate({
         modeId: mode.id,
         formatterConfig: {
+          ...mode.formatterConfig,
           enabled,
-          modelId: mode.formatterConfig.modelId,
-          fallbackModelId: mode.formatterConfig.fallbackModelId,
         },
       });
     },
@@ -381,9 +425,40 @@ export function ModeCard({
       updateModeMutation.mutate({
         modeId: mode.id,
         formatterConfig: {
-          enabled: mode.formatterConfig.enabled,
+          ...mode.formatterConfig,
           modelId,
-          fallbackModelId: modelId,
+          // A fallback identical to the primary model is pointless
+          fallbackModelId:
+            mode.formatterConfig.fallbackModelId === modelId
+              ? undefined
+              : mode.formatterConfig.fallbackModelId,
+        },
+      });
+    },
+    [mode.id, mode.formatterConfig, updateModeMutation],
+  );
+
+  const handlePresetChange = useCallback(
+    (presetId: string) => {
+      updateModeMutation.mutate({
+        modeId: mode.id,
+        formatterConfig: {
+          ...mode.formatterConfig,
+          presetId: presetId === AUTO_PRESET ? undefined : presetId,
+        },
+      });
+    },
+    [mode.id, mode.formatterConfig, updateModeMutation],
+  );
+
+  const handleFallbackModelChange = useCallback(
+    (modelId: string) => {
+      updateModeMutation.mutate({
+        modeId: mode.id,
+        formatterConfig: {
+          ...mode.formatterConfig,
+          fallbackModelId:
+            modelId && modelId !== NO_FALLBACK_MODEL ? modelId : undefined,
         },
       });
     },
@@ -416,6 +491,64 @@ export function ModeCard({
     [mode.id, updateModeMutation],
   );
 
+  const handleSpokenCommandsChange = useCallback(
+    (enabled: boolean) => {
+      updateModeMutation.mutate({
+        modeId: mode.id,
+        spokenCommandsEnabled: enabled,
+      });
+    },
+    [mode.id, updateModeMutation],
+  );
+
+  const handleVoiceEditingChange = useCallback(
+    (enabled: boolean) => {
+      updateModeMutation.mutate({
+        modeId: mode.id,
+        voiceEditingEnabled: enabled,
+      });
+    },
+    [mode.id, updateModeMutation],
+  );
+
+  const handleVocabularyCorrectionChange = useCallback(
+    (enabled: boolean) => {
+      updateModeMutation.mutate({
+        modeId: mode.id,
+        vocabularyCorrectionEnabled: enabled,
+      });
+    },
+    [mode.id, updateModeMutation],
+  );
+
+  const handleMessageSplittingChange = useCallback(
+    (updates: Partial<NonNullable<ModeConfig["messageSplitting"]>>) => {
+      updateModeMutation.mutate({
+        modeId: mode.id,
+        messageSplitting: {
+          ...DEFAULT_MESSAGE_SPLITTING,
+          ...mode.messageSplitting,
+          ...updates,
+        },
+      });
+    },
+    [mode.id, mode.messageSplitting, updateModeMutation],
+  );
+
+  const handleNormalizationChange = useCallback(
+    (updates: Partial<NormalizationConfig>) => {
+      updateModeMutation.mutate({
+        modeId: mode.id,
+        normalization: {
+          ...DEFAULT_NORMALIZATION,
+          ...mode.normalization,
+          ...updates,
+        },
+      });
+    },
+    [mode.id, mode.normalization, updateModeMutation],
+  );
+
   const handleAppBindingsChange = useCallback(
     (values: string[]) => {
       const dedupedValues = Array.from(new Set(values));
@@ -700,6 +833,146 @@ export function ModeCard({
                     placeholder="Select a speech model..."
                   />
                 </div>
+
+                {/* Spoken commands */}
+                <div className="flex items-center justify-between">
+                  <div>
+                    <Label className="text-sm font-medium text-foreground">
+                      Spoken commands
+                    </Label>
+                    <p className="text-xs text-muted-foreground">
+                      Say "new line", "comma" or "delete that" to punctuate
+                      and edit as you dictate.
+                    </p>
+                  </div>
+                  <Switch
+                    checked={mode.spokenCommandsEnabled ?? false}
+                    onCheckedChange={handleSpokenCommandsChange}
+                  />
+                </div>
+
+                {/* Voice editing */}
+                <div className="flex items-center justify-between">
+                  <div>
+                    <Label className="text-sm font-medium text-foreground">
+                      Voice editing
+                    </Label>
+                    <p className="text-xs text-muted-foreground">
+                      Say "replace X with Y" or "make it more formal" right
+                      after dictating to edit what you just inserted.
+                    </p>
+                  </div>
+                  <Switch
+                    checked={mode.voiceEditingEnabled ?? false}
+                    onCheckedChange={handleVoiceEditingChange}
+                  />
+                </div>
+
+                {/* Vocabulary correction */}
+                <div className="flex items-center justify-between">
+                  <div>
+                    <Label className="text-sm font-medium text-foreground">
+                      Fix vocabulary terms
+                    </Label>
+                    <p className="text-xs text-muted-foreground">
+                      Ask the formatting model to correct misheard vocabulary
+                      words. Any other change it makes is discarded.
+                    </p>
+                  </div>
+                  <Switch
+                    checked={mode.vocabularyCorrectionEnabled ?? false}
+                    onCheckedChange={handleVocabularyCorrectionChange}
+                  />
+                </div>
+
+                {/* Message splitting */}
+                <div className="flex items-center justify-between">
+                  <div>
+                    <Label className="text-sm font-medium text-foreground">
+                      Split long messages
+                    </Label>
+                    <p className="text-xs text-muted-foreground">
+                      Send long dictation as several chat messages, split at
+                      paragraphs and sentences.
+                    </p>
+                  </div>
+                  <Switch
+                    checked={mode.messageSplitting?.enabled ?? false}
+                    onCheckedChange={(enabled) =>
+                      handleMessageSplittingChange({ enabled })
+                    }
+                  />
+                </div>
+                {mode.messageSplitting?.enabled && (
+                  <div className="flex items-center gap-3 pl-4 border-l-2 border-muted">
+                    <Label className="text-xs text-muted-foreground">
+                      Max characters
+                    </Label>
+                    <Input
+                      type="number"
+                      min={50}
+                      max={10000}
+                      defaultValue={mode.messageSplitting.maxLength}
+                      onBlur={(event) => {
+                        const maxLength = Number(event.target.value);
+                        if (maxLength >= 50 && maxLength <= 10000) {
+                          handleMessageSplittingChange({ maxLength });
+                        }
+                      }}
+                      className="h-8 w-24"
+                    />
+                    <Label className="text-xs text-muted-foreground">
+                      Delay (ms)
+                    </Label>
+                    <Input
+                      type="number"
+                      min={MIN_MESSAGE_GAP_MS}
+                      max={10000}
+                      step={100}
+                      defaultValue={mode.messageSplitting.delayMs}
+                      onBlur={(event) => {
+                        const delayMs = Number(event.target.value);
+                        if (
+                          delayMs >= MIN_MESSAGE_GAP_MS &&
+                          delayMs <= 10000
+                        ) {
+                          handleMessageSplittingChange({ delayMs });
+                        }
+                      }}
+                      className="h-8 w-24"
+                    />
+                  </div>
+                )}
+
+                {/* Profanity */}
+                <div className="flex items-center justify-between">
+                  <div>
+                    <Label className="text-sm font-medium text-foreground">
+                      Profanity
+                    </Label>
+                    <p className="text-xs text-muted-foreground">
+                      Keep, mask or remove swear words in the final text.
+                    </p>
+                  </div>
+                  <Select
+                    value={mode.profanityPolicy ?? "keep"}
+                    onValueChange={(policy) =>
+                      updateModeMutation.mutate({
+                        modeId: mode.id,
+                        profanityPolicy: policy as ProfanityPolicy,
+                      })
+                    }
+                  >
+                    <SelectTrigger className="w-32">
+                      <SelectValue />
+                    </SelectTrigger>
+                    <SelectContent>
+                      <SelectItem value="keep">Keep</SelectItem>
+                      <SelectItem value="mask">Mask (f***)</SelectItem>
+                      <SelectItem value="remove">Remove</SelectItem>
+                    </SelectContent>
+                  </Select>
+                </div>
               </div>
             </div>
 
@@ -785,10 +1058,140 @@ export function ModeCard({
                       placeholder="Select a model..."
                     />
                   </div>
+                  <div>
+                    <Label className="text-sm font-medium text-foreground mb-2 block">
+                      Style preset
+                    </Label>
+                    <Select
+                      value={mode.formatterConfig.presetId ?? AUTO_PRESET}
+                      onValueChange={handlePresetChange}
+                    >
+                      <SelectTrigger className="w-full">
+                        <SelectValue />
+                      </SelectTrigger>
+                      <SelectContent>
+                        <SelectItem value={AUTO_PRESET}>
+                          Match the current app
+                        </SelectItem>
+                        {presets.map((preset) => (
+                          <SelectItem key={preset.id} value={preset.id}>
+                            {preset.name} — {preset.description}
+                          </SelectItem>
+                        ))}
+                      </SelectContent>
+                    </Select>
+                  </div>
+                  <div>
+                    <Label className="text-sm font-medium text-foreground mb-1 block">
+                      Fallback model
+                    </Label>
+                    <p className="text-xs text-muted-foreground mb-2">
+                      Used when the formatting model errors or times out.
+                    </p>
+                    <Combobox
+                      options={fallbackOptions}
+                      value={
+                        mode.formatterConfig.fallbackModelId ??
+                        NO_FALLBACK_MODEL
+                      }
+                      onChange={handleFallbackModelChange}
+                      placeholder="Select a model..."
+                    />
+                  </div>
                   <CustomInstructionsEditor
                     value={localCustomInstructions}
                     onChange={handleCustomInstructionsChange}
                   />
+                  <FormattingPreview modeId={mode.id} />
+                </div>
+              )}
+            </div>
+
+            <Separator />
+
+            {/* Normalization settings */}
+            <div>
+              <div className="flex items-center justify-between mb-2">
+                <div>
+                  <Label className="text-base font-semibold text-foreground">
+                    Normalization
+                  </Label>
+                  <p className="text-xs text-muted-foreground mb-2">
+                    Consistently format numbers, dates and amounts without a
+                    language model. English only.
+                  </p>
+                </div>
+                <Switch
+                  checked={mode.normalization?.enabled ?? false}
+                  onCheckedChange={(enabled) =>
+                    handleNormalizationChange({ enabled })
+                  }
+                />
+              </div>
+
+              {mode.normalization?.enabled && (
+                <div className="mt-4 space-y-4">
+                  <div>
+                    <Label className="text-sm font-medium text-foreground mb-2 block">
+                      Numbers
+                    </Label>
+                    <Select
+                      value={mode.normalization.numbers}
+                      onValueChange={(numbers) =>
+                        handleNormalizationChange({
+                          numbers: numbers as NumberStyle,
+                        })
+                      }
+                    >
+                      <SelectTrigger className="w-full">
+                        <SelectValue />
+                      </SelectTrigger>
+                      <SelectContent>
+                        <SelectItem value="digits">
+                          Digits (twenty five → 25)
+                        </SelectItem>
+                        <SelectItem value="words">
+                          Words (3 → three)
+                        </SelectItem>
+                        <SelectItem value="off">Leave as spoken</SelectItem>
+                      </SelectContent>
+                    </Select>
+                  </div>
+                  <div>
+                    <Label className="text-sm font-medium text-foreground mb-2 block">
+                      Dates
+                    </Label>
+                    <Select
+                      value={mode.normalization.dateFormat}
+                      onValueChange={(dateFormat) =>
+                        handleNormalizationChange({
+                          dateFormat: dateFormat as DateFormat,
+                        })
+                      }
+                    >
+                      <SelectTrigger className="w-full">
+                        <SelectValue />
+                      </SelectTrigger>
+                      <SelectContent>
+                        <SelectItem value="long">March 5, 2024</SelectItem>
+                        <SelectItem value="iso">2024-03-05</SelectItem>
+                        <SelectItem value="us">03/05/2024</SelectItem>
+                        <SelectItem value="eu">05/03/2024</SelectItem>
+                        <SelectItem value="off">Leave as spoken</SelectItem>
+                      </SelectContent>
+                    </Select>
+                  </div>
+                  <div className="flex items-center justify-between">
+                    <Label className="text-sm font-medium text-foreground">
+                      Currency symbols (five dollars → $5)
+                    </Label>
+                    <Switch
+                      checked={mode.normalization.currency}
+                      onCheckedChange={(currency) =>
+                        handleNormalizationChange({ currency })
+                      }
+                    />
+                  </div>
                 </div>
               )}
             </div>
@@ -2,14 +2,14 @@ import { createRootRoute, Outlet } from "@tanstack/react-router";
 import { TanStackRouterDevtools } from "@tanstack/react-router-devtools";
 import { QueryClient, QueryClientProvider } from "@tanstack/react-query";
 import { useEffect } from "react";
-import { api, trpcClient } from "@/trpc/react";
+import { api, retryIfDatabaseLocked, trpcClient } from "@/trpc/react";
 import { usePostHog } from "../lib/posthog";
 
 // Create a client
 const queryClient = new QueryClient({
   defaultOptions: {
     queries: {
-      retry: false,
+      retry: retryIfDatabaseLocked,
       refetchOnWindowFocus: false,
     },
   },
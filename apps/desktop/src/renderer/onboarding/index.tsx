@@ -3,7 +3,7 @@ import ReactDOM from "react-dom/client";
 import { QueryClient, QueryClientProvider } from "@tanstack/react-query";
 import { App } from "./App";
 import { OnboardingErrorBoundary } from "./components/ErrorBoundary";
-import { api, trpcClient } from "@/trpc/react";
+import { api, retryIfDatabaseLocked, trpcClient } from "@/trpc/react";
 import { ThemeProvider } from "@/components/theme-provider";
 import { Toaster } from "@/components/ui/sonner";
 import "@/styles/globals.css";
@@ -12,7 +12,7 @@ import "@/styles/globals.css";
 const queryClient = new QueryClient({
   defaultOptions: {
     queries: {
-      retry: false,
+      retry: retryIfDatabaseLocked,
       refetchOnWindowFocus: false,
     },
   },
@@ -2,7 +2,7 @@ import React from "react";
 import { createRoot } from "react-dom/client";
 import { QueryClient, QueryClientProvider } from "@tanstack/react-query";
 import { WidgetPage } from "./pages/widget";
-import { api, trpcClient } from "@/trpc/react";
+import { api, retryIfDatabaseLocked, trpcClient } from "@/trpc/react";
 import { ThemeProvider } from "@/components/theme-provider";
 import { ToasterWrapper } from "./components/ToasterWrapper";
 import "@/styles/globals.css";
@@ -61,7 +61,7 @@ console.original = originalConsole;
 const queryClient = new QueryClient({
   defaultOptions: {
     queries: {
-      retry: false,
+      retry: retryIfDatabaseLocked,
       refetchOnWindowFocus: false,
     },
   },
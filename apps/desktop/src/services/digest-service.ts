@@ -0,0 +1,181 @@
+import { Notification } from "electron";
+import { logger } from "../main/logger";
+import type { SettingsService } from "./settings-service";
+import type { TranscriptionService } from "./transcription-service";
+import { getDictationStats } from "../db/stats";
+import {
+  buildTranscriptionPreview,
+  getTranscriptionsByDateRange,
+} from "../db/transcriptions";
+import {
+  getWeeklySummary,
+  upsertWeeklySummary,
+} from "../db/weekly-summaries";
+import type { WeeklySummary } from "../db/schema";
+
+const CHECK_INTERVAL_MS = 60 * 60 * 1000;
+// Let startup finish before the first check
+const INITIAL_DELAY_MS = 2 * 60 * 1000;
+// Keep the digest prompt small: one short excerpt per dictation
+const MAX_DIGEST_EXCERPTS = 200;
+const MAX_DIGEST_CHARS = 12_000;
+
+// Local midnight of the Monday starting the week that contains `date`
+export function startOfWeek(date: Date): Date {
+  const start = new Date(date.getFullYear(), date.getMonth(), date.getDate());
+  start.setDate(start.getDate() - ((start.getDay() + 6) % 7));
+  return start;
+}
+
+function addDays(date: Date, days: number): Date {
+  const result = new Date(date);
+  result.setDate(result.getDate() + days);
+  return result;
+}
+
+/**
+ * Builds weekly dictation summaries: totals and breakdowns from SQL, plus
+ * an optional language model digest. When enabled, last week's summary is
+ * generated automatically once the week is over.
+ */
+export class DigestService {
+  private timer: NodeJS.Timeout | null = null;
+
+  constructor(
+    private settingsService: SettingsService,
+    private transcriptionService: TranscriptionService | null,
+  ) {}
+
+  initialize(): void {
+    this.scheduleNext(INITIAL_DELAY_MS);
+  }
+
+  stop(): void {
+    if (this.timer) {
+      clearTimeout(this.timer);
+      this.timer = null;
+    }
+  }
+
+  private scheduleNext(delayMs: number): void {
+    this.timer = setTimeout(() => {
+      this.summarizeLastWeekIfDue()
+        .catch((error) => {
+          logger.main.error("Weekly summary generation failed", { error });
+        })
+        .finally(() => {
+          if (this.timer) this.scheduleNext(CHECK_INTERVAL_MS);
+        });
+    }, delayMs);
+    // Don't keep the process alive just for housekeeping
+    this.timer.unref();
+  }
+
+  private async summarizeLastWeekIfDue(): Promise<void> {
+    const settings = await this.settingsService.getWeeklyDigestSettings();
+    if (!settings?.enabled) {
+      return;
+    }
+    const lastWeek = addDays(startOfWeek(new Date()), -7);
+    if (await getWeeklySummary(lastWeek)) {
+      return;
+    }
+
+    const { summary } = await this.generate(lastWeek, {
+      useLanguageModel: settings.useLanguageModel ?? false,
+    });
+    if (settings.notify && summary.transcriptionCount > 0) {
+      this.notify(summary);
+    }
+  }
+
+  /**
+   * Summarize the week containing `date` and store the result, replacing
+   * any earlier summary of that week. A failed digest leaves `digest` null
+   * and is reported in `digestError`.
+   */
+  async generate(
+    date: Date,
+    options: { useLanguageModel: boolean },
+  ): Promise<{ summary: WeeklySummary; digestError?: string }> {
+    const weekStart = startOfWeek(date);
+    const weekEnd = addDays(weekStart, 7);
+    const stats = await getDictationStats({ from: weekStart, to: weekEnd });
+
+    let digest: string | null = null;
+    let digestModel: string | null = null;
+    let digestError: string | undefined;
+    if (options.useLanguageModel && stats.totals.transcriptions > 0) {
+      try {
+        if (!this.transcriptionService) {
+          throw new Error("Transcription service not available");
+        }
+        const result = await this.transcriptionService.generateWeeklyDigest(
+          await this.collectExcerpts(weekStart, weekEnd),
+        );
+        digest = result.digest;
+        digestModel = result.model;
+      } catch (error) {
+        digestError =
+          error instanceof Error ? error.message : "Digest generation failed";
+        logger.main.warn("Weekly digest generation failed", { error });
+      }
+    }
+
+    const summary = await upsertWeeklySummary({
+      weekStart,
+      transcriptionCount: stats.totals.transcriptions,
+      wordCount: stats.totals.words,
+      durationSeconds: stats.totals.durationSeconds,
+      stats: {
+        averageWpm: stats.totals.averageWpm,
+        daily: stats.daily,
+        byModel: stats.byModel,
+        byLanguage: stats.byLanguage,
+      },
+      digest,
+      digestModel,
+      createdAt: new Date(),
+    });
+    logger.main.info("Weekly summary generated", {
+      weekStart: weekStart.toISOString(),
+      transcriptions: summary.transcriptionCount,
+      withDigest: digest !== null,
+    });
+    return { summary, digestError };
+  }
+
+  private async collectExcerpts(from: Date, to: Date): Promise<string[]> {
+    const rows = await getTranscriptionsByDateRange(from, to);
+    const excerpts: string[] = [];
+    let chars = 0;
+    // Oldest first, so the model reads the week in order
+    for (const row of rows.reverse()) {
+      if (row.timestamp >= to) continue;
+      const excerpt = row.preview || buildTranscriptionPreview(row.text);
+      if (!excerpt) continue;
+      if (
+        excerpts.length >= MAX_DIGEST_EXCERPTS ||
+        chars + excerpt.length > MAX_DIGEST_CHARS
+      ) {
+        break;
+      }
+      excerpts.push(excerpt);
+      chars += excerpt.length;
+    }
+    return excerpts;
+  }
+
+  private notify(summary: WeeklySummary): void {
+    if (!Notification.isSupported()) {
+      return;
+    }
+    const words = summary.wordCount.toLocaleString();
+    new Notification({
+      title: "Your week in dictation",
+      body:
+        summary.digest?.split("\n")[0] ??
+        `${summary.transcriptionCount} dictations, ${words} words.`,
+    }).show();
+  }
+}
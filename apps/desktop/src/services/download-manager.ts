@@ -0,0 +1,292 @@
+import { EventEmitter } from "events";
+import * as fs from "node:fs";
+import { Readable, Transform } from "node:stream";
+import { pipeline } from "node:stream/promises";
+import type { ReadableStream as WebReadableStream } from "node:stream/web";
+import type { DownloadProgress } from "../constants/models";
+import { logger } from "../main/logger";
+import { getUserAgent } from "../utils/http-client";
+
+// Emit progress at most every 1% or 1 MB
+const PROGRESS_STEP_BYTES = 1024 * 1024;
+
+export class DownloadCancelledError extends Error {
+  constructor(id: string) {
+    super(`Download cancelled: ${id}`);
+    this.name = "DownloadCancelledError";
+  }
+}
+
+// One file of a download and the sources to try for it, in order
+export interface DownloadFile {
+  urls: string[];
+  destination: string;
+  expectedBytes: number;
+}
+
+interface DownloadTask {
+  files: DownloadFile[]; // Fetched one after another
+  progress: DownloadProgress; // Totals over all the files
+  controller: AbortController;
+  finished?: Promise<void>; // Set once the download starts
+  resolve: (bytes: number) => void;
+  reject: (error: Error) => void;
+}
+
+interface DownloadManagerEvents {
+  progress: (id: string, progress: DownloadProgress) => void;
+}
+
+/**
+ * Runs file downloads with a limit on how many run at once; the rest wait
+ * in order. Data is written to `<destination>.part` and only renamed into
+ * place once complete, so a cancelled or failed download never leaves a
+ * file that looks finished. Cancelling aborts the HTTP request and removes
+ * the partial file. When a download has several sources, a failed one is
+ * retried from the next. A download may span several files, which share one
+ * queue slot and one progress total.
+ */
+export class DownloadManager extends EventEmitter {
+  private tasks = new Map<string, DownloadTask>();
+
+  constructor(private maxConcurrent: number) {
+    super();
+  }
+
+  on<U extends keyof DownloadManagerEvents>(
+    event: U,
+    listener: DownloadManagerEvents[U],
+  ): this {
+    return super.on(event, listener);
+  }
+
+  emit<U extends keyof DownloadManagerEvents>(
+    event: U,
+    ...args: Parameters<DownloadManagerEvents[U]>
+  ): boolean {
+    return super.emit(event, ...args);
+  }
+
+  has(id: string): boolean {
+    return this.tasks.has(id);
+  }
+
+  getProgress(id: string): DownloadProgress | null {
+    const task = this.tasks.get(id);
+    return task ? { ...task.progress } : null;
+  }
+
+  // Running and queued downloads, in the order they were requested
+  getAll(): DownloadProgress[] {
+    return Array.from(this.tasks.values(), (task) => ({ ...task.progress }));
+  }
+
+  /**
+   * Download `url` to `destination`, trying each URL in turn if several are
+   * given. Resolves with the file size once it's in place; rejects with
+   * DownloadCancelledError if cancelled, or the last source's error.
+   */
+  download(
+    id: string,
+    url: string | string[],
+    destination: string,
+    expectedBytes: number,
+  ): Promise<number> {
+    return this.downloadFiles(id, [
+      { urls: Array.isArray(url) ? url : [url], destination, expectedBytes },
+    ]);
+  }
+
+  /**
+   * Download several files as one task. Resolves with their total size once
+   * all are in place. Files finished before a failure or cancellation are
+   * left for the caller to remove.
+   */
+  downloadFiles(id: string, files: DownloadFile[]): Promise<number> {
+    if (this.tasks.has(id)) {
+      return Promise.reject(new Error(`Download already in progress: ${id}`));
+    }
+
+    return new Promise<number>((resolve, reject) => {
+      this.tasks.set(id, {
+        files: files.map((file) => ({ ...file })),
+        progress: {
+          modelId: id,
+          progress: 0,
+          status: "queued",
+          bytesDownloaded: 0,
+          totalBytes: files.reduce((sum, file) => sum + file.expectedBytes, 0),
+        },
+        controller: new AbortController(),
+        resolve,
+        reject,
+      });
+      this.emit("progress", id, this.getProgress(id)!);
+      this.startQueued();
+    });
+  }
+
+  /**
+   * Stop a download, or drop it from the queue. Returns once the partial
+   * file is gone.
+   */
+  async cancel(id: string): Promise<void> {
+    const task = this.tasks.get(id);
+    if (!task) {
+      throw new Error(`No active download found for model: ${id}`);
+    }
+
+    if (task.progress.status === "queued") {
+      this.tasks.delete(id);
+      task.reject(new DownloadCancelledError(id));
+      return;
+    }
+
+    task.progress.status = "cancelling";
+    this.emit("progress", id, this.getProgress(id)!);
+    task.controller.abort();
+    await task.finished;
+  }
+
+  async cancelAll(): Promise<void> {
+    await Promise.all(
+      Array.from(this.tasks.keys(), (id) => this.cancel(id).catch(() => {})),
+    );
+  }
+
+  private startQueued(): void {
+    const running = Array.from(this.tasks.values()).filter(
+      (task) => task.progress.status !== "queued",
+    ).length;
+    let slots = this.maxConcurrent - running;
+    for (const [id, task] of this.tasks) {
+      if (slots <= 0) break;
+      if (task.progress.status !== "queued") continue;
+      slots--;
+      task.progress.status = "downloading";
+      this.emit("progress", id, this.getProgress(id)!);
+      task.finished = this.run(id, task);
+    }
+  }
+
+  private async run(id: string, task: DownloadTask): Promise<void> {
+    const { signal } = task.controller;
+    let partPath = "";
+    try {
+      let total = 0;
+      for (const file of task.files) {
+        partPath = `${file.destination}.part`;
+        await this.fetchFile(id, task, file, partPath);
+        await fs.promises.rename(partPath, file.destination);
+        total += (await fs.promises.stat(file.destination)).size;
+      }
+      task.resolve(total);
+    } catch (error) {
+      await fs.promises.rm(partPath, { force: true }).catch((rmError) => {
+        logger.main.warn("Failed to remove partial download", {
+          file: partPath,
+          error: rmError,
+        });
+      });
+      task.reject(
+        signal.aborted
+          ? new DownloadCancelledError(id)
+          : error instanceof Error
+            ? error
+            : new Error(String(error)),
+      );
+    } finally {
+      this.tasks.delete(id);
+      this.startQueued();
+    }
+  }
+
+  // Fetch one file into its partial file, falling back through its sources
+  private async fetchFile(
+    id: string,
+    task: DownloadTask,
+    file: DownloadFile,
+    partPath: string,
+  ): Promise<void> {
+    const startBytes = task.progress.bytesDownloaded;
+    for (const [index, url] of file.urls.entries()) {
+      try {
+        await this.fetchTo(id, task, file, url, partPath);
+        return;
+      } catch (error) {
+        if (task.controller.signal.aborted || index === file.urls.length - 1) {
+          throw error;
+        }
+        logger.main.warn("Download source failed, trying the next one", {
+          id,
+          url,
+          error: error instanceof Error ? error.message : String(error),
+        });
+        task.progress.bytesDownloaded = startBytes;
+        task.progress.progress = Math.round(
+          (startBytes / task.progress.totalBytes) * 100,
+        );
+        this.emit("progress", id, this.getProgress(id)!);
+      }
+    }
+  }
+
+  // Stream one source into the partial file, replacing anything a failed
+  // source left there
+  private async fetchTo(
+    id: string,
+    task: DownloadTask,
+    file: DownloadFile,
+    url: string,
+    partPath: string,
+  ): Promise<void> {
+    const { signal } = task.controller;
+    const response = await fetch(url, {
+      signal,
+      headers: { "User-Agent": getUserAgent() },
+    });
+    if (!response.ok || !response.body) {
+      throw new Error(
+        `Failed to download: ${response.status} ${response.statusText}`,
+      );
+    }
+
+    // Swap this file's estimate in the total for the actual size
+    const progress = task.progress;
+    const contentLength = parseInt(
+      response.headers.get("content-length") || "0",
+    );
+    if (contentLength) {
+      progress.totalBytes += contentLength - file.expectedBytes;
+      file.expectedBytes = contentLength;
+    }
+    let lastPercent = 0;
+    let lastBytes = 0;
+
+    const counter = new Transform({
+      transform: (chunk: Buffer, _encoding, callback) => {
+        progress.bytesDownloaded += chunk.length;
+        progress.progress = Math.round(
+          (progress.bytesDownloaded / progress.totalBytes) * 100,
+        );
+        if (
+          progress.progress > lastPercent ||
+          progress.bytesDownloaded - lastBytes >= PROGRESS_STEP_BYTES
+        ) {
+          lastPercent = progress.progress;
+          lastBytes = progress.bytesDownloaded;
+          this.emit("progress", id, { ...progress });
+        }
+        callback(null, chunk);
+      },
+    });
+
+    // pipeline applies backpressure and closes every stream on abort
+    await pipeline(
+      Readable.fromWeb(response.body as WebReadableStream<Uint8Array>),
+      counter,
+      fs.createWriteStream(partPath),
+      { signal },
+    );
+  }
+}
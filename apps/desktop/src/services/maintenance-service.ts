@@ -0,0 +1,150 @@
+import { powerMonitor } from "electron";
+import { EventEmitter } from "node:events";
+import { logger } from "../main/logger";
+import type { RecordingManager } from "../main/managers/recording-manager";
+import type { SettingsService } from "./settings-service";
+import {
+  analyzeDatabase,
+  checkpointDatabase,
+  getDatabaseSize,
+  getLastMaintenanceRun,
+  recordMaintenanceRun,
+  vacuumDatabase,
+} from "../db/maintenance";
+import type { AppSettingsData, MaintenanceRun } from "../db/schema";
+import { recordDatabaseError } from "../db/errors";
+
+const CHECK_INTERVAL_MS = 15 * 60 * 1000;
+// Let startup finish before the first check
+const INITIAL_DELAY_MS = 5 * 60 * 1000;
+const DAY_MS = 24 * 60 * 60 * 1000;
+// Seconds without keyboard or mouse input before the user counts as away
+const IDLE_THRESHOLD_S = 5 * 60;
+
+type MaintenanceSettings = NonNullable<AppSettingsData["maintenance"]>;
+
+/**
+ * Whether a scheduled pass is due. Without an hour, passes are spaced a day
+ * (or a week) apart; with one, a pass is due once that hour has come round
+ * since the last pass (for weekly, six days after it).
+ */
+export function isMaintenanceDue(
+  lastRun: Date | null,
+  settings: MaintenanceSettings,
+  now: Date = new Date(),
+): boolean {
+  if (!lastRun) return true;
+  const intervalMs = settings.frequency === "weekly" ? 7 * DAY_MS : DAY_MS;
+  if (settings.hour === undefined) {
+    return now.getTime() - lastRun.getTime() >= intervalMs;
+  }
+
+  // Most recent occurrence of the hour
+  const slot = new Date(now);
+  slot.setHours(settings.hour, 0, 0, 0);
+  if (slot > now) slot.setDate(slot.getDate() - 1);
+  return lastRun.getTime() < slot.getTime() - (intervalMs - DAY_MS);
+}
+
+/**
+ * Keeps the database compact and its planner statistics fresh. Deleted
+ * transcriptions, revisions and embeddings leave free pages behind; on the
+ * user's schedule (daily by default), while nothing is being recorded and
+ * the user is away, this vacuums them, runs ANALYZE and truncates the WAL.
+ * Emits "run-finished" with the run's stats after every pass.
+ */
+export class MaintenanceService extends EventEmitter {
+  private timer: NodeJS.Timeout | null = null;
+  private running: Promise<MaintenanceRun> | null = null;
+
+  constructor(
+    private settingsService: SettingsService,
+    private recordingManager: RecordingManager,
+  ) {
+    super();
+  }
+
+  initialize(): void {
+    this.scheduleNext(INITIAL_DELAY_MS);
+  }
+
+  stop(): void {
+    if (this.timer) {
+      clearTimeout(this.timer);
+      this.timer = null;
+    }
+  }
+
+  private scheduleNext(delayMs: number): void {
+    this.timer = setTimeout(() => {
+      this.runIfDue()
+        .catch((error) => {
+          logger.db.error("Database maintenance failed", { error });
+          recordDatabaseError(error, "maintenance");
+        })
+        .finally(() => {
+          if (this.timer) this.scheduleNext(CHECK_INTERVAL_MS);
+        });
+    }, delayMs);
+    // Don't keep the process alive just for housekeeping
+    this.timer.unref();
+  }
+
+  private async runIfDue(): Promise<void> {
+    const settings: MaintenanceSettings =
+      (await this.settingsService.getMaintenanceSettings()) ?? {};
+    if (settings.enabled === false) {
+      return;
+    }
+    const lastRun = await getLastMaintenanceRun();
+    if (!isMaintenanceDue(lastRun?.startedAt ?? null, settings)) {
+      return;
+    }
+    if (this.recordingManager.getState() !== "idle") {
+      return;
+    }
+    if (
+      settings.onlyWhenIdle !== false &&
+      powerMonitor.getSystemIdleTime() < IDLE_THRESHOLD_S
+    ) {
+      return;
+    }
+    await this.run();
+  }
+
+  /**
+   * Run a maintenance pass now. Concurrent callers share one pass.
+   */
+  async run(): Promise<MaintenanceRun> {
+    if (!this.running) {
+      this.running = this.runPass().finally(() => {
+        this.running = null;
+      });
+    }
+    return await this.running;
+  }
+
+  private async runPass(): Promise<MaintenanceRun> {
+    const startedAt = new Date();
+    const sizeBefore = await getDatabaseSize();
+
+    const fullVacuum = await vacuumDatabase();
+    await analyzeDatabase();
+    await checkpointDatabase();
+
+    const run = await recordMaintenanceRun({
+      startedAt,
+      durationMs: Date.now() - startedAt.getTime(),
+      fullVacuum,
+      sizeBefore,
+      sizeAfter: await getDatabaseSize(),
+    });
+    logger.db.info("Database maintenance completed", {
+      durationMs: run.durationMs,
+      fullVacuum,
+      bytesFreed: run.sizeBefore - run.sizeAfter,
+    });
+    this.emit("run-finished", run);
+    return run;
+  }
+}
@@ -2,12 +2,17 @@ import { EventEmitter } from "events";
 import * as fs from "fs";
 import * as path from "path";
 import * as crypto from "crypto";
-import { app } from "electron";
 import {
   AvailableSpeechModel,
+  DeleteModelResult,
   DownloadProgress,
-  ModelManagerState,
+  ModelFile,
+  ModelInUseWarning,
+  ModelUpdate,
+  ModeReference,
+  UnusedModel,
   AVAILABLE_MODELS,
+  getLocalRuntime,
 } from "../constants/models";
 import { Model as DBModel, NewModel } from "../db/schema";
 import {
@@ -25,7 +30,6 @@ import {
 import {
   ValidationResult,
   OpenRouterResponse,
-  OllamaResponse,
   OpenRouterModel,
   OllamaModel,
   OpenAIModelsResponse,
@@ -36,8 +40,32 @@ import {
   GoogleModel,
 } from "../types/providers";
 import { SettingsService } from "./settings-service";
+import { getModelUsageStats } from "../db/model-usage";
 import { logger } from "../main/logger";
 import { getUserAgent } from "../utils/http-client";
+import { getModelsDirectory } from "../utils/data-directory";
+import { DownloadCancelledError, DownloadManager } from "./download-manager";
+import {
+  type HuggingFaceModelFile,
+  huggingFaceFileUrl,
+  importedModelId,
+  listHuggingFaceModelFiles,
+  modelSourceUrls,
+  parseImportedModelId,
+} from "../utils/huggingface";
+import {
+  localModelId,
+  validateLocalModelFile,
+} from "../utils/local-model-file";
+import { OllamaClient } from "../pipeline/providers/ollama-client";
+import {
+  formatContextWindow,
+  getKnownContextWindow,
+} from "../constants/model-context-windows";
+import {
+  PERFORMANCE_PROFILES,
+  type PerformancePreset,
+} from "../constants/performance-presets";
 
 // Type for models fetched from external APIs
 type FetchedModel = Pick<DBModel, "id" | "name" | "provider"> &
@@ -49,6 +77,11 @@ interface ModelManagerEvents {
   "download-error": (modelId: string, error: Error) => void;
   "download-cancelled": (modelId: string) => void;
   "model-deleted": (modelId: string) => void;
+  // Modes whose speech model was deleted; they now use the selected model
+  "modes-repointed": (modelId: string, modes: ModeReference[]) => void;
+  "update-available": (updates: ModelUpdate[]) => void;
+  // Offer from the daily check when unused model cleanup is enabled
+  "unused-models": (models: UnusedModel[]) => void;
   "selection-changed": (
     oldModelId: string | null,
     newModelId: string | null,
@@ -61,20 +94,31 @@ interface ModelManagerEvents {
   ) => void;
 }
 
+// Further downloads wait in a queue
+const MAX_CONCURRENT_DOWNLOADS = 2;
+const VALIDATION_TIMEOUT_MS = 10000;
+// First update check shortly after startup, then daily
+const UPDATE_CHECK_DELAY_MS = 2 * 60 * 1000;
+const UPDATE_CHECK_INTERVAL_MS = 24 * 60 * 60 * 1000;
+const DEFAULT_UNUSED_MODEL_DAYS = 30;
+const DAY_MS = 24 * 60 * 60 * 1000;
+
 class ModelService extends EventEmitter {
-  private state: ModelManagerState;
+  private downloads = new DownloadManager(MAX_CONCURRENT_DOWNLOADS);
   private modelsDirectory: string;
   private settingsService: SettingsService;
+  private availableUpdates = new Map<string, ModelUpdate>();
+  private updateCheckTimer: NodeJS.Timeout | null = null;
 
   constructor(settingsService: SettingsService) {
     super();
-    this.state = {
-      activeDownloads: new Map(),
-    };
+    this.downloads.on("progress", (modelId, progress) => {
+      this.emit("download-progress", modelId, progress);
+    });
     this.settingsService = settingsService;
 
-    // Create models directory in app data
-    this.modelsDirectory = path.join(app.getPath("userData"), "models");
+    // Create models directory in the data directory
+    this.modelsDirectory = getModelsDirectory();
     this.ensureModelsDirectory();
   }
 
@@ -123,6 +167,7 @@ class ModelService extends EventEmitter {
         speed: model.speed,
         accuracy: model.accuracy,
         filename: model.filename,
+        files: model.files?.map((file) => file.path),
       }));
 
       const syncResult = await syncLocalWhisperModels(
@@ -136,50 +181,14 @@ class ModelService extends EventEmitter {
         removed: syncResult.removed,
       });
 
-      // Restore selected model from settings and validate availability
-      const savedSelection = await this.settingsService.getDefaultSpeechModel();
-
-      if (savedSelection) {
-        // Validate the saved selection is still available
-        const availableModel = AVAILABLE_MODELS.find(
-          (m) => m.id === savedSelection,
-        );
-
-      } else {
-        // No saved selection, check if we have downloaded models to auto-select
-        const downloadedModels = await this.getValidDownloadedModels();
-        const downloadedModelCount = Object.keys(downloadedModels).length;
-
-        if (downloadedModelCount > 0) {
-          // Auto-select the best available model using the preferred order
-          const preferredOrder = [
-            "whisper-large-v3-turbo",
-            "whisper-large-v3",
-            "whisper-medium",
-            "whisper-small",
-            "whisper-base",
-            "whisper-tiny",
-          ];
-
-          for (const candidateId of preferredOrder) {
-            if (downloadedModels[candidateId]) {
-              await this.applySpeechModelSelection(
-                candidateId,
-                "auto-first-download",
-                null,
-              );
-              logger.main.info("Auto-selected speech model on initialization", {
-                modelId: candidateId,
-                availableModels: Object.keys(downloadedModels),
-              });
-              break;
-            }
-          }
-        }
+      // Drop a saved selection whose model is no longer downloaded, then
+      // fall back to the best downloaded model so dictation keeps working
+      await this.validateAndClearInvalidSpeechSelection();
+      if (!(await this.settingsService.getDefaultSpeechModel())) {
+        await this.autoSelectDownloadedModel();
       }
 
-      // Validate speech selection after sync
-      await this.validateAndClearInvalidSpeechSelection();
+      this.scheduleUpdateCheck(UPDATE_CHECK_DELAY_MS);
     } catch (error) {
       logger.main.error("Error initializing model manager", {
         error: error instanceof Error ? error.message : String(error),
@@ -228,16 +237,18 @@ class ModelService extends EventEmitter {
 
   // Get download progress for a model
   getDownloadProgress(modelId: string): DownloadProgress | null {
-    return this.state.activeDownloads.get(modelId) || null;
+    return this.downloads.getProgress(modelId);
   }
 
-  // Get all active downloads
+  // Get all downloads, running and queued
   getActiveDownloads(): DownloadProgress[] {
-    return Array.from(this.state.activeDownloads.values());
+    return this.downloads.getAll();
   }
 
-  // Download a model (only offline models can be downloaded)
-  async downloadModel(modelId: string): Promise<void> {
+  // Download a model (only offline models can be downloaded). Waits in the
+  // queue when MAX_CONCURRENT_DOWNLOADS are already running. With `replace`,
+  // a downloaded model is swapped for a fresh copy.
+  async downloadModel(modelId: string, replace = false): Promise<void> {
     const model = AVAILABLE_MODELS.find((m) => m.id === modelId);
     if (!model) {
       throw new Error(`Model not found: ${modelId}`);
@@ -246,146 +257,217 @@ class ModelService extends EventEmitter {
       throw new Error(`Model ${modelId} is not a downloadable offline model`);
     }
 
-    if (await this.isModelDownloaded(modelId)) {
-      throw new Error(`Model already downloaded: ${modelId}`);
+    await this.downloadAndRegister({
+      replace,
+      source: model.files
+        ? { files: model.files }
+        : { url: model.downloadUrl, expectedBytes: model.size },
+      filename: model.filename,
+      checksum: model.checksum
+        ? { algorithm: "sha1", value: model.checksum }
+        : undefined,
+      record: {
+        id: model.id,
+        provider: "local-whisper",
+        name: model.name,
+        type: "speech",
+        size: model.sizeFormatted,
+        description: model.description,
+        checksum: model.checksum,
+        speed: model.speed,
+        accuracy: model.accuracy,
+        context: null,
+        originalModel: null,
+      },
+    });
+  }
+
+  // Download a ggml model file from any Hugging Face repository. It's
+  // stored and selected like a built-in local model.
+  async downloadHuggingFaceModel(
+    repoId: string,
+    filePath: string,
+    replace = false,
+  ): Promise<string> {
+    const file = (await listHuggingFaceModelFiles(repoId)).find(
+      (item) => item.path === filePath,
+    );
+    if (!file) {
+      throw new Error(`No ggml model file ${filePath} in ${repoId}`);
     }
 
-    if (this.state.activeDownloads.has(modelId)) {
-      throw new Error(`Download already in progress: ${modelId}`);
+    const modelId = importedModelId(repoId, filePath);
+    const name = path.basename(filePath);
+    await this.downloadAndRegister({
+      replace,
+      source: {
+        url: huggingFaceFileUrl(repoId, filePath),
+        expectedBytes: file.sizeBytes,
+      },
+      filename: `hf--${repoId.replace("/", "--")}--${name}`,
+      checksum: file.sha256
+        ? { algorithm: "sha256", value: file.sha256 }
+        : undefined,
+      record: {
+        id: modelId,
+        provider: "local-whisper",
+        name: `${name} (${repoId})`,
+        type: "speech",
+        size: `~${Math.round(file.sizeBytes / (1024 * 1024))} MB`,
+        description: `Imported from Hugging Face: ${repoId}`,
+        checksum: file.sha256,
+        speed: null,
+        accuracy: null,
+        context: null,
+        originalModel: null,
+      },
+    });
+    return modelId;
+  }
+
+  /**
+   * Register a ggml model file the user already has. It's copied into the
+   * models directory, or with `copy: false` used where it is (and then
+   * never deleted by the app).
+   */
+  async importLocalModel(
+    filePath: string,
+    options: { name: string; copy: boolean },
+  ): Promise<string> {
+    const modelId = localModelId(options.name);
+    if (await this.isModelDownloaded(modelId)) {
+      throw new Error(`A model named "${options.name}" already exists`);
     }
+    const sizeBytes = await validateLocalModelFile(filePath);
 
-    const abortController = new AbortController();
-    const downloadPath = path.join(this.modelsDirectory, model.filename);
+    let localPath = filePath;
+    if (options.copy) {
+      localPath = path.join(
+        this.modelsDirectory,
+        `${modelId.replace(":", "--")}.bin`,
+      );
+      // Copy under a temporary name so a failed copy isn't picked up
+      await fs.promises.copyFile(filePath, `${localPath}.part`);
+      await fs.promises.rename(`${localPath}.part`, localPath);
+    }
 
-    const progress: DownloadProgress = {
+    await upsertModel({
+      id: modelId,
+      provider: "local-whisper",
+      name: options.name,
+      type: "speech",
+      size: `~${Math.round(sizeBytes / (1024 * 1024))} MB`,
+      description: `Imported from ${path.basename(filePath)}`,
+      checksum: null,
+      speed: null,
+      accuracy: null,
+      context: null,
+      originalModel: null,
+      localPath,
+      sizeBytes,
+      downloadedAt: new Date(),
+    });
+    logger.main.info("Imported local model", {
       modelId,
-      progress: 0,
-      status: "downloading",
-      bytesDownloaded: 0,
-      totalBytes: model.size,
-      abortController,
-    };
+      source: filePath,
+      copied: options.copy,
+    });
+
+    const model = (await this.getDownloadedModels())[modelId];
+    this.emit("download-complete", modelId, model);
+    return modelId;
+  }
+
+  private async downloadAndRegister(download: {
+    replace?: boolean; // Keep the current file until the new one is verified
+    // A single file, or the files of a model stored as a folder
+    source: { url: string; expectedBytes: number } | { files: ModelFile[] };
+    filename: string; // File or folder name in the models directory
+    checksum?: { algorithm: "sha1" | "sha256"; value: string };
+    record: Omit<NewModel, "localPath" | "sizeBytes" | "downloadedAt">;
+  }): Promise<void> {
+    const modelId = download.record.id;
+    if (!download.replace && (await this.isModelDownloaded(modelId))) {
+      throw new Error(`Model already downloaded: ${modelId}`);
+    }
+
+    if (this.downloads.has(modelId)) {
+      throw new Error(`Download already in progress: ${modelId}`);
+    }
 
-    this.state.activeDownloads.set(modelId, progress);
-    this.emit("download-progress", modelId, progress);
+    const downloadPath = path.join(this.modelsDirectory, download.filename);
+    const destination = download.replace ? `${downloadPath}.new` : downloadPath;
+    const { source } = download;
 
     try {
+      const sources = await this.settingsService.getModelDownloadSettings();
       logger.main.info("Starting model download", {
         modelId,
-        size: model.sizeFormatted,
-        url: model.downloadUrl,
+        size: download.record.size,
+        url: "url" in source ? source.url : source.files.map((f) => f.url),
+        mirrors: sources?.mirrors ?? [],
       });
 
-      const response = await fetch(model.downloadUrl, {
-        signal: abortController.signal,
-        headers: {
-          "User-Agent": getUserAgent(),
-        },
-      });
-
-      if (!response.ok) {
-        throw new Error(
-          `Failed to download: ${response.status} ${response.statusText}`,
-        );
-      }
-
-      const totalBytes =
-        parseInt(response.headers.get("content-length") || "0") || model.size;
-      progress.totalBytes = totalBytes;
-
-      const fileStream = fs.createWriteStream(downloadPath);
-      let bytesDownloaded = 0;
-      let lastProgressEmit = 0;
-
-      const reader = response.body?.getReader();
-      if (!reader) {
-        throw new Error("Failed to get response reader");
-      }
-
-      while (true) {
-        const { done, value } = await reader.read();
-
-        if (done) break;
-
-        if (abortController.signal.aborted) {
-          fileStream.close();
-          fs.unlinkSync(downloadPath);
-          throw new Error("Download cancelled");
-        }
-
-        fileStream.write(value);
-        bytesDownloaded += value.length;
-
-        progress.bytesDownloaded = bytesDownloaded;
-        progress.progress = Math.round((bytesDownloaded / totalBytes) * 100);
-
-        // Emit progress every 1% or 1MB to avoid too many events
-        const progressPercent = progress.progress;
-        if (
-          progressPercent - lastProgressEmit >= 1 ||
-          bytesDownloaded - (lastProgressEmit * totalBytes) / 100 >= 1024 * 1024
-        ) {
-          this.emit("download-progress", modelId, { ...progress });
-          lastProgressEmit = progressPercent;
-        }
-      }
-
-      fileStream.end();
-
-      // Get actual file size (no validation against expected size)
-      const stats = fs.statSync(downloadPath);
+      // Mirrors only serve files whose checksum is checked below
+      const size =
+        "url" in source
+          ? await this.downloads.download(
+              modelId,
+              download.checksum
+                ? modelSourceUrls(source.url, sources)
+                : [source.url],
+              destination,
+              source.expectedBytes,
+            )
+          : await this.downloadFiles(modelId, destination, source.files);
       logger.main.info("Download completed", {
         modelId,
-        expectedSize: totalBytes,
-        actualSize: stats.size,
-        sizeDifference: Math.abs(stats.size - totalBytes),
+        expectedSize:
+          "url" in source
+            ? source.expectedBytes
+            : source.files.reduce((sum, f) => sum + f.size, 0),
+        actualSize: size,
       });
 
       // Verify checksum if provided
-      if (model.checksum) {
-        const fileChecksum = await this.calculateFileChecksum(downloadPath);
-        if (fileChecksum !== model.checksum) {
-          fs.unlinkSync(downloadPath);
+      if (download.checksum) {
+        const { algorithm, value } = download.checksum;
+        const fileChecksum = await this.calculateFileChecksum(
+          destination,
+          algorithm,
+        );
+        if (fileChecksum !== value) {
+          fs.unlinkSync(destination);
           throw new Error(
-            `Checksum mismatch. Expected: ${model.checksum}, Got: ${fileChecksum}`,
+            `Checksum mismatch. Expected: ${value}, Got: ${fileChecksum}`,
           );
         }
       }
+      if (destination !== downloadPath) {
+        await this.swapIntoPlace(destination, downloadPath);
+      }
 
       // Create/update model record in database with download info
       await upsertModel({
-        id: model.id,
-        provider: "local-whisper",
-        name: model.name,
-        type: "speech",
-        size: model.sizeFormatted,
-        description: model.description,
-        checksum: model.checksum,
-        speed: model.speed,
-        accuracy: model.accuracy,
+        ...download.record,
         localPath: downloadPath,
-        sizeBytes: stats.size,
+        sizeBytes: size,
         downloadedAt: new Date(),
-        context: null,
-        originalModel: null,
       });
 
       // Get the updated model from database
       const downloadedModel = await getModelsByProvider("local-whisper").then(
-        (models) => models.find((m) => m.id === model.id),
+        (models) => models.find((m) => m.id === modelId),
       );
 
       if (!downloadedModel) {
         throw new Error("Failed to retrieve downloaded model from database");
       }
 
-      // Clean up active download
-      this.state.activeDownloads.delete(modelId);
-
       logger.main.info("Model download completed", {
         modelId,
         path: downloadPath,
-        size: stats.size,
+        size,
       });
 
       // Auto-select if this is the first model
@@ -405,49 +487,102 @@ class ModelService extends EventEmitter {
 
       this.emit("download-complete", modelId, downloadedModel);
     } catch (error) {
-      // Clean up on error
-      this.state.activeDownloads.delete(modelId);
-
-      if (fs.existsSync(downloadPath)) {
-        fs.unlinkSync(downloadPath);
-      }
-
-      const err = error instanceof Error ? error : new Error(String(error));
-
-      if (abortController.signal.aborted) {
+      if (error instanceof DownloadCancelledError) {
         logger.main.info("Model download cancelled", { modelId });
         this.emit("download-cancelled", modelId);
         return; // Don't throw - it's an intentional cancellation
-      } else {
-        logger.main.error("Model download failed", {
-          modelId,
-          error: err.message,
-        });
-        this.emit("download-error", modelId, err);
-        throw err; // Only throw for actual errors
       }
+
+      const err = error instanceof Error ? error : new Error(String(error));
+      logger.main.error("Model download failed", {
+        modelId,
+        error: err.message,
+      });
+      this.emit("download-error", modelId, err);
+      throw err; // Only throw for actual errors
     }
   }
 
-  // Cancel a model download
-  cancelDownload(modelId: string): void {
-    const download = this.state.activeDownloads.get(modelId);
-    if (!download) {
-      throw new Error(`No active download found for model: ${modelId}`);
+  // Download the files of a folder model as one task under the model's id,
+  // so it's queued, cancelled and reported on as a whole. The folder is
+  // removed again if any of them fails. Returns the total size. The files
+  // have no checksums, so they always come from their original host.
+  private async downloadFiles(
+    modelId: string,
+    directory: string,
+    files: ModelFile[],
+  ): Promise<number> {
+    const downloads = files.map((file) => ({
+      urls: [file.url],
+      destination: path.join(directory, file.path),
+      expectedBytes: file.size,
+    }));
+    try {
+      for (const { destination } of downloads) {
+        await fs.promises.mkdir(path.dirname(destination), { recursive: true });
+      }
+      return await this.downloads.downloadFiles(modelId, downloads);
+    } catch (error) {
+      await fs.promises.rm(directory, { recursive: true, force: true });
+      throw error;
     }
+  }
 
-    download.status = "cancelling";
-    download.abortController?.abort();
-
-    // Immediately remove from active downloads to prevent restart issues
-    this.state.activeDownloads.delete(modelId);
+  // Put a verified replacement in place of the current file or folder. The
+  // old copy is moved aside first, since a folder can't be renamed over,
+  // and restored if the swap fails.
+  private async swapIntoPlace(
+    replacement: string,
+    target: string,
+  ): Promise<void> {
+    const aside = `${target}.old`;
+    await fs.promises.rm(aside, { recursive: true, force: true });
+    const hadTarget = fs.existsSync(target);
+    if (hadTarget) {
+      await fs.promises.rename(target, aside);
+    }
+    try {
+      await fs.promises.rename(replacement, target);
+    } catch (error) {
+      if (hadTarget) {
+        await fs.promises.rename(aside, target);
+      }
+      throw error;
+    }
+    await fs.promises.rm(aside, { recursive: true, force: true });
+  }
 
+  // Cancel a model download: aborts the request and deletes the partial
+  // file, or drops the model from the queue
+  async cancelDownload(modelId: string): Promise<void> {
+    await this.downloads.cancel(modelId);
     logger.main.info("Cancelled model download", { modelId });
-    this.emit("download-cancelled", modelId);
   }
 
-  // Delete a downloaded model
-  async deleteModel(modelId: string): Promise<void> {
+  // The selected speech model and modes that depend on a model, or null
+  // when nothing does
+  async getModelInUseWarning(
+    modelId: string,
+  ): Promise<ModelInUseWarning | null> {
+    const isDefault =
+      (await this.settingsService.getDefaultSpeechModel()) === modelId;
+    const { items } = await this.settingsService.getModes();
+    const modes = items
+      .filter((mode) => mode.speechModelId === modelId)
+      .map((mode) => ({ id: mode.id, name: mode.name }));
+
+    if (!isDefault && modes.length === 0) {
+      return null;
+    }
+    return { modelId, isDefault, modes };
+  }
+
+  // Delete a downloaded model. A model that's in use is only deleted with
+  // `force`; otherwise the result says what uses it.
+  async deleteModel(
+    modelId: string,
+    options: { force?: boolean } = {},
+  ): Promise<DeleteModelResult> {
     const models = await getModelsByProvider("local-whisper");
     const downloadedModel = models.find((m) => m.id === modelId);
 
@@ -456,12 +591,35 @@ class ModelService extends EventEmitter {
     }
 
     // Check if this is the selected model BEFORE deletion
-    const currentSelection = await this.settingsService.getDefaultSpeechModel();
-    const wasSelected = currentSelection === modelId;
+    const warning = await this.getModelInUseWarning(modelId);
+    if (warning && !options.force) {
+      return { deleted: false, warning };
+    }
+    const wasSelected = warning?.isDefault ?? false;
 
-    // Delete file
-    if (downloadedModel.localPath && fs.existsSync(downloadedModel.localPath)) {
-      fs.unlinkSync(downloadedModel.localPath);
+    // Modes that used this model fall back to the selected speech model
+    const repointedModes = warning?.modes ?? [];
+    for (const mode of repointedModes) {
+      await this.settingsService.updateMode(mode.id, {
+        speechModelId: undefined,
+      });
+    }
+    if (repointedModes.length > 0) {
+      logger.main.info("Cleared deleted model from modes", {
+        modelId,
+        modes: repointedModes.map((mode) => mode.id),
+      });
+      this.emit("modes-repointed", modelId, repointedModes);
+    }
+
+    // Delete the file, unless it's a user's file imported in place
+    if (
+      this.ownsModelFile(downloadedModel.localPath) &&
+      downloadedModel.localPath &&
+      fs.existsSync(downloadedModel.localPath)
+    ) {
+      // Folder models are directories
+      fs.rmSync(downloadedModel.localPath, { recursive: true, force: true });
       logger.main.info("Deleted model file", {
         modelId,
         path: downloadedModel.localPath,
@@ -514,12 +672,104 @@ class ModelService extends EventEmitter {
 
     // Validate speech selection after deletion
     await this.validateAndClearInvalidSpeechSelection();
+    return { deleted: true, repointedModes };
+  }
+
+  // Files inside the models directory are the app's; anything else was
+  // imported in place and belongs to the user
+  private ownsModelFile(localPath: string | null): boolean {
+    const relative = localPath
+      ? path.relative(this.modelsDirectory, localPath)
+      : "";
+    return !!relative && !relative.startsWith("..");
+  }
+
+  /**
+   * Downloaded models that haven't transcribed anything in `days` days
+   * (the cleanup setting by default). A model that was never used counts
+   * from its download. The selected model and models used by a mode are
+   * never listed.
+   */
+  async getUnusedModels(days?: number): Promise<UnusedModel[]> {
+    const unusedDays =
+      days ??
+      (await this.settingsService.getModelCleanupSettings())?.unusedDays ??
+      DEFAULT_UNUSED_MODEL_DAYS;
+    const cutoff = Date.now() - unusedDays * DAY_MS;
+    const lastUsed = new Map(
+      (await getModelUsageStats("speech")).map((row) => [
+        row.modelId,
+        row.lastUsedAt,
+      ]),
+    );
+
+    const unused: UnusedModel[] = [];
+    for (const model of Object.values(await this.getDownloadedModels())) {
+      const lastUsedAt = lastUsed.get(model.id) ?? null;
+      const since = lastUsedAt ?? model.downloadedAt;
+      if (!since || since.getTime() > cutoff || this.downloads.has(model.id)) {
+        continue;
+      }
+      if (await this.getModelInUseWarning(model.id)) {
+        continue;
+      }
+      unused.push({
+        modelId: model.id,
+        name: model.name,
+        lastUsedAt,
+        reclaimableBytes: this.ownsModelFile(model.localPath)
+          ? (model.sizeBytes ?? 0)
+          : 0,
+      });
+    }
+    return unused;
+  }
+
+  /**
+   * Delete unused models, or only the listed ones among them. Models that
+   * were used again since they were offered are kept.
+   */
+  async cleanupUnusedModels(options: {
+    days?: number;
+    modelIds?: string[];
+  }): Promise<{ deleted: UnusedModel[]; reclaimedBytes: number }> {
+    const candidates = (await this.getUnusedModels(options.days)).filter(
+      (model) => !options.modelIds || options.modelIds.includes(model.modelId),
+    );
+
+    const deleted: UnusedModel[] = [];
+    for (const model of candidates) {
+      try {
+        const result = await this.deleteModel(model.modelId);
+        if (result.deleted) {
+          deleted.push(model);
+        }
+      } catch (error) {
+        logger.main.warn("Failed to delete unused model", {
+          modelId: model.modelId,
+          error: error instanceof Error ? error.message : String(error),
+        });
+      }
+    }
+
+    const reclaimedBytes = deleted.reduce(
+      (sum, model) => sum + model.reclaimableBytes,
+      0,
+    );
+    logger.main.info("Cleaned up unused models", {
+      deleted: deleted.map((model) => model.modelId),
+      reclaimedBytes,
+    });
+    return { deleted, reclaimedBytes };
   }
 
   // Calculate file checksum (SHA-1)
-  private async calculateFileChecksum(filePath: string): Promise<string> {
+  private async calculateFileChecksum(
+    filePath: string,
+    algorithm: "sha1" | "sha256" = "sha1",
+  ): Promise<string> {
     return new Promise((resolve, reject) => {
-      const hash = crypto.createHash("sha1");
+      const hash = crypto.createHash(algorithm);
       const stream = fs.createReadStream(filePath);
 
       stream.on("data", (data) => hash.update(data));
@@ -602,13 +852,59 @@ class ModelService extends EventEmitter {
     await this.applySpeechModelSelection(modelId, "manual", oldModelId);
   }
 
+  // Pick the downloaded local model that best fits a performance preset.
+  // Returns null when no local model is downloaded.
+  async getSpeechModelForPreset(
+    preset: PerformancePreset,
+  ): Promise<string | null> {
+    const downloadedModels = await this.getValidDownloadedModels();
+    const rank = PERFORMANCE_PROFILES[preset].rankSpeechModel;
+    const candidates = AVAILABLE_MODELS.filter(
+      (model) => model.setup === "offline" && downloadedModels[model.id],
+    ).sort((a, b) => rank(b) - rank(a));
+    return candidates[0]?.id ?? null;
+  }
+
+  // Select the best downloaded local model, if any. Returns the chosen ID.
+  private async autoSelectDownloadedModel(): Promise<string | null> {
+    const downloadedModels = await this.getValidDownloadedModels();
+    const preferredOrder = [
+      "whisper-large-v3-turbo",
+      "whisper-large-v3",
+      "whisper-medium",
+      "whisper-small",
+      "whisper-base",
+      "whisper-tiny",
+    ];
+
+    const candidateId = preferredOrder.find((id) => downloadedModels[id]);
+    if (!candidateId) {
+      return null;
+    }
+    await this.applySpeechModelSelection(
+      candidateId,
+      "auto-first-download",
+      null,
+    );
+    logger.main.info("Auto-selected speech model on initialization", {
+      modelId: candidateId,
+      availableModels: Object.keys(downloadedModels),
+    });
+    return candidateId;
+  }
+
   // Get best available model path for transcription (used by WhisperProvider)
   async getBestAvailableModelPath(): Promise<string | null> {
     const downloadedModels = await this.getValidDownloadedModels();
     const selectedModelId = await this.getSelectedModel();
 
-    // If a specific model is selected and available, use it
-    if (selectedModelId && downloadedModels[selectedModelId]) {
+    // If a specific model is selected and available, use it; models for
+    // other runtimes can't be loaded by whisper.cpp
+    if (
+      selectedModelId &&
+      downloadedModels[selectedModelId] &&
+      getLocalRuntime(selectedModelId) === "whisper"
+    ) {
       return downloadedModels[selectedModelId].localPath;
     }
 
@@ -633,61 +929,177 @@ class ModelService extends EventEmitter {
   }
 
   // Cleanup - cancel all active downloads
-  cleanup(): void {
+  async cleanup(): Promise<void> {
+    if (this.updateCheckTimer) {
+      clearTimeout(this.updateCheckTimer);
+      this.updateCheckTimer = null;
+    }
     logger.main.info("Cleaning up model downloads", {
-      activeDownloads: this.state.activeDownloads.size,
+      activeDownloads: this.downloads.getAll().length,
     });
+    await this.downloads.cancelAll();
+  }
+
+  // ============================================
+  // Model Updates
+  // ============================================
+
+  // Also runs the unused model check, on the same daily schedule
+  private scheduleUpdateCheck(delayMs: number): void {
+    this.updateCheckTimer = setTimeout(() => {
+      Promise.all([
+        this.checkForUpdates().catch((error) => {
+          logger.main.warn("Model update check failed", { error });
+        }),
+        this.checkUnusedModels().catch((error) => {
+          logger.main.warn("Unused model check failed", { error });
+        }),
+      ]).finally(() => {
+        if (this.updateCheckTimer) {
+          this.scheduleUpdateCheck(UPDATE_CHECK_INTERVAL_MS);
+        }
+      });
+    }, delayMs);
+    // Don't keep the process alive just for update checks
+    this.updateCheckTimer.unref();
+  }
+
+  private async checkUnusedModels(): Promise<void> {
+    const settings = await this.settingsService.getModelCleanupSettings();
+    if (!settings?.enabled) {
+      return;
+    }
+    const unused = await this.getUnusedModels();
+    if (unused.length > 0) {
+      this.emit("unused-models", unused);
+    }
+  }
+
+  getAvailableUpdates(): ModelUpdate[] {
+    return Array.from(this.availableUpdates.values());
+  }
+
+  /**
+   * Compare downloaded models with their source: built-in models with the
+   * checksum in this release's model list, imported models with the file's
+   * current SHA-256 on the Hugging Face Hub. Emits "update-available" when
+   * an update wasn't known before.
+   */
+  async checkForUpdates(): Promise<ModelUpdate[]> {
+    const downloaded = Object.values(await this.getDownloadedModels());
+    const hubFiles = new Map<string, HuggingFaceModelFile[]>();
+    const updates: ModelUpdate[] = [];
+
+    for (const model of downloaded) {
+      if (!model.checksum || this.downloads.has(model.id)) continue;
+
+      const imported = parseImportedModelId(model.id);
+      if (!imported) {
+        const available = AVAILABLE_MODELS.find((m) => m.id === model.id);
+        if (
+          available?.setup === "offline" &&
+          available.checksum &&
+          available.checksum !== model.checksum
+        ) {
+          updates.push({
+            modelId: model.id,
+            name: model.name,
+            source: "release",
+            sizeBytes: available.size,
+          });
+        }
+        continue;
+      }
 
-    for (const [modelId] of this.state.activeDownloads) {
       try {
-        this.cancelDownload(modelId);
+        const { repoId, filePath } = imported;
+        if (!hubFiles.has(repoId)) {
+          hubFiles.set(repoId, await listHuggingFaceModelFiles(repoId));
+        }
+        const file = hubFiles.get(repoId)!.find((f) => f.path === filePath);
+        if (file?.sha256 && file.sha256 !== model.checksum) {
+          updates.push({
+            modelId: model.id,
+            name: model.name,
+            source: "hub",
+            sizeBytes: file.sizeBytes,
+          });
+        }
       } catch (error) {
-        logger.main.warn("Error cancelling download during cleanup", {
-          modelId,
+        logger.main.warn("Failed to check imported model for updates", {
+          modelId: model.id,
           error: error instanceof Error ? error.message : String(error),
         });
       }
     }
+
+    const isNew = updates.some((u) => !this.availableUpdates.has(u.modelId));
+    this.availableUpdates = new Map(updates.map((u) => [u.modelId, u]));
+    if (isNew) {
+      logger.main.info("Model updates available", {
+        modelIds: updates.map((u) => u.modelId),
+      });
+      this.emit("update-available", updates);
+    }
+    return updates;
+  }
+
+  /**
+   * Download the new version of a model over the current one. The old file
+   * stays in use until the new one has been downloaded and verified.
+   */
+  async updateModel(modelId: string): Promise<void> {
+    if (!this.availableUpdates.has(modelId)) {
+      throw new Error(`No update available for model: ${modelId}`);
+    }
+
+    const previousChecksum = (await this.getDownloadedModels())[modelId]
+      ?.checksum;
+    const imported = parseImportedModelId(modelId);
+    if (imported) {
+      await this.downloadHuggingFaceModel(
+        imported.repoId,
+        imported.filePath,
+        true,
+      );
+    } else {
+      await this.downloadModel(modelId, true);
+    }
+
+    // Unchanged when the download was cancelled
+    const model = (await this.getDownloadedModels())[modelId];
+    if (model && model.checksum !== previousChecksum) {
+      this.availableUpdates.delete(modelId);
+    }
   }
 
   // ============================================
   // Provider Model Methods (OpenRouter, Ollama)
   // ============================================
 
+  // The key passed by the caller, or the one saved in settings
+  private async resolveApiKey(
+    provider: "openRouter" | "openAI" | "anthropic" | "google",
+    apiKey?: string,
+  ): Promise<string> {
+    if (apiKey) return apiKey;
+    const config = await this.settingsService.getModelProvidersConfig();
+    const stored = config?.[provider]?.apiKey;
+    if (!stored) {
+      throw new Error(`No API key saved for ${provider}`);
+    }
+    return stored;
+  }
+
   /**
    * Validate OpenRouter connection by testing API key
    */
   async validateOpenRouterConnection(
     apiKey: string,
   ): Promise<ValidationResult> {
-    try {
-      const response = await fetch("https://openrouter.ai/api/v1/key", {
-        method: "GET",
-        headers: {
-          Authorization: `Bearer ${apiKey}`,
-          "Content-Type": "application/json",
-          "User-Agent": getUserAgent(),
-        },
-      });
-
-      if (!response.ok) {
-        const errorData = await response.json().catch(() => ({}));
-        const errorMessage =
-          (errorData as any)?.error?.message ||
-          `HTTP ${response.status}: ${response.statusText}`;
-        return {
-          success: false,
-          error: errorMessage,
-        };
-      }
-
-      return { success: true };
-    } catch (error) {
-      return {
-        success: false,
-        error: error instanceof Error ? error.message : "Connection failed",
-      };
-    }
+    return this.validateApiKey("https://openrouter.ai/api/v1/key", {
+      Authorization: `Bearer ${apiKey}`,
+    });
   }
 
   /**
@@ -695,24 +1107,7 @@ class ModelService extends EventEmitter {
    */
   async validateOllamaConnection(url: string): Promise<ValidationResult> {
     try {
-      const cleanUrl = url.replace(/\/$/, "");
-      const versionUrl = `${cleanUrl}/api/version`;
-
-      const response = await fetch(versionUrl, {
-        method: "GET",
-        headers: {
-          "Content-Type": "application/json",
-          "User-Agent": getUserAgent(),
-        },
-      });
-
-      if (!response.ok) {
-        return {
-          success: false,
-          error: `HTTP ${response.status}: ${response.statusText}`,
-        };
-      }
-
+      await new OllamaClient(url, { timeoutMs: 5000 }).version();
       return { success: true };
     } catch (error) {
       return {
@@ -728,7 +1123,8 @@ class ModelService extends EventEmitter {
   /**
    * Fetch available models from OpenRouter
    */
-  async fetchOpenRouterModels(apiKey: string): Promise<FetchedModel[]> {
+  async fetchOpenRouterModels(apiKey?: string): Promise<FetchedModel[]> {
+    apiKey = await this.resolveApiKey("openRouter", apiKey);
     try {
       const response = await fetch("https://openrouter.ai/api/v1/models", {
         method: "GET",
@@ -759,9 +1155,7 @@ class ModelService extends EventEmitter {
         }
 
         // Convert context length to readable format
-        const contextLength = model.context_length
-          ? `${Math.floor(model.context_length / 1000)}k`
-          : "Unknown";
+        const contextLength = formatContextWindow(model.context_length);
 
         return {
           id: model.id,
@@ -787,25 +1181,12 @@ class ModelService extends EventEmitter {
    */
   async fetchOllamaModels(url: string): Promise<FetchedModel[]> {
     try {
-      const cleanUrl = url.replace(/\/$/, "");
-      const modelsUrl = `${cleanUrl}/api/tags`;
-
-      const response = await fetch(modelsUrl, {
-        method: "GET",
-        headers: {
-          "Content-Type": "application/json",
-          "User-Agent": getUserAgent(),
-        },
-      });
-
-      if (!response.ok) {
-        throw new Error(`HTTP ${response.status}: ${response.statusText}`);
-      }
-
-      const data: OllamaResponse = await response.json();
+      const models = await new OllamaClient(url, {
+        timeoutMs: 10000,
+      }).listModels();
 
       // Transform Ollama models to unified format
-      return data.models.map((model: OllamaModel): FetchedModel => {
+      return models.map((model: OllamaModel): FetchedModel => {
         // Extract model size from details or calculate from size
         let size = "Unknown";
         if (model.details?.parameter_size) {
@@ -853,30 +1234,9 @@ class ModelService extends EventEmitter {
    * Validate OpenAI connection by testing API key
    */
   async validateOpenAIConnection(apiKey: string): Promise<ValidationResult> {
-    try {
-      const response = await fetch("https://api.openai.com/v1/models", {
-        method: "GET",
-        headers: {
-          Authorization: `Bearer ${apiKey}`,
-          "User-Agent": getUserAgent(),
-        },
-      });
-
-      if (!response.ok) {
-        const errorData = await response.json().catch(() => ({}));
-        const errorMessage =
-          (errorData as any)?.error?.message ||
-          `HTTP ${response.status}: ${response.statusText}`;
-        return { success: false, error: errorMessage };
-      }
-
-      return { success: true };
-    } catch (error) {
-      return {
-        success: false,
-        error: error instanceof Error ? error.message : "Connection failed",
-      };
-    }
+    return this.validateApiKey("https://api.openai.com/v1/models", {
+      Authorization: `Bearer ${apiKey}`,
+    });
   }
 
   /**
@@ -885,63 +1245,20 @@ class ModelService extends EventEmitter {
   async validateAnthropicConnection(
     apiKey: string,
   ): Promise<ValidationResult> {
-    try {
-      const response = await fetch("https://api.anthropic.com/v1/models", {
-        method: "GET",
-        headers: {
-          "x-api-key": apiKey,
-          "anthropic-version": "2023-06-01",
-          "User-Agent": getUserAgent(),
-        },
-      });
-
-      if (!response.ok) {
-        const errorData = await response.json().catch(() => ({}));
-        const errorMessage =
-          (errorData as any)?.error?.message ||
-          `HTTP ${response.status}: ${response.statusText}`;
-        return { success: false, error: errorMessage };
-      }
-
-      return { success: true };
-    } catch (error) {
-      return {
-        success: false,
-        error: error instanceof Error ? error.message : "Connection failed",
-      };
-    }
+    return this.validateApiKey("https://api.anthropic.com/v1/models?limit=1", {
+      "x-api-key": apiKey,
+      "anthropic-version": "2023-06-01",
+    });
   }
 
   /**
    * Validate Google Generative AI connection by testing API key
    */
   async validateGoogleConnection(apiKey: string): Promise<ValidationResult> {
-    try {
-      const response = await fetch(
-        `https://generativelanguage.googleapis.com/v1beta/models?key=${apiKey}`,
-        {
-          method: "GET",
-          headers: {
-            "User-Agent": getUserAgent(),
-          },
-        },
-      );
-
-      if (!response.ok) {
-        const errorData = await response.json().catch(() => ({}));
-        const errorMessage =
-          (errorData as any)?.error?.message ||
-          `HTTP ${response.status}: ${response.statusText}`;
-        return { success: false, error: errorMessage };
-      }
-
-      return { success: true };
-    } catch (error) {
-      return {
-        success: false,
-        error: error instanceof Error ? error.message : "Connection failed",
-      };
-    }
+    return this.validateApiKey(
+      "https://generativelanguage.googleapis.com/v1beta/models?pageSize=1",
+      { "x-goog-api-key": apiKey },
+    );
   }
 
   // --- Transcription Provider Validation ---
@@ -949,68 +1266,92 @@ class ModelService extends EventEmitter {
   async validateTranscriptionGroqConnection(
     apiKey: string,
   ): Promise<ValidationResult> {
-    try {
-      const response = await fetch(
-        "https://api.groq.com/openai/v1/models",
-        {
-          method: "GET",
-          headers: {
-            Authorization: `Bearer ${apiKey}`,
-            "User-Agent": getUserAgent(),
-          },
-        },
-      );
-
-      if (!response.ok) {
-        const errorData = await response.json().catch(() => ({}));
-        const errorMessage =
-          (errorData as any)?.error?.message ||
-          `HTTP ${response.status}: ${response.statusText}`;
-        return { success: false, error: errorMessage };
-      }
-
-      return { success: true };
-    } catch (error) {
-      return {
-        success: false,
-        error: error instanceof Error ? error.message : "Connection failed",
-      };
-    }
+    return this.validateApiKey("https://api.groq.com/openai/v1/models", {
+      Authorization: `Bearer ${apiKey}`,
+    });
   }
 
   async validateTranscriptionGrokConnection(
     apiKey: string,
   ): Promise<ValidationResult> {
+    return this.validateApiKey("https://api.x.ai/v1/models", {
+      Authorization: `Bearer ${apiKey}`,
+    });
+  }
+
+  /**
+   * Make a cheap authenticated GET and sort failures into a bad key, an
+   * exhausted quota, or a connection problem, so the user knows what to fix
+   */
+  private async validateApiKey(
+    url: string,
+    headers: Record<string, string>,
+  ): Promise<ValidationResult> {
+    let response: Response;
     try {
-      const response = await fetch("https://api.x.ai/v1/models", {
+      response = await fetch(url, {
         method: "GET",
-        headers: {
-          Authorization: `Bearer ${apiKey}`,
-          "User-Agent": getUserAgent(),
-        },
+        headers: { ...headers, "User-Agent": getUserAgent() },
+        signal: AbortSignal.timeout(VALIDATION_TIMEOUT_MS),
       });
-
-      if (!response.ok) {
-        const errorData = await response.json().catch(() => ({}));
-        const errorMessage =
-          (errorData as any)?.error?.message ||
-          `HTTP ${response.status}: ${response.statusText}`;
-        return { success: false, error: errorMessage };
+    } catch (error) {
+      if (error instanceof Error && error.name === "TimeoutError") {
+        return {
+          success: false,
+          errorType: "timeout",
+          error: `The provider didn't respond within ${VALIDATION_TIMEOUT_MS / 1000} seconds`,
+        };
       }
+      return {
+        success: false,
+        errorType: "network",
+        error: `Couldn't reach the provider. Check your internet connection.${
+          error instanceof Error ? ` (${error.message})` : ""
+        }`,
+      };
+    }
 
+    if (response.ok) {
       return { success: true };
-    } catch (error) {
+    }
+
+    const errorData: any = await response.json().catch(() => ({}));
+    const providerMessage: string | undefined = errorData?.error?.message;
+    const detail = providerMessage ?? `HTTP ${response.status}`;
+
+    // Google answers a bad key with 400 API_KEY_INVALID rather than 401
+    const invalidKey =
+      response.status === 401 ||
+      response.status === 403 ||
+      (response.status === 400 && /api.?key/i.test(providerMessage ?? ""));
+    if (invalidKey) {
+      return {
+        success: false,
+        errorType: "invalid_key",
+        error: `Invalid API key: ${detail}`,
+      };
+    }
+    // 402 is OpenRouter's "out of credits"
+    if (response.status === 402 || response.status === 429) {
       return {
         success: false,
-        error: error instanceof Error ? error.message : "Connection failed",
+        errorType: "quota_exceeded",
+        error: `Quota or rate limit exceeded: ${detail}`,
       };
     }
+    return {
+      success: false,
+      errorType: "unknown",
+      error:
+        providerMessage ?? `HTTP ${response.status}: ${response.statusText}`,
+    };
   }
 
   /**
    * Fetch available models from OpenAI
    */
-  async fetchOpenAIModels(apiKey: string): Promise<FetchedModel[]> {
+  async fetchOpenAIModels(apiKey?: string): Promise<FetchedModel[]> {
+    apiKey = await this.resolveApiKey("openAI", apiKey);
     try {
       const response = await fetch("https://api.openai.com/v1/models", {
         method: "GET",
@@ -1032,16 +1373,20 @@ class ModelService extends EventEmitter {
         /^o[0-9]/, // o1, o3, etc.
         /^chatgpt-/,
       ];
+      // Same prefixes, but not usable for text formatting
+      const nonChatPattern = /audio|realtime|tts|transcribe|search|image/;
 
       return data.data
-        .filter((model: OpenAIModel) =>
-          chatModelPatterns.some((pattern) => pattern.test(model.id)),
+        .filter(
+          (model: OpenAIModel) =>
+            chatModelPatterns.some((pattern) => pattern.test(model.id)) &&
+            !nonChatPattern.test(model.id),
         )
         .map((model: OpenAIModel): FetchedModel => ({
           id: model.id,
           name: model.id,
           provider: "OpenAI",
-          context: "Unknown",
+          context: formatContextWindow(getKnownContextWindow(model.id)),
           originalModel: model,
         }));
     } catch (error) {
@@ -1056,33 +1401,42 @@ class ModelService extends EventEmitter {
   /**
    * Fetch available models from Anthropic
    */
-  async fetchAnthropicModels(apiKey: string): Promise<FetchedModel[]> {
+  async fetchAnthropicModels(apiKey?: string): Promise<FetchedModel[]> {
+    apiKey = await this.resolveApiKey("anthropic", apiKey);
     try {
-      const response = await fetch(
-        "https://api.anthropic.com/v1/models?limit=100",
-        {
-          method: "GET",
-          headers: {
-            "x-api-key": apiKey,
-            "anthropic-version": "2023-06-01",
-            "User-Agent": getUserAgent(),
+      const models: AnthropicModel[] = [];
+      let afterId: string | undefined;
+      do {
+        const params = new URLSearchParams({ limit: "100" });
+        if (afterId) params.set("after_id", afterId);
+        const response = await fetch(
+          `https://api.anthropic.com/v1/models?${params}`,
+          {
+            method: "GET",
+            headers: {
+              "x-api-key": apiKey,
+              "anthropic-version": "2023-06-01",
+              "User-Agent": getUserAgent(),
+            },
           },
-        },
-      );
+        );
 
-      if (!response.ok) {
-        throw new Error(`HTTP ${response.status}: ${response.statusText}`);
-      }
+        if (!response.ok) {
+          throw new Error(`HTTP ${response.status}: ${response.statusText}`);
+        }
 
-      const data: AnthropicModelsResponse = await response.json();
+        const data: AnthropicModelsResponse = await response.json();
+        models.push(...data.data);
+        afterId = data.has_more ? data.last_id : undefined;
+      } while (afterId);
 
-      return data.data
+      return models
         .filter((model: AnthropicModel) => model.type === "model")
         .map((model: AnthropicModel): FetchedModel => ({
           id: model.id,
           name: model.display_name,
           provider: "Anthropic",
-          context: "200k",
+          context: formatContextWindow(getKnownContextWindow(model.id)),
           originalModel: model,
         }));
     } catch (error) {
@@ -1097,33 +1451,40 @@ class ModelService extends EventEmitter {
   /**
    * Fetch available models from Google Generative AI
    */
-  async fetchGoogleModels(apiKey: string): Promise<FetchedModel[]> {
+  async fetchGoogleModels(apiKey?: string): Promise<FetchedModel[]> {
+    apiKey = await this.resolveApiKey("google", apiKey);
     try {
-      const response = await fetch(
-        `https://generativelanguage.googleapis.com/v1beta/models?key=${apiKey}`,
-        {
-          method: "GET",
-          headers: {
-            "User-Agent": getUserAgent(),
+      const models: GoogleModel[] = [];
+      let pageToken: string | undefined;
+      do {
+        const params = new URLSearchParams({ key: apiKey, pageSize: "1000" });
+        if (pageToken) params.set("pageToken", pageToken);
+        const response = await fetch(
+          `https://generativelanguage.googleapis.com/v1beta/models?${params}`,
+          {
+            method: "GET",
+            headers: {
+              "User-Agent": getUserAgent(),
+            },
           },
-        },
-      );
+        );
 
-      if (!response.ok) {
-        throw new Error(`HTTP ${response.status}: ${response.statusText}`);
-      }
+        if (!response.ok) {
+          throw new Error(`HTTP ${response.status}: ${response.statusText}`);
+        }
 
-      const data: GoogleModelsResponse = await response.json();
+        const data: GoogleModelsResponse = await response.json();
+        models.push(...(data.models ?? []));
+        pageToken = data.nextPageToken;
+      } while (pageToken);
 
       // Filter to models that support text generation
-      return data.models
+      return models
         .filter((model: GoogleModel) =>
           model.supportedGenerationMethods?.includes("generateContent"),
         )
         .map((model: GoogleModel): FetchedModel => {
-          const contextLength = model.inputTokenLimit
-            ? `${Math.floor(model.inputTokenLimit / 1000)}k`
-            : "Unknown";
+          const contextLength = formatContextWindow(model.inputTokenLimit);
 
           // Strip "models/" prefix from name for the ID
           const modelId = model.name.replace(/^models\//, "");
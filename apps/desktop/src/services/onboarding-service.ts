@@ -4,6 +4,7 @@ import { logger } from "../main/logger";
 import type { SettingsService } from "./settings-service";
 import type { TelemetryService } from "./telemetry-service";
 import type { AppSettingsData } from "../db/schema";
+import { getSystemInfo, type SystemInfo } from "../utils/system-info";
 import {
   OnboardingScreen,
   type OnboardingState,
@@ -394,6 +395,14 @@ export class OnboardingService extends EventEmitter {
     return hasRtx50 || hasM3ProMax || hasM4Plus;
   }
 
+  /**
+   * Dedicated VRAM of a discrete NVIDIA or AMD GPU, for cards the model
+   * lists above don't know about
+   */
+  private discreteGpuVramMb(systemInfo: SystemInfo): number {
+    return systemInfo.gpu_discrete ? systemInfo.gpu_vram_mb : 0;
+  }
+
   /**
    * Check for NVIDIA RTX 20 series
    */
@@ -416,14 +425,15 @@ export class OnboardingService extends EventEmitter {
   /**
    * Get recommended local model ID based on hardware
    * - High-end (RTX 50, M3 Pro/Max, M4+) → whisper-large-v3-turbo
-   * - Mid-tier (RTX 30/40, M2/M3 base) → whisper-medium
-   * - Entry (RTX 20, M1) → whisper-small
+   * - Mid-tier (RTX 30/40, M2/M3 base, other 8 GB+ GPUs) → whisper-medium
+   * - Entry (RTX 20, M1, other 4 GB+ GPUs) → whisper-small
    * - Default → whisper-base
    */
-  getRecommendedLocalModelId(): string {
-    const systemInfo = this.telemetryService.getSystemInfo();
-    const gpuModel = systemInfo?.gpu_model || "";
-    const cpuModel = systemInfo?.cpu_model || "";
+  async getRecommendedLocalModelId(): Promise<string> {
+    const systemInfo = await getSystemInfo();
+    const gpuModel = systemInfo.gpu_model;
+    const cpuModel = systemInfo.cpu_model;
+    const vramMb = this.discreteGpuVramMb(systemInfo);
 
     // High-end: RTX 50 series or M3 Pro/Max/M4+
     if (this.hasHighEndHardware(gpuModel, cpuModel)) {
@@ -433,13 +443,18 @@ export class OnboardingService extends EventEmitter {
     // Mid-tier: RTX 30/40 series or M2/M3 base
     if (
       this.hasNvidia30SeriesOrBetter(gpuModel) ||
-      this.hasAppleSiliconM2OrBetter(cpuModel)
+      this.hasAppleSiliconM2OrBetter(cpuModel) ||
+      vramMb >= 8192
     ) {
       return "whisper-medium";
     }
 
     // Entry: RTX 20 series or M1
-    if (this.hasNvidia20Series(gpuModel) || this.hasAppleSiliconM1(cpuModel)) {
+    if (
+      this.hasNvidia20Series(gpuModel) ||
+      this.hasAppleSiliconM1(cpuModel) ||
+      vramMb >= 4096
+    ) {
       return "whisper-small";
     }
 
@@ -0,0 +1,180 @@
+import * as http from "node:http";
+import { timingSafeEqual } from "node:crypto";
+import { logger } from "../main/logger";
+import type { SettingsService } from "./settings-service";
+import type { RecordingManager } from "../main/managers/recording-manager";
+import type { AppSettingsData } from "../db/schema";
+
+type RemoteControlSettings = NonNullable<AppSettingsData["remoteControl"]>;
+
+/**
+ * Local HTTP API for triggering recordings from other devices
+ * (home automation, meeting-room controllers, scripts).
+ *
+ * Endpoints (all require `Authorization: Bearer <token>`):
+ * - POST /record/start?mode=<name>  Start a hands-free recording, optionally switching mode
+ * - POST /record/stop               Stop the current recording
+ * - GET  /record/status             Current recording state
+ */
+export class RemoteControlService {
+  private server: http.Server | null = null;
+  private activeSettings: RemoteControlSettings | null = null;
+
+  constructor(
+    private settingsService: SettingsService,
+    private recordingManager: RecordingManager,
+  ) {}
+
+  async initialize(): Promise<void> {
+    this.settingsService.on("remote-control-changed", () => {
+      this.applySettings().catch((error) => {
+        logger.main.error("Failed to apply remote control settings", {
+          error,
+        });
+      });
+    });
+    await this.applySettings();
+  }
+
+  /**
+   * Start, restart or stop the server to match the stored settings
+   */
+  private async applySettings(): Promise<void> {
+    const settings = await this.settingsService.getRemoteControlSettings();
+
+    if (!settings?.enabled || !settings.token) {
+      await this.stop();
+      return;
+    }
+
+    const current = this.activeSettings;
+    if (
+      this.server &&
+      current?.port === settings.port &&
+      current.allowLan === settings.allowLan
+    ) {
+      // Token changes don't need a rebind
+      this.activeSettings = settings;
+      return;
+    }
+
+    await this.stop();
+    await this.start(settings);
+  }
+
+  private start(settings: RemoteControlSettings): Promise<void> {
+    const host = settings.allowLan ? "0.0.0.0" : "127.0.0.1";
+    const server = http.createServer((req, res) => {
+      this.handleRequest(req, res).catch((error) => {
+        logger.main.error("Remote control request failed", { error });
+        this.sendJson(res, 500, { error: "Internal error" });
+      });
+    });
+
+    return new Promise((resolve) => {
+      server.once("error", (error) => {
+        logger.main.error("Remote control server failed to start", {
+          port: settings.port,
+          error,
+        });
+        this.server = null;
+        this.activeSettings = null;
+        resolve();
+      });
+      server.listen(settings.port, host, () => {
+        this.server = server;
+        this.activeSettings = settings;
+        logger.main.info("Remote control server listening", {
+          host,
+          port: settings.port,
+        });
+        resolve();
+      });
+    });
+  }
+
+  async stop(): Promise<void> {
+    const server = this.server;
+    if (!server) {
+      return;
+    }
+    this.server = null;
+    this.activeSettings = null;
+    await new Promise<void>((resolve) => server.close(() => resolve()));
+    logger.main.info("Remote control server stopped");
+  }
+
+  private isAuthorized(req: http.IncomingMessage): boolean {
+    const token = this.activeSettings?.token;
+    const header = req.headers.authorization;
+    if (!token || !header?.startsWith("Bearer ")) {
+      return false;
+    }
+    const provided = Buffer.from(header.slice("Bearer ".length));
+    const expected = Buffer.from(token);
+    return (
+      provided.length === expected.length && timingSafeEqual(provided, expected)
+    );
+  }
+
+  private async handleRequest(
+    req: http.IncomingMessage,
+    res: http.ServerResponse,
+  ): Promise<void> {
+    if (!this.isAuthorized(req)) {
+      logger.main.warn("Rejected unauthorized remote control request", {
+        remoteAddress: req.socket.remoteAddress,
+      });
+      this.sendJson(res, 401, { error: "Unauthorized" });
+      return;
+    }
+
+    const url = new URL(req.url ?? "/", "http://localhost");
+    const route = `${req.method} ${url.pathname}`;
+
+    switch (route) {
+      case "POST /record/start": {
+        const modeName = url.searchParams.get("mode");
+        if (modeName) {
+          const modes = await this.settingsService.getModes();
+          const mode = modes.items.find(
+            (m) => m.name.toLowerCase() === modeName.toLowerCase(),
+          );
+          if (!mode) {
+            this.sendJson(res, 404, { error: `Mode "${modeName}" not found` });
+            return;
+          }
+          await this.settingsService.setActiveMode(mode.id);
+        }
+        logger.main.info("Remote control: start recording", {
+          mode: modeName,
+        });
+        await this.recordingManager.signalStart();
+        this.sendJson(res, 202, { state: this.recordingManager.getState() });
+        return;
+      }
+      case "POST /record/stop":
+        logger.main.info("Remote control: stop recording");
+        await this.recordingManager.signalStop();
+        this.sendJson(res, 202, { state: this.recordingManager.getState() });
+        return;
+      case "GET /record/status":
+        this.sendJson(res, 200, { state: this.recordingManager.getState() });
+        return;
+      default:
+        this.sendJson(res, 404, { error: "Not found" });
+    }
+  }
+
+  private sendJson(
+    res: http.ServerResponse,
+    status: number,
+    body: Record<string, unknown>,
+  ): void {
+    if (res.headersSent) {
+      return;
+    }
+    res.writeHead(status, { "Content-Type": "application/json" });
+    res.end(JSON.stringify(body));
+  }
+}
@@ -0,0 +1,151 @@
+import { logger } from "../main/logger";
+import type { SettingsService } from "./settings-service";
+import {
+  deleteTranscriptions,
+  detachExpiredAudioFiles,
+  getExpiredTranscriptionIds,
+} from "../db/transcriptions";
+import type { AppSettingsData } from "../db/schema";
+import { deleteAudioFiles } from "../utils/audio-file-cleanup";
+
+const RETENTION_INTERVAL_MS = 60 * 60 * 1000;
+// Let startup finish before the first pass
+const INITIAL_DELAY_MS = 60 * 1000;
+const DELETE_BATCH_SIZE = 500;
+const DAY_MS = 24 * 60 * 60 * 1000;
+
+type HistorySettings = AppSettingsData["history"];
+
+export interface RetentionReport {
+  transcriptionsDeleted: number;
+  audioFilesDeleted: number;
+}
+
+/**
+ * Enforces the history retention policy ("keep 90 days", "keep 1000
+ * items") by periodically deleting old transcriptions and their audio,
+ * and the separate audio policy that drops recordings but keeps the text.
+ */
+export class RetentionService {
+  private timer: NodeJS.Timeout | null = null;
+  private running: Promise<RetentionReport> | null = null;
+
+  constructor(private settingsService: SettingsService) {}
+
+  initialize(): void {
+    this.scheduleNext(INITIAL_DELAY_MS);
+
+    // Apply a tightened policy right away
+    this.settingsService.on("history-settings-changed", () => {
+      this.enforce().catch((error) => {
+        logger.main.error("History retention pass failed", { error });
+      });
+    });
+  }
+
+  stop(): void {
+    if (this.timer) {
+      clearTimeout(this.timer);
+      this.timer = null;
+    }
+  }
+
+  private scheduleNext(delayMs: number): void {
+    this.timer = setTimeout(() => {
+      this.enforce()
+        .catch((error) => {
+          logger.main.error("History retention pass failed", { error });
+        })
+        .finally(() => {
+          if (this.timer) this.scheduleNext(RETENTION_INTERVAL_MS);
+        });
+    }, delayMs);
+    // Don't keep the process alive just for housekeeping
+    this.timer.unref();
+  }
+
+  /**
+   * Apply the retention policy now. Concurrent callers share one pass.
+   */
+  async enforce(): Promise<RetentionReport> {
+    if (!this.running) {
+      this.running = this.runPass().finally(() => {
+        this.running = null;
+      });
+    }
+    return await this.running;
+  }
+
+  private async runPass(): Promise<RetentionReport> {
+    const history = await this.settingsService.getHistorySettings();
+    const report: RetentionReport = {
+      transcriptionsDeleted: 0,
+      audioFilesDeleted: 0,
+    };
+    await this.purgeTranscriptions(history, report);
+    await this.purgeAudio(history, report);
+
+    if (report.transcriptionsDeleted > 0 || report.audioFilesDeleted > 0) {
+      logger.main.info("Applied history retention", {
+        ...report,
+        retentionDays: history?.retentionDays,
+        maxItems: history?.maxItems,
+        audioRetentionDays: history?.audioRetentionDays,
+      });
+    }
+    return report;
+  }
+
+  // Whole rows, with their audio
+  private async purgeTranscriptions(
+    history: HistorySettings,
+    report: RetentionReport,
+  ): Promise<void> {
+    const olderThan = history?.retentionDays
+      ? new Date(Date.now() - history.retentionDays * DAY_MS)
+      : undefined;
+    const keepLatest = history?.maxItems;
+    if (!olderThan && keepLatest === undefined) {
+      return;
+    }
+
+    for (;;) {
+      const ids = await getExpiredTranscriptionIds({
+        olderThan,
+        keepLatest,
+        limit: DELETE_BATCH_SIZE,
+      });
+      if (ids.length === 0) break;
+
+      const rows = await deleteTranscriptions(ids);
+      if (rows.length === 0) break;
+      report.audioFilesDeleted += await deleteAudioFiles(
+        rows.flatMap((row) => (row.audioFile ? [row.audioFile] : [])),
+      );
+      report.transcriptionsDeleted += rows.length;
+    }
+  }
+
+  // Recordings only; the text rows stay. The reference is cleared before
+  // the file is deleted, so a failed delete leaves an orphan that startup
+  // repair removes rather than a row pointing at nothing.
+  private async purgeAudio(
+    history: HistorySettings,
+    report: RetentionReport,
+  ): Promise<void> {
+    if (!history?.audioRetentionDays) {
+      return;
+    }
+    const olderThan = new Date(
+      Date.now() - history.audioRetentionDays * DAY_MS,
+    );
+
+    for (;;) {
+      const rows = await detachExpiredAudioFiles(olderThan, DELETE_BATCH_SIZE);
+      if (rows.length === 0) break;
+      report.audioFilesDeleted += await deleteAudioFiles(
+        rows.map((row) => row.audioFile),
+      );
+    }
+  }
+}
@@ -0,0 +1,175 @@
+import { logger } from "../main/logger";
+import type { SettingsService } from "./settings-service";
+import {
+  createEmbeddingClient,
+  type EmbeddingClient,
+} from "../pipeline/providers/embedding-client";
+import {
+  deleteOrphanedEmbeddings,
+  getTranscriptionEmbeddings,
+  getTranscriptionsNeedingEmbedding,
+  upsertTranscriptionEmbeddings,
+} from "../db/embeddings";
+import { getTranscriptionsByIds } from "../db/transcriptions";
+import type { Transcription } from "../db/schema";
+
+const INDEX_BATCH_SIZE = 32;
+// Cap on catch-up indexing done inline before a query; the rest is picked
+// up by later searches
+const MAX_INDEX_PER_SEARCH = 256;
+// Longest a query waits on indexing (e.g. the startup backfill) before it
+// searches what's indexed so far
+const MAX_INDEX_WAIT_MS = 3000;
+// Embedding APIs reject very long inputs; the opening carries the topic
+const MAX_EMBED_CHARS = 8000;
+
+export interface SemanticSearchResult {
+  transcription: Transcription;
+  score: number; // Cosine similarity, -1..1
+}
+
+function cosineSimilarity(a: ArrayLike<number>, b: ArrayLike<number>) {
+  if (a.length !== b.length) return 0;
+  let dot = 0;
+  let normA = 0;
+  let normB = 0;
+  for (let i = 0; i < a.length; i++) {
+    dot += a[i] * b[i];
+    normA += a[i] * a[i];
+    normB += b[i] * b[i];
+  }
+  return normA === 0 || normB === 0 ? 0 : dot / Math.sqrt(normA * normB);
+}
+
+/**
+ * Embeddings-based search over transcription history. Vectors live in the
+ * transcription_embeddings table and are scanned in memory; indexing is
+ * incremental and happens lazily (at startup and before each query).
+ */
+export class SearchService {
+  private indexing: Promise<void> | null = null;
+
+  constructor(private settingsService: SettingsService) {}
+
+  /**
+   * Start catching up on unindexed transcriptions in the background
+   */
+  initialize(): void {
+    this.getClient()
+      .then((client) => client && this.indexPending(client, Infinity))
+      .catch((error) => {
+        logger.main.warn("Background embedding indexing failed", { error });
+      });
+  }
+
+  async semanticSearch(
+    query: string,
+    limit: number,
+  ): Promise<SemanticSearchResult[]> {
+    const client = await this.getClient();
+    if (!client) {
+      throw new Error("No embedding model configured for semantic search");
+    }
+
+    await this.indexPending(client, MAX_INDEX_PER_SEARCH, MAX_INDEX_WAIT_MS);
+
+    const [queryVector] = await client.embed([query]);
+    const scored = (await getTranscriptionEmbeddings(client.modelKey))
+      .map((row) => ({
+        id: row.transcriptionId,
+        score: cosineSimilarity(queryVector, row.embedding),
+      }))
+      .sort((a, b) => b.score - a.score)
+      .slice(0, limit);
+
+    const byId = new Map(
+      (await getTranscriptionsByIds(scored.map((row) => row.id))).map((t) => [
+        t.id,
+        t,
+      ]),
+    );
+    return scored.flatMap(({ id, score }) => {
+      const transcription = byId.get(id);
+      return transcription ? [{ transcription, score }] : [];
+    });
+  }
+
+  private async getClient(): Promise<EmbeddingClient | null> {
+    const config = await this.settingsService.getEmbeddingConfig();
+    if (!config) {
+      return null;
+    }
+    return createEmbeddingClient(
+      config,
+      await this.settingsService.getModelProvidersConfig(),
+    );
+  }
+
+  /**
+   * Embed up to `max` transcriptions that are new, edited, or were indexed
+   * with a different model. Concurrent callers share one run; with
+   * `maxWaitMs`, the caller stops waiting on it after that long.
+   */
+  private async indexPending(
+    client: EmbeddingClient,
+    max: number,
+    maxWaitMs?: number,
+  ): Promise<void> {
+    if (!this.indexing) {
+      this.indexing = this.runIndexing(client, max).finally(() => {
+        this.indexing = null;
+      });
+    }
+    if (maxWaitMs === undefined) {
+      await this.indexing;
+      return;
+    }
+
+    let timer: ReturnType<typeof setTimeout> | undefined;
+    await Promise.race([
+      this.indexing,
+      new Promise<void>((resolve) => {
+        timer = setTimeout(resolve, maxWaitMs);
+      }),
+    ]).finally(() => clearTimeout(timer));
+  }
+
+  private async runIndexing(client: EmbeddingClient, max: number) {
+    await deleteOrphanedEmbeddings();
+
+    let indexed = 0;
+    // Each transcription is embedded at most once per run, so rows that
+    // keep matching can't loop forever on paid API calls
+    const embedded = new Set<number>();
+    while (indexed < max) {
+      const batch = (
+        await getTranscriptionsNeedingEmbedding(
+          client.modelKey,
+          Math.min(INDEX_BATCH_SIZE, max - indexed),
+        )
+      ).filter((row) => !embedded.has(row.id));
+      if (batch.length === 0) break;
+
+      const vectors = await client.embed(
+        batch.map((row) => row.text.slice(0, MAX_EMBED_CHARS) || " "),
+      );
+      await upsertTranscriptionEmbeddings(
+        batch.map((row, i) => ({
+          transcriptionId: row.id,
+          model: client.modelKey,
+          embedding: vectors[i],
+          textUpdatedAt: row.updatedAt,
+        })),
+      );
+      for (const row of batch) embedded.add(row.id);
+      indexed += batch.length;
+    }
+
+    if (indexed > 0) {
+      logger.main.info("Indexed transcriptions for semantic search", {
+        model: client.modelKey,
+        count: indexed,
+      });
+    }
+  }
+}
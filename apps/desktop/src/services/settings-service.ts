@@ -1,13 +1,39 @@
 import { app } from "electron";
 import { EventEmitter } from "events";
 import { FormatterConfig } from "../types/formatter";
+import { isAutoLaunchEnabled, setAutoLaunch } from "../utils/auto-launch";
+import type { EmbeddingConfig } from "../types/embedding";
 import {
   getSettingsSection,
   updateSettingsSection,
   getAppSettings,
   updateAppSettings,
+  defaultSettings,
 } from "../db/app-settings";
-import type { AppSettingsData, ModeConfig } from "../db/schema";
+import {
+  PROFILE_SECTIONS,
+  type AppSettingsData,
+  type ModeConfig,
+  type ProfileSettings,
+  type SettingsProfile,
+} from "../db/schema";
+import {
+  createSettingsProfile,
+  deleteSettingsProfile,
+  getSettingsProfile,
+  getSettingsProfileByName,
+  getSettingsProfiles,
+  updateSettingsProfile,
+} from "../db/settings-profiles";
+
+// Profile the existing settings become when profiles are first used
+const DEFAULT_PROFILE_ID = "default";
+
+function pickProfileSettings(settings: AppSettingsData): ProfileSettings {
+  return Object.fromEntries(
+    PROFILE_SECTIONS.map((section) => [section, settings[section]]),
+  ) as ProfileSettings;
+}
 
 /**
  * Database-backed settings service with typed configuration
@@ -26,11 +52,53 @@ export interface AppPreferences {
   autoUpdate: boolean;
 }
 
+// Groups of settings that can be restored to their defaults on their own
+export type ResettableSection =
+  | "shortcuts"
+  | "providers"
+  | "modes"
+  | "preferences";
+
+export interface SettingsChange<
+  K extends keyof AppSettingsData = keyof AppSettingsData,
+> {
+  section: K;
+  value: AppSettingsData[K];
+}
+
 export class SettingsService extends EventEmitter {
   constructor() {
     super();
   }
 
+  /**
+   * Persist one section and emit "settings-changed" for it
+   */
+  private async saveSection<K extends keyof AppSettingsData>(
+    section: K,
+    value: AppSettingsData[K],
+  ): Promise<AppSettingsData> {
+    const settings = await updateSettingsSection(section, value);
+    this.emit("settings-changed", { section, value } as SettingsChange);
+    return settings;
+  }
+
+  /**
+   * Persist several sections, emitting "settings-changed" for each
+   */
+  private async saveSettings(
+    settings: Partial<AppSettingsData>,
+  ): Promise<AppSettingsData> {
+    const merged = await updateAppSettings(settings);
+    for (const section of Object.keys(settings) as (keyof AppSettingsData)[]) {
+      this.emit("settings-changed", {
+        section,
+        value: merged[section],
+      } as SettingsChange);
+    }
+    return merged;
+  }
+
   /**
    * Get formatter configuration
    */
@@ -43,7 +111,22 @@ export class SettingsService extends EventEmitter {
    * Set formatter configuration
    */
   async setFormatterConfig(config: FormatterConfig): Promise<void> {
-    await updateSettingsSection("formatterConfig", config);
+    await this.saveSection("formatterConfig", config);
+  }
+
+  /**
+   * Get the embedding model used for semantic search
+   */
+  async getEmbeddingConfig(): Promise<EmbeddingConfig | null> {
+    const embeddingConfig = await getSettingsSection("embeddingConfig");
+    return embeddingConfig || null;
+  }
+
+  /**
+   * Set the embedding model (null disables semantic search)
+   */
+  async setEmbeddingConfig(config: EmbeddingConfig | null): Promise<void> {
+    await this.saveSection("embeddingConfig", config ?? undefined);
   }
 
   /**
@@ -59,7 +142,7 @@ export class SettingsService extends EventEmitter {
   async updateSettings(
     settings: Partial<AppSettingsData>,
   ): Promise<AppSettingsData> {
-    return await updateAppSettings(settings);
+    return await this.saveSettings(settings);
   }
 
   /**
@@ -77,7 +160,7 @@ export class SettingsService extends EventEmitter {
         ...uiSettings,
         theme: "dark",
       };
-      await updateSettingsSection("ui", normalizedSettings);
+      await this.saveSection("ui", normalizedSettings);
       return normalizedSettings;
     }
 
@@ -88,7 +171,7 @@ export class SettingsService extends EventEmitter {
    * Update UI settings
    */
   async setUISettings(uiSettings: AppSettingsData["ui"]): Promise<void> {
-    await updateSettingsSection("ui", uiSettings);
+    await this.saveSection("ui", uiSettings);
 
     // Emit event if theme changed (AppManager will handle window updates)
     if (uiSettings?.theme !== undefined) {
@@ -109,7 +192,7 @@ export class SettingsService extends EventEmitter {
   async setTranscriptionSettings(
     transcriptionSettings: AppSettingsData["transcription"],
   ): Promise<void> {
-    await updateSettingsSection("transcription", transcriptionSettings);
+    await this.saveSection("transcription", transcriptionSettings);
   }
 
   /**
@@ -125,10 +208,93 @@ export class SettingsService extends EventEmitter {
   async setRecordingSettings(
     recordingSettings: AppSettingsData["recording"],
   ): Promise<void> {
-    await updateSettingsSection("recording", recordingSettings);
+    await this.saveSection("recording", recordingSettings);
     this.emit("recording-settings-changed", { recordingSettings });
   }
 
+  /**
+   * Get history retention settings
+   */
+  async getHistorySettings(): Promise<AppSettingsData["history"]> {
+    return await getSettingsSection("history");
+  }
+
+  /**
+   * Update history retention settings
+   */
+  async setHistorySettings(
+    historySettings: AppSettingsData["history"],
+  ): Promise<void> {
+    await this.saveSection("history", historySettings);
+    this.emit("history-settings-changed", { historySettings });
+  }
+
+  /**
+   * Get weekly digest settings
+   */
+  async getWeeklyDigestSettings(): Promise<AppSettingsData["weeklyDigest"]> {
+    return await getSettingsSection("weeklyDigest");
+  }
+
+  /**
+   * Update weekly digest settings
+   */
+  async setWeeklyDigestSettings(
+    weeklyDigestSettings: AppSettingsData["weeklyDigest"],
+  ): Promise<void> {
+    await this.saveSection("weeklyDigest", weeklyDigestSettings);
+  }
+
+  /**
+   * Get the database maintenance schedule
+   */
+  async getMaintenanceSettings(): Promise<AppSettingsData["maintenance"]> {
+    return await getSettingsSection("maintenance");
+  }
+
+  /**
+   * Update the database maintenance schedule
+   */
+  async setMaintenanceSettings(
+    maintenanceSettings: AppSettingsData["maintenance"],
+  ): Promise<void> {
+    await this.saveSection("maintenance", maintenanceSettings);
+  }
+
+  /**
+   * Get the unused model cleanup settings
+   */
+  async getModelCleanupSettings(): Promise<AppSettingsData["modelCleanup"]> {
+    return await getSettingsSection("modelCleanup");
+  }
+
+  /**
+   * Update the unused model cleanup settings
+   */
+  async setModelCleanupSettings(
+    modelCleanupSettings: AppSettingsData["modelCleanup"],
+  ): Promise<void> {
+    await this.saveSection("modelCleanup", modelCleanupSettings);
+  }
+
+  /**
+   * Get the model download source settings
+   */
+  async getModelDownloadSettings(): Promise<
+    AppSettingsData["modelDownloads"]
+  > {
+    return await getSettingsSection("modelDownloads");
+  }
+
+  /**
+   * Update the model download source settings
+   */
+  async setModelDownloadSettings(
+    modelDownloadSettings: AppSettingsData["modelDownloads"],
+  ): Promise<void> {
+    await this.saveSection("modelDownloads", modelDownloadSettings);
+  }
+
   /**
    * Get dictation settings
    */
@@ -142,7 +308,7 @@ export class SettingsService extends EventEmitter {
   async setDictationSettings(
     dictationSettings: AppSettingsData["dictation"],
   ): Promise<void> {
-    await updateSettingsSection("dictation", dictationSettings);
+    await this.saveSection("dictation", dictationSettings);
   }
 
   /**
@@ -172,7 +338,7 @@ export class SettingsService extends EventEmitter {
         : undefined,
       cycleMode: shortcuts.cycleMode?.length ? shortcuts.cycleMode : undefined,
     };
-    await updateSettingsSection("shortcuts", dataToStore);
+    await this.saveSection("shortcuts", dataToStore);
   }
 
   /**
@@ -190,7 +356,7 @@ export class SettingsService extends EventEmitter {
   async setModelProvidersConfig(
     config: AppSettingsData["modelProvidersConfig"],
   ): Promise<void> {
-    await updateSettingsSection("modelProvidersConfig", config);
+    await this.saveSection("modelProvidersConfig", config);
   }
 
   /**
@@ -359,7 +525,11 @@ export class SettingsService extends EventEmitter {
   async getPreferences(): Promise<AppPreferences> {
     const preferences = await getSettingsSection("preferences");
     return {
-      launchAtLogin: preferences?.launchAtLogin ?? true,
+      // What the OS will actually do, which the user can change outside
+      // the app; development builds aren't registered
+      launchAtLogin: app.isPackaged
+        ? isAutoLaunchEnabled()
+        : (preferences?.launchAtLogin ?? true),
       minimizeToTray: preferences?.minimizeToTray ?? true,
       showWidgetWhileInactive: preferences?.showWidgetWhileInactive ?? true,
       showInDock: preferences?.showInDock ?? true,
@@ -375,7 +545,7 @@ export class SettingsService extends EventEmitter {
     const newPreferences = { ...currentPreferences, ...preferences };
 
     // Save to database
-    await updateSettingsSection("preferences", newPreferences);
+    await this.saveSection("preferences", newPreferences);
 
     // Handle launch at login change
     if (
@@ -394,17 +564,51 @@ export class SettingsService extends EventEmitter {
     });
   }
 
+  /**
+   * Restore one group of settings to its defaults, leaving the rest (and
+   * the history) alone
+   */
+  async resetSection(section: ResettableSection): Promise<void> {
+    switch (section) {
+      case "shortcuts":
+        await this.saveSection(
+          "shortcuts",
+          structuredClone(defaultSettings.shortcuts),
+        );
+        break;
+      case "providers":
+        await this.saveSection(
+          "modelProvidersConfig",
+          structuredClone(defaultSettings.modelProvidersConfig),
+        );
+        break;
+      case "modes":
+        await this.saveSection("modes", structuredClone(defaultSettings.modes));
+        this.emit("active-mode-changed", { modeId: "default" });
+        break;
+      case "preferences":
+        await this.saveSection(
+          "preferences",
+          structuredClone(defaultSettings.preferences),
+        );
+        this.syncAutoLaunch();
+        this.emit("preferences-changed", {
+          changes: defaultSettings.preferences,
+          showWidgetWhileInactiveChanged: true,
+          showInDockChanged: true,
+        });
+        break;
+    }
+  }
+
   /**
    * Sync the auto-launch setting with the OS
    * This ensures the OS setting matches our stored preference
    */
   syncAutoLaunch(): void {
-    // Get the current preference asynchronously and apply it
-    this.getPreferences().then((preferences) => {
-      app.setLoginItemSettings({
-        openAtLogin: preferences.launchAtLogin,
-        openAsHidden: false,
-      });
+    // Get the stored preference asynchronously and apply it
+    getSettingsSection("preferences").then((preferences) => {
+      setAutoLaunch(preferences?.launchAtLogin ?? true);
     });
   }
 
@@ -442,7 +646,41 @@ export class SettingsService extends EventEmitter {
   async setTelemetrySettings(
     telemetrySettings: AppSettingsData["telemetry"],
   ): Promise<void> {
-    await updateSettingsSection("telemetry", telemetrySettings);
+    await this.saveSection("telemetry", telemetrySettings);
+  }
+
+  /**
+   * Get remote control (local REST trigger) settings
+   */
+  async getRemoteControlSettings(): Promise<AppSettingsData["remoteControl"]> {
+    return await getSettingsSection("remoteControl");
+  }
+
+  /**
+   * Update remote control settings and restart the server
+   */
+  async setRemoteControlSettings(
+    remoteControlSettings: AppSettingsData["remoteControl"],
+  ): Promise<void> {
+    await this.saveSection("remoteControl", remoteControlSettings);
+    this.emit("remote-control-changed", remoteControlSettings);
+  }
+
+  /**
+   * Get proxy settings for outbound requests
+   */
+  async getProxySettings(): Promise<AppSettingsData["proxy"]> {
+    return await getSettingsSection("proxy");
+  }
+
+  /**
+   * Update proxy settings and apply them to outbound requests
+   */
+  async setProxySettings(
+    proxySettings: AppSettingsData["proxy"],
+  ): Promise<void> {
+    await this.saveSection("proxy", proxySettings);
+    this.emit("proxy-changed", proxySettings);
   }
 
   // --- Modes CRUD ---
@@ -490,7 +728,7 @@ export class SettingsService extends EventEmitter {
       throw new Error(`Mode with id "${modeId}" not found`);
     }
     const settings = await getAppSettings();
-    await updateAppSettings({
+    await this.saveSettings({
       modes: { ...settings.modes!, activeModeId: modeId },
     });
     this.emit("active-mode-changed", { modeId });
@@ -548,7 +786,7 @@ export class SettingsService extends EventEmitter {
       createdAt: now,
       updatedAt: now,
     };
-    await updateAppSettings({
+    await this.saveSettings({
       modes: { items: [...items, newMode], activeModeId },
     });
     return newMode;
@@ -557,7 +795,7 @@ export class SettingsService extends EventEmitter {
   async updateMode(
     modeId: string,
     updates: Partial<
-      Pick<ModeConfig, "name" | "dictation" | "formatterConfig" | "customInstructions" | "speechModelId" | "appBindings">
+      Pick<ModeConfig, "name" | "dictation" | "formatterConfig" | "customInstructions" | "speechModelId" | "appBindings" | "spokenCommandsEnabled" | "normalization" | "voiceEditingEnabled" | "messageSplitting" | "profanityPolicy" | "vocabularyCorrectionEnabled">
     >,
   ): Promise<ModeConfig> {
     const { items, activeModeId } = await this.getModes();
@@ -572,7 +810,7 @@ export class SettingsService extends EventEmitter {
     };
     const newItems = [...items];
     newItems[index] = updated;
-    await updateAppSettings({
+    await this.saveSettings({
       modes: { items: newItems, activeModeId },
     });
     return updated;
@@ -593,7 +831,7 @@ export class SettingsService extends EventEmitter {
     const newItems = items.filter((m) => m.id !== modeId);
     const newActiveModeId =
       activeModeId === modeId ? "default" : activeModeId;
-    await updateAppSettings({
+    await this.saveSettings({
       modes: { items: newItems, activeModeId: newActiveModeId },
     });
     if (activeModeId === modeId) {
@@ -610,4 +848,106 @@ export class SettingsService extends EventEmitter {
     }
     return null;
   }
+
+  // --- Profiles ---
+
+  /**
+   * Id of the active profile. The first call turns the current settings
+   * into the "Default" profile.
+   */
+  private async getActiveProfileId(): Promise<string> {
+    const settings = await getAppSettings();
+    if (settings.profiles?.activeProfileId) {
+      return settings.profiles.activeProfileId;
+    }
+    if (!(await getSettingsProfile(DEFAULT_PROFILE_ID))) {
+      await createSettingsProfile({
+        id: DEFAULT_PROFILE_ID,
+        name: "Default",
+        data: pickProfileSettings(settings),
+      });
+    }
+    await this.saveSection("profiles", { activeProfileId: DEFAULT_PROFILE_ID });
+    return DEFAULT_PROFILE_ID;
+  }
+
+  async getProfiles(): Promise<{
+    items: Omit<SettingsProfile, "data">[];
+    activeProfileId: string;
+  }> {
+    const activeProfileId = await this.getActiveProfileId();
+    const items = (await getSettingsProfiles()).map(
+      ({ data: _data, ...profile }) => profile,
+    );
+    return { items, activeProfileId };
+  }
+
+  /**
+   * Create a profile, starting from a copy of the active profile's settings
+   * or from the defaults
+   */
+  async createProfile(
+    name: string,
+    options: { copyActive: boolean },
+  ): Promise<Omit<SettingsProfile, "data">> {
+    await this.getActiveProfileId();
+    if (await getSettingsProfileByName(name)) {
+      throw new Error(`A profile named "${name}" already exists`);
+    }
+    const source = options.copyActive
+      ? await getAppSettings()
+      : defaultSettings;
+    const { data: _data, ...profile } = await createSettingsProfile({
+      id: crypto.randomUUID(),
+      name,
+      data: structuredClone(pickProfileSettings(source)),
+    });
+    return profile;
+  }
+
+  async renameProfile(id: string, name: string): Promise<void> {
+    const existing = await getSettingsProfileByName(name);
+    if (existing && existing.id !== id) {
+      throw new Error(`A profile named "${name}" already exists`);
+    }
+    if (!(await updateSettingsProfile(id, { name }))) {
+      throw new Error(`Profile with id "${id}" not found`);
+    }
+  }
+
+  async deleteProfile(id: string): Promise<void> {
+    if (id === (await this.getActiveProfileId())) {
+      throw new Error("Cannot delete the active profile");
+    }
+    if (!(await getSettingsProfile(id))) {
+      throw new Error(`Profile with id "${id}" not found`);
+    }
+    await deleteSettingsProfile(id);
+  }
+
+  /**
+   * Store the active profile's modes, providers and shortcuts in its row
+   * and load the target profile's in their place
+   */
+  async switchProfile(id: string): Promise<void> {
+    const activeProfileId = await this.getActiveProfileId();
+    if (id === activeProfileId) return;
+    const target = await getSettingsProfile(id);
+    if (!target) {
+      throw new Error(`Profile with id "${id}" not found`);
+    }
+
+    await updateSettingsProfile(activeProfileId, {
+      data: pickProfileSettings(await getAppSettings()),
+    });
+    const settings = await this.saveSettings({
+      ...pickProfileSettings(target.data),
+      profiles: { activeProfileId: id },
+    });
+
+    this.emit("profile-switched", { profileId: id });
+    this.emit("active-mode-changed", {
+      modeId: settings.modes?.activeModeId ?? "default",
+    });
+  }
 }
@@ -1,8 +1,10 @@
 import { PostHog } from "posthog-node";
 import { machineId } from "node-machine-id";
-import * as si from "systeminformation";
-import { app } from "electron";
+import { app, net } from "electron";
+import * as fs from "node:fs";
+import * as path from "node:path";
 import { logger } from "../main/logger";
+import { getSystemInfo, type SystemInfo } from "../utils/system-info";
 import type { SettingsService } from "./settings-service";
 import type {
   OnboardingStartedEvent,
@@ -35,31 +37,51 @@ export interface TranscriptionMetrics {
   vocabulary_size?: number;
 }
 
-export interface SystemInfo {
-  // Hardware
-  cpu_model: string;
-  cpu_cores: number;
-  cpu_threads: number;
-  cpu_speed_ghz: number;
-  memory_total_gb: number;
-
-  // OS
-  os_platform: string;
-  os_distro: string;
-  os_release: string;
-  os_arch: string;
-
-  // Graphics
-  gpu_model: string;
-  gpu_vendor: string;
-
-  // System
-  manufacturer: string;
-  model: string;
+export interface TelemetryConfig {
+  apiKey: string;
+  host: string;
+  feedbackSurveyId: string;
+}
+
+/**
+ * PostHog credentials: runtime environment first, then the values bundled
+ * at build time. Empty when telemetry is switched off for the build.
+ */
+export function getTelemetryConfig(): TelemetryConfig {
+  const telemetryEnabled = process.env.TELEMETRY_ENABLED
+    ? process.env.TELEMETRY_ENABLED !== "false"
+    : __BUNDLED_TELEMETRY_ENABLED;
+  if (!telemetryEnabled) {
+    return { apiKey: "", host: "", feedbackSurveyId: "" };
+  }
+  return {
+    apiKey: process.env.POSTHOG_API_KEY || __BUNDLED_POSTHOG_API_KEY,
+    host: process.env.POSTHOG_HOST || __BUNDLED_POSTHOG_HOST,
+    feedbackSurveyId:
+      process.env.FEEDBACK_SURVEY_ID || __BUNDLED_FEEDBACK_SURVEY_ID,
+  };
+}
+
+// Events are sent in batches of this size, or every FLUSH_INTERVAL_MS
+const BATCH_SIZE = 20;
+const FLUSH_INTERVAL_MS = 30 * 1000;
+// How often events held while offline are retried
+const OFFLINE_RETRY_MS = 60 * 1000;
+// Events kept while offline; the oldest are dropped beyond this
+const MAX_OFFLINE_EVENTS = 500;
+
+interface QueuedEvent {
+  distinctId: string;
+  event: string;
+  properties: Record<string, unknown>;
+  timestamp: string; // ISO 8601, when the event happened
 }
 
 export class TelemetryService {
   private posthog: PostHog | null = null;
+  // Captured while offline, saved to disk until they can be sent
+  private offlineQueue: QueuedEvent[] = [];
+  private offlineTimer: NodeJS.Timeout | null = null;
   private machineId: string = "";
   private systemInfo: SystemInfo | null = null;
   private enabled: boolean = false;
@@ -70,15 +92,8 @@ export class TelemetryService {
   constructor(settingsService: SettingsService) {
     this.settingsService = settingsService;
     // Initialize PostHog
-    const host = process.env.POSTHOG_HOST || __BUNDLED_POSTHOG_HOST;
-    // Check runtime env first, then fall back to bundled values
-    const apiKey = process.env.POSTHOG_API_KEY || __BUNDLED_POSTHOG_API_KEY;
-
-    const telemetryEnabled = process.env.TELEMETRY_ENABLED
-      ? process.env.TELEMETRY_ENABLED !== "false"
-      : __BUNDLED_TELEMETRY_ENABLED;
-
-    if (!host || !apiKey || !telemetryEnabled) {
+    const { apiKey, host } = getTelemetryConfig();
+    if (!host || !apiKey) {
       logger.main.info(
         "Telemetry disabled since either api key or host has not been provided",
       );
@@ -87,11 +102,115 @@ export class TelemetryService {
 
     this.posthog = new PostHog(apiKey, {
       host,
-      flushAt: 1,
-      flushInterval: 10000,
+      flushAt: BATCH_SIZE,
+      flushInterval: FLUSH_INTERVAL_MS,
+    });
+  }
+
+  private get offlineQueueFile(): string {
+    return path.join(app.getPath("userData"), "telemetry-queue.json");
+  }
+
+  private loadOfflineQueue(): void {
+    try {
+      this.offlineQueue = JSON.parse(
+        fs.readFileSync(this.offlineQueueFile, "utf8"),
+      );
+    } catch {
+      this.offlineQueue = [];
+    }
+  }
+
+  private saveOfflineQueue(): void {
+    try {
+      if (this.offlineQueue.length === 0) {
+        fs.rmSync(this.offlineQueueFile, { force: true });
+      } else {
+        fs.writeFileSync(
+          this.offlineQueueFile,
+          JSON.stringify(this.offlineQueue),
+        );
+      }
+    } catch (error) {
+      logger.main.warn("Failed to save telemetry queue", { error });
+    }
+  }
+
+  /**
+   * Queue an event. Nothing is sent unless the user has telemetry on;
+   * while offline, events are held and sent once the network is back.
+   */
+  private capture(
+    event: string,
+    properties: Record<string, unknown>,
+    distinctId: string = this.machineId,
+  ): void {
+    if (!this.posthog || !this.enabled) return;
+
+    const queued: QueuedEvent = {
+      distinctId,
+      event,
+      properties,
+      timestamp: new Date().toISOString(),
+    };
+    if (!net.isOnline()) {
+      this.offlineQueue.push(queued);
+      this.offlineQueue.splice(
+        0,
+        this.offlineQueue.length - MAX_OFFLINE_EVENTS,
+      );
+      this.saveOfflineQueue();
+      this.scheduleOfflineRetry();
+      return;
+    }
+    this.send(queued);
+  }
+
+  private send(queued: QueuedEvent): void {
+    this.posthog?.capture({
+      distinctId: queued.distinctId,
+      event: queued.event,
+      properties: queued.properties,
+      timestamp: new Date(queued.timestamp),
     });
   }
 
+  private scheduleOfflineRetry(): void {
+    if (this.offlineTimer) return;
+    this.offlineTimer = setTimeout(() => {
+      this.offlineTimer = null;
+      this.drainOfflineQueue();
+    }, OFFLINE_RETRY_MS);
+    // Don't keep the process alive just for telemetry
+    this.offlineTimer.unref();
+  }
+
+  // Hand events held while offline to PostHog once the network is back
+  private drainOfflineQueue(): void {
+    if (this.offlineQueue.length === 0 || !this.enabled) return;
+    if (!net.isOnline()) {
+      this.scheduleOfflineRetry();
+      return;
+    }
+    for (const queued of this.offlineQueue) {
+      this.send(queued);
+    }
+    logger.main.debug("Sent telemetry events held while offline", {
+      count: this.offlineQueue.length,
+    });
+    this.offlineQueue = [];
+    this.saveOfflineQueue();
+  }
+
+  private clearOfflineQueue(): void {
+    if (this.offlineTimer) {
+      clearTimeout(this.offlineTimer);
+      this.offlineTimer = null;
+    }
+    this.offlineQueue = [];
+    this.saveOfflineQueue();
+  }
+
   async initialize(): Promise<void> {
     if (this.initialized || !this.posthog) {
       return;
@@ -114,7 +233,7 @@ export class TelemetryService {
     });
 
     // Collect system information
-    this.systemInfo = await this.collectSystemInfo();
+    this.systemInfo = await getSystemInfo();
     logger.main.info("System information collected for telemetry", {
       systemInfo: this.systemInfo,
     });
@@ -131,77 +250,26 @@ export class TelemetryService {
     };
 
     this.enabled = telemetrySettings?.enabled !== false;
+    if (this.enabled) {
+      this.loadOfflineQueue();
+      this.drainOfflineQueue();
+    } else {
+      this.clearOfflineQueue();
+    }
     this.initialized = true;
     logger.main.info("Telemetry service initialized successfully", {
       enabled: this.enabled,
     });
   }
 
-  private async collectSystemInfo(): Promise<SystemInfo> {
-    try {
-      const [cpu, mem, osInfo, graphics, system] = await Promise.all([
-        si.cpu(),
-        si.mem(),
-        si.osInfo(),
-        si.graphics(),
-        si.system(),
-      ]);
-
-      return {
-        // Hardware
-        cpu_model: `${cpu.manufacturer} ${cpu.brand}`.trim(),
-        cpu_cores: cpu.physicalCores,
-        cpu_threads: cpu.cores,
-        cpu_speed_ghz: cpu.speed,
-        memory_total_gb: Math.round(mem.total / 1073741824),
-
-        // OS
-        os_platform: osInfo.platform,
-        os_distro: osInfo.distro,
-        os_release: osInfo.release,
-        os_arch: osInfo.arch,
-
-        // Graphics
-        gpu_model: graphics.controllers[0]?.model || "Unknown",
-        gpu_vendor: graphics.controllers[0]?.vendor || "Unknown",
-
-        // System
-        manufacturer: system.manufacturer || "Unknown",
-        model: system.model || "Unknown",
-      };
-    } catch (error) {
-      logger.main.error("Failed to collect system info:", error);
-      // Return minimal info on error
-      return {
-        cpu_model: "Unknown",
-        cpu_cores: 0,
-        cpu_threads: 0,
-        cpu_speed_ghz: 0,
-        memory_total_gb: 0,
-        os_platform: process.platform,
-        os_distro: "Unknown",
-        os_release: "Unknown",
-        os_arch: process.arch,
-        gpu_model: "Unknown",
-        gpu_vendor: "Unknown",
-        manufacturer: "Unknown",
-        model: "Unknown",
-      };
-    }
-  }
-
   trackTranscriptionCompleted(metrics: TranscriptionMetrics): void {
-    if (!this.posthog) {
+    if (!this.posthog || !this.enabled) {
       return;
     }
 
-    this.posthog.capture({
-      distinctId: this.machineId,
-      event: "transcription_completed",
-      properties: {
-        ...metrics,
-        ...this.persistedProperties,
-      },
+    this.capture("transcription_completed", {
+      ...metrics,
+      ...this.persistedProperties,
     });
 
     logger.main.debug("Tracked transcription completion", {
@@ -218,6 +286,11 @@ export class TelemetryService {
       return;
     }
 
+    if (this.offlineTimer) {
+      clearTimeout(this.offlineTimer);
+      this.offlineTimer = null;
+    }
+    // Sends whatever is still batched; offline events stay on disk
     await this.posthog.shutdown();
     logger.main.info("Telemetry service shut down");
   }
@@ -249,6 +322,8 @@ export class TelemetryService {
       return;
     }
 
+    // Don't send anything captured before opting out
+    this.clearOfflineQueue();
     await this.posthog.optOut();
 
     logger.main.info("Telemetry opt-out successful");
@@ -293,11 +368,7 @@ export class TelemetryService {
   trackAppLaunch(): void {
     if (!this.posthog || !this.enabled) return;
 
-    this.posthog.capture({
-      distinctId: this.machineId,
-      event: "app_launch",
-      properties: { ...this.persistedProperties },
-    });
+    this.capture("app_launch", { ...this.persistedProperties });
 
     logger.main.debug("Tracked app launch");
   }
@@ -309,10 +380,9 @@ export class TelemetryService {
   trackOnboardingStarted(props: OnboardingStartedEvent): void {
     if (!this.posthog || !this.enabled) return;
 
-    this.posthog.capture({
-      distinctId: this.machineId,
-      event: "onboarding_started",
-      properties: { ...props, ...this.persistedProperties },
+    this.capture("onboarding_started", {
+      ...props,
+      ...this.persistedProperties,
     });
 
     logger.main.debug("Tracked onboarding started", props);
@@ -321,10 +391,9 @@ export class TelemetryService {
   trackOnboardingScreenViewed(props: OnboardingScreenViewedEvent): void {
     if (!this.posthog || !this.enabled) return;
 
-    this.posthog.capture({
-      distinctId: this.machineId,
-      event: "onboarding_screen_viewed",
-      properties: { ...props, ...this.persistedProperties },
+    this.capture("onboarding_screen_viewed", {
+      ...props,
+      ...this.persistedProperties,
     });
 
     logger.main.debug("Tracked onboarding screen viewed", props);
@@ -335,10 +404,9 @@ export class TelemetryService {
   ): void {
     if (!this.posthog || !this.enabled) return;
 
-    this.posthog.capture({
-      distinctId: this.machineId,
-      event: "onboarding_discovery_selected",
-      properties: { ...props, ...this.persistedProperties },
+    this.capture("onboarding_discovery_selected", {
+      ...props,
+      ...this.persistedProperties,
     });
 
     logger.main.debug("Tracked onboarding discovery selected", props);
@@ -347,10 +415,9 @@ export class TelemetryService {
   trackOnboardingCompleted(props: OnboardingCompletedEvent): void {
     if (!this.posthog || !this.enabled) return;
 
-    this.posthog.capture({
-      distinctId: this.machineId,
-      event: "onboarding_completed",
-      properties: { ...props, ...this.persistedProperties },
+    this.capture("onboarding_completed", {
+      ...props,
+      ...this.persistedProperties,
     });
 
     logger.main.debug("Tracked onboarding completed", props);
@@ -359,10 +426,9 @@ export class TelemetryService {
   trackOnboardingAbandoned(props: OnboardingAbandonedEvent): void {
     if (!this.posthog || !this.enabled) return;
 
-    this.posthog.capture({
-      distinctId: this.machineId,
-      event: "onboarding_abandoned",
-      properties: { ...props, ...this.persistedProperties },
+    this.capture("onboarding_abandoned", {
+      ...props,
+      ...this.persistedProperties,
     });
 
     logger.main.debug("Tracked onboarding abandoned", props);
@@ -375,10 +441,9 @@ export class TelemetryService {
   trackNativeHelperCrashed(props: NativeHelperCrashedEvent): void {
     if (!this.posthog || !this.enabled) return;
 
-    this.posthog.capture({
-      distinctId: this.machineId,
-      event: "native_helper_crashed",
-      properties: { ...props, ...this.persistedProperties },
+    this.capture("native_helper_crashed", {
+      ...props,
+      ...this.persistedProperties,
     });
 
     logger.main.debug("Tracked native helper crash", props);
@@ -391,23 +456,8 @@ export class TelemetryService {
   trackNoteCreated(props: NoteCreatedEvent): void {
     if (!this.posthog || !this.enabled) return;
 
-    this.posthog.capture({
-      distinctId: this.machineId,
-      event: "note_created",
-      properties: { ...props, ...this.persistedProperties },
-    });
+    this.capture("note_created", { ...props, ...this.persistedProperties });
 
     logger.main.debug("Tracked note created", props);
   }
-
-  // ============================================================================
-  // Transcription Events
-  // ============================================================================
-
-  /**
-   * Get system information for model recommendations
-   */
-  getSystemInfo(): SystemInfo | null {
-    return this.systemInfo;
-  }
 }
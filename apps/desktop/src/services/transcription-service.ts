@@ -4,28 +4,74 @@ import {
   StreamingSession,
   TranscriptionProvider,
   FormattingProvider,
+  FinalizedTranscription,
 } from "../pipeline/core/pipeline-types";
 import { createDefaultContext } from "../pipeline/core/context";
+import type { ModeConfig } from "../db/schema";
+import type { FormatterConfig } from "../types/formatter";
 import { WhisperProvider } from "../pipeline/providers/transcription/whisper-provider";
+import { LocalTranscriptionProvider } from "../pipeline/providers/transcription/local-provider";
+import { OnnxSpeechProvider } from "../pipeline/providers/transcription/onnx-speech-provider";
+import {
+  benchmarkWhisperModel,
+  type ModelBenchmarkResult,
+} from "../pipeline/providers/transcription/whisper-benchmark";
 import { OpenAITranscriptionProvider } from "../pipeline/providers/transcription/openai-transcription-provider";
 import { OpenRouterProvider } from "../pipeline/providers/formatting/openrouter-formatter";
 import { OllamaFormatter } from "../pipeline/providers/formatting/ollama-formatter";
 import { OpenAIFormatter } from "../pipeline/providers/formatting/openai-formatter";
 import { AnthropicFormatter } from "../pipeline/providers/formatting/anthropic-formatter";
 import { GoogleFormatter } from "../pipeline/providers/formatting/google-formatter";
+import { applySpokenCommands } from "../pipeline/processing/spoken-commands";
+import { assessTranscriptionQuality } from "../pipeline/processing/review-heuristics";
+import { normalizeText } from "../pipeline/processing/text-normalization";
+import { splitIntoMessages } from "../pipeline/processing/message-splitting";
+import { applySymbolShortcuts } from "../pipeline/processing/symbol-shortcuts";
+import { applyProfanityPolicy } from "../pipeline/processing/profanity-filter";
+import {
+  applyVocabularyCorrection,
+  buildVocabularyCorrectionPrompt,
+  MAX_CORRECTION_WORDS,
+  parseVocabularyCorrectionResponse,
+  VOCABULARY_CORRECTION_SYSTEM_PROMPT,
+} from "../pipeline/processing/vocabulary-correction";
+import {
+  applyReplaceCommand,
+  diffForReinjection,
+  parseEditCommand,
+} from "../pipeline/processing/edit-commands";
 import { ModelService } from "../services/model-service";
 import { SettingsService } from "../services/settings-service";
 import { TelemetryService } from "../services/telemetry-service";
 import type { NativeBridge } from "./platform/native-bridge-service";
 import type { OnboardingService } from "./onboarding-service";
-import { createTranscription } from "../db/transcriptions";
+import {
+  createTranscription,
+  findRecentDuplicate,
+  getTranscriptionById,
+  updateTranscription,
+} from "../db/transcriptions";
 import { getVocabulary } from "../db/vocabulary";
+import { getSymbolShortcuts } from "../db/symbol-shortcuts";
+import { recordModelUsage } from "../db/model-usage";
+import {
+  applyAudioFileNameTemplate,
+  DEFAULT_AUDIO_FILENAME_TEMPLATE,
+} from "../utils/audio-file-naming";
+import { deleteAudioFiles } from "../utils/audio-file-cleanup";
+import { isImportedModelId } from "../utils/huggingface";
+import { isLocalModelId } from "../utils/local-model-file";
 import { logger } from "../main/logger";
 import { v4 as uuid } from "uuid";
 import { VADService } from "./vad-service";
 import { Mutex } from "async-mutex";
 import { dialog } from "electron";
-import { AVAILABLE_MODELS, type OpenAISpeechModel } from "../constants/models";
+import {
+  AVAILABLE_MODELS,
+  getLocalRuntime,
+  type OpenAISpeechModel,
+} from "../constants/models";
+import { PERFORMANCE_PROFILES } from "../constants/performance-presets";
 
 const TRANSCRIPTION_API_ENDPOINTS: Record<string, string> = {
   OpenAI: "https://api.openai.com/v1/audio/transcriptions",
@@ -33,12 +79,64 @@ const TRANSCRIPTION_API_ENDPOINTS: Record<string, string> = {
   Grok: "https://api.x.ai/v1/audio/transcriptions",
 };
 
+// Streaming chunks are 16 kHz mono
+const STREAM_SAMPLE_RATE = 16000;
+
+// Repeats of a dictation within this window count as accidental duplicates
+const DUPLICATE_WINDOW_MS = 2 * 60 * 1000;
+
+// How long after a dictation a follow-up voice edit may target it
+const VOICE_EDIT_WINDOW_MS = 2 * 60 * 1000;
+
+// Per-attempt formatting limit when the mode doesn't configure one
+const DEFAULT_FORMATTING_TIMEOUT_MS = 20_000;
+
+// Summaries run on demand over possibly long recordings
+const SUMMARY_TIMEOUT_MS = 90_000;
+const SUMMARY_SYSTEM_PROMPT = [
+  "You summarize dictated text and meeting transcripts.",
+  "Write a short overview sentence, then the key points as '- ' bullets.",
+  "List decisions and action items (with owners, if named) under separate 'Decisions:' and 'Action items:' headings when there are any.",
+  "Use the language of the transcript. Do not invent details.",
+  "Return only the summary.",
+].join("\n");
+
+const WEEKLY_DIGEST_SYSTEM_PROMPT = [
+  "You write a weekly digest of a person's dictations.",
+  "The input lists excerpts of what they dictated this week, one per line.",
+  "Write two or three sentences on the main themes, then up to five '- ' bullets with notable topics or follow-ups.",
+  "Use the language of the excerpts. Do not invent details.",
+  "Return only the digest.",
+].join("\n");
+
+// Folds each chunk's confidence into the session's running total, weighted
+// by how much text the chunk produced
+function confidenceReporter(session: StreamingSession) {
+  return (text: string, confidence: number) => {
+    const weight = text.trim().length;
+    session.confidence.sum += confidence * weight;
+    session.confidence.weight += weight;
+  };
+}
+
+// Models to try in order: the primary, then the fallback if it's different
+function formattingCandidates(
+  config: FormatterConfig | null | undefined,
+): string[] {
+  return [config?.modelId, config?.fallbackModelId].filter(
+    (id, index, ids): id is string => !!id && ids.indexOf(id) === index,
+  );
+}
+
 /**
  * Service for audio transcription and optional formatting
  */
 export class TranscriptionService {
   private whisperProvider: WhisperProvider | null = null;
-  private whisperProvidersByModelId = new Map<string, WhisperProvider>();
+  private localProvidersByModelId = new Map<
+    string,
+    LocalTranscriptionProvider
+  >();
   private apiProviders = new Map<string, OpenAITranscriptionProvider>();
   private currentProvider: TranscriptionProvider | null = null;
   private streamingSessions = new Map<string, StreamingSession>();
@@ -47,11 +145,19 @@ export class TranscriptionService {
   private vadMutex: Mutex;
   private transcriptionMutex: Mutex;
   private modelLoadMutex: Mutex;
+  private modelChangeMutex: Mutex;
   private telemetryService: TelemetryService;
   private modelService: ModelService;
   private modelWasPreloaded: boolean = false;
+  private benchmarkRunning = false;
 
   private resolvedUseGPU: boolean | undefined;
+  private lastDictation: {
+    transcriptionId: number;
+    text: string;
+    bundleId?: string;
+    completedAt: number;
+  } | null = null;
 
   constructor(
     modelService: ModelService,
@@ -66,6 +172,7 @@ export class TranscriptionService {
     this.vadMutex = new Mutex();
     this.transcriptionMutex = new Mutex();
     this.modelLoadMutex = new Mutex();
+    this.modelChangeMutex = new Mutex();
     this.telemetryService = telemetryService;
     this.modelService = modelService;
   }
@@ -94,18 +201,26 @@ export class TranscriptionService {
     return this.resolvedUseGPU;
   }
 
-  private async getOrCreateWhisperProvider(
+  // Provider for one local model, running on the model's runtime
+  private async getOrCreateLocalProvider(
     modelId: string,
-  ): Promise<WhisperProvider> {
+  ): Promise<LocalTranscriptionProvider> {
     return this.modelLoadMutex.runExclusive(async () => {
-      const cached = this.whisperProvidersByModelId.get(modelId);
+      const cached = this.localProvidersByModelId.get(modelId);
       if (cached) {
         return cached;
       }
 
-      const useGPU = await this.resolveUseGPU();
-      const provider = new WhisperProvider(this.modelService, modelId, useGPU);
-      this.whisperProvidersByModelId.set(modelId, provider);
+      const runtime = getLocalRuntime(modelId);
+      const provider =
+        runtime === "whisper"
+          ? new WhisperProvider(
+              this.modelService,
+              modelId,
+              await this.resolveUseGPU(),
+            )
+          : new OnnxSpeechProvider(this.modelService, modelId, runtime);
+      this.localProvidersByModelId.set(modelId, provider);
       return provider;
     });
   }
@@ -136,9 +251,14 @@ export class TranscriptionService {
       return apiProvider;
     }
 
-    // Use model-scoped whisper provider for offline model overrides
-    if (model?.setup === "offline" && effectiveModelId) {
-      const provider = await this.getOrCreateWhisperProvider(effectiveModelId);
+    // Use model-scoped local provider for offline model overrides,
+    // including models imported from Hugging Face or a file
+    if (
+      model?.setup === "offline" ||
+      isImportedModelId(effectiveModelId) ||
+      isLocalModelId(effectiveModelId)
+    ) {
+      const provider = await this.getOrCreateLocalProvider(effectiveModelId);
       this.currentProvider = provider;
       return provider;
     }
@@ -213,12 +333,16 @@ export class TranscriptionService {
       }
 
       const model = AVAILABLE_MODELS.find((item) => item.id === effectiveModelId);
-      if (!model) {
+      if (
+        !model &&
+        !isImportedModelId(effectiveModelId) &&
+        !isLocalModelId(effectiveModelId)
+      ) {
         preloadFallbackProvider = true;
         continue;
       }
 
-      if (model.setup === "api") {
+      if (model?.setup === "api") {
         continue;
       }
 
@@ -234,10 +358,10 @@ export class TranscriptionService {
     };
   }
 
-  private async disposeUnusedWhisperProviders(
+  private async disposeUnusedLocalProviders(
     requiredModelIds: Set<string>,
   ): Promise<void> {
-    for (const [modelId, provider] of this.whisperProvidersByModelId) {
+    for (const [modelId, provider] of this.localProvidersByModelId) {
       if (requiredModelIds.has(modelId)) {
         continue;
       }
@@ -245,12 +369,12 @@ export class TranscriptionService {
       try {
         await provider.dispose();
       } catch (error) {
-        logger.transcription.warn("Failed to dispose Whisper provider", {
+        logger.transcription.warn("Failed to dispose local provider", {
           modelId,
           error: error instanceof Error ? error.message : String(error),
         });
       } finally {
-        this.whisperProvidersByModelId.delete(modelId);
+        this.localProvidersByModelId.delete(modelId);
       }
     }
   }
@@ -323,7 +447,7 @@ export class TranscriptionService {
 
     for (const modelId of modelIds) {
       try {
-        const provider = await this.getOrCreateWhisperProvider(modelId);
+        const provider = await this.getOrCreateLocalProvider(modelId);
         await provider.preloadModel();
         loadedCount++;
         logger.transcription.info("Preloaded mode Whisper model", { modelId });
@@ -348,7 +472,7 @@ export class TranscriptionService {
       }
     }
 
-    await this.disposeUnusedWhisperProviders(requiredModelIds);
+    await this.disposeUnusedLocalProviders(requiredModelIds);
 
     if (preloadAttempts > 0 && loadedCount === 0) {
       throw new Error("Failed to preload any Whisper models");
@@ -370,6 +494,32 @@ export class TranscriptionService {
     }
   }
 
+  /**
+   * Measure how fast a downloaded local model transcribes on this machine,
+   * using the same GPU setting as dictation
+   */
+  async benchmarkModel(modelId: string): Promise<ModelBenchmarkResult> {
+    if (this.benchmarkRunning) {
+      throw new Error("A benchmark is already running");
+    }
+    if (getLocalRuntime(modelId) !== "whisper") {
+      throw new Error("Benchmarks are only available for Whisper models");
+    }
+    const downloadedModels = await this.modelService.getValidDownloadedModels();
+    const modelPath = downloadedModels[modelId]?.localPath;
+    if (!modelPath) {
+      throw new Error(`Model is not downloaded: ${modelId}`);
+    }
+
+    this.benchmarkRunning = true;
+    try {
+      const useGPU = await this.resolveUseGPU();
+      return await benchmarkWhisperModel(modelId, modelPath, useGPU);
+    } finally {
+      this.benchmarkRunning = false;
+    }
+  }
+
   /**
    * Clear cached API providers (e.g., when API keys are updated)
    */
@@ -378,14 +528,16 @@ export class TranscriptionService {
   }
 
   /**
-   * Handle model change - load new model if preloading is enabled
-   * Uses mutex to serialize concurrent model loads
+   * Handle model change - load new model if preloading is enabled, or free
+   * the loaded ones if it was turned off.
+   * Uses its own mutex to serialize changes; modelLoadMutex is taken per
+   * provider while preloading, so holding it here would deadlock.
    */
   async handleModelChange(): Promise<void> {
     // Clear cached API providers since model or API key may have changed
     this.apiProviders.clear();
 
-    await this.modelLoadMutex.runExclusive(async () => {
+    await this.modelChangeMutex.runExclusive(async () => {
       try {
         this.modelWasPreloaded = false;
 
@@ -412,6 +564,8 @@ export class TranscriptionService {
                 "No offline models available to preload",
               );
             }
+          } else {
+            await this.unloadModels();
           }
         }
       } catch (error) {
@@ -421,6 +575,50 @@ export class TranscriptionService {
     });
   }
 
+  /**
+   * The selected speech model, if it's a local model held in memory
+   */
+  async getLoadedModel(): Promise<string | null> {
+    const selectedModelId = await this.modelService.getSelectedModel();
+    if (!selectedModelId) {
+      return null;
+    }
+    const provider = this.localProvidersByModelId.get(selectedModelId);
+    return provider?.isModelLoaded() ? selectedModelId : null;
+  }
+
+  /**
+   * Free the memory held by local models. They're loaded again on the next
+   * dictation, or by the next preload.
+   */
+  async unloadModels(): Promise<void> {
+    if (this.streamingSessions.size > 0) {
+      throw new Error("Models can't be unloaded while recording");
+    }
+    await this.modelLoadMutex.runExclusive(async () => {
+      await this.disposeUnusedLocalProviders(new Set());
+      await this.whisperProvider?.dispose();
+      this.whisperProvider = null;
+      this.modelWasPreloaded = false;
+    });
+    logger.transcription.info("Unloaded local models");
+  }
+
+  /**
+   * Drop the workers that hold the old file of an updated model, then
+   * preload again so the new file is used
+   */
+  async handleModelUpdated(modelId: string): Promise<void> {
+    await this.modelLoadMutex.runExclusive(async () => {
+      await this.localProvidersByModelId.get(modelId)?.dispose();
+      this.localProvidersByModelId.delete(modelId);
+      // The fallback provider may be using the same model
+      await this.whisperProvider?.dispose();
+      this.whisperProvider = null;
+    });
+    await this.handleModelChange();
+  }
+
   /**
    * Process a single audio chunk in streaming mode
    * For finalization, use finalizeSession() instead
@@ -482,6 +680,8 @@ export class TranscriptionService {
           transcriptionResults: [],
           firstChunkReceivedAt: performance.now(),
           recordingStartedAt: recordingStartedAt,
+          confidence: { sum: 0, weight: 0 },
+          audioSamples: 0,
         };
 
         this.streamingSessions.set(sessionId, session);
@@ -491,6 +691,8 @@ export class TranscriptionService {
         });
       }
 
+      session.audioSamples += audioChunk.length;
+
       // Direct frame to Whisper - it will handle aggregation and VAD internally
       const previousChunk =
         session.transcriptionResults.length > 0
@@ -515,6 +717,11 @@ export class TranscriptionService {
           previousChunk,
           aggregatedTranscription: aggregatedTranscription || undefined,
           language: session.context.sharedData.userPreferences?.language,
+          chunking:
+            PERFORMANCE_PROFILES[
+              session.context.sharedData.performancePreset ?? "balanced"
+            ],
+          reportConfidence: confidenceReporter(session),
         },
       });
 
@@ -573,14 +780,14 @@ export class TranscriptionService {
     audioFilePath?: string;
     recordingStartedAt?: number;
     recordingStoppedAt?: number;
-  }): Promise<string> {
+  }): Promise<FinalizedTranscription> {
     const { sessionId, audioFilePath, recordingStartedAt, recordingStoppedAt } =
       options;
 
     const session = this.streamingSessions.get(sessionId);
     if (!session) {
       logger.transcription.warn("No session found to finalize", { sessionId });
-      return "";
+      return { text: "" };
     }
 
     // Update session timestamps
@@ -589,7 +796,18 @@ export class TranscriptionService {
     if (recordingStartedAt && !session.recordingStartedAt) {
       session.recordingStartedAt = recordingStartedAt;
     }
+    // Length of the recording, from the audio actually received; falls back
+    // to the record button timestamps if no chunk arrived
+    session.context.sharedData.audioMetadata.duration =
+      session.audioSamples > 0
+        ? session.audioSamples / STREAM_SAMPLE_RATE
+        : session.recordingStartedAt && session.recordingStoppedAt
+          ? (session.recordingStoppedAt - session.recordingStartedAt) / 1000
+          : undefined;
+
+    await this.applyStopTimeMode(session);
 
+    const speechModelId = session.context.sharedData.speechModelId;
     const formatterConfig = session.context.sharedData.formatter;
     let activeProvider: TranscriptionProvider | null = null;
 
@@ -604,7 +822,6 @@ export class TranscriptionService {
           : undefined;
       const aggregatedTranscription = session.transcriptionResults.join("");
 
-      const speechModelId = session.context.sharedData.speechModelId;
       activeProvider = await this.selectProvider(speechModelId);
       const finalTranscription = await activeProvider.flush({
         sessionId,
@@ -614,6 +831,7 @@ export class TranscriptionService {
         aggregatedTranscription: aggregatedTranscription || undefined,
         language: session.context.sharedData.userPreferences?.language,
         formattingEnabled: false,
+        reportConfidence: confidenceReporter(session),
       });
 
       if (finalTranscription.trim()) {
@@ -641,6 +859,49 @@ export class TranscriptionService {
       );
     }
 
+    // Kept alongside the final text so the literal speech can be recovered
+    const rawTranscription = completeTranscription;
+
+    // Interpret spoken punctuation and dictation commands before formatting
+    if (session.context.sharedData.spokenCommandsEnabled) {
+      const beforeCommands = completeTranscription;
+      completeTranscription = applySpokenCommands(
+        completeTranscription,
+        session.context.sharedData.userPreferences?.language,
+      );
+      if (beforeCommands !== completeTranscription) {
+        logger.transcription.info("Applied spoken commands", {
+          sessionId,
+          originalLength: beforeCommands.length,
+          newLength: completeTranscription.length,
+        });
+      }
+    }
+
+    // Replace spoken emoji/symbol phrases ("thumbs up emoji" → 👍)
+    const beforeShortcuts = completeTranscription;
+    completeTranscription = applySymbolShortcuts(
+      completeTranscription,
+      session.context.sharedData.symbolShortcuts,
+    );
+    if (beforeShortcuts !== completeTranscription) {
+      logger.transcription.info("Applied symbol shortcuts", { sessionId });
+    }
+
+    // Follow-up voice edit of the previous dictation
+    if (session.context.sharedData.voiceEditingEnabled) {
+      const edit = await this.tryVoiceEdit(session, completeTranscription);
+      if (edit) {
+        // The edit command has no transcription of its own to keep its
+        // recording with
+        if (audioFilePath) {
+          await deleteAudioFiles([audioFilePath]);
+        }
+        this.streamingSessions.delete(sessionId);
+        return edit;
+      }
+    }
+
     let formattingDuration: number | undefined;
 
     logger.transcription.info("Finalizing streaming session", {
@@ -652,164 +913,87 @@ export class TranscriptionService {
     // Fetch formatter config on-demand
     let formattingUsed = false;
     let formattingModel: string | undefined;
+    let formattingFellBack = false;
 
     if (!formatterConfig || !formatterConfig.enabled) {
       logger.transcription.debug("Formatting skipped: disabled in config");
     } else if (!completeTranscription.trim().length) {
       logger.transcription.debug("Formatting skipped: empty transcription");
     } else {
-      // Get formatting model and look up provider
-      const modelId = formatterConfig.modelId;
-      if (!modelId) {
+      // Try the primary model, then the fallback if it errors or times out
+      const candidates = formattingCandidates(formatterConfig);
+
+      if (candidates.length === 0) {
         logger.transcription.debug(
           "Formatting skipped: no formatting model selected",
         );
-      } else {
-        const allModels = await this.modelService.getSyncedProviderModels();
-        const model = allModels.find(
-          (m) => m.id === modelId && m.type === "language",
-        );
+      }
 
-        if (!model) {
-          logger.transcription.warn("Formatting skipped: model not found", {
-            modelId,
-          });
-        } else if (model.provider === "OpenRouter") {
-          const config = await this.settingsService.getOpenRouterConfig();
-          if (!config?.apiKey) {
-            logger.transcription.warn(
-              "Formatting skipped: OpenRouter API key missing",
-            );
-          } else {
-            logger.transcription.info("Starting formatting", {
-              sessionId,
-              provider: model.provider,
-              model: modelId,
-            });
-            const provider = new OpenRouterProvider(config.apiKey, modelId);
-            const result = await this.formatWithProvider(
-              provider,
-              sessionId,
-              completeTranscription,
-              session,
-            );
-            if (result) {
-              completeTranscription = result.text;
-              formattingDuration = result.duration;
-              formattingUsed = true;
-              formattingModel = modelId;
-            }
-          }
-        } else if (model.provider === "Ollama") {
-          const config = await this.settingsService.getOllamaConfig();
-          if (!config?.url) {
-            logger.transcription.warn("Formatting skipped: Ollama URL missing");
-          } else {
-            logger.transcription.info("Starting formatting", {
-              sessionId,
-              provider: model.provider,
-              model: modelId,
-            });
-            const provider = new OllamaFormatter(config.url, modelId);
-            const result = await this.formatWithProvider(
-              provider,
-              sessionId,
-              completeTranscription,
-              session,
-            );
-            if (result) {
-              completeTranscription = result.text;
-              formattingDuration = result.duration;
-              formattingUsed = true;
-              formattingModel = modelId;
-            }
-          }
-        } else if (model.provider === "OpenAI") {
-          const config = await this.settingsService.getOpenAIConfig();
-          if (!config?.apiKey) {
-            logger.transcription.warn(
-              "Formatting skipped: OpenAI API key missing",
-            );
-          } else {
-            logger.transcription.info("Starting formatting", {
-              sessionId,
-              provider: model.provider,
-              model: modelId,
-            });
-            const provider = new OpenAIFormatter(config.apiKey, modelId);
-            const result = await this.formatWithProvider(
-              provider,
-              sessionId,
-              completeTranscription,
-              session,
-            );
-            if (result) {
-              completeTranscription = result.text;
-              formattingDuration = result.duration;
-              formattingUsed = true;
-              formattingModel = modelId;
-            }
-          }
-        } else if (model.provider === "Anthropic") {
-          const config = await this.settingsService.getAnthropicConfig();
-          if (!config?.apiKey) {
-            logger.transcription.warn(
-              "Formatting skipped: Anthropic API key missing",
-            );
-          } else {
-            logger.transcription.info("Starting formatting", {
-              sessionId,
-              provider: model.provider,
-              model: modelId,
-            });
-            const provider = new AnthropicFormatter(config.apiKey, modelId);
-            const result = await this.formatWithProvider(
-              provider,
-              sessionId,
-              completeTranscription,
-              session,
-            );
-            if (result) {
-              completeTranscription = result.text;
-              formattingDuration = result.duration;
-              formattingUsed = true;
-              formattingModel = modelId;
-            }
-          }
-        } else if (model.provider === "Google") {
-          const config = await this.settingsService.getGoogleConfig();
-          if (!config?.apiKey) {
-            logger.transcription.warn(
-              "Formatting skipped: Google API key missing",
-            );
-          } else {
-            logger.transcription.info("Starting formatting", {
-              sessionId,
-              provider: model.provider,
-              model: modelId,
-            });
-            const provider = new GoogleFormatter(config.apiKey, modelId);
-            const result = await this.formatWithProvider(
-              provider,
-              sessionId,
-              completeTranscription,
-              session,
-            );
-            if (result) {
-              completeTranscription = result.text;
-              formattingDuration = result.duration;
-              formattingUsed = true;
-              formattingModel = modelId;
-            }
-          }
-        } else {
-          logger.transcription.warn(
-            "Formatting skipped: unsupported provider",
-            {
-              provider: model.provider,
-            },
-          );
+      for (const modelId of candidates) {
+        const provider = await this.createFormattingProvider(modelId);
+        if (!provider) {
+          continue;
         }
+        logger.transcription.info("Starting formatting", {
+          sessionId,
+          provider: provider.name,
+          model: modelId,
+          isFallback: modelId !== formatterConfig.modelId,
+        });
+        const result = await this.formatWithProvider(
+          provider,
+          sessionId,
+          completeTranscription,
+          session,
+          formatterConfig.timeoutMs ?? DEFAULT_FORMATTING_TIMEOUT_MS,
+        );
+        if (result) {
+          completeTranscription = result.text;
+          formattingDuration = result.duration;
+          formattingUsed = true;
+          formattingModel = modelId;
+          break;
+        }
+      }
+      if (!formattingUsed && candidates.length > 0) {
+        logger.transcription.warn("Formatting failed, using unformatted text", {
+          sessionId,
+          models: candidates,
+        });
+      }
+      formattingFellBack =
+        formattingUsed && formattingModel !== formatterConfig.modelId;
+    }
+
+    // Fix misheard vocabulary terms; anything else the model changes is rejected
+    let vocabularyCorrections = 0;
+    if (session.context.sharedData.vocabularyCorrectionEnabled) {
+      const corrected = await this.correctVocabularyTerms(
+        sessionId,
+        completeTranscription,
+        session,
+      );
+      if (corrected) {
+        completeTranscription = corrected.text;
+        vocabularyCorrections = corrected.corrections;
+      }
+    }
+
+    // Normalize numbers, dates and currency (deterministic, runs after the LLM)
+    const normalization = session.context.sharedData.normalization;
+    if (normalization?.enabled) {
+      const beforeNormalization = completeTranscription;
+      completeTranscription = normalizeText(
+        completeTranscription,
+        normalization,
+        session.context.sharedData.userPreferences?.language,
+      );
+      if (beforeNormalization !== completeTranscription) {
+        logger.transcription.info("Applied text normalization", {
+          sessionId,
+          originalLength: beforeNormalization.length,
+          newLength: completeTranscription.length,
+        });
       }
     }
 
@@ -831,29 +1015,138 @@ export class TranscriptionService {
       }
     }
 
-    // Save directly to database
-    logger.transcription.info("Saving transcription with audio file", {
-      sessionId,
-      audioFilePath,
-      hasAudioFile: !!audioFilePath,
-    });
+    // Mask or drop profanity last so no earlier step can reintroduce it
+    const profanityPolicy = session.context.sharedData.profanityPolicy;
+    if (profanityPolicy && profanityPolicy !== "keep") {
+      const beforeProfanity = completeTranscription;
+      completeTranscription = applyProfanityPolicy(
+        completeTranscription,
+        profanityPolicy,
+      );
+      if (beforeProfanity !== completeTranscription) {
+        logger.transcription.info("Applied profanity policy", {
+          sessionId,
+          policy: profanityPolicy,
+        });
+      }
+    }
 
-    await createTranscription({
+    // Flag likely-bad output for the review queue
+    const transcriptionSettings =
+      await this.settingsService.getTranscriptionSettings();
+    const confidence =
+      session.confidence.weight > 0
+        ? session.confidence.sum / session.confidence.weight
+        : null;
+    const review = assessTranscriptionQuality({
       text: completeTranscription,
-      language: session.context.sharedData.userPreferences?.language || "en",
-      duration: session.context.sharedData.audioMetadata?.duration,
-      speechModel: "whisper-local",
-      formattingModel,
-      audioFile: audioFilePath,
-      meta: {
-        sessionId,
-        source: session.context.sharedData.audioMetadata?.source,
-        vocabularySize: session.context.sharedData.vocabulary?.length || 0,
-        formattingStyle:
-          session.context.sharedData.userPreferences?.formattingStyle,
-      },
+      confidence,
+      audioDurationSeconds: session.context.sharedData.audioMetadata?.duration,
+      confidenceThreshold: transcriptionSettings?.confidenceThreshold,
     });
 
+    // A double-triggered shortcut produces the same text twice
+    const duplicatePolicy =
+      (await this.settingsService.getHistorySettings())?.duplicatePolicy ??
+      "flag";
+    const duplicate =
+      duplicatePolicy === "keep"
+        ? null
+        : await findRecentDuplicate(
+            completeTranscription,
+            new Date(Date.now() - DUPLICATE_WINDOW_MS),
+          );
+    if (duplicate && duplicatePolicy === "flag") {
+      review.needsReview = true;
+      review.reasons.push("duplicate");
+    }
+    const skipDuplicate = duplicate !== null && duplicatePolicy === "skip";
+    if (skipDuplicate) {
+      logger.transcription.info("Not saving duplicate transcription", {
+        sessionId,
+        duplicateOf: duplicate.id,
+      });
+      if (audioFilePath) {
+        await deleteAudioFiles([audioFilePath]);
+      }
+    }
+
+    if (review.needsReview) {
+      logger.transcription.info("Transcription flagged for review", {
+        sessionId,
+        reasons: review.reasons,
+      });
+    }
+
+    // Give the recording a readable name now that the text is known
+    let savedAudioFilePath = audioFilePath;
+    if (audioFilePath && !skipDuplicate) {
+      const recordingSettings =
+        await this.settingsService.getRecordingSettings();
+      savedAudioFilePath = await applyAudioFileNameTemplate(
+        audioFilePath,
+        recordingSettings?.fileNameTemplate || DEFAULT_AUDIO_FILENAME_TEMPLATE,
+        {
+          sessionId,
+          // recordingStartedAt is a performance.now() timestamp
+          recordedAt: new Date(
+            session.recordingStartedAt
+              ? Date.now() - (performance.now() - session.recordingStartedAt)
+              : Date.now(),
+          ),
+          modeName: session.context.sharedData.modeName,
+          appName:
+            session.context.sharedData.accessibilityContext?.context
+              ?.application?.name,
+          text: completeTranscription,
+        },
+      );
+    }
+
+    // Save directly to database
+    if (!skipDuplicate) {
+      logger.transcription.info("Saving transcription with audio file", {
+        sessionId,
+        audioFilePath: savedAudioFilePath,
+        hasAudioFile: !!savedAudioFilePath,
+      });
+    }
+
+    const targetApp =
+      session.context.sharedData.accessibilityContext?.context?.application;
+    // A skipped duplicate stands in for its earlier copy
+    const savedTranscription = skipDuplicate && duplicate
+      ? duplicate
+      : await createTranscription({
+          text: completeTranscription,
+          rawText: rawTranscription,
+          language:
+            session.context.sharedData.userPreferences?.language || "en",
+          duration: session.context.sharedData.audioMetadata?.duration,
+          // Without any selection the best downloaded model was used
+          speechModel: speechModelId ?? "whisper-local",
+          formattingModel,
+          audioFile: savedAudioFilePath,
+          confidence,
+          needsReview: review.needsReview,
+          targetAppBundleId: targetApp?.bundleIdentifier ?? null,
+          targetAppName: targetApp?.name ?? null,
+          meta: {
+            sessionId,
+            source: session.context.sharedData.audioMetadata?.source,
+            modeId: session.context.sharedData.modeId,
+            modeName: session.context.sharedData.modeName,
+            vocabularySize: session.context.sharedData.vocabulary?.length || 0,
+            formattingStyle:
+              session.context.sharedData.userPreferences?.formattingStyle,
+            reviewReasons:
+              review.reasons.length > 0 ? review.reasons : undefined,
+            formattingModel,
+            formattingFallback: formattingFellBack || undefined,
+            vocabularyCorrections: vocabularyCorrections || undefined,
+          },
+        });
+
     // Track transcription completion
     const completionTime = performance.now();
 
@@ -872,10 +1165,25 @@ export class TranscriptionService {
       ? completionTime - session.recordingStartedAt
       : undefined;
 
-    const selectedModel = await this.modelService.getSelectedModel();
     const audioDurationSeconds =
       session.context.sharedData.audioMetadata?.duration;
 
+    // Per-model totals for the models screen
+    if (speechModelId) {
+      void recordModelUsage({
+        modelType: "speech",
+        modelId: speechModelId,
+        audioSeconds: audioDurationSeconds,
+      });
+    }
+    if (formattingModel) {
+      void recordModelUsage({
+        modelType: "formatting",
+        modelId: formattingModel,
+        audioSeconds: audioDurationSeconds,
+      });
+    }
+
     // Get native binding info if using local whisper
     let whisperNativeBinding: string | undefined;
     if (activeProvider instanceof WhisperProvider) {
@@ -889,7 +1197,7 @@ export class TranscriptionService {
 
     this.telemetryService.trackTranscriptionCompleted({
       session_id: sessionId,
-      model_id: selectedModel!,
+      model_id: speechModelId!,
       model_preloaded: this.modelWasPreloaded,
       whisper_native_binding: whisperNativeBinding,
       total_duration_ms: totalDuration || 0,
@@ -911,39 +1219,253 @@ export class TranscriptionService {
       vocabulary_size: session.context.sharedData.vocabulary?.length || 0,
     });
 
+    // Long output for chat apps goes out as several messages
+    const splitting = session.context.sharedData.messageSplitting;
+    const messages = splitting?.enabled
+      ? splitIntoMessages(completeTranscription, splitting.maxLength)
+      : [];
+
+    // Sent messages can't be edited by backspacing, so only plain
+    // insertions are eligible for voice edits
+    this.lastDictation =
+      messages.length > 1
+        ? null
+        : {
+            transcriptionId: savedTranscription.id,
+            text: completeTranscription,
+            bundleId:
+              session.context.sharedData.accessibilityContext?.context
+                ?.application?.bundleIdentifier ?? undefined,
+            completedAt: Date.now(),
+          };
+
     this.streamingSessions.delete(sessionId);
 
     logger.transcription.info("Streaming session completed", { sessionId });
-    return completeTranscription;
+    if (messages.length > 1) {
+      logger.transcription.info("Split transcription into messages", {
+        sessionId,
+        messageCount: messages.length,
+      });
+      return {
+        text: completeTranscription,
+        messages,
+        messageDelayMs: splitting!.delayMs,
+      };
+    }
+    return { text: completeTranscription };
   }
 
-  private async buildContext(): Promise<PipelineContext> {
-    // Create default context
-    const context = createDefaultContext(uuid());
+  /**
+   * Apply the utterance as an edit of the previous dictation if it is an edit
+   * command. Returns null to process the utterance as a normal dictation.
+   */
+  private async tryVoiceEdit(
+    session: StreamingSession,
+    utterance: string,
+  ): Promise<FinalizedTranscription | null> {
+    const last = this.lastDictation;
+    if (!last || Date.now() - last.completedAt > VOICE_EDIT_WINDOW_MS) {
+      return null;
+    }
 
-    // Resolve effective mode at session start:
-    const activeMode = await this.settingsService.getActiveMode();
-    let effectiveMode = activeMode;
+    const { sessionId, sharedData } = session.context;
+    const command = parseEditCommand(
+      utterance,
+      sharedData.userPreferences?.language,
+    );
+    if (!command) {
+      return null;
+    }
 
-    const appContext = this.nativeBridge?.getAccessibilityContext();
-    const bundleId = appContext?.context?.application?.bundleIdentifier;
+    const accessibility = sharedData.accessibilityContext?.context;
+    const bundleId = accessibility?.application?.bundleIdentifier ?? undefined;
+    if (bundleId !== last.bundleId) {
+      logger.transcription.debug("Voice edit skipped: different application", {
+        sessionId,
+      });
+      return null;
+    }
+
+    let edited: string | null = null;
+    if (command.type === "replace") {
+      edited = applyReplaceCommand(
+        last.text,
+        command.target,
+        command.replacement,
+      );
+    } else {
+      const modelId = sharedData.formatter?.modelId;
+      const provider = modelId
+        ? await this.createFormattingProvider(modelId)
+        : null;
+      if (provider) {
+        try {
+          const rewritten = await provider.format({
+            text: last.text,
+            context: {
+              vocabulary: sharedData.vocabulary,
+              accessibilityContext: sharedData.accessibilityContext,
+              customInstructions: [
+                sharedData.customInstructions,
+                `Apply this edit requested by the user: "${command.instruction}"`,
+              ]
+                .filter(Boolean)
+                .join("\n\n"),
+            },
+          });
+          // Keep the leading space the original insertion relied on
+          const leadingSpace = last.text.match(/^\s*/)?.[0] ?? "";
+          edited = leadingSpace + rewritten.trimStart();
+        } catch (error) {
+          logger.transcription.error("Voice edit rewrite failed", {
+            sessionId,
+            error,
+          });
+        }
+      }
+    }
+
+    if (edited === null || edited === last.text) {
+      logger.transcription.info(
+        "Voice edit could not be applied, treating as dictation",
+        { sessionId, command: command.type },
+      );
+      return null;
+    }
+
+    await updateTranscription(last.transcriptionId, { text: edited });
+    const previousText = last.text;
+    this.lastDictation = { ...last, text: edited, completedAt: Date.now() };
+
+    // Only re-inject when the cursor still sits right after the previous
+    // insertion; otherwise the backspaces would eat unrelated text.
+    const selection = accessibility?.textSelection;
+    const preSelection = selection?.preSelectionText;
+    const cursorFollowsPrevious =
+      !selection?.selectedText &&
+      !!preSelection &&
+      (preSelection.endsWith(previousText) ||
+        previousText.endsWith(preSelection));
+
+    logger.transcription.info("Applied voice edit to previous dictation", {
+      sessionId,
+      transcriptionId: last.transcriptionId,
+      command: command.type,
+      reinjected: cursorFollowsPrevious,
+    });
+
+    if (!cursorFollowsPrevious) {
+      return { text: "", replaceLength: 0 };
+    }
+
+    const { replaceLength, insertText } = diffForReinjection(
+      previousText,
+      edited,
+    );
+    return { text: insertText, replaceLength };
+  }
+
+  /**
+   * Mode bound to the given app, or the active mode when none is bound
+   */
+  private async resolveEffectiveMode(
+    bundleId: string | null | undefined,
+  ): Promise<ModeConfig> {
     if (bundleId) {
       const appBoundMode =
         await this.settingsService.findModeByBundleId(bundleId);
       if (appBoundMode) {
-        effectiveMode = appBoundMode;
+        return appBoundMode;
       }
     }
+    return await this.settingsService.getActiveMode();
+  }
+
+  /**
+   * Snapshot the settings that shape the output text (not recognition)
+   */
+  private async applyModeOutputSettings(
+    context: PipelineContext,
+    mode: ModeConfig,
+  ): Promise<void> {
+    context.sharedData.modeId = mode.id;
+    context.sharedData.modeName = mode.name;
+    context.sharedData.customInstructions = mode.customInstructions;
+    context.sharedData.formatter =
+      mode.formatterConfig ?? (await this.settingsService.getFormatterConfig());
+    const preset = context.sharedData.performancePreset ?? "balanced";
+    if (
+      context.sharedData.formatter?.enabled &&
+      !PERFORMANCE_PROFILES[preset].formattingAllowed
+    ) {
+      context.sharedData.formatter = {
+        ...context.sharedData.formatter,
+        enabled: false,
+      };
+    }
+    context.sharedData.spokenCommandsEnabled =
+      mode.spokenCommandsEnabled ?? false;
+    context.sharedData.normalization = mode.normalization;
+    context.sharedData.voiceEditingEnabled = mode.voiceEditingEnabled ?? false;
+    context.sharedData.messageSplitting = mode.messageSplitting;
+    context.sharedData.profanityPolicy = mode.profanityPolicy;
+    context.sharedData.vocabularyCorrectionEnabled =
+      (mode.vocabularyCorrectionEnabled ?? false) &&
+      PERFORMANCE_PROFILES[preset].formattingAllowed;
+  }
+
+  /**
+   * The text is pasted into whichever app is focused when recording stops,
+   * which may not be where dictation started. Re-resolve the mode for that
+   * app and swap in its output settings; language and speech model stay as
+   * they were since recognition has already run.
+   */
+  private async applyStopTimeMode(session: StreamingSession): Promise<void> {
+    const appContext = this.nativeBridge?.getAccessibilityContext() ?? null;
+    const bundleId = appContext?.context?.application?.bundleIdentifier;
+    if (!bundleId) {
+      return;
+    }
+
+    session.context.sharedData.accessibilityContext = appContext;
+    const mode = await this.resolveEffectiveMode(bundleId);
+    if (mode.id === session.context.sharedData.modeId) {
+      return;
+    }
+
+    logger.transcription.info("Focused app changed mode at stop", {
+      bundleId,
+      fromMode: session.context.sharedData.modeName,
+      toMode: mode.name,
+    });
+    await this.applyModeOutputSettings(session.context, mode);
+  }
+
+  private async buildContext(): Promise<PipelineContext> {
+    // Create default context
+    const context = createDefaultContext(uuid());
+
+    // Resolve effective mode at session start:
+    const appContext = this.nativeBridge?.getAccessibilityContext();
+    const effectiveMode = await this.resolveEffectiveMode(
+      appContext?.context?.application?.bundleIdentifier,
+    );
 
     context.sharedData.userPreferences.language = effectiveMode.dictation
       .autoDetectEnabled
       ? undefined
       : effectiveMode.dictation.selectedLanguage || "en";
-    context.sharedData.customInstructions = effectiveMode.customInstructions;
-    context.sharedData.formatter =
-      effectiveMode.formatterConfig ??
-      (await this.settingsService.getFormatterConfig());
-    context.sharedData.speechModelId = effectiveMode.speechModelId;
+    // Pin the speech model for the whole session: the mode's own model, else
+    // the global selection
+    context.sharedData.speechModelId =
+      effectiveMode.speechModelId ??
+      (await this.modelService.getSelectedModel()) ??
+      undefined;
+    context.sharedData.performancePreset = (
+      await this.settingsService.getTranscriptionSettings()
+    )?.performancePreset;
+    await this.applyModeOutputSettings(context, effectiveMode);
 
     // Load vocabulary and replacements
     const vocabEntries = await getVocabulary({ limit: 50 });
@@ -958,6 +1480,19 @@ export class TranscriptionService {
       }
     }
 
+    // Load emoji/symbol shortcuts for the dictation language (all languages
+    // when auto-detecting). Language-neutral ones go in first so a phrase
+    // with its own shortcut for the language overrides them.
+    const shortcuts = await getSymbolShortcuts(
+      context.sharedData.userPreferences.language,
+    );
+    const neutralFirst = [...shortcuts].sort(
+      (a, b) => Number(a.language !== null) - Number(b.language !== null),
+    );
+    for (const shortcut of neutralFirst) {
+      context.sharedData.symbolShortcuts.set(shortcut.phrase, shortcut.symbol);
+    }
+
     return context;
   }
 
@@ -1017,14 +1552,153 @@ export class TranscriptionService {
     return result;
   }
 
+  /**
+   * Resolve a formatting provider for a language model ID.
+   * Returns null (and logs why) when the model or its credentials are missing.
+   */
+  private async createFormattingProvider(
+    modelId: string,
+  ): Promise<FormattingProvider | null> {
+    const allModels = await this.modelService.getSyncedProviderModels();
+    const model = allModels.find(
+      (m) => m.id === modelId && m.type === "language",
+    );
+
+    if (!model) {
+      logger.transcription.warn("Formatting skipped: model not found", {
+        modelId,
+      });
+      return null;
+    }
+
+    switch (model.provider) {
+      case "OpenRouter": {
+        const config = await this.settingsService.getOpenRouterConfig();
+        if (!config?.apiKey) {
+          logger.transcription.warn(
+            "Formatting skipped: OpenRouter API key missing",
+          );
+          return null;
+        }
+        return new OpenRouterProvider(config.apiKey, modelId);
+      }
+      case "Ollama": {
+        const config = await this.settingsService.getOllamaConfig();
+        if (!config?.url) {
+          logger.transcription.warn("Formatting skipped: Ollama URL missing");
+          return null;
+        }
+        return new OllamaFormatter(config.url, modelId);
+      }
+      case "OpenAI": {
+        const config = await this.settingsService.getOpenAIConfig();
+        if (!config?.apiKey) {
+          logger.transcription.warn("Formatting skipped: OpenAI API key missing");
+          return null;
+        }
+        return new OpenAIFormatter(config.apiKey, modelId);
+      }
+      case "Anthropic": {
+        const config = await this.settingsService.getAnthropicConfig();
+        if (!config?.apiKey) {
+          logger.transcription.warn(
+            "Formatting skipped: Anthropic API key missing",
+          );
+          return null;
+        }
+        return new AnthropicFormatter(config.apiKey, modelId);
+      }
+      case "Google": {
+        const config = await this.settingsService.getGoogleConfig();
+        if (!config?.apiKey) {
+          logger.transcription.warn("Formatting skipped: Google API key missing");
+          return null;
+        }
+        return new GoogleFormatter(config.apiKey, modelId);
+      }
+      default:
+        logger.transcription.warn("Formatting skipped: unsupported provider", {
+          provider: model.provider,
+        });
+        return null;
+    }
+  }
+
+  /**
+   * Ask the mode's formatting model to fix misrecognised vocabulary terms.
+   * Returns null (keep the text as is) when the pass doesn't apply, fails,
+   * or the model changed more than vocabulary terms.
+   */
+  private async correctVocabularyTerms(
+    sessionId: string,
+    text: string,
+    session: StreamingSession,
+  ): Promise<{ text: string; corrections: number } | null> {
+    const { vocabulary, formatter } = session.context.sharedData;
+    const wordCount = text.split(/\s+/).filter(Boolean).length;
+    if (
+      !vocabulary?.length ||
+      !formatter?.modelId ||
+      wordCount === 0 ||
+      wordCount > MAX_CORRECTION_WORDS
+    ) {
+      return null;
+    }
+
+    const provider = await this.createFormattingProvider(formatter.modelId);
+    if (!provider) {
+      return null;
+    }
+
+    try {
+      const response = await provider.generate({
+        system: VOCABULARY_CORRECTION_SYSTEM_PROMPT,
+        prompt: buildVocabularyCorrectionPrompt(text, vocabulary),
+        // Room for the whole transcript plus tags
+        maxTokens: Math.ceil(text.length / 2) + 100,
+        abortSignal: AbortSignal.timeout(
+          formatter.timeoutMs ?? DEFAULT_FORMATTING_TIMEOUT_MS,
+        ),
+      });
+      const result = applyVocabularyCorrection(
+        text,
+        parseVocabularyCorrectionResponse(response),
+        vocabulary,
+      );
+      if (!result) {
+        logger.transcription.warn(
+          "Vocabulary correction rejected: changed more than vocabulary terms",
+          { sessionId, model: formatter.modelId },
+        );
+        return null;
+      }
+      if (result.corrections > 0) {
+        logger.transcription.info("Applied vocabulary corrections", {
+          sessionId,
+          model: formatter.modelId,
+          corrections: result.corrections,
+        });
+      }
+      return result;
+    } catch (error) {
+      logger.transcription.error("Vocabulary correction failed", {
+        sessionId,
+        error,
+      });
+      return null;
+    }
+  }
+
   private async formatWithProvider(
     provider: FormattingProvider,
     sessionId: string,
     text: string,
     session: StreamingSession,
+    timeoutMs: number,
   ): Promise<{ text: string; duration: number } | null> {
     const startTime = performance.now();
     const style = session.context.sharedData.userPreferences?.formattingStyle;
+    const abortSignal = AbortSignal.timeout(timeoutMs);
 
     try {
       const formattedText = await provider.format({
@@ -1041,7 +1715,9 @@ export class TranscriptionService {
               : undefined,
           aggregatedTranscription: text,
           customInstructions: session.context.sharedData.customInstructions,
+          presetId: session.context.sharedData.formatter?.presetId,
         },
+        abortSignal,
       });
 
       const duration = performance.now() - startTime;
@@ -1055,14 +1731,173 @@ export class TranscriptionService {
 
       return { text: formattedText, duration };
     } catch (error) {
-      logger.transcription.error("Formatting failed, using unformatted text", {
+      logger.transcription.error("Formatting failed", {
         sessionId,
+        provider: provider.name,
+        timedOut: abortSignal.aborted,
         error,
       });
       return null;
     }
   }
 
+  /**
+   * Format text with a mode's formatter settings without saving anything.
+   * Backs the "try your instructions" panel in mode settings.
+   */
+  async previewFormatting(
+    text: string,
+    modeId: string,
+  ): Promise<{ text: string; model: string; duration: number }> {
+    const { items } = await this.settingsService.getModes();
+    const mode = items.find((m) => m.id === modeId);
+    if (!mode) {
+      throw new Error(`Mode with id "${modeId}" not found`);
+    }
+
+    const formatterConfig = mode.formatterConfig;
+    const candidates = formattingCandidates(formatterConfig);
+    if (candidates.length === 0) {
+      throw new Error("No formatting model selected for this mode");
+    }
+
+    const vocabulary = (await getVocabulary({ limit: 50 }))
+      .filter((entry) => !entry.isReplacement)
+      .map((entry) => entry.word);
+
+    let lastError: unknown;
+    for (const modelId of candidates) {
+      const provider = await this.createFormattingProvider(modelId);
+      if (!provider) {
+        continue;
+      }
+      const startTime = performance.now();
+      try {
+        const formatted = await provider.format({
+          text,
+          context: {
+            vocabulary,
+            accessibilityContext: null,
+            aggregatedTranscription: text,
+            customInstructions: mode.customInstructions,
+            presetId: formatterConfig.presetId,
+          },
+          abortSignal: AbortSignal.timeout(
+            formatterConfig.timeoutMs ?? DEFAULT_FORMATTING_TIMEOUT_MS,
+          ),
+        });
+        return {
+          text: formatted,
+          model: modelId,
+          duration: performance.now() - startTime,
+        };
+      } catch (error) {
+        lastError = error;
+      }
+    }
+
+    throw lastError instanceof Error
+      ? lastError
+      : new Error("No usable formatting model for this mode");
+  }
+
+  /**
+   * Summarize a saved transcription with the default language model (the
+   * global formatting model, then its fallback) and store the result in
+   * the transcription's meta.
+   */
+  async summarizeTranscription(
+    id: number,
+  ): Promise<{ summary: string; model: string }> {
+    const transcription = await getTranscriptionById(id);
+    if (!transcription) {
+      throw new Error(`Transcription ${id} not found`);
+    }
+    if (!transcription.text.trim()) {
+      throw new Error("Transcription is empty");
+    }
+
+    const { text: summary, model } = await this.generateWithSelectedModel({
+      system: SUMMARY_SYSTEM_PROMPT,
+      prompt: transcription.text,
+      timeoutMs: SUMMARY_TIMEOUT_MS,
+    });
+
+    await updateTranscription(id, {
+      meta: {
+        ...(transcription.meta as Record<string, unknown> | null),
+        summary,
+        summaryModel: model,
+        summarizedAt: new Date().toISOString(),
+      },
+    });
+    logger.transcription.info("Transcription summarized", {
+      transcriptionId: id,
+      model,
+      summaryLength: summary.length,
+    });
+    return { summary, model };
+  }
+
+  /**
+   * Write a short digest of a week's dictations with the selected language
+   * model. `excerpts` are the week's transcripts, already trimmed to fit.
+   */
+  async generateWeeklyDigest(
+    excerpts: string[],
+  ): Promise<{ digest: string; model: string }> {
+    const { text: digest, model } = await this.generateWithSelectedModel({
+      system: WEEKLY_DIGEST_SYSTEM_PROMPT,
+      prompt: excerpts.map((excerpt) => `- ${excerpt}`).join("\n"),
+      timeoutMs: SUMMARY_TIMEOUT_MS,
+    });
+    return { digest, model };
+  }
+
+  /**
+   * Run a one-off generation with the formatter's primary model, falling
+   * back to its fallback model. Throws the last error if neither works.
+   */
+  private async generateWithSelectedModel(request: {
+    system: string;
+    prompt: string;
+    timeoutMs: number;
+  }): Promise<{ text: string; model: string }> {
+    const formatterConfig = await this.settingsService.getFormatterConfig();
+    const candidates = formattingCandidates(formatterConfig);
+    if (candidates.length === 0) {
+      throw new Error("No language model selected");
+    }
+
+    let lastError: unknown;
+    for (const modelId of candidates) {
+      const provider = await this.createFormattingProvider(modelId);
+      if (!provider) {
+        continue;
+      }
+      try {
+        const text = (
+          await provider.generate({
+            system: request.system,
+            prompt: request.prompt,
+            abortSignal: AbortSignal.timeout(request.timeoutMs),
+          })
+        ).trim();
+        return { text, model: modelId };
+      } catch (error) {
+        lastError = error;
+        logger.transcription.error("Generation failed", {
+          model: modelId,
+          error,
+        });
+      }
+    }
+
+    throw lastError instanceof Error
+      ? lastError
+      : new Error("No usable language model");
+  }
+
   /**
    * Cleanup method
    */
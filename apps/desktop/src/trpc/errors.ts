@@ -0,0 +1,126 @@
+import { TRPCError } from "@trpc/server";
+import { ZodError } from "zod";
+import type { AppErrorCode, AppErrorData } from "../types/app-error";
+import { isDatabaseError } from "../db/errors";
+
+const TRPC_CODES: Record<AppErrorCode, TRPCError["code"]> = {
+  NOT_FOUND: "NOT_FOUND",
+  INVALID_INPUT: "BAD_REQUEST",
+  PRECONDITION_FAILED: "PRECONDITION_FAILED",
+  SERVICE_UNAVAILABLE: "SERVICE_UNAVAILABLE",
+  DATABASE_LOCKED: "CONFLICT",
+  DATABASE_ERROR: "INTERNAL_SERVER_ERROR",
+  NETWORK_ERROR: "BAD_GATEWAY",
+  INTERNAL: "INTERNAL_SERVER_ERROR",
+};
+
+// Node socket errors, as found on the cause of a failed fetch
+const NETWORK_ERROR_CODES = new Set([
+  "ECONNREFUSED",
+  "ECONNRESET",
+  "ENOTFOUND",
+  "ETIMEDOUT",
+  "EAI_AGAIN",
+  "UND_ERR_CONNECT_TIMEOUT",
+  "UND_ERR_SOCKET",
+]);
+
+/**
+ * A handler error with an app error code. Errors thrown as anything else
+ * are classified by toAppErrorData().
+ */
+export class AppError extends TRPCError {
+  readonly appCode: AppErrorCode;
+  readonly details?: Record<string, unknown>;
+
+  constructor(opts: {
+    code: AppErrorCode;
+    message: string;
+    details?: Record<string, unknown>;
+    cause?: unknown;
+  }) {
+    super({
+      code: TRPC_CODES[opts.code],
+      message: opts.message,
+      cause: opts.cause,
+    });
+    this.name = "AppError";
+    this.appCode = opts.code;
+    this.details = opts.details;
+  }
+}
+
+export function serviceUnavailable(service: string): AppError {
+  return new AppError({
+    code: "SERVICE_UNAVAILABLE",
+    message: `${service} not available`,
+    details: { service },
+  });
+}
+
+function errorCode(error: unknown): string | undefined {
+  const code = (error as { code?: unknown } | null)?.code;
+  return typeof code === "string" ? code : undefined;
+}
+
+function isNetworkError(error: unknown): boolean {
+  // fetch rejects with "fetch failed" and the socket error as the cause
+  for (let e = error, depth = 0; e && depth < 4; depth++) {
+    const code = errorCode(e);
+    if (code && NETWORK_ERROR_CODES.has(code)) return true;
+    e = (e as { cause?: unknown }).cause;
+  }
+  return false;
+}
+
+/**
+ * The structured form of an error, sent to the client as
+ * `error.data.appError`
+ */
+export function toAppErrorData(error: TRPCError): AppErrorData {
+  if (error instanceof AppError) {
+    return {
+      code: error.appCode,
+      message: error.message,
+      ...(error.details && { details: error.details }),
+    };
+  }
+
+  const cause = error.cause;
+  if (isDatabaseError(cause)) {
+    const locked =
+      cause.code === "SQLITE_BUSY" || cause.code === "SQLITE_LOCKED";
+    return {
+      code: locked ? "DATABASE_LOCKED" : "DATABASE_ERROR",
+      message: error.message,
+      details: { sqliteCode: cause.code },
+    };
+  }
+  if (cause instanceof ZodError) {
+    return {
+      code: "INVALID_INPUT",
+      message: "Invalid input",
+      details: {
+        issues: cause.issues.map((issue) => ({
+          path: issue.path.join("."),
+          message: issue.message,
+        })),
+      },
+    };
+  }
+  if (isNetworkError(cause)) {
+    return { code: "NETWORK_ERROR", message: error.message };
+  }
+
+  switch (error.code) {
+    case "NOT_FOUND":
+      return { code: "NOT_FOUND", message: error.message };
+    case "BAD_REQUEST":
+    case "PARSE_ERROR":
+      return { code: "INVALID_INPUT", message: error.message };
+    case "PRECONDITION_FAILED":
+      return { code: "PRECONDITION_FAILED", message: error.message };
+    default:
+      return { code: "INTERNAL", message: error.message };
+  }
+}
@@ -3,6 +3,7 @@ import { createTRPCProxyClient } from "@trpc/client";
 import { ipcLink } from "electron-trpc-experimental/renderer";
 import superjson from "superjson";
 import type { AppRouter } from "./router";
+import { getAppError } from "../types/app-error";
 
 // Create the tRPC React hooks
 export const api = createTRPCReact<AppRouter>();
@@ -11,3 +12,9 @@ export const api = createTRPCReact<AppRouter>();
 export const trpcClient = createTRPCProxyClient<AppRouter>({
   links: [ipcLink({ transformer: superjson })],
 });
+
+// Query retry policy: only a locked database is worth retrying, and only a
+// few times
+export function retryIfDatabaseLocked(failureCount: number, error: unknown) {
+  return failureCount < 3 && getAppError(error)?.code === "DATABASE_LOCKED";
+}
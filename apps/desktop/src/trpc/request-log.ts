@@ -0,0 +1,56 @@
+import type { AppErrorCode } from "../types/app-error";
+
+/**
+ * Timings of recent tRPC calls, kept for diagnostics.getSlowRequests.
+ * Inputs are never stored; logs only get their shape from redactInput().
+ */
+
+export interface RequestTiming {
+  path: string;
+  type: "query" | "mutation";
+  durationMs: number;
+  ok: boolean;
+  errorCode?: AppErrorCode;
+  at: Date;
+}
+
+const MAX_RECENT_REQUESTS = 500;
+
+let recentRequests: RequestTiming[] = [];
+
+export function recordRequest(timing: RequestTiming): void {
+  recentRequests.push(timing);
+  if (recentRequests.length > MAX_RECENT_REQUESTS) {
+    recentRequests = recentRequests.slice(-MAX_RECENT_REQUESTS);
+  }
+}
+
+// Slowest of the recent calls, slowest first
+export function getSlowRequests(
+  limit = 20,
+  minDurationMs = 0,
+): RequestTiming[] {
+  return recentRequests
+    .filter((timing) => timing.durationMs >= minDurationMs)
+    .sort((a, b) => b.durationMs - a.durationMs)
+    .slice(0, limit);
+}
+
+function describeValue(value: unknown): string {
+  if (value === null) return "null";
+  if (Array.isArray(value)) return `array(${value.length})`;
+  if (typeof value === "string") return `string(${value.length})`;
+  return typeof value;
+}
+
+// Field names and value types of a call's input, never the values, since
+// inputs carry transcripts, vocabulary and API keys
+export function redactInput(input: unknown): unknown {
+  if (input === undefined) return undefined;
+  if (typeof input !== "object" || input === null || Array.isArray(input)) {
+    return describeValue(input);
+  }
+  return Object.fromEntries(
+    Object.entries(input).map(([key, value]) => [key, describeValue(value)]),
+  );
+}
@@ -8,6 +8,12 @@ import { recordingRouter } from "./routers/recording";
 import { widgetRouter } from "./routers/widget";
 import { authRouter } from "./routers/auth";
 import { onboardingRouter } from "./routers/onboarding";
+import { formatterRouter } from "./routers/formatter";
+import { usageRouter } from "./routers/usage";
+import { searchRouter } from "./routers/search";
+import { statsRouter } from "./routers/stats";
+import { maintenanceRouter } from "./routers/maintenance";
+import { diagnosticsRouter } from "./routers/diagnostics";
 import { createRouter, procedure } from "./trpc";
 
 export const router = createRouter({
@@ -61,6 +67,24 @@ export const router = createRouter({
 
   // Onboarding router
   onboarding: onboardingRouter,
+
+  // Formatter router
+  formatter: formatterRouter,
+
+  // Usage router
+  usage: usageRouter,
+
+  // Search router
+  search: searchRouter,
+
+  // Stats router
+  stats: statsRouter,
+
+  // Maintenance router
+  maintenance: maintenanceRouter,
+
+  // Diagnostics router
+  diagnostics: diagnosticsRouter,
 });
 
 export type AppRouter = typeof router;
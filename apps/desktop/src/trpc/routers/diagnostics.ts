@@ -0,0 +1,20 @@
+import { z } from "zod";
+import { createRouter, procedure } from "../trpc";
+import { getSlowRequests } from "../request-log";
+
+export const diagnosticsRouter = createRouter({
+  // Slowest of the last 500 queries and mutations, for tracking down UI
+  // freezes. Subscriptions aren't timed.
+  getSlowRequests: procedure
+    .input(
+      z
+        .object({
+          limit: z.number().int().min(1).max(100).default(20),
+          minDurationMs: z.number().min(0).default(0),
+        })
+        .optional(),
+    )
+    .query(({ input }) => {
+      return getSlowRequests(input?.limit, input?.minDurationMs);
+    }),
+});
@@ -0,0 +1,39 @@
+import { z } from "zod";
+import { createRouter, procedure } from "../trpc";
+import { AppError, serviceUnavailable } from "../errors";
+import { FORMATTER_PRESETS } from "../../pipeline/providers/formatting/formatter-presets";
+
+export const formatterRouter = createRouter({
+  // Built-in tone/style presets selectable per mode
+  getPresets: procedure.query(() => {
+    return FORMATTER_PRESETS;
+  }),
+
+  // Dry-run a mode's formatter on raw text; nothing is persisted
+  preview: procedure
+    .input(
+      z.object({
+        text: z.string().min(1).max(10000),
+        modeId: z.string().min(1),
+      }),
+    )
+    .mutation(async ({ input, ctx }) => {
+      const transcriptionService = ctx.serviceManager.getService(
+        "transcriptionService",
+      );
+      if (!transcriptionService) {
+        throw serviceUnavailable("TranscriptionService");
+      }
+      try {
+        return await transcriptionService.previewFormatting(
+          input.text,
+          input.modeId,
+        );
+      } catch (error) {
+        throw new AppError({
+          code: "PRECONDITION_FAILED",
+          message: error instanceof Error ? error.message : "Preview failed",
+        });
+      }
+    }),
+});
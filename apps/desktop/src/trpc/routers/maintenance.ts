@@ -0,0 +1,130 @@
+import { observable } from "@trpc/server/observable";
+import { z } from "zod";
+import { createRouter, procedure } from "../trpc";
+import { serviceUnavailable } from "../errors";
+import {
+  getDatabaseRecovery,
+  type DatabaseRecovery,
+} from "../../db/recovery";
+import {
+  getDatabaseHealth,
+  getLastMaintenanceRun,
+} from "../../db/maintenance";
+import type { MaintenanceRun } from "../../db/schema";
+import { getStorageBreakdown } from "../../utils/storage-breakdown";
+import { cleanupRecordings, scanRecordings } from "../../utils/recording-scan";
+
+export const maintenanceRouter = createRouter({
+  // Recordings no transcription points to, and transcriptions whose
+  // recording is gone
+  scanRecordings: procedure.query(async () => {
+    return await scanRecordings();
+  }),
+
+  // Act on a fresh scan: delete orphaned files and/or clear dangling
+  // audio_file references
+  cleanupRecordings: procedure
+    .input(
+      z.object({
+        deleteOrphans: z.boolean(),
+        clearMissing: z.boolean(),
+      }),
+    )
+    .mutation(async ({ input }) => {
+      return await cleanupRecordings(input);
+    }),
+
+  // Disk usage of the database (per table), recordings, models and logs
+  getStorageBreakdown: procedure.query(async () => {
+    return await getStorageBreakdown();
+  }),
+
+  // WAL size, page counts, last maintenance pass and last database error
+  getDbHealth: procedure.query(async () => {
+    return await getDatabaseHealth();
+  }),
+
+  // Most recent vacuum/ANALYZE pass, or null if none has run yet
+  getLastRun: procedure.query(async () => {
+    return await getLastMaintenanceRun();
+  }),
+
+  // Run a database maintenance pass now instead of waiting for idle time
+  runNow: procedure.mutation(async ({ ctx }) => {
+    const maintenanceService =
+      ctx.serviceManager.getService("maintenanceService");
+    if (!maintenanceService) {
+      throw serviceUnavailable("MaintenanceService");
+    }
+    return await maintenanceService.run();
+  }),
+
+  // When automatic passes run
+  getSchedule: procedure.query(async ({ ctx }) => {
+    const settingsService = ctx.serviceManager.getService("settingsService");
+    if (!settingsService) {
+      throw serviceUnavailable("SettingsService");
+    }
+    const settings = await settingsService.getMaintenanceSettings();
+    return {
+      enabled: settings?.enabled ?? true,
+      frequency: settings?.frequency ?? "daily",
+      hour: settings?.hour ?? null,
+      onlyWhenIdle: settings?.onlyWhenIdle ?? true,
+    };
+  }),
+
+  setSchedule: procedure
+    .input(
+      z.object({
+        enabled: z.boolean(),
+        frequency: z.enum(["daily", "weekly"]),
+        hour: z.number().int().min(0).max(23).nullable(), // null = any time
+        onlyWhenIdle: z.boolean(),
+      }),
+    )
+    .mutation(async ({ input, ctx }) => {
+      const settingsService = ctx.serviceManager.getService("settingsService");
+      if (!settingsService) {
+        throw serviceUnavailable("SettingsService");
+      }
+      await settingsService.setMaintenanceSettings({
+        ...input,
+        hour: input.hour ?? undefined,
+      });
+      return true;
+    }),
+
+  // Stats of each finished pass, scheduled or run by hand
+  onCompactionFinished: procedure.subscription(({ ctx }) => {
+    return observable<MaintenanceRun>((emit) => {
+      const maintenanceService =
+        ctx.serviceManager.getService("maintenanceService");
+      if (!maintenanceService) {
+        return () => {};
+      }
+
+      const handleRunFinished = (run: MaintenanceRun) => {
+        emit.next(run);
+      };
+
+      maintenanceService.on("run-finished", handleRunFinished);
+
+      return () => {
+        maintenanceService.off("run-finished", handleRunFinished);
+      };
+    });
+  }),
+
+  // Recovery only runs at startup, before any window subscribes, so the
+  // report (if there was one this launch) is emitted on subscription
+  onDatabaseRecovered: procedure.subscription(() => {
+    return observable<DatabaseRecovery>((emit) => {
+      const recovery = getDatabaseRecovery();
+      if (recovery) {
+        emit.next(recovery);
+      }
+      return () => {};
+    });
+  }),
+});
@@ -1,13 +1,23 @@
 import { observable } from "@trpc/server/observable";
+import { dialog } from "electron";
 import { z } from "zod";
 import { createRouter, procedure } from "../trpc";
+import { AppError, serviceUnavailable } from "../errors";
 import type {
   AvailableSpeechModel,
   DownloadProgress,
+  ModelUpdate,
+  ModeReference,
+  UnusedModel,
 } from "../../constants/models";
 import type { Model } from "../../db/schema";
 import type { ValidationResult } from "../../types/providers";
 import { removeModel } from "../../db/models";
+import { getModelUsageStats } from "../../db/model-usage";
+import {
+  listHuggingFaceModelFiles,
+  searchHuggingFaceModels,
+} from "../../utils/huggingface";
 
 export const modelsRouter = createRouter({
   // Unified models fetching
@@ -22,7 +32,7 @@ export const modelsRouter = createRouter({
     .query(async ({ input, ctx }): Promise<Model[]> => {
       const modelService = ctx.serviceManager.getService("modelService");
       if (!modelService) {
-        throw new Error("Model manager service not available");
+        throw serviceUnavailable("Model manager service");
       }
 
       // For speech models (local whisper)
@@ -125,7 +135,7 @@ export const modelsRouter = createRouter({
     async ({ ctx }): Promise<Record<string, Model>> => {
       const modelService = ctx.serviceManager.getService("modelService");
       if (!modelService) {
-        throw new Error("Model manager service not available");
+        throw serviceUnavailable("Model manager service");
       }
       return await modelService.getDownloadedModels();
     },
@@ -187,29 +197,110 @@ export const modelsRouter = createRouter({
     .mutation(async ({ input, ctx }) => {
       const modelService = ctx.serviceManager.getService("modelService");
       if (!modelService) {
-        throw new Error("Model manager service not initialized");
+        throw serviceUnavailable("Model manager service");
       }
       return await modelService.downloadModel(input.modelId);
     }),
 
+  // Search the Hugging Face Hub for model repositories
+  searchHuggingFace: procedure
+    .input(z.object({ query: z.string().max(100) }))
+    .query(async ({ input }) => {
+      return await searchHuggingFaceModels(input.query);
+    }),
+
+  // ggml files in a Hugging Face repository that can be imported
+  getHuggingFaceFiles: procedure
+    .input(z.object({ repoId: z.string() }))
+    .query(async ({ input }) => {
+      return await listHuggingFaceModelFiles(input.repoId);
+    }),
+
+  // Download a ggml file from Hugging Face; returns the new model's ID.
+  // Progress and completion arrive on the usual download subscriptions.
+  downloadHuggingFaceModel: procedure
+    .input(z.object({ repoId: z.string(), path: z.string() }))
+    .mutation(async ({ input, ctx }) => {
+      const modelService = ctx.serviceManager.getService("modelService");
+      if (!modelService) {
+        throw serviceUnavailable("Model manager service");
+      }
+      return await modelService.downloadHuggingFaceModel(
+        input.repoId,
+        input.path,
+      );
+    }),
+
   cancelDownload: procedure
     .input(z.object({ modelId: z.string() }))
     .mutation(async ({ input, ctx }) => {
       const modelService = ctx.serviceManager.getService("modelService");
       if (!modelService) {
-        throw new Error("Model manager service not initialized");
+        throw serviceUnavailable("Model manager service");
       }
-      return modelService.cancelDownload(input.modelId);
+      await modelService.cancelDownload(input.modelId);
     }),
 
+  // A model that's selected or used by a mode is only deleted with force;
+  // otherwise the result carries a warning listing what uses it
   deleteModel: procedure
-    .input(z.object({ modelId: z.string() }))
+    .input(
+      z.object({ modelId: z.string(), force: z.boolean().default(false) }),
+    )
+    .mutation(async ({ input, ctx }) => {
+      const modelService = ctx.serviceManager.getService("modelService");
+      if (!modelService) {
+        throw serviceUnavailable("Model manager service");
+      }
+      const result = await modelService.deleteModel(input.modelId, {
+        force: input.force,
+      });
+
+      // Stop holding the deleted model (fire-and-forget to avoid blocking UI)
+      const transcriptionService = ctx.serviceManager.getService(
+        "transcriptionService",
+      );
+      if (result.deleted && transcriptionService) {
+        transcriptionService.handleModelChange().catch((err) => {
+          const logger = ctx.serviceManager.getLogger();
+          logger?.main.error("Failed to handle model change:", err);
+        });
+      }
+
+      return result;
+    }),
+
+  // Downloaded models without a transcription in `days` days (the cleanup
+  // setting by default), with the space deleting them would free
+  getUnusedModels: procedure
+    .input(
+      z
+        .object({ days: z.number().int().min(1).max(3650).optional() })
+        .optional(),
+    )
+    .query(async ({ input, ctx }): Promise<UnusedModel[]> => {
+      const modelService = ctx.serviceManager.getService("modelService");
+      if (!modelService) {
+        throw serviceUnavailable("Model manager service");
+      }
+      return await modelService.getUnusedModels(input?.days);
+    }),
+
+  // Delete the unused models (or the listed ones among them) and report the
+  // space reclaimed
+  cleanupUnused: procedure
+    .input(
+      z.object({
+        days: z.number().int().min(1).max(3650).optional(),
+        modelIds: z.array(z.string()).optional(),
+      }),
+    )
     .mutation(async ({ input, ctx }) => {
       const modelService = ctx.serviceManager.getService("modelService");
       if (!modelService) {
-        throw new Error("Model manager service not initialized");
+        throw serviceUnavailable("Model manager service");
       }
-      return modelService.deleteModel(input.modelId);
+      return await modelService.cleanupUnusedModels(input);
     }),
 
   setSelectedModel: procedure
@@ -217,7 +308,7 @@ export const modelsRouter = createRouter({
     .mutation(async ({ input, ctx }) => {
       const modelService = ctx.serviceManager.getService("modelService");
       if (!modelService) {
-        throw new Error("Model manager service not initialized");
+        throw serviceUnavailable("Model manager service");
       }
       await modelService.setSelectedModel(input.modelId);
 
@@ -235,13 +326,157 @@ export const modelsRouter = createRouter({
       return true;
     }),
 
+  // Transcriptions and audio seconds handled by each model
+  getUsageStats: procedure
+    .input(
+      z
+        .object({ modelType: z.enum(["speech", "formatting"]).optional() })
+        .optional(),
+    )
+    .query(async ({ input }) => {
+      return await getModelUsageStats(input?.modelType);
+    }),
+
+  getAvailableUpdates: procedure.query(({ ctx }): ModelUpdate[] => {
+    const modelService = ctx.serviceManager.getService("modelService");
+    if (!modelService) {
+      throw serviceUnavailable("Model manager service");
+    }
+    return modelService.getAvailableUpdates();
+  }),
+
+  checkForUpdates: procedure.mutation(async ({ ctx }) => {
+    const modelService = ctx.serviceManager.getService("modelService");
+    if (!modelService) {
+      throw serviceUnavailable("Model manager service");
+    }
+    return modelService.checkForUpdates();
+  }),
+
+  // Re-download a model that has an update, replacing the current file
+  updateModel: procedure
+    .input(z.object({ modelId: z.string() }))
+    .mutation(async ({ input, ctx }) => {
+      const modelService = ctx.serviceManager.getService("modelService");
+      if (!modelService) {
+        throw serviceUnavailable("Model manager service");
+      }
+      await modelService.updateModel(input.modelId);
+
+      // Reload workers still holding the old file
+      const transcriptionService = ctx.serviceManager.getService(
+        "transcriptionService",
+      );
+      if (transcriptionService) {
+        transcriptionService
+          .handleModelUpdated(input.modelId)
+          .catch((err) => {
+            const logger = ctx.serviceManager.getLogger();
+            logger?.main.error("Failed to reload updated model:", err);
+          });
+      }
+
+      return true;
+    }),
+
+  // Pick a ggml model file for importLocalModel; null when cancelled
+  chooseLocalModelFile: procedure.mutation(async () => {
+    const result = await dialog.showOpenDialog({
+      title: "Choose a Whisper model file",
+      properties: ["openFile"],
+      filters: [{ name: "Whisper models", extensions: ["bin", "gguf"] }],
+    });
+    return result.canceled ? null : (result.filePaths[0] ?? null);
+  }),
+
+  // Register a model file from disk. With testInference, the model must
+  // also transcribe the benchmark sample, or the import is undone.
+  importLocalModel: procedure
+    .input(
+      z.object({
+        path: z.string().min(1),
+        name: z.string().trim().min(1),
+        copy: z.boolean().default(true),
+        testInference: z.boolean().default(false),
+      }),
+    )
+    .mutation(async ({ input, ctx }) => {
+      const modelService = ctx.serviceManager.getService("modelService");
+      if (!modelService) {
+        throw serviceUnavailable("Model manager service");
+      }
+      const modelId = await modelService.importLocalModel(input.path, {
+        name: input.name,
+        copy: input.copy,
+      });
+      if (!input.testInference) {
+        return { modelId, benchmark: null };
+      }
+
+      const transcriptionService = ctx.serviceManager.getService(
+        "transcriptionService",
+      );
+      if (!transcriptionService) {
+        throw serviceUnavailable("Transcription service");
+      }
+      try {
+        const benchmark = await transcriptionService.benchmarkModel(modelId);
+        return { modelId, benchmark };
+      } catch (error) {
+        await modelService.deleteModel(modelId, { force: true });
+        throw new AppError({
+          code: "INVALID_INPUT",
+          message: `The model couldn't be loaded: ${
+            error instanceof Error ? error.message : String(error)
+          }`,
+          details: { modelId },
+          cause: error,
+        });
+      }
+    }),
+
+  benchmark: procedure
+    .input(z.object({ modelId: z.string() }))
+    .mutation(async ({ input, ctx }) => {
+      const transcriptionService = ctx.serviceManager.getService(
+        "transcriptionService",
+      );
+      if (!transcriptionService) {
+        throw serviceUnavailable("Transcription service");
+      }
+      return transcriptionService.benchmarkModel(input.modelId);
+    }),
+
+  // The selected local model, when it's held in memory
+  getLoadedModel: procedure.query(async ({ ctx }) => {
+    const transcriptionService = ctx.serviceManager.getService(
+      "transcriptionService",
+    );
+    if (!transcriptionService) {
+      throw serviceUnavailable("Transcription service");
+    }
+    return await transcriptionService.getLoadedModel();
+  }),
+
+  // Free the RAM used by local models until they're needed again
+  unloadModel: procedure.mutation(async ({ ctx }) => {
+    const transcriptionService = ctx.serviceManager.getService(
+      "transcriptionService",
+    );
+    if (!transcriptionService) {
+      throw serviceUnavailable("Transcription service");
+    }
+    await transcriptionService.unloadModels();
+    return true;
+  }),
+
   // Provider validation endpoints
   validateOpenRouterConnection: procedure
     .input(z.object({ apiKey: z.string() }))
     .mutation(async ({ input, ctx }): Promise<ValidationResult> => {
       const modelService = ctx.serviceManager.getService("modelService");
       if (!modelService) {
-        throw new Error("Model manager service not initialized");
+        throw serviceUnavailable("Model manager service");
       }
       return await modelService.validateOpenRouterConnection(input.apiKey);
     }),
@@ -251,7 +486,7 @@ export const modelsRouter = createRouter({
     .mutation(async ({ input, ctx }): Promise<ValidationResult> => {
       const modelService = ctx.serviceManager.getService("modelService");
       if (!modelService) {
-        throw new Error("Model manager service not initialized");
+        throw serviceUnavailable("Model manager service");
       }
       return await modelService.validateOllamaConnection(input.url);
     }),
@@ -261,7 +496,7 @@ export const modelsRouter = createRouter({
     .mutation(async ({ input, ctx }): Promise<ValidationResult> => {
       const modelService = ctx.serviceManager.getService("modelService");
       if (!modelService) {
-        throw new Error("Model manager service not initialized");
+        throw serviceUnavailable("Model manager service");
       }
       return await modelService.validateOpenAIConnection(input.apiKey);
     }),
@@ -271,7 +506,7 @@ export const modelsRouter = createRouter({
     .mutation(async ({ input, ctx }): Promise<ValidationResult> => {
       const modelService = ctx.serviceManager.getService("modelService");
       if (!modelService) {
-        throw new Error("Model manager service not initialized");
+        throw serviceUnavailable("Model manager service");
       }
       return await modelService.validateAnthropicConnection(input.apiKey);
     }),
@@ -281,7 +516,7 @@ export const modelsRouter = createRouter({
     .mutation(async ({ input, ctx }): Promise<ValidationResult> => {
       const modelService = ctx.serviceManager.getService("modelService");
       if (!modelService) {
-        throw new Error("Model manager service not initialized");
+        throw serviceUnavailable("Model manager service");
       }
       return await modelService.validateGoogleConnection(input.apiKey);
     }),
@@ -292,7 +527,7 @@ export const modelsRouter = createRouter({
     .mutation(async ({ input, ctx }): Promise<ValidationResult> => {
       const modelService = ctx.serviceManager.getService("modelService");
       if (!modelService) {
-        throw new Error("Model manager service not initialized");
+        throw serviceUnavailable("Model manager service");
       }
       return await modelService.validateTranscriptionGroqConnection(
         input.apiKey,
@@ -304,7 +539,7 @@ export const modelsRouter = createRouter({
     .mutation(async ({ input, ctx }): Promise<ValidationResult> => {
       const modelService = ctx.serviceManager.getService("modelService");
       if (!modelService) {
-        throw new Error("Model manager service not initialized");
+        throw serviceUnavailable("Model manager service");
       }
       return await modelService.validateTranscriptionGrokConnection(
         input.apiKey,
@@ -315,7 +550,7 @@ export const modelsRouter = createRouter({
   getTranscriptionProviderStatus: procedure.query(async ({ ctx }) => {
     const settingsService = ctx.serviceManager.getService("settingsService");
     if (!settingsService) {
-      throw new Error("SettingsService not available");
+      throw serviceUnavailable("SettingsService");
     }
     const modelConfig = await settingsService.getModelProvidersConfig();
     return {
@@ -325,13 +560,13 @@ export const modelsRouter = createRouter({
     };
   }),
 
-  // Provider model fetching
+  // Provider model fetching; without an apiKey the saved key is used
   fetchOpenRouterModels: procedure
-    .input(z.object({ apiKey: z.string() }))
+    .input(z.object({ apiKey: z.string().optional() }))
     .query(async ({ input, ctx }) => {
       const modelService = ctx.serviceManager.getService("modelService");
       if (!modelService) {
-        throw new Error("Model manager service not initialized");
+        throw serviceUnavailable("Model manager service");
       }
       return await modelService.fetchOpenRouterModels(input.apiKey);
     }),
@@ -341,37 +576,37 @@ export const modelsRouter = createRouter({
     .query(async ({ input, ctx }) => {
       const modelService = ctx.serviceManager.getService("modelService");
       if (!modelService) {
-        throw new Error("Model manager service not initialized");
+        throw serviceUnavailable("Model manager service");
       }
       return await modelService.fetchOllamaModels(input.url);
     }),
 
   fetchOpenAIModels: procedure
-    .input(z.object({ apiKey: z.string() }))
+    .input(z.object({ apiKey: z.string().optional() }))
     .query(async ({ input, ctx }) => {
       const modelService = ctx.serviceManager.getService("modelService");
       if (!modelService) {
-        throw new Error("Model manager service not initialized");
+        throw serviceUnavailable("Model manager service");
       }
       return await modelService.fetchOpenAIModels(input.apiKey);
     }),
 
   fetchAnthropicModels: procedure
-    .input(z.object({ apiKey: z.string() }))
+    .input(z.object({ apiKey: z.string().optional() }))
     .query(async ({ input, ctx }) => {
       const modelService = ctx.serviceManager.getService("modelService");
       if (!modelService) {
-        throw new Error("Model manager service not initialized");
+        throw serviceUnavailable("Model manager service");
       }
       return await modelService.fetchAnthropicModels(input.apiKey);
     }),
 
   fetchGoogleModels: procedure
-    .input(z.object({ apiKey: z.string() }))
+    .input(z.object({ apiKey: z.string().optional() }))
     .query(async ({ input, ctx }) => {
       const modelService = ctx.serviceManager.getService("modelService");
       if (!modelService) {
-        throw new Error("Model manager service not initialized");
+        throw serviceUnavailable("Model manager service");
       }
       return await modelService.fetchGoogleModels(input.apiKey);
     }),
@@ -381,7 +616,7 @@ export const modelsRouter = createRouter({
     async ({ ctx }): Promise<Model[]> => {
       const modelService = ctx.serviceManager.getService("modelService");
       if (!modelService) {
-        throw new Error("Model manager service not initialized");
+        throw serviceUnavailable("Model manager service");
       }
       return await modelService.getSyncedProviderModels();
     },
@@ -397,7 +632,7 @@ export const modelsRouter = createRouter({
     .mutation(async ({ input, ctx }) => {
       const modelService = ctx.serviceManager.getService("modelService");
       if (!modelService) {
-        throw new Error("Model manager service not initialized");
+        throw serviceUnavailable("Model manager service");
       }
       await modelService.syncProviderModelsToDatabase(
         input.provider,
@@ -412,7 +647,7 @@ export const modelsRouter = createRouter({
     .mutation(async ({ input, ctx }) => {
       const modelService = ctx.serviceManager.getService("modelService");
       if (!modelService) {
-        throw new Error("Model manager service not initialized");
+        throw serviceUnavailable("Model manager service");
       }
 
       // Find the model to get its provider
@@ -420,7 +655,11 @@ export const modelsRouter = createRouter({
       const model = allModels.find((m) => m.id === input.modelId);
 
       if (!model) {
-        throw new Error(`Model not found: ${input.modelId}`);
+        throw new AppError({
+          code: "NOT_FOUND",
+          message: `Model not found: ${input.modelId}`,
+          details: { modelId: input.modelId },
+        });
       }
 
       await removeModel(model.provider, input.modelId);
@@ -431,7 +670,7 @@ export const modelsRouter = createRouter({
   removeOpenRouterProvider: procedure.mutation(async ({ ctx }) => {
     const modelService = ctx.serviceManager.getService("modelService");
     if (!modelService) {
-      throw new Error("Model manager service not initialized");
+      throw serviceUnavailable("Model manager service");
     }
 
     // Remove all OpenRouter models from database
@@ -453,7 +692,7 @@ export const modelsRouter = createRouter({
   removeOllamaProvider: procedure.mutation(async ({ ctx }) => {
     const modelService = ctx.serviceManager.getService("modelService");
     if (!modelService) {
-      throw new Error("Model manager service not initialized");
+      throw serviceUnavailable("Model manager service");
     }
 
     // Remove all Ollama models from database
@@ -475,7 +714,7 @@ export const modelsRouter = createRouter({
   removeOpenAIProvider: procedure.mutation(async ({ ctx }) => {
     const modelService = ctx.serviceManager.getService("modelService");
     if (!modelService) {
-      throw new Error("Model manager service not initialized");
+      throw serviceUnavailable("Model manager service");
     }
 
     await modelService.removeProviderModels("OpenAI");
@@ -508,7 +747,7 @@ export const modelsRouter = createRouter({
   removeGroqProvider: procedure.mutation(async ({ ctx }) => {
     const settingsService = ctx.serviceManager.getService("settingsService");
     if (!settingsService) {
-      throw new Error("SettingsService not available");
+      throw serviceUnavailable("SettingsService");
     }
 
     const currentConfig = await settingsService.getModelProvidersConfig();
@@ -536,7 +775,7 @@ export const modelsRouter = createRouter({
   removeGrokProvider: procedure.mutation(async ({ ctx }) => {
     const settingsService = ctx.serviceManager.getService("settingsService");
     if (!settingsService) {
-      throw new Error("SettingsService not available");
+      throw serviceUnavailable("SettingsService");
     }
 
     const currentConfig = await settingsService.getModelProvidersConfig();
@@ -564,7 +803,7 @@ export const modelsRouter = createRouter({
   removeAnthropicProvider: procedure.mutation(async ({ ctx }) => {
     const modelService = ctx.serviceManager.getService("modelService");
     if (!modelService) {
-      throw new Error("Model manager service not initialized");
+      throw serviceUnavailable("Model manager service");
     }
 
     await modelService.removeProviderModels("Anthropic");
@@ -584,7 +823,7 @@ export const modelsRouter = createRouter({
   removeGoogleProvider: procedure.mutation(async ({ ctx }) => {
     const modelService = ctx.serviceManager.getService("modelService");
     if (!modelService) {
-      throw new Error("Model manager service not initialized");
+      throw serviceUnavailable("Model manager service");
     }
 
     await modelService.removeProviderModels("Google");
@@ -613,7 +852,7 @@ export const modelsRouter = createRouter({
       (emit) => {
         const modelService = ctx.serviceManager.getService("modelService");
         if (!modelService) {
-          throw new Error("Model manager service not initialized");
+          throw serviceUnavailable("Model manager service");
         }
 
         const handleDownloadProgress = (
@@ -642,7 +881,7 @@ export const modelsRouter = createRouter({
     }>((emit) => {
       const modelService = ctx.serviceManager.getService("modelService");
       if (!modelService) {
-        throw new Error("Model manager service not initialized");
+        throw serviceUnavailable("Model manager service");
       }
 
       const handleDownloadComplete = (
@@ -667,7 +906,7 @@ export const modelsRouter = createRouter({
     return observable<{ modelId: string; error: string }>((emit) => {
       const modelService = ctx.serviceManager.getService("modelService");
       if (!modelService) {
-        throw new Error("Model manager service not initialized");
+        throw serviceUnavailable("Model manager service");
       }
 
       const handleDownloadError = (modelId: string, error: Error) => {
@@ -689,7 +928,7 @@ export const modelsRouter = createRouter({
     return observable<{ modelId: string }>((emit) => {
       const modelService = ctx.serviceManager.getService("modelService");
       if (!modelService) {
-        throw new Error("Model manager service not initialized");
+        throw serviceUnavailable("Model manager service");
       }
 
       const handleDownloadCancelled = (modelId: string) => {
@@ -705,13 +944,57 @@ export const modelsRouter = createRouter({
     });
   }),
 
+  // Using Observable instead of async generator due to Symbol.asyncDispose conflict
+  // eslint-disable-next-line deprecation/deprecation
+  onUpdateAvailable: procedure.subscription(({ ctx }) => {
+    return observable<{ updates: ModelUpdate[] }>((emit) => {
+      const modelService = ctx.serviceManager.getService("modelService");
+      if (!modelService) {
+        throw serviceUnavailable("Model manager service");
+      }
+
+      const handleUpdateAvailable = (updates: ModelUpdate[]) => {
+        emit.next({ updates });
+      };
+
+      modelService.on("update-available", handleUpdateAvailable);
+
+      // Cleanup function
+      return () => {
+        modelService?.off("update-available", handleUpdateAvailable);
+      };
+    });
+  }),
+
+  // Using Observable instead of async generator due to Symbol.asyncDispose conflict
+  // eslint-disable-next-line deprecation/deprecation
+  onUnusedModels: procedure.subscription(({ ctx }) => {
+    return observable<UnusedModel[]>((emit) => {
+      const modelService = ctx.serviceManager.getService("modelService");
+      if (!modelService) {
+        throw serviceUnavailable("Model manager service");
+      }
+
+      const handleUnusedModels = (models: UnusedModel[]) => {
+        emit.next(models);
+      };
+
+      modelService.on("unused-models", handleUnusedModels);
+
+      // Cleanup function
+      return () => {
+        modelService?.off("unused-models", handleUnusedModels);
+      };
+    });
+  }),
+
   // Using Observable instead of async generator due to Symbol.asyncDispose conflict
   // eslint-disable-next-line deprecation/deprecation
   onModelDeleted: procedure.subscription(({ ctx }) => {
     return observable<{ modelId: string }>((emit) => {
       const modelService = ctx.serviceManager.getService("modelService");
       if (!modelService) {
-        throw new Error("Model manager service not initialized");
+        throw serviceUnavailable("Model manager service");
       }
 
       const handleModelDeleted = (modelId: string) => {
@@ -727,6 +1010,31 @@ export const modelsRouter = createRouter({
     });
   }),
 
+  // Using Observable instead of async generator due to Symbol.asyncDispose conflict
+  // eslint-disable-next-line deprecation/deprecation
+  onModesRepointed: procedure.subscription(({ ctx }) => {
+    return observable<{ modelId: string; modes: ModeReference[] }>((emit) => {
+      const modelService = ctx.serviceManager.getService("modelService");
+      if (!modelService) {
+        throw serviceUnavailable("Model manager service");
+      }
+
+      const handleModesRepointed = (
+        modelId: string,
+        modes: ModeReference[],
+      ) => {
+        emit.next({ modelId, modes });
+      };
+
+      modelService.on("modes-repointed", handleModesRepointed);
+
+      // Cleanup function
+      return () => {
+        modelService?.off("modes-repointed", handleModesRepointed);
+      };
+    });
+  }),
+
   // Using Observable instead of async generator due to Symbol.asyncDispose conflict
   // eslint-disable-next-line deprecation/deprecation
   onSelectionChanged: procedure.subscription(({ ctx }) => {
@@ -742,7 +1050,7 @@ export const modelsRouter = createRouter({
     }>((emit) => {
       const modelService = ctx.serviceManager.getService("modelService");
       if (!modelService) {
-        throw new Error("Model manager service not initialized");
+        throw serviceUnavailable("Model manager service");
       }
 
       const handleSelectionChanged = (
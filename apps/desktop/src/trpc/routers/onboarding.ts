@@ -3,6 +3,7 @@ import { systemPreferences, shell, app } from "electron";
 import os from "node:os";
 import path from "node:path";
 import { createRouter, procedure } from "../trpc";
+import { serviceUnavailable } from "../errors";
 import {
   OnboardingPreferencesSchema,
   OnboardingStateSchema,
@@ -42,17 +43,19 @@ export const onboardingRouter = createRouter({
   /**
    * Get recommended local model ID based on hardware
    */
-  getRecommendedLocalModel: procedure.query(({ ctx }): string => {
-    const { serviceManager } = ctx;
-    if (!serviceManager) {
-      return "whisper-base";
-    }
-    const onboardingService = serviceManager.getOnboardingService();
-    if (!onboardingService) {
-      return "whisper-base";
-    }
-    return onboardingService.getRecommendedLocalModelId();
-  }),
+  getRecommendedLocalModel: procedure.query(
+    async ({ ctx }): Promise<string> => {
+      const { serviceManager } = ctx;
+      if (!serviceManager) {
+        return "whisper-base";
+      }
+      const onboardingService = serviceManager.getOnboardingService();
+      if (!onboardingService) {
+        return "whisper-base";
+      }
+      return onboardingService.getRecommendedLocalModelId();
+    },
+  ),
 
   /**
    * Check if onboarding is needed
@@ -118,12 +121,12 @@ export const onboardingRouter = createRouter({
         try {
           const { serviceManager } = ctx;
           if (!serviceManager) {
-            throw new Error("ServiceManager not available");
+            throw serviceUnavailable("ServiceManager");
           }
           const onboardingService = serviceManager.getOnboardingService();
 
           if (!onboardingService) {
-            throw new Error("OnboardingService not available");
+            throw serviceUnavailable("OnboardingService");
           }
 
           await onboardingService.savePreferences(input);
@@ -149,12 +152,12 @@ export const onboardingRouter = createRouter({
       try {
         const { serviceManager } = ctx;
         if (!serviceManager) {
-          throw new Error("ServiceManager not available");
+          throw serviceUnavailable("ServiceManager");
         }
         const onboardingService = serviceManager.getOnboardingService();
 
         if (!onboardingService) {
-          throw new Error("OnboardingService not available");
+          throw serviceUnavailable("OnboardingService");
         }
 
         // Complete onboarding through the service
@@ -179,12 +182,12 @@ export const onboardingRouter = createRouter({
     try {
       const { serviceManager } = ctx;
       if (!serviceManager) {
-        throw new Error("ServiceManager not available");
+        throw serviceUnavailable("ServiceManager");
       }
       const onboardingService = serviceManager.getOnboardingService();
 
       if (!onboardingService) {
-        throw new Error("OnboardingService not available");
+        throw serviceUnavailable("OnboardingService");
       }
 
       await onboardingService.cancelOnboardingFlow();
@@ -203,12 +206,12 @@ export const onboardingRouter = createRouter({
     try {
       const { serviceManager } = ctx;
       if (!serviceManager) {
-        throw new Error("ServiceManager not available");
+        throw serviceUnavailable("ServiceManager");
       }
       const onboardingService = serviceManager.getOnboardingService();
 
       if (!onboardingService) {
-        throw new Error("OnboardingService not available");
+        throw serviceUnavailable("OnboardingService");
       }
 
       await onboardingService.resetOnboarding();
@@ -1,5 +1,6 @@
 import { observable } from "@trpc/server/observable";
 import { createRouter, procedure } from "../trpc";
+import { serviceUnavailable } from "../errors";
 import { v4 as uuid } from "uuid";
 import type { RecordingState } from "../../types/recording";
 import type { RecordingMode } from "../../main/managers/recording-manager";
@@ -18,7 +19,7 @@ export const recordingRouter = createRouter({
   signalStart: procedure.mutation(async ({ ctx }) => {
     const recordingManager = ctx.serviceManager.getService("recordingManager");
     if (!recordingManager) {
-      throw new Error("Recording manager not available");
+      throw serviceUnavailable("Recording manager");
     }
     return await recordingManager.signalStart();
   }),
@@ -26,7 +27,7 @@ export const recordingRouter = createRouter({
   signalStop: procedure.mutation(async ({ ctx }) => {
     const recordingManager = ctx.serviceManager.getService("recordingManager");
     if (!recordingManager) {
-      throw new Error("Recording manager not available");
+      throw serviceUnavailable("Recording manager");
     }
     return await recordingManager.signalStop();
   }),
@@ -42,7 +43,7 @@ export const recordingRouter = createRouter({
       const recordingManager =
         ctx.serviceManager.getService("recordingManager");
       if (!recordingManager) {
-        throw new Error("Recording manager not available");
+        throw serviceUnavailable("Recording manager");
       }
 
       // Emit initial state
@@ -115,7 +116,7 @@ export const recordingRouter = createRouter({
       const recordingManager =
         ctx.serviceManager.getService("recordingManager");
       if (!recordingManager) {
-        throw new Error("Recording manager not available");
+        throw serviceUnavailable("Recording manager");
       }
 
       const handleNotification = (data: { type: WidgetNotificationType }) => {
@@ -0,0 +1,30 @@
+import { z } from "zod";
+import { createRouter, procedure } from "../trpc";
+import { AppError, serviceUnavailable } from "../errors";
+
+export const searchRouter = createRouter({
+  // Rank transcriptions by embedding similarity to the query; catches
+  // paraphrases the LIKE-based transcriptions.search misses
+  semantic: procedure
+    .input(
+      z.object({
+        query: z.string().trim().min(1).max(1000),
+        limit: z.number().int().min(1).max(100).default(20),
+      }),
+    )
+    .query(async ({ input, ctx }) => {
+      const searchService = ctx.serviceManager.getService("searchService");
+      if (!searchService) {
+        throw serviceUnavailable("SearchService");
+      }
+      try {
+        return await searchService.semanticSearch(input.query, input.limit);
+      } catch (error) {
+        throw new AppError({
+          code: "PRECONDITION_FAILED",
+          message:
+            error instanceof Error ? error.message : "Semantic search failed",
+        });
+      }
+    }),
+});
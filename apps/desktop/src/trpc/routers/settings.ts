@@ -1,18 +1,74 @@
 import { observable } from "@trpc/server/observable";
-import { TRPCError } from "@trpc/server";
 import { z } from "zod";
-import { app } from "electron";
+import { app, dialog, safeStorage } from "electron";
 import path from "node:path";
 import { createRouter, procedure } from "../trpc";
+import { AppError, serviceUnavailable } from "../errors";
+import { logger } from "../../main/logger";
 import { dbPath, closeDatabase } from "../../db";
+import {
+  getPendingEncryptionChange,
+  isDatabaseEncrypted,
+  removeDatabaseKeys,
+  setDatabaseEncryption,
+} from "../../db/encryption";
 import type { ModeConfig } from "../../db/schema";
+import type { SettingsChange } from "../../services/settings-service";
+import { DEFAULT_AUDIO_FILENAME_TEMPLATE } from "../../utils/audio-file-naming";
+import { DEFAULT_WATCHDOG_TIMEOUT } from "../../main/managers/recording-manager";
+import { getTelemetryConfig } from "../../services/telemetry-service";
+import { getFormatterPreset } from "../../pipeline/providers/formatting/formatter-presets";
+import { MIN_MESSAGE_GAP_MS } from "../../pipeline/processing/message-splitting";
 import * as fs from "fs/promises";
+import { randomBytes } from "node:crypto";
+import { AVAILABLE_MODELS } from "../../constants/models";
+import {
+  createDatabaseBackup,
+  listDatabaseBackups,
+  restoreDatabaseBackup,
+} from "../../utils/database-backup";
+import {
+  getCustomDataDirectory,
+  getDataDirectory,
+  getModelsDirectory,
+  setCustomDataDirectory,
+} from "../../utils/data-directory";
+import {
+  moveDataDirectory,
+  validateDataDirectory,
+} from "../../utils/move-data-directory";
+
+const DEFAULT_REMOTE_CONTROL_PORT = 47615;
+
+const generateRemoteControlToken = () => randomBytes(24).toString("base64url");
+
+// Per-attempt formatting timeout in milliseconds
+const FormatterTimeoutSchema = z
+  .number()
+  .int()
+  .min(1000)
+  .max(120000)
+  .optional();
+
+const FormatterPresetSchema = z
+  .string()
+  .refine((id) => getFormatterPreset(id) !== undefined, "Unknown preset")
+  .optional();
+
+const ProfanityPolicySchema = z.enum(["keep", "mask", "remove"]);
 
 // FormatterConfig schema
 const FormatterConfigSchema = z.object({
   enabled: z.boolean(),
   modelId: z.string().optional(),
   fallbackModelId: z.string().optional(),
+  presetId: FormatterPresetSchema,
+  timeoutMs: FormatterTimeoutSchema,
+});
+
+const EmbeddingConfigSchema = z.object({
+  provider: z.enum(["ollama", "openai"]),
+  model: z.string().trim().min(1),
 });
 
 // Shortcut schema (array of key names)
@@ -66,6 +122,26 @@ const DictationSettingsSchema = z.object({
 });
 
 // Mode schemas
+const NormalizationConfigSchema = z.object({
+  enabled: z.boolean(),
+  numbers: z.enum(["digits", "words", "off"]),
+  dateFormat: z.enum(["long", "iso", "us", "eu", "off"]),
+  currency: z.boolean(),
+});
+
+const MessageSplittingSchema = z.object({
+  enabled: z.boolean(),
+  maxLength: z.number().int().min(50).max(10000),
+  // Modes saved before the minimum existed may hold shorter gaps; raise them
+  // instead of rejecting the whole update
+  delayMs: z
+    .number()
+    .int()
+    .min(0)
+    .max(10000)
+    .transform((delayMs) => Math.max(delayMs, MIN_MESSAGE_GAP_MS)),
+});
+
 const CreateModeSchema = z.object({
   name: z.string().min(1).max(50),
   dictation: z.object({
@@ -76,10 +152,18 @@ const CreateModeSchema = z.object({
     enabled: z.boolean(),
     modelId: z.string().optional(),
     fallbackModelId: z.string().optional(),
+    presetId: FormatterPresetSchema,
+    timeoutMs: FormatterTimeoutSchema,
   }),
   customInstructions: z.string().max(2000).optional(),
   speechModelId: z.string().optional(),
   appBindings: z.array(z.string()).max(20).optional(),
+  spokenCommandsEnabled: z.boolean().optional(),
+  normalization: NormalizationConfigSchema.optional(),
+  voiceEditingEnabled: z.boolean().optional(),
+  messageSplitting: MessageSplittingSchema.optional(),
+  profanityPolicy: ProfanityPolicySchema.optional(),
+  vocabularyCorrectionEnabled: z.boolean().optional(),
 });
 
 const UpdateModeSchema = z.object({
@@ -96,11 +180,19 @@ const UpdateModeSchema = z.object({
       enabled: z.boolean(),
       modelId: z.string().optional(),
       fallbackModelId: z.string().optional(),
+      presetId: FormatterPresetSchema,
+      timeoutMs: FormatterTimeoutSchema,
     })
     .optional(),
   customInstructions: z.string().max(2000).optional().nullable(),
   speechModelId: z.string().optional().nullable(),
   appBindings: z.array(z.string()).max(20).optional().nullable(),
+  spokenCommandsEnabled: z.boolean().optional(),
+  normalization: NormalizationConfigSchema.optional().nullable(),
+  voiceEditingEnabled: z.boolean().optional(),
+  messageSplitting: MessageSplittingSchema.optional().nullable(),
+  profanityPolicy: ProfanityPolicySchema.optional().nullable(),
+  vocabularyCorrectionEnabled: z.boolean().optional(),
 });
 
 const AppPreferencesSchema = z.object({
@@ -115,13 +207,42 @@ const UIThemeSchema = z.object({
   theme: z.enum(["light", "dark", "system"]),
 });
 
+/**
+ * Back up the database before a destructive change. If that fails, the
+ * change is refused unless the caller explicitly opted out of the backup.
+ */
+async function backupBeforeChange(
+  reason: string,
+  options: { withoutBackup?: boolean; keep?: string } = {},
+) {
+  try {
+    await createDatabaseBackup(reason, { keep: options.keep });
+  } catch (error) {
+    if (options.withoutBackup) {
+      logger.main.warn("Backup failed, continuing without one", {
+        reason,
+        error,
+      });
+      return;
+    }
+    throw new AppError({
+      code: "PRECONDITION_FAILED",
+      message: `Couldn't back up the database: ${
+        error instanceof Error ? error.message : String(error)
+      }`,
+      details: { backupFailed: true, reason },
+      cause: error,
+    });
+  }
+}
+
 export const settingsRouter = createRouter({
   // Get all settings
   getSettings: procedure.query(async ({ ctx }) => {
     try {
       const settingsService = ctx.serviceManager.getService("settingsService");
       if (!settingsService) {
-        throw new Error("SettingsService not available");
+        throw serviceUnavailable("SettingsService");
       }
       return await settingsService.getAllSettings();
     } catch (error) {
@@ -151,7 +272,7 @@ export const settingsRouter = createRouter({
         const settingsService =
           ctx.serviceManager.getService("settingsService");
         if (!settingsService) {
-          throw new Error("SettingsService not available");
+          throw serviceUnavailable("SettingsService");
         }
 
         // Check if preloadWhisperModel setting is changing
@@ -203,7 +324,7 @@ export const settingsRouter = createRouter({
     try {
       const settingsService = ctx.serviceManager.getService("settingsService");
       if (!settingsService) {
-        throw new Error("SettingsService not available");
+        throw serviceUnavailable("SettingsService");
       }
       return await settingsService.getFormatterConfig();
     } catch (error) {
@@ -223,11 +344,32 @@ export const settingsRouter = createRouter({
       await settingsService.setFormatterConfig(input);
       return true;
     }),
+
+  // Embedding model used by search.semantic
+  getEmbeddingConfig: procedure.query(async ({ ctx }) => {
+    const settingsService = ctx.serviceManager.getService("settingsService");
+    if (!settingsService) {
+      throw serviceUnavailable("SettingsService");
+    }
+    return await settingsService.getEmbeddingConfig();
+  }),
+
+  // Null turns semantic search off; changing the model re-indexes lazily
+  setEmbeddingConfig: procedure
+    .input(EmbeddingConfigSchema.nullable())
+    .mutation(async ({ input, ctx }) => {
+      const settingsService = ctx.serviceManager.getService("settingsService");
+      if (!settingsService) {
+        throw serviceUnavailable("SettingsService");
+      }
+      await settingsService.setEmbeddingConfig(input);
+      return true;
+    }),
   // Get shortcuts configuration
   getShortcuts: procedure.query(async ({ ctx }) => {
     const settingsService = ctx.serviceManager.getService("settingsService");
     if (!settingsService) {
-      throw new Error("SettingsService not available");
+      throw serviceUnavailable("SettingsService");
     }
     return await settingsService.getShortcuts();
   }),
@@ -237,10 +379,7 @@ export const settingsRouter = createRouter({
     .mutation(async ({ input, ctx }) => {
       const shortcutManager = ctx.serviceManager.getService("shortcutManager");
       if (!shortcutManager) {
-        throw new TRPCError({
-          code: "INTERNAL_SERVER_ERROR",
-          message: "ShortcutManager not available",
-        });
+        throw serviceUnavailable("ShortcutManager");
       }
 
       const result = await shortcutManager.setShortcut(
@@ -249,8 +388,8 @@ export const settingsRouter = createRouter({
       );
 
       if (!result.valid) {
-        throw new TRPCError({
-          code: "BAD_REQUEST",
+        throw new AppError({
+          code: "INVALID_INPUT",
           message: result.error || "Invalid shortcut",
         });
       }
@@ -266,7 +405,7 @@ export const settingsRouter = createRouter({
         const shortcutManager =
           ctx.serviceManager.getService("shortcutManager");
         if (!shortcutManager) {
-          throw new Error("ShortcutManager not available");
+          throw serviceUnavailable("ShortcutManager");
         }
 
         shortcutManager.setIsRecordingShortcut(input);
@@ -319,6 +458,27 @@ export const settingsRouter = createRouter({
     });
   }),
 
+  // Emits each settings section as it's saved, so windows stay in sync
+  // without refetching
+  onChanged: procedure.subscription(({ ctx }) => {
+    return observable<SettingsChange>((emit) => {
+      const settingsService = ctx.serviceManager.getService("settingsService");
+      if (!settingsService) {
+        return () => {};
+      }
+
+      const handleSettingsChanged = (change: SettingsChange) => {
+        emit.next(change);
+      };
+
+      settingsService.on("settings-changed", handleSettingsChanged);
+
+      return () => {
+        settingsService.off("settings-changed", handleSettingsChanged);
+      };
+    });
+  }),
+
   // Set preferred microphone
   setPreferredMicrophone: procedure
     .input(
@@ -331,7 +491,7 @@ export const settingsRouter = createRouter({
         const settingsService =
           ctx.serviceManager.getService("settingsService");
         if (!settingsService) {
-          throw new Error("SettingsService not available");
+          throw serviceUnavailable("SettingsService");
         }
 
         // Get current recording settings
@@ -365,6 +525,250 @@ export const settingsRouter = createRouter({
       }
     }),
 
+  // Get the filename template used for saved recordings
+  getAudioFileNameTemplate: procedure.query(async ({ ctx }) => {
+    const settingsService = ctx.serviceManager.getService("settingsService");
+    if (!settingsService) {
+      throw serviceUnavailable("SettingsService");
+    }
+    const recordingSettings = await settingsService.getRecordingSettings();
+    return {
+      template:
+        recordingSettings?.fileNameTemplate || DEFAULT_AUDIO_FILENAME_TEMPLATE,
+      defaultTemplate: DEFAULT_AUDIO_FILENAME_TEMPLATE,
+    };
+  }),
+
+  // Set the filename template (empty string restores the default)
+  setAudioFileNameTemplate: procedure
+    .input(
+      z.object({
+        template: z
+          .string()
+          .trim()
+          .max(200)
+          .refine((value) => !/[/\\]/.test(value), {
+            message: "Template must not contain path separators",
+          }),
+      }),
+    )
+    .mutation(async ({ input, ctx }) => {
+      const settingsService = ctx.serviceManager.getService("settingsService");
+      if (!settingsService) {
+        throw serviceUnavailable("SettingsService");
+      }
+      const currentSettings = await settingsService.getRecordingSettings();
+      await settingsService.setRecordingSettings({
+        defaultFormat: "wav" as const,
+        sampleRate: 16000 as const,
+        autoStopSilence: false,
+        silenceThreshold: 0.1,
+        maxRecordingDuration: 300,
+        ...currentSettings,
+        fileNameTemplate: input.template || undefined,
+      });
+      return true;
+    }),
+
+  // How long a session may sit stuck before the watchdog recovers it
+  getRecordingWatchdogTimeout: procedure.query(async ({ ctx }) => {
+    const settingsService = ctx.serviceManager.getService("settingsService");
+    if (!settingsService) {
+      throw serviceUnavailable("SettingsService");
+    }
+    const recordingSettings = await settingsService.getRecordingSettings();
+    return {
+      timeoutMs:
+        recordingSettings?.watchdogTimeoutMs ?? DEFAULT_WATCHDOG_TIMEOUT,
+      defaultTimeoutMs: DEFAULT_WATCHDOG_TIMEOUT,
+    };
+  }),
+
+  // Applies from the next recording
+  setRecordingWatchdogTimeout: procedure
+    .input(z.object({ timeoutMs: z.number().int().min(10000).max(600000) }))
+    .mutation(async ({ input, ctx }) => {
+      const settingsService = ctx.serviceManager.getService("settingsService");
+      if (!settingsService) {
+        throw serviceUnavailable("SettingsService");
+      }
+      const currentSettings = await settingsService.getRecordingSettings();
+      await settingsService.setRecordingSettings({
+        defaultFormat: "wav" as const,
+        sampleRate: 16000 as const,
+        autoStopSilence: false,
+        silenceThreshold: 0.1,
+        maxRecordingDuration: 300,
+        ...currentSettings,
+        watchdogTimeoutMs: input.timeoutMs,
+      });
+      return true;
+    }),
+
+  // History and audio retention; unset limits keep everything
+  getHistoryRetention: procedure.query(async ({ ctx }) => {
+    const settingsService = ctx.serviceManager.getService("settingsService");
+    if (!settingsService) {
+      throw serviceUnavailable("SettingsService");
+    }
+    const history = await settingsService.getHistorySettings();
+    return {
+      retentionDays: history?.retentionDays ?? null,
+      maxItems: history?.maxItems ?? null,
+      audioRetentionDays: history?.audioRetentionDays ?? null,
+    };
+  }),
+
+  // Old transcriptions are purged as soon as the policy is saved
+  setHistoryRetention: procedure
+    .input(
+      z.object({
+        retentionDays: z.number().int().min(1).max(3650).nullable(),
+        maxItems: z.number().int().min(10).max(1000000).nullable(),
+        // Recordings only; transcription text is kept
+        audioRetentionDays: z.number().int().min(1).max(3650).nullable(),
+      }),
+    )
+    .mutation(async ({ input, ctx }) => {
+      const settingsService = ctx.serviceManager.getService("settingsService");
+      if (!settingsService) {
+        throw serviceUnavailable("SettingsService");
+      }
+      const currentSettings = await settingsService.getHistorySettings();
+      await settingsService.setHistorySettings({
+        ...currentSettings,
+        retentionDays: input.retentionDays ?? undefined,
+        maxItems: input.maxItems ?? undefined,
+        audioRetentionDays: input.audioRetentionDays ?? undefined,
+      });
+      return true;
+    }),
+
+  // What to do with a dictation that repeats one from the last two minutes
+  getDuplicatePolicy: procedure.query(async ({ ctx }) => {
+    const settingsService = ctx.serviceManager.getService("settingsService");
+    if (!settingsService) {
+      throw serviceUnavailable("SettingsService");
+    }
+    const history = await settingsService.getHistorySettings();
+    return history?.duplicatePolicy ?? "flag";
+  }),
+
+  setDuplicatePolicy: procedure
+    .input(z.object({ policy: z.enum(["keep", "flag", "skip"]) }))
+    .mutation(async ({ input, ctx }) => {
+      const settingsService = ctx.serviceManager.getService("settingsService");
+      if (!settingsService) {
+        throw serviceUnavailable("SettingsService");
+      }
+      const currentSettings = await settingsService.getHistorySettings();
+      await settingsService.setHistorySettings({
+        ...currentSettings,
+        duplicatePolicy: input.policy,
+      });
+      return true;
+    }),
+
+  // Offer to delete downloaded models that go unused for a number of days
+  getModelCleanupSettings: procedure.query(async ({ ctx }) => {
+    const settingsService = ctx.serviceManager.getService("settingsService");
+    if (!settingsService) {
+      throw serviceUnavailable("SettingsService");
+    }
+    const modelCleanup = await settingsService.getModelCleanupSettings();
+    return {
+      enabled: modelCleanup?.enabled ?? false,
+      unusedDays: modelCleanup?.unusedDays ?? 30,
+    };
+  }),
+
+  setModelCleanupSettings: procedure
+    .input(
+      z.object({
+        enabled: z.boolean(),
+        unusedDays: z.number().int().min(1).max(3650),
+      }),
+    )
+    .mutation(async ({ input, ctx }) => {
+      const settingsService = ctx.serviceManager.getService("settingsService");
+      if (!settingsService) {
+        throw serviceUnavailable("SettingsService");
+      }
+      await settingsService.setModelCleanupSettings(input);
+      return true;
+    }),
+
+  // Mirrors of huggingface.co to download models from, e.g. a local server
+  getModelDownloadSettings: procedure.query(async ({ ctx }) => {
+    const settingsService = ctx.serviceManager.getService("settingsService");
+    if (!settingsService) {
+      throw serviceUnavailable("SettingsService");
+    }
+    const modelDownloads = await settingsService.getModelDownloadSettings();
+    return {
+      mirrors: modelDownloads?.mirrors ?? [],
+      fallbackToOrigin: modelDownloads?.fallbackToOrigin ?? true,
+    };
+  }),
+
+  setModelDownloadSettings: procedure
+    .input(
+      z.object({
+        mirrors: z
+          .array(
+            z
+              .string()
+              .trim()
+              .url()
+              .refine((url) => /^https?:\/\//i.test(url), {
+                message: "Mirror must be an http or https URL",
+              })
+              .transform((url) => url.replace(/\/+$/, "")),
+          )
+          .max(10),
+        fallbackToOrigin: z.boolean(),
+      }),
+    )
+    .mutation(async ({ input, ctx }) => {
+      const settingsService = ctx.serviceManager.getService("settingsService");
+      if (!settingsService) {
+        throw serviceUnavailable("SettingsService");
+      }
+      await settingsService.setModelDownloadSettings(input);
+      return true;
+    }),
+
+  // Weekly dictation digest
+  getWeeklyDigestSettings: procedure.query(async ({ ctx }) => {
+    const settingsService = ctx.serviceManager.getService("settingsService");
+    if (!settingsService) {
+      throw serviceUnavailable("SettingsService");
+    }
+    const weeklyDigest = await settingsService.getWeeklyDigestSettings();
+    return {
+      enabled: weeklyDigest?.enabled ?? false,
+      useLanguageModel: weeklyDigest?.useLanguageModel ?? false,
+      notify: weeklyDigest?.notify ?? false,
+    };
+  }),
+
+  setWeeklyDigestSettings: procedure
+    .input(
+      z.object({
+        enabled: z.boolean(),
+        useLanguageModel: z.boolean(),
+        notify: z.boolean(),
+      }),
+    )
+    .mutation(async ({ input, ctx }) => {
+      const settingsService = ctx.serviceManager.getService("settingsService");
+      if (!settingsService) {
+        throw serviceUnavailable("SettingsService");
+      }
+      await settingsService.setWeeklyDigestSettings(input);
+      return true;
+    }),
+
   // Get app version
   getAppVersion: procedure.query(() => {
     return app.getVersion();
@@ -375,7 +779,7 @@ export const settingsRouter = createRouter({
     try {
       const settingsService = ctx.serviceManager.getService("settingsService");
       if (!settingsService) {
-        throw new Error("SettingsService not available");
+        throw serviceUnavailable("SettingsService");
       }
 
       const allSettings = await settingsService.getAllSettings();
@@ -405,7 +809,7 @@ export const settingsRouter = createRouter({
         const settingsService =
           ctx.serviceManager.getService("settingsService");
         if (!settingsService) {
-          throw new Error("SettingsService not available");
+          throw serviceUnavailable("SettingsService");
         }
 
         // Validation: if autoDetectEnabled is false, ensure selectedLanguage is valid
@@ -413,9 +817,11 @@ export const settingsRouter = createRouter({
           !input.autoDetectEnabled &&
           (!input.selectedLanguage || input.selectedLanguage === "auto")
         ) {
-          throw new Error(
-            "Selected language must be specified when auto-detect is disabled",
-          );
+          throw new AppError({
+            code: "INVALID_INPUT",
+            message:
+              "Selected language must be specified when auto-detect is disabled",
+          });
         }
 
         // Set default to "en" if switching from auto-detect enabled to disabled with invalid language
@@ -454,7 +860,7 @@ export const settingsRouter = createRouter({
     try {
       const settingsService = ctx.serviceManager.getService("settingsService");
       if (!settingsService) {
-        throw new Error("SettingsService not available");
+        throw serviceUnavailable("SettingsService");
       }
       return await settingsService.getModelProvidersConfig();
     } catch (error) {
@@ -474,7 +880,7 @@ export const settingsRouter = createRouter({
         const settingsService =
           ctx.serviceManager.getService("settingsService");
         if (!settingsService) {
-          throw new Error("SettingsService not available");
+          throw serviceUnavailable("SettingsService");
         }
         await settingsService.setModelProvidersConfig(input);
 
@@ -501,7 +907,7 @@ export const settingsRouter = createRouter({
         const settingsService =
           ctx.serviceManager.getService("settingsService");
         if (!settingsService) {
-          throw new Error("SettingsService not available");
+          throw serviceUnavailable("SettingsService");
         }
         await settingsService.setOpenRouterConfig(input);
 
@@ -528,7 +934,7 @@ export const settingsRouter = createRouter({
         const settingsService =
           ctx.serviceManager.getService("settingsService");
         if (!settingsService) {
-          throw new Error("SettingsService not available");
+          throw serviceUnavailable("SettingsService");
         }
         await settingsService.setOllamaConfig(input);
 
@@ -555,7 +961,7 @@ export const settingsRouter = createRouter({
         const settingsService =
           ctx.serviceManager.getService("settingsService");
         if (!settingsService) {
-          throw new Error("SettingsService not available");
+          throw serviceUnavailable("SettingsService");
         }
         await settingsService.setOpenAIConfig(input);
         ctx.serviceManager
@@ -585,7 +991,7 @@ export const settingsRouter = createRouter({
         const settingsService =
           ctx.serviceManager.getService("settingsService");
         if (!settingsService) {
-          throw new Error("SettingsService not available");
+          throw serviceUnavailable("SettingsService");
         }
         await settingsService.setGroqConfig(input);
 
@@ -616,7 +1022,7 @@ export const settingsRouter = createRouter({
         const settingsService =
           ctx.serviceManager.getService("settingsService");
         if (!settingsService) {
-          throw new Error("SettingsService not available");
+          throw serviceUnavailable("SettingsService");
         }
         await settingsService.setGrokConfig(input);
 
@@ -647,7 +1053,7 @@ export const settingsRouter = createRouter({
         const settingsService =
           ctx.serviceManager.getService("settingsService");
         if (!settingsService) {
-          throw new Error("SettingsService not available");
+          throw serviceUnavailable("SettingsService");
         }
         await settingsService.setAnthropicConfig(input);
 
@@ -674,7 +1080,7 @@ export const settingsRouter = createRouter({
         const settingsService =
           ctx.serviceManager.getService("settingsService");
         if (!settingsService) {
-          throw new Error("SettingsService not available");
+          throw serviceUnavailable("SettingsService");
         }
         await settingsService.setGoogleConfig(input);
 
@@ -695,9 +1101,62 @@ export const settingsRouter = createRouter({
 
   // Get data path
   getDataPath: procedure.query(() => {
-    return app.getPath("userData");
+    return getDataDirectory();
   }),
 
+  // Where the database, models and recordings are stored
+  getDataDirectory: procedure.query(() => {
+    return {
+      path: getDataDirectory(),
+      isDefault: getCustomDataDirectory() === null,
+    };
+  }),
+
+  // Pick a folder for moveDataDirectory; null when cancelled
+  chooseDataDirectory: procedure.mutation(async () => {
+    const result = await dialog.showOpenDialog({
+      title: "Choose where to store Grizzo's data",
+      properties: ["openDirectory", "createDirectory"],
+    });
+    return result.canceled ? null : (result.filePaths[0] ?? null);
+  }),
+
+  // Move the data to another folder (null for the default), then restart
+  moveDataDirectory: procedure
+    .input(z.object({ path: z.string().min(1).nullable() }))
+    .mutation(async ({ input, ctx }) => {
+      const logger = ctx.serviceManager.getLogger();
+      try {
+        validateDataDirectory(input.path ?? app.getPath("userData"));
+      } catch (error) {
+        throw new AppError({
+          code: "INVALID_INPUT",
+          message:
+            error instanceof Error
+              ? error.message
+              : "Can't move the data to this folder",
+        });
+      }
+
+      let moved = true;
+      try {
+        await moveDataDirectory(input.path);
+      } catch (error) {
+        // The database is closed either way; restart on the old location
+        logger?.main.error("Failed to move data directory:", error);
+        moved = false;
+      }
+
+      if (process.env.NODE_ENV === "development" || !app.isPackaged) {
+        //! restarting will not work properly in dev mode
+        app.quit();
+      } else {
+        app.relaunch();
+        app.quit();
+      }
+      return { success: moved };
+    }),
+
   // Get log file path
   getLogFilePath: procedure.query(() => {
     const isDev = process.env.NODE_ENV === "development" || !app.isPackaged;
@@ -716,12 +1175,9 @@ export const settingsRouter = createRouter({
   getTelemetryConfig: procedure.query(async ({ ctx }) => {
     const telemetryService = ctx.serviceManager.getService("telemetryService");
     return {
-      apiKey: process.env.POSTHOG_API_KEY || __BUNDLED_POSTHOG_API_KEY,
-      host: process.env.POSTHOG_HOST || __BUNDLED_POSTHOG_HOST,
+      ...getTelemetryConfig(),
       machineId: telemetryService?.getMachineId() ?? "",
       enabled: telemetryService?.isEnabled() ?? false,
-      feedbackSurveyId:
-        process.env.FEEDBACK_SURVEY_ID || __BUNDLED_FEEDBACK_SURVEY_ID,
     };
   }),
 
@@ -753,7 +1209,7 @@ export const settingsRouter = createRouter({
   getPreferences: procedure.query(async ({ ctx }) => {
     const settingsService = ctx.serviceManager.getService("settingsService");
     if (!settingsService) {
-      throw new Error("SettingsService not available");
+      throw serviceUnavailable("SettingsService");
     }
     return await settingsService.getPreferences();
   }),
@@ -764,7 +1220,7 @@ export const settingsRouter = createRouter({
     .mutation(async ({ input, ctx }) => {
       const settingsService = ctx.serviceManager.getService("settingsService");
       if (!settingsService) {
-        throw new Error("SettingsService not available");
+        throw serviceUnavailable("SettingsService");
       }
 
       await settingsService.setPreferences(input);
@@ -779,7 +1235,7 @@ export const settingsRouter = createRouter({
     .mutation(async ({ input, ctx }) => {
       const settingsService = ctx.serviceManager.getService("settingsService");
       if (!settingsService) {
-        throw new Error("SettingsService not available");
+        throw serviceUnavailable("SettingsService");
       }
 
       // Get current UI settings
@@ -808,7 +1264,7 @@ export const settingsRouter = createRouter({
     try {
       const settingsService = ctx.serviceManager.getService("settingsService");
       if (!settingsService) {
-        throw new Error("SettingsService not available");
+        throw serviceUnavailable("SettingsService");
       }
       return await settingsService.getTelemetrySettings();
     } catch (error) {
@@ -832,7 +1288,7 @@ export const settingsRouter = createRouter({
         const telemetryService =
           ctx.serviceManager.getService("telemetryService");
         if (!telemetryService) {
-          throw new Error("TelemetryService not available");
+          throw serviceUnavailable("TelemetryService");
         }
 
         // Update the telemetry service state
@@ -855,14 +1311,198 @@ export const settingsRouter = createRouter({
       }
     }),
 
+  // Get the global speed/accuracy preset
+  getPerformancePreset: procedure.query(async ({ ctx }) => {
+    const settingsService = ctx.serviceManager.getService("settingsService");
+    if (!settingsService) {
+      throw serviceUnavailable("SettingsService");
+    }
+    const settings = await settingsService.getTranscriptionSettings();
+    return settings?.performancePreset ?? "balanced";
+  }),
+
+  // Apply a speed/accuracy preset: picks the best-fitting downloaded local
+  // model, and sets VAD/chunking and formatting behaviour for new sessions
+  setPerformancePreset: procedure
+    .input(z.object({ preset: z.enum(["fast", "balanced", "accurate"]) }))
+    .mutation(async ({ input, ctx }) => {
+      const settingsService = ctx.serviceManager.getService("settingsService");
+      const modelService = ctx.serviceManager.getService("modelService");
+      if (!settingsService || !modelService) {
+        throw serviceUnavailable("SettingsService or ModelService");
+      }
+
+      // Only swap between local models; an explicitly chosen cloud model
+      // stays selected
+      const currentModelId = await modelService.getSelectedModel();
+      const currentModel = AVAILABLE_MODELS.find(
+        (m) => m.id === currentModelId,
+      );
+      const speechModelId =
+        currentModel?.setup === "api"
+          ? currentModelId
+          : ((await modelService.getSpeechModelForPreset(input.preset)) ??
+            currentModelId);
+
+      const currentSettings = await settingsService.getTranscriptionSettings();
+      await settingsService.setTranscriptionSettings({
+        language: "en",
+        autoTranscribe: true,
+        confidenceThreshold: 0.5,
+        enablePunctuation: true,
+        enableTimestamps: false,
+        ...currentSettings,
+        performancePreset: input.preset,
+      });
+
+      if (speechModelId !== currentModelId) {
+        await modelService.setSelectedModel(speechModelId);
+        const transcriptionService = ctx.serviceManager.getService(
+          "transcriptionService",
+        );
+        transcriptionService?.handleModelChange().catch((err) => {
+          const logger = ctx.serviceManager.getLogger();
+          logger?.main.error("Failed to handle model change:", err);
+        });
+      }
+
+      return { preset: input.preset, speechModelId };
+    }),
+
+  // Get remote control (local REST trigger) settings
+  getRemoteControlSettings: procedure.query(async ({ ctx }) => {
+    const settingsService = ctx.serviceManager.getService("settingsService");
+    if (!settingsService) {
+      throw serviceUnavailable("SettingsService");
+    }
+    const settings = await settingsService.getRemoteControlSettings();
+    return {
+      enabled: settings?.enabled ?? false,
+      port: settings?.port ?? DEFAULT_REMOTE_CONTROL_PORT,
+      token: settings?.token ?? "",
+      allowLan: settings?.allowLan ?? false,
+    };
+  }),
+
+  // Update remote control settings (generates a token on first enable)
+  updateRemoteControlSettings: procedure
+    .input(
+      z.object({
+        enabled: z.boolean().optional(),
+        port: z.number().int().min(1024).max(65535).optional(),
+        allowLan: z.boolean().optional(),
+      }),
+    )
+    .mutation(async ({ input, ctx }) => {
+      const settingsService = ctx.serviceManager.getService("settingsService");
+      if (!settingsService) {
+        throw serviceUnavailable("SettingsService");
+      }
+      const current = await settingsService.getRemoteControlSettings();
+      const next = {
+        enabled: input.enabled ?? current?.enabled ?? false,
+        port: input.port ?? current?.port ?? DEFAULT_REMOTE_CONTROL_PORT,
+        token: current?.token || generateRemoteControlToken(),
+        allowLan: input.allowLan ?? current?.allowLan ?? false,
+      };
+      await settingsService.setRemoteControlSettings(next);
+
+      const logger = ctx.serviceManager.getLogger();
+      if (logger) {
+        logger.main.info("Remote control settings updated", {
+          enabled: next.enabled,
+          port: next.port,
+          allowLan: next.allowLan,
+        });
+      }
+      return next;
+    }),
+
+  // Replace the remote control token, invalidating the old one
+  regenerateRemoteControlToken: procedure.mutation(async ({ ctx }) => {
+    const settingsService = ctx.serviceManager.getService("settingsService");
+    if (!settingsService) {
+      throw serviceUnavailable("SettingsService");
+    }
+    const current = await settingsService.getRemoteControlSettings();
+    const next = {
+      enabled: current?.enabled ?? false,
+      port: current?.port ?? DEFAULT_REMOTE_CONTROL_PORT,
+      allowLan: current?.allowLan ?? false,
+      token: generateRemoteControlToken(),
+    };
+    await settingsService.setRemoteControlSettings(next);
+    return next;
+  }),
+
+  // Get the proxy used for outbound requests
+  getProxySettings: procedure.query(async ({ ctx }) => {
+    const settingsService = ctx.serviceManager.getService("settingsService");
+    if (!settingsService) {
+      throw serviceUnavailable("SettingsService");
+    }
+    const settings = await settingsService.getProxySettings();
+    return {
+      enabled: settings?.enabled ?? false,
+      url: settings?.url ?? "",
+      username: settings?.username ?? "",
+      password: settings?.password ?? "",
+      bypass: settings?.bypass ?? [],
+    };
+  }),
+
+  // Update proxy settings; applied immediately to new requests
+  setProxySettings: procedure
+    .input(
+      z.object({
+        enabled: z.boolean(),
+        url: z
+          .string()
+          .trim()
+          .refine(
+            (url) =>
+              url === "" ||
+              /^(https?|socks4|socks5):\/\/[^/\s]+\/?$/.test(url),
+            "Proxy URL must look like http://host:port",
+          ),
+        username: z.string().optional(),
+        password: z.string().optional(),
+        bypass: z.array(z.string().trim().min(1)).optional(),
+      }),
+    )
+    .mutation(async ({ input, ctx }) => {
+      const settingsService = ctx.serviceManager.getService("settingsService");
+      if (!settingsService) {
+        throw serviceUnavailable("SettingsService");
+      }
+      if (input.enabled && !input.url) {
+        throw new AppError({
+          code: "INVALID_INPUT",
+          message: "A proxy URL is required",
+        });
+      }
+      await settingsService.setProxySettings({
+        enabled: input.enabled,
+        url: input.url,
+        username: input.username || undefined,
+        password: input.username ? input.password : undefined,
+        bypass: input.bypass,
+      });
+
+      const logger = ctx.serviceManager.getLogger();
+      logger?.main.info("Proxy settings updated", {
+        enabled: input.enabled,
+        url: input.url ? new URL(input.url).host : undefined,
+        bypass: input.bypass,
+      });
+      return true;
+    }),
+
   // Get all modes
   getModes: procedure.query(async ({ ctx }) => {
     const settingsService = ctx.serviceManager.getService("settingsService");
     if (!settingsService) {
-      throw new TRPCError({
-        code: "INTERNAL_SERVER_ERROR",
-        message: "SettingsService not available",
-      });
+      throw serviceUnavailable("SettingsService");
     }
     return await settingsService.getModes();
   }),
@@ -871,10 +1511,7 @@ export const settingsRouter = createRouter({
   getActiveMode: procedure.query(async ({ ctx }) => {
     const settingsService = ctx.serviceManager.getService("settingsService");
     if (!settingsService) {
-      throw new TRPCError({
-        code: "INTERNAL_SERVER_ERROR",
-        message: "SettingsService not available",
-      });
+      throw serviceUnavailable("SettingsService");
     }
     return await settingsService.getActiveMode();
   }),
@@ -885,18 +1522,15 @@ export const settingsRouter = createRouter({
     .mutation(async ({ input, ctx }) => {
       const settingsService = ctx.serviceManager.getService("settingsService");
       if (!settingsService) {
-        throw new TRPCError({
-          code: "INTERNAL_SERVER_ERROR",
-          message: "SettingsService not available",
-        });
+        throw serviceUnavailable("SettingsService");
       }
       try {
         await settingsService.setActiveMode(input.modeId);
 
         return true;
       } catch (error) {
-        throw new TRPCError({
-          code: "BAD_REQUEST",
+        throw new AppError({
+          code: "INVALID_INPUT",
           message:
             error instanceof Error
               ? error.message
@@ -911,10 +1545,7 @@ export const settingsRouter = createRouter({
     .mutation(async ({ input, ctx }) => {
       const settingsService = ctx.serviceManager.getService("settingsService");
       if (!settingsService) {
-        throw new TRPCError({
-          code: "INTERNAL_SERVER_ERROR",
-          message: "SettingsService not available",
-        });
+        throw serviceUnavailable("SettingsService");
       }
       try {
         const createdMode = await settingsService.createMode(input);
@@ -934,8 +1565,8 @@ export const settingsRouter = createRouter({
 
         return createdMode;
       } catch (error) {
-        throw new TRPCError({
-          code: "BAD_REQUEST",
+        throw new AppError({
+          code: "INVALID_INPUT",
           message:
             error instanceof Error ? error.message : "Failed to create mode",
         });
@@ -948,10 +1579,7 @@ export const settingsRouter = createRouter({
     .mutation(async ({ input, ctx }) => {
       const settingsService = ctx.serviceManager.getService("settingsService");
       if (!settingsService) {
-        throw new TRPCError({
-          code: "INTERNAL_SERVER_ERROR",
-          message: "SettingsService not available",
-        });
+        throw serviceUnavailable("SettingsService");
       }
       const { modeId, ...updates } = input;
       // Build cleanUpdates from only the keys actually present in `updates`.
@@ -972,6 +1600,12 @@ export const settingsRouter = createRouter({
           | "customInstructions"
           | "speechModelId"
           | "appBindings"
+          | "spokenCommandsEnabled"
+          | "normalization"
+          | "voiceEditingEnabled"
+          | "messageSplitting"
+          | "profanityPolicy"
+          | "vocabularyCorrectionEnabled"
         >
       >;
       const shouldRefreshPreload = Object.prototype.hasOwnProperty.call(
@@ -998,8 +1632,8 @@ export const settingsRouter = createRouter({
 
         return updatedMode;
       } catch (error) {
-        throw new TRPCError({
-          code: "BAD_REQUEST",
+        throw new AppError({
+          code: "INVALID_INPUT",
           message:
             error instanceof Error ? error.message : "Failed to update mode",
         });
@@ -1012,10 +1646,7 @@ export const settingsRouter = createRouter({
     .mutation(async ({ input, ctx }) => {
       const settingsService = ctx.serviceManager.getService("settingsService");
       if (!settingsService) {
-        throw new TRPCError({
-          code: "INTERNAL_SERVER_ERROR",
-          message: "SettingsService not available",
-        });
+        throw serviceUnavailable("SettingsService");
       }
       try {
         await settingsService.deleteMode(input.modeId);
@@ -1032,68 +1663,299 @@ export const settingsRouter = createRouter({
 
         return true;
       } catch (error) {
-        throw new TRPCError({
-          code: "BAD_REQUEST",
+        throw new AppError({
+          code: "INVALID_INPUT",
           message:
             error instanceof Error ? error.message : "Failed to delete mode",
         });
       }
     }),
 
-  // Reset app - deletes database and models, then restarts
-  resetApp: procedure.mutation(async ({ ctx }) => {
-    try {
-      const logger = ctx.serviceManager.getLogger();
-      if (logger) {
-        logger.main.info("Resetting app - deleting database and models");
+  // Settings profiles ("Work", "Personal"), each with its own modes,
+  // providers and shortcuts
+  getProfiles: procedure.query(async ({ ctx }) => {
+    const settingsService = ctx.serviceManager.getService("settingsService");
+    if (!settingsService) {
+      throw serviceUnavailable("SettingsService");
+    }
+    return await settingsService.getProfiles();
+  }),
+
+  createProfile: procedure
+    .input(
+      z.object({
+        name: z.string().trim().min(1).max(50),
+        copyActive: z.boolean().default(true),
+      }),
+    )
+    .mutation(async ({ input, ctx }) => {
+      const settingsService = ctx.serviceManager.getService("settingsService");
+      if (!settingsService) {
+        throw serviceUnavailable("SettingsService");
       }
+      try {
+        return await settingsService.createProfile(input.name, {
+          copyActive: input.copyActive,
+        });
+      } catch (error) {
+        throw new AppError({
+          code: "INVALID_INPUT",
+          message:
+            error instanceof Error ? error.message : "Failed to create profile",
+        });
+      }
+    }),
 
-      // Close database connection before deleting
-      await closeDatabase();
+  renameProfile: procedure
+    .input(
+      z.object({
+        profileId: z.string().min(1),
+        name: z.string().trim().min(1).max(50),
+      }),
+    )
+    .mutation(async ({ input, ctx }) => {
+      const settingsService = ctx.serviceManager.getService("settingsService");
+      if (!settingsService) {
+        throw serviceUnavailable("SettingsService");
+      }
+      try {
+        await settingsService.renameProfile(input.profileId, input.name);
+        return true;
+      } catch (error) {
+        throw new AppError({
+          code: "INVALID_INPUT",
+          message:
+            error instanceof Error ? error.message : "Failed to rename profile",
+        });
+      }
+    }),
 
-      // Add a small delay to ensure the connection is fully closed on Windows
-      await new Promise((resolve) => setTimeout(resolve, 100));
+  deleteProfile: procedure
+    .input(z.object({ profileId: z.string().min(1) }))
+    .mutation(async ({ input, ctx }) => {
+      const settingsService = ctx.serviceManager.getService("settingsService");
+      if (!settingsService) {
+        throw serviceUnavailable("SettingsService");
+      }
+      try {
+        await settingsService.deleteProfile(input.profileId);
+        return true;
+      } catch (error) {
+        throw new AppError({
+          code: "INVALID_INPUT",
+          message:
+            error instanceof Error ? error.message : "Failed to delete profile",
+        });
+      }
+    }),
+
+  switchProfile: procedure
+    .input(z.object({ profileId: z.string().min(1) }))
+    .mutation(async ({ input, ctx }) => {
+      const settingsService = ctx.serviceManager.getService("settingsService");
+      if (!settingsService) {
+        throw serviceUnavailable("SettingsService");
+      }
+      try {
+        await settingsService.switchProfile(input.profileId);
+      } catch (error) {
+        throw new AppError({
+          code: "INVALID_INPUT",
+          message:
+            error instanceof Error ? error.message : "Failed to switch profile",
+        });
+      }
+
+      // Register the new profile's shortcuts and load its models
+      const shortcutManager = ctx.serviceManager.getService("shortcutManager");
+      await shortcutManager?.reloadShortcuts();
+      const transcriptionService = ctx.serviceManager.getService(
+        "transcriptionService",
+      );
+      if (transcriptionService) {
+        transcriptionService.handleModelChange().catch((err) => {
+          const logger = ctx.serviceManager.getLogger();
+          logger?.main.error("Failed to handle profile change:", err);
+        });
+      }
+      return true;
+    }),
 
-      const userDataPath = app.getPath("userData");
+  // Encrypt local data at rest; changes apply on the next launch
+  getDatabaseEncryption: procedure.query(() => {
+    const pending = getPendingEncryptionChange(dbPath);
+    return {
+      enabled: isDatabaseEncrypted(dbPath),
+      pending: pending ? pending === "encrypt" : null,
+      available: safeStorage.isEncryptionAvailable(),
+    };
+  }),
 
-      // Delete database files (main db + WAL/SHM files)
-      const dbFile = path.join(userDataPath, "grizzo.db");
-      await fs.rm(dbFile, { force: true }).catch(() => {});
-      await fs.rm(`${dbFile}-wal`, { force: true }).catch(() => {});
-      await fs.rm(`${dbFile}-shm`, { force: true }).catch(() => {});
+  setDatabaseEncryption: procedure
+    .input(z.object({ enabled: z.boolean() }))
+    .mutation(({ input }) => {
+      try {
+        return {
+          restartRequired: setDatabaseEncryption(dbPath, input.enabled),
+        };
+      } catch (error) {
+        throw new AppError({
+          code: "PRECONDITION_FAILED",
+          message:
+            error instanceof Error
+              ? error.message
+              : "Failed to change database encryption",
+        });
+      }
+    }),
 
-      // Delete models directory
-      const modelsDir = path.join(userDataPath, "models");
-      await fs.rm(modelsDir, { recursive: true, force: true }).catch(() => {});
+  // Restore one group of settings to its defaults without resetting the app
+  resetSection: procedure
+    .input(
+      z.object({
+        section: z.enum(["shortcuts", "providers", "modes", "preferences"]),
+        withoutBackup: z.boolean().default(false),
+      }),
+    )
+    .mutation(async ({ input, ctx }) => {
+      const settingsService = ctx.serviceManager.getService("settingsService");
+      if (!settingsService) {
+        throw serviceUnavailable("SettingsService");
+      }
+      await backupBeforeChange(`reset-${input.section}`, {
+        withoutBackup: input.withoutBackup,
+      });
+      await settingsService.resetSection(input.section);
 
-      // In development, also delete the local db file if it exists
-      if (process.env.NODE_ENV === "development" || !app.isPackaged) {
-        try {
-          await fs.unlink(dbPath);
-        } catch {
-          // Ignore if file doesn't exist
+      if (input.section === "shortcuts") {
+        const shortcutManager =
+          ctx.serviceManager.getService("shortcutManager");
+        await shortcutManager?.reloadShortcuts();
+      } else if (input.section === "modes" || input.section === "providers") {
+        const transcriptionService = ctx.serviceManager.getService(
+          "transcriptionService",
+        );
+        transcriptionService?.handleModelChange().catch((err) => {
+          const logger = ctx.serviceManager.getLogger();
+          logger?.main.error("Failed to handle settings reset:", err);
+        });
+      }
+      return true;
+    }),
+
+  // Reset app - deletes database and models, then restarts
+  resetApp: procedure
+    .input(
+      z.object({ withoutBackup: z.boolean().default(false) }).optional(),
+    )
+    .mutation(async ({ input, ctx }) => {
+      // Keep a copy so a reset by mistake can be undone
+      await backupBeforeChange("reset-app", {
+        withoutBackup: input?.withoutBackup,
+      });
+
+      try {
+        const logger = ctx.serviceManager.getLogger();
+        if (logger) {
+          logger.main.info("Resetting app - deleting database and models");
+        }
+
+        // Close database connection before deleting
+        await closeDatabase();
+
+        // Add a small delay to ensure the connection is fully closed on Windows
+        await new Promise((resolve) => setTimeout(resolve, 100));
+
+        // Delete database files (main db + WAL/SHM files)
+        const dbFile = path.join(getDataDirectory(), "grizzo.db");
+        await fs.rm(dbFile, { force: true }).catch(() => {});
+        await fs.rm(`${dbFile}-wal`, { force: true }).catch(() => {});
+        await fs.rm(`${dbFile}-shm`, { force: true }).catch(() => {});
+        removeDatabaseKeys(dbFile);
+
+        // Delete models directory
+        const modelsDir = getModelsDirectory();
+        await fs.rm(modelsDir, { recursive: true, force: true }).catch(() => {});
+
+        // Start over in the default location
+        setCustomDataDirectory(null);
+
+        // In development, also delete the local db file if it exists
+        if (process.env.NODE_ENV === "development" || !app.isPackaged) {
+          try {
+            removeDatabaseKeys(dbPath);
+            await fs.unlink(dbPath);
+          } catch {
+            // Ignore if file doesn't exist
+          }
+        }
+
+        // Handle restart differently in development vs production
+        if (process.env.NODE_ENV === "development" || !app.isPackaged) {
+          //! restarting will not work properly in dev mode
+          app.quit();
+        } else {
+          // Production mode: relaunch the app
+          app.relaunch();
+          app.quit();
+        }
+
+        return { success: true };
+      } catch (error) {
+        const logger = ctx.serviceManager.getLogger();
+        if (logger) {
+          logger.main.error("Error resetting app:", error);
         }
+        throw new AppError({
+          code: "INTERNAL",
+          message: "Failed to reset app",
+          cause: error,
+        });
+      }
+    }),
+
+  // Backups taken before destructive operations, newest first
+  listBackups: procedure.query(() => {
+    return listDatabaseBackups();
+  }),
+
+  // Replace the database with a backup, then restart to open it
+  restoreBackup: procedure
+    .input(
+      z.object({
+        id: z.string(),
+        withoutBackup: z.boolean().default(false),
+      }),
+    )
+    .mutation(async ({ input, ctx }) => {
+      const logger = ctx.serviceManager.getLogger();
+      if (!listDatabaseBackups().some((backup) => backup.id === input.id)) {
+        throw new AppError({
+          code: "NOT_FOUND",
+          message: `Backup not found: ${input.id}`,
+        });
       }
+      logger?.main.info("Restoring database backup", { id: input.id });
+
+      // The current database is overwritten, so it gets a backup too
+      await backupBeforeChange("restore", {
+        withoutBackup: input.withoutBackup,
+        keep: input.id,
+      });
+      await closeDatabase();
+      // Add a small delay to ensure the connection is fully closed on Windows
+      await new Promise((resolve) => setTimeout(resolve, 100));
+      await restoreDatabaseBackup(input.id);
 
-      // Handle restart differently in development vs production
       if (process.env.NODE_ENV === "development" || !app.isPackaged) {
         //! restarting will not work properly in dev mode
         app.quit();
       } else {
-        // Production mode: relaunch the app
         app.relaunch();
         app.quit();
       }
-
       return { success: true };
-    } catch (error) {
-      const logger = ctx.serviceManager.getLogger();
-      if (logger) {
-        logger.main.error("Error resetting app:", error);
-      }
-      throw new Error("Failed to reset app");
-    }
-  }),
+    }),
 
   relaunchApp: procedure.mutation(async () => {
     // Relaunch the app to apply settings changes
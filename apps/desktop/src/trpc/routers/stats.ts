@@ -0,0 +1,51 @@
+import { z } from "zod";
+import { createRouter, procedure } from "../trpc";
+import { serviceUnavailable } from "../errors";
+import { getDictationStats } from "../../db/stats";
+import { getWeeklySummaries } from "../../db/weekly-summaries";
+
+const GetDictationStatsSchema = z.object({
+  from: z.date().optional(),
+  to: z.date().optional(), // Exclusive
+});
+
+export const statsRouter = createRouter({
+  // Words dictated, speaking rate, streaks and per-model/language breakdowns
+  getDictationStats: procedure
+    .input(GetDictationStatsSchema.optional())
+    .query(async ({ input }) => {
+      return await getDictationStats(input ?? {});
+    }),
+
+  // Stored weekly summaries, most recent first
+  getWeeklySummaries: procedure
+    .input(z.object({ limit: z.number().int().min(1).max(104).optional() }))
+    .query(async ({ input }) => {
+      return await getWeeklySummaries(input.limit);
+    }),
+
+  // Summarize the week containing `date` (default: this week so far).
+  // useLanguageModel defaults to the weekly digest setting.
+  generateWeeklySummary: procedure
+    .input(
+      z
+        .object({
+          date: z.date().optional(),
+          useLanguageModel: z.boolean().optional(),
+        })
+        .optional(),
+    )
+    .mutation(async ({ input, ctx }) => {
+      const digestService = ctx.serviceManager.getService("digestService");
+      if (!digestService) {
+        throw serviceUnavailable("DigestService");
+      }
+      const settings = await ctx.serviceManager
+        .getService("settingsService")
+        .getWeeklyDigestSettings();
+      return await digestService.generate(input?.date ?? new Date(), {
+        useLanguageModel:
+          input?.useLanguageModel ?? settings?.useLanguageModel ?? false,
+      });
+    }),
+});
@@ -1,30 +1,101 @@
 import { z } from "zod";
-import { dialog } from "electron";
+import { clipboard, dialog } from "electron";
 import * as fs from "node:fs";
 import * as path from "node:path";
 import { createRouter, procedure } from "../trpc";
+import { AppError, serviceUnavailable } from "../errors";
 import {
   getTranscriptions,
   getTranscriptionById,
+  getLatestTranscription,
   createTranscription,
   updateTranscription,
   deleteTranscription,
+  deleteTranscriptions,
   getTranscriptionsCount,
+  getAllTranscriptions,
   searchTranscriptions,
+  getReviewQueue,
+  getReviewQueueCount,
+  getReferencedAudioFiles,
+  setTranscriptionFavorite,
+  getTranscriptionTags,
+  setTranscriptionTags,
+  getAllTags,
+  getTargetApps,
+  getTranscriptionRevisions,
+  revertToRevision,
+  getDictationSessions,
 } from "../../db/transcriptions.js";
-import { deleteAudioFile } from "../../utils/audio-file-cleanup.js";
+import {
+  deleteAudioFile,
+  deleteAudioFiles,
+  getAudioStorageUsage,
+} from "../../utils/audio-file-cleanup.js";
+import { serializeTranscriptions } from "../../utils/transcription-export.js";
 
 // Input schemas
 const GetTranscriptionsSchema = z.object({
   limit: z.number().optional(),
   offset: z.number().optional(),
+  // nextCursor from the previous page; takes precedence over offset
+  cursor: z.string().regex(/^-?\d+:\d+$/).optional(),
   sortBy: z.enum(["timestamp", "createdAt"]).optional(),
   sortOrder: z.enum(["asc", "desc"]).optional(),
   search: z.string().optional(),
+  // Timestamp range, from inclusive and to exclusive
+  from: z.date().optional(),
+  to: z.date().optional(),
+  language: z.string().optional(),
+  speechModel: z.string().optional(),
+  favorite: z.boolean().optional(),
+  tag: z.string().optional(),
+  // Only transcriptions inserted into this app
+  targetAppBundleId: z.string().optional(),
+  // Only transcriptions in this dictation session (see getSessions)
+  sessionId: z.number().optional(),
+  // Return only these columns (plus id), e.g. ["preview", "timestamp"]
+  fields: z
+    .array(
+      z.enum([
+        "text",
+        "rawText",
+        "preview",
+        "timestamp",
+        "language",
+        "audioFile",
+        "confidence",
+        "duration",
+        "speechModel",
+        "formattingModel",
+        "meta",
+        "needsReview",
+        "isFavorite",
+        "targetAppBundleId",
+        "targetAppName",
+        "dictationSessionId",
+        "createdAt",
+        "updatedAt",
+      ]),
+    )
+    .optional(),
+});
+
+const TranscriptionFiltersSchema = GetTranscriptionsSchema.pick({
+  search: true,
+  from: true,
+  to: true,
+  language: true,
+  speechModel: true,
+  favorite: true,
+  tag: true,
+  targetAppBundleId: true,
+  sessionId: true,
 });
 
 const CreateTranscriptionSchema = z.object({
   text: z.string(),
+  rawText: z.string().optional(),
   timestamp: z.date().optional(),
   audioFile: z.string().optional(),
   language: z.string().optional(),
@@ -47,16 +118,18 @@ export const transcriptionsRouter = createRouter({
 
   // Get transcriptions count
   getTranscriptionsCount: procedure
-    .input(z.object({ search: z.string().optional() }))
+    .input(TranscriptionFiltersSchema)
     .query(async ({ input }) => {
-      return await getTranscriptionsCount(input.search);
+      return await getTranscriptionsCount(input);
     }),
 
-  // Get transcription by ID
+  // Get transcription by ID, with its tags
   getTranscriptionById: procedure
     .input(z.object({ id: z.number() }))
     .query(async ({ input }) => {
-      return await getTranscriptionById(input.id);
+      const transcription = await getTranscriptionById(input.id);
+      if (!transcription) return null;
+      return { ...transcription, tags: await getTranscriptionTags(input.id) };
     }),
 
   // Search transcriptions
@@ -87,7 +160,159 @@ export const transcriptionsRouter = createRouter({
       }),
     )
     .mutation(async ({ input }) => {
-      return await updateTranscription(input.id, input.data);
+      // Editing the text counts as reviewing it
+      const data =
+        input.data.text !== undefined
+          ? { ...input.data, needsReview: false }
+          : input.data;
+      return await updateTranscription(input.id, data);
+    }),
+
+  // Earlier texts of a transcription, newest first
+  getRevisions: procedure
+    .input(z.object({ id: z.number() }))
+    .query(async ({ input }) => {
+      return await getTranscriptionRevisions(input.id);
+    }),
+
+  // Restore an earlier text; the current text is kept as a revision
+  revertToRevision: procedure
+    .input(z.object({ revisionId: z.number() }))
+    .mutation(async ({ input }) => {
+      const transcription = await revertToRevision(input.revisionId);
+      if (!transcription) {
+        throw new AppError({
+          code: "NOT_FOUND",
+          message: "Revision not found",
+        });
+      }
+      return transcription;
+    }),
+
+  // Star or unstar a transcription
+  setFavorite: procedure
+    .input(z.object({ id: z.number(), isFavorite: z.boolean() }))
+    .mutation(async ({ input }) => {
+      return await setTranscriptionFavorite(input.id, input.isFavorite);
+    }),
+
+  // Replace a transcription's tags
+  setTags: procedure
+    .input(
+      z.object({
+        id: z.number(),
+        tags: z.array(z.string().max(50)).max(20),
+      }),
+    )
+    .mutation(async ({ input }) => {
+      const transcription = await getTranscriptionById(input.id);
+      if (!transcription) {
+        throw new AppError({
+          code: "NOT_FOUND",
+          message: "Transcription not found",
+        });
+      }
+      return await setTranscriptionTags(input.id, input.tags);
+    }),
+
+  // Every tag in use, with counts
+  getTags: procedure.query(async () => {
+    return await getAllTags();
+  }),
+
+  // Dictations grouped into sessions of quick successive inserts into the
+  // same app, most recent first
+  getSessions: procedure
+    .input(
+      z.object({
+        limit: z.number().optional(),
+        offset: z.number().optional(),
+      }),
+    )
+    .query(async ({ input }) => {
+      return await getDictationSessions(input);
+    }),
+
+  // Apps that have been dictated into, with counts
+  getTargetApps: procedure.query(async () => {
+    return await getTargetApps();
+  }),
+
+  // Get transcriptions flagged for review
+  getReviewQueue: procedure
+    .input(
+      z.object({
+        limit: z.number().optional(),
+        offset: z.number().optional(),
+      }),
+    )
+    .query(async ({ input }) => {
+      return await getReviewQueue(input);
+    }),
+
+  // Get review queue size
+  getReviewQueueCount: procedure.query(async () => {
+    return await getReviewQueueCount();
+  }),
+
+  // Approve a flagged transcription as-is and remove it from the review queue
+  approveTranscription: procedure
+    .input(z.object({ id: z.number() }))
+    .mutation(async ({ input }) => {
+      return await updateTranscription(input.id, { needsReview: false });
+    }),
+
+  // Summarize a transcription with the default language model; the summary
+  // is also stored in the transcription's meta
+  summarize: procedure
+    .input(z.object({ id: z.number() }))
+    .mutation(async ({ input, ctx }) => {
+      const transcriptionService = ctx.serviceManager.getService(
+        "transcriptionService",
+      );
+      if (!transcriptionService) {
+        throw serviceUnavailable("TranscriptionService");
+      }
+      try {
+        return await transcriptionService.summarizeTranscription(input.id);
+      } catch (error) {
+        throw new AppError({
+          code: "PRECONDITION_FAILED",
+          message:
+            error instanceof Error ? error.message : "Summarization failed",
+        });
+      }
+    }),
+
+  // Copy a transcription's text (default: the latest) to the system
+  // clipboard from the main process
+  copyToClipboard: procedure
+    .input(
+      z
+        .object({
+          id: z.number().optional(),
+          // "raw" copies the recognizer output, falling back to the final text
+          variant: z.enum(["formatted", "raw"]).optional(),
+        })
+        .optional(),
+    )
+    .mutation(async ({ input }) => {
+      const transcription =
+        input?.id !== undefined
+          ? await getTranscriptionById(input.id)
+          : await getLatestTranscription();
+      if (!transcription) {
+        throw new AppError({
+          code: "NOT_FOUND",
+          message: "Transcription not found",
+        });
+      }
+      const text =
+        input?.variant === "raw"
+          ? (transcription.rawText ?? transcription.text)
+          : transcription.text;
+      clipboard.writeText(text);
+      return { id: transcription.id, length: text.length };
     }),
 
   // Delete transcription
@@ -120,6 +345,22 @@ export const transcriptionsRouter = createRouter({
       return result;
     }),
 
+  // Delete several transcriptions and their audio files
+  bulkDelete: procedure
+    .input(z.object({ ids: z.array(z.number()).min(1).max(1000) }))
+    .mutation(async ({ input }) => {
+      const rows = await deleteTranscriptions(input.ids);
+      await deleteAudioFiles(
+        rows.flatMap((row) => (row.audioFile ? [row.audioFile] : [])),
+      );
+      return { deleted: rows.length };
+    }),
+
+  // Disk space used by recordings
+  getStorageUsage: procedure.query(async () => {
+    return await getAudioStorageUsage(await getReferencedAudioFiles());
+  }),
+
   // Get audio file for playback
   // Implemented as mutation instead of query because:
   // 1. Large binary data (audio files) shouldn't be cached by React Query
@@ -132,7 +373,11 @@ export const transcriptionsRouter = createRouter({
       const transcription = await getTranscriptionById(input.transcriptionId);
 
       if (!transcription?.audioFile) {
-        throw new Error("No audio file associated with this transcription");
+        throw new AppError({
+          code: "NOT_FOUND",
+          message: "No audio file associated with this transcription",
+          details: { transcriptionId: input.transcriptionId },
+        });
       }
 
       try {
@@ -173,7 +418,12 @@ export const transcriptionsRouter = createRouter({
           audioFile: transcription.audioFile,
           error,
         });
-        throw new Error("Audio file not found or inaccessible");
+        throw new AppError({
+          code: "NOT_FOUND",
+          message: "Audio file not found or inaccessible",
+          details: { transcriptionId: input.transcriptionId },
+          cause: error,
+        });
       }
     }),
 
@@ -187,7 +437,11 @@ export const transcriptionsRouter = createRouter({
       const transcription = await getTranscriptionById(input.transcriptionId);
 
       if (!transcription?.audioFile) {
-        throw new Error("No audio file associated with this transcription");
+        throw new AppError({
+          code: "NOT_FOUND",
+          message: "No audio file associated with this transcription",
+          details: { transcriptionId: input.transcriptionId },
+        });
       }
 
       try {
@@ -229,7 +483,53 @@ export const transcriptionsRouter = createRouter({
           audioFile: transcription.audioFile,
           error,
         });
-        throw new Error("Failed to download audio file");
+        throw new AppError({
+          code: "INTERNAL",
+          message: "Failed to download audio file",
+          cause: error,
+        });
+      }
+    }),
+
+  // Export history matching the filters to a CSV or JSON file chosen in a
+  // save dialog
+  exportHistory: procedure
+    .input(
+      TranscriptionFiltersSchema.extend({
+        format: z.enum(["csv", "json"]),
+      }),
+    )
+    .mutation(async ({ input, ctx }) => {
+      const { format, ...filters } = input;
+      const rows = await getAllTranscriptions(filters);
+
+      const result = await dialog.showSaveDialog({
+        defaultPath: `transcriptions-${new Date().toISOString().slice(0, 10)}.${format}`,
+        filters: [
+          format === "csv"
+            ? { name: "CSV", extensions: ["csv"] }
+            : { name: "JSON", extensions: ["json"] },
+          { name: "All Files", extensions: ["*"] },
+        ],
+      });
+
+      if (result.canceled || !result.filePath) {
+        return { success: false, canceled: true };
       }
+
+      await fs.promises.writeFile(
+        result.filePath,
+        serializeTranscriptions(rows, format),
+        "utf8",
+      );
+
+      const logger = ctx.serviceManager.getLogger();
+      logger.main.info("Transcription history exported", {
+        format,
+        count: rows.length,
+        savedTo: result.filePath,
+      });
+
+      return { success: true, filePath: result.filePath, count: rows.length };
     }),
 });
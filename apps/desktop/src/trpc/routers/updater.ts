@@ -1,6 +1,7 @@
 import { observable } from "@trpc/server/observable";
 import { z } from "zod";
 import { createRouter, procedure } from "../trpc";
+import { serviceUnavailable } from "../errors";
 
 // Download progress type from electron-updater
 interface DownloadProgress {
@@ -23,7 +24,7 @@ export const updaterRouter = createRouter({
         const autoUpdaterService =
           ctx.serviceManager.getService("autoUpdaterService");
         if (!autoUpdaterService) {
-          throw new Error("Auto-updater service not available");
+          throw serviceUnavailable("Auto-updater service");
         }
 
         const userInitiated = input?.userInitiated ?? false;
@@ -49,7 +50,7 @@ export const updaterRouter = createRouter({
       const autoUpdaterService =
         ctx.serviceManager.getService("autoUpdaterService");
       if (!autoUpdaterService) {
-        throw new Error("Auto-updater service not available");
+        throw serviceUnavailable("Auto-updater service");
       }
 
       await autoUpdaterService.checkForUpdatesAndNotify();
@@ -72,7 +73,7 @@ export const updaterRouter = createRouter({
       const autoUpdaterService =
         ctx.serviceManager.getService("autoUpdaterService");
       if (!autoUpdaterService) {
-        throw new Error("Auto-updater service not available");
+        throw serviceUnavailable("Auto-updater service");
       }
 
       await autoUpdaterService.downloadUpdate();
@@ -95,7 +96,7 @@ export const updaterRouter = createRouter({
       const autoUpdaterService =
         ctx.serviceManager.getService("autoUpdaterService");
       if (!autoUpdaterService) {
-        throw new Error("Auto-updater service not available");
+        throw serviceUnavailable("Auto-updater service");
       }
 
       const logger = ctx.serviceManager.getLogger();
@@ -184,7 +185,7 @@ export const updaterRouter = createRouter({
       const autoUpdaterService =
         ctx.serviceManager.getService("autoUpdaterService");
       if (!autoUpdaterService) {
-        throw new Error("Auto-updater service not initialized");
+        throw serviceUnavailable("Auto-updater service");
       }
 
       const handleDownloadProgress = (progressObj: DownloadProgress) => {
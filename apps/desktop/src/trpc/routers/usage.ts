@@ -0,0 +1,34 @@
+import { z } from "zod";
+import { createRouter, procedure } from "../trpc";
+import { getUsageSummary } from "../../db/usage";
+
+const GetUsageSummarySchema = z.object({
+  from: z.date().optional(),
+  to: z.date().optional(), // Exclusive
+});
+
+export const usageRouter = createRouter({
+  // Token counts and estimated spend per day, provider and model
+  getSummary: procedure
+    .input(GetUsageSummarySchema.optional())
+    .query(async ({ input }) => {
+      const rows = await getUsageSummary(input ?? {});
+      const totals = rows.reduce(
+        (acc, row) => ({
+          requests: acc.requests + row.requests,
+          promptTokens: acc.promptTokens + row.promptTokens,
+          completionTokens: acc.completionTokens + row.completionTokens,
+          audioSeconds: acc.audioSeconds + row.audioSeconds,
+          estimatedCost: acc.estimatedCost + row.estimatedCost,
+        }),
+        {
+          requests: 0,
+          promptTokens: 0,
+          completionTokens: 0,
+          audioSeconds: 0,
+          estimatedCost: 0,
+        },
+      );
+      return { rows, totals };
+    }),
+});
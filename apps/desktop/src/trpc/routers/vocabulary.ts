@@ -13,6 +13,12 @@ import {
   trackWordUsage,
   getMostUsedWords,
 } from "../../db/vocabulary";
+import {
+  getSymbolShortcuts,
+  createSymbolShortcut,
+  updateSymbolShortcut,
+  deleteSymbolShortcut,
+} from "../../db/symbol-shortcuts";
 
 // Input schemas
 const GetVocabularySchema = z.object({
@@ -96,6 +102,12 @@ const BulkImportSchema = z.array(
   }),
 );
 
+const SymbolShortcutSchema = z.object({
+  phrase: z.string().trim().min(1).max(100),
+  symbol: z.string().min(1).max(32),
+  language: z.string().nullable().optional(), // null/absent = all languages
+});
+
 export const vocabularyRouter = createRouter({
   // Get vocabulary list with pagination and filtering
   getVocabulary: procedure
@@ -183,4 +195,40 @@ export const vocabularyRouter = createRouter({
     .mutation(async ({ input }) => {
       return await bulkImportVocabulary(input);
     }),
+
+  // Get emoji/symbol shortcuts
+  getSymbolShortcuts: procedure
+    .input(z.object({ language: z.string().optional() }))
+    .query(async ({ input }) => {
+      return await getSymbolShortcuts(input.language);
+    }),
+
+  // Create emoji/symbol shortcut
+  createSymbolShortcut: procedure
+    .input(SymbolShortcutSchema)
+    .mutation(async ({ input }) => {
+      return await createSymbolShortcut({
+        ...input,
+        language: input.language ?? null,
+      });
+    }),
+
+  // Update emoji/symbol shortcut
+  updateSymbolShortcut: procedure
+    .input(
+      z.object({
+        id: z.number(),
+        data: SymbolShortcutSchema.partial(),
+      }),
+    )
+    .mutation(async ({ input }) => {
+      return await updateSymbolShortcut(input.id, input.data);
+    }),
+
+  // Delete emoji/symbol shortcut
+  deleteSymbolShortcut: procedure
+    .input(z.object({ id: z.number() }))
+    .mutation(async ({ input }) => {
+      return await deleteSymbolShortcut(input.id);
+    }),
 });
@@ -1,11 +1,59 @@
 import { initTRPC } from "@trpc/server";
 import superjson from "superjson";
 import type { Context } from "./context";
+import { isDatabaseError, recordDatabaseError } from "../db/errors";
+import { isScopeDebug, logger } from "../main/logger";
+import { toAppErrorData } from "./errors";
+import { recordRequest, redactInput } from "./request-log";
 
 const t = initTRPC.context<Context>().create({
   isServer: true,
   transformer: superjson,
+  // Every error carries an app error code; see types/app-error.ts
+  errorFormatter: ({ shape, error }) => ({
+    ...shape,
+    data: { ...shape.data, appError: toAppErrorData(error) },
+  }),
 });
 
-export const procedure = t.procedure;
+// Time queries and mutations for diagnostics.getSlowRequests, and log them
+// at debug level (LOG_DEBUG_SCOPES=ipc). Subscriptions stay open for as long
+// as the window listens, so they aren't timed.
+const logRequests = t.middleware(async ({ path, type, getRawInput, next }) => {
+  if (type === "subscription") {
+    return next();
+  }
+
+  const start = performance.now();
+  const result = await next();
+  const durationMs = Math.round(performance.now() - start);
+  const errorCode = result.ok ? undefined : toAppErrorData(result.error).code;
+  recordRequest({
+    path,
+    type,
+    durationMs,
+    ok: result.ok,
+    errorCode,
+    at: new Date(),
+  });
+
+  if (isScopeDebug("ipc")) {
+    const input = await getRawInput().catch(() => undefined);
+    logger.ipc.debug(`${type} ${path} ${errorCode ?? "ok"} ${durationMs}ms`, {
+      input: redactInput(input),
+    });
+  }
+  return result;
+});
+
+// Remember database failures for maintenance.getDbHealth
+const trackDatabaseErrors = t.middleware(async ({ path, next }) => {
+  const result = await next();
+  if (!result.ok && isDatabaseError(result.error.cause)) {
+    recordDatabaseError(result.error.cause, path);
+  }
+  return result;
+});
+
+export const procedure = t.procedure.use(logRequests).use(trackDatabaseErrors);
 export const createRouter = t.router;
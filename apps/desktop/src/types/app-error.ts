@@ -0,0 +1,27 @@
+/**
+ * Error codes sent with every tRPC error, so the UI can react to what went
+ * wrong instead of parsing messages. Read them from a client error with
+ * getAppError().
+ */
+export type AppErrorCode =
+  | "NOT_FOUND" // The requested record or file doesn't exist
+  | "INVALID_INPUT" // Rejected input, including schema validation failures
+  | "PRECONDITION_FAILED" // Needs setup first, e.g. a model or API key
+  | "SERVICE_UNAVAILABLE" // A main-process service hasn't started
+  | "DATABASE_LOCKED" // SQLite busy or locked; usually worth retrying
+  | "DATABASE_ERROR"
+  | "NETWORK_ERROR" // A remote API or download host couldn't be reached
+  | "INTERNAL";
+
+export interface AppErrorData {
+  code: AppErrorCode;
+  message: string;
+  details?: Record<string, unknown>;
+}
+
+// The structured error from a tRPC client error, or null for anything else
+export function getAppError(error: unknown): AppErrorData | null {
+  if (typeof error !== "object" || error === null) return null;
+  const data = (error as { data?: { appError?: AppErrorData } }).data;
+  return data?.appError ?? null;
+}
@@ -0,0 +1,6 @@
+export type EmbeddingProvider = "ollama" | "openai";
+
+export interface EmbeddingConfig {
+  provider: EmbeddingProvider;
+  model: string; // e.g. "nomic-embed-text" or "text-embedding-3-small"
+}
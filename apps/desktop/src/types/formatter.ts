@@ -1,5 +1,7 @@
 export interface FormatterConfig {
   enabled: boolean;
   modelId?: string;
-  fallbackModelId?: string;
+  fallbackModelId?: string; // Tried when the primary model errors or times out
+  presetId?: string; // Built-in tone/style preset, see formatter-presets.ts
+  timeoutMs?: number; // Per-attempt limit before failing over to the fallback
 }
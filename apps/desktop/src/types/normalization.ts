@@ -0,0 +1,10 @@
+export type NumberStyle = "digits" | "words" | "off";
+
+export type DateFormat = "long" | "iso" | "us" | "eu" | "off";
+
+export interface NormalizationConfig {
+  enabled: boolean;
+  numbers: NumberStyle; // digits: "twenty five" → "25"; words: "3" → "three"
+  dateFormat: DateFormat;
+  currency: boolean; // "five dollars" → "$5"
+}
@@ -0,0 +1,2 @@
+// keep: leave as transcribed; mask: "f***"; remove: drop the word entirely
+export type ProfanityPolicy = "keep" | "mask" | "remove";
@@ -1,6 +1,14 @@
+export type ValidationErrorType =
+  | "invalid_key"
+  | "quota_exceeded"
+  | "network"
+  | "timeout"
+  | "unknown";
+
 export interface ValidationResult {
   success: boolean;
   error?: string;
+  errorType?: ValidationErrorType;
 }
 
 export interface OpenRouterValidationRequest {
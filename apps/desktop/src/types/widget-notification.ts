@@ -1,4 +1,7 @@
-export type WidgetNotificationType = "no_audio" | "empty_transcript";
+export type WidgetNotificationType =
+  | "no_audio"
+  | "empty_transcript"
+  | "recording_recovered";
 
 export type WidgetNotificationActionIcon = "discord";
 
@@ -36,6 +39,8 @@ export const getNotificationDescription = (
       return `No audio from "${micDisplay}"`;
     case "empty_transcript":
       return `No speech detected from "${micDisplay}"`;
+    case "recording_recovered":
+      return "The last recording got stuck and was stopped";
   }
 };
 
@@ -72,6 +77,19 @@ export const WIDGET_NOTIFICATION_CONFIG: Record<
       externalUrl: DISCORD_SUPPORT_URL,
     },
   },
+  recording_recovered: {
+    title: "Recording recovered",
+    description: "The last recording got stuck and was stopped",
+    primaryAction: {
+      label: "View History",
+      navigateTo: "/settings/history",
+    },
+    secondaryAction: {
+      label: "Support",
+      icon: "discord",
+      externalUrl: DISCORD_SUPPORT_URL,
+    },
+  },
 };
 
 export const WIDGET_NOTIFICATION_TIMEOUT = 5000;
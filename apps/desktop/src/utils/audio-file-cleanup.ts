@@ -1,21 +1,30 @@
-import { app } from "electron";
 import * as fs from "node:fs";
 import * as path from "node:path";
 import { logger } from "../main/logger";
+import { getRecordingsDirectory } from "./data-directory";
+
+// Name a recording has until it is renamed with the filename template
+const UNRENAMED_RECORDING = /^audio-.+-\d{4}-\d\d-\d\dT[\d-]+Z\.wav$/;
 
 /**
- * Clean up old audio files from the temporary directory
+ * Clean up old audio files from the temporary directory. Only recordings a
+ * transcription references, or that still have the name the recorder gave
+ * them, are considered; anything else the user put there is left alone.
+ * @param referenced Audio file paths stored on transcriptions
  * @param maxAgeMs Maximum age of files to keep in milliseconds (default: 24 hours)
  * @param maxSizeBytes Maximum total size of audio files in bytes (default: 500MB)
  */
-export async function cleanupAudioFiles(options?: {
-  maxAgeMs?: number;
-  maxSizeBytes?: number;
-}): Promise<void> {
+export async function cleanupAudioFiles(
+  referenced: Set<string>,
+  options?: {
+    maxAgeMs?: number;
+    maxSizeBytes?: number;
+  },
+): Promise<void> {
   const maxAgeMs = options?.maxAgeMs ?? 7 * 24 * 60 * 60 * 1000; // 7 days
   const maxSizeBytes = options?.maxSizeBytes ?? 500 * 1024 * 1024; // 500MB
 
-  const audioDir = path.join(app.getPath("temp"), "grizzo-audio");
+  const audioDir = getRecordingsDirectory();
 
   try {
     // Check if directory exists
@@ -47,9 +56,11 @@ export async function cleanupAudioFiles(options?: {
       }),
     );
 
-    // Filter out null entries and audio files only
+    // Filter out null entries and files the app didn't record
     const audioFiles = fileStats.filter(
-      (stat) => stat !== null && stat.name.startsWith("audio-"),
+      (stat) =>
+        stat !== null &&
+        (referenced.has(stat.path) || UNRENAMED_RECORDING.test(stat.name)),
     ) as NonNullable<(typeof fileStats)[number]>[];
 
     // Sort by age (oldest first)
@@ -107,7 +118,7 @@ export async function cleanupAudioFiles(options?: {
 export async function deleteAudioFile(filePath: string): Promise<void> {
   try {
     // Ensure the file is in the audio directory
-    const audioDir = path.join(app.getPath("temp"), "grizzo-audio");
+    const audioDir = getRecordingsDirectory();
     if (!filePath.startsWith(audioDir)) {
       throw new Error("File is not in the audio directory");
     }
@@ -122,3 +133,55 @@ export async function deleteAudioFile(filePath: string): Promise<void> {
     // File doesn't exist, that's fine
   }
 }
+
+/**
+ * Delete several audio files, logging failures instead of throwing
+ * @returns Number of files deleted (or already gone)
+ */
+export async function deleteAudioFiles(filePaths: string[]): Promise<number> {
+  let deleted = 0;
+  for (const filePath of filePaths) {
+    try {
+      await deleteAudioFile(filePath);
+      deleted++;
+    } catch (error) {
+      logger.main.warn("Failed to delete audio file", { filePath, error });
+    }
+  }
+  return deleted;
+}
+
+/**
+ * Disk space taken by recordings, split into files a transcription still
+ * references and everything else in the audio directory
+ */
+export async function getAudioStorageUsage(referenced: Set<string>) {
+  const usage = {
+    totalBytes: 0,
+    fileCount: 0,
+    referencedBytes: 0,
+    referencedCount: 0,
+  };
+
+  const audioDir = getRecordingsDirectory();
+  if (!fs.existsSync(audioDir)) {
+    return usage;
+  }
+
+  for (const name of await fs.promises.readdir(audioDir)) {
+    if (!name.endsWith(".wav")) continue;
+    const filePath = path.join(audioDir, name);
+    try {
+      const stats = await fs.promises.stat(filePath);
+      usage.totalBytes += stats.size;
+      usage.fileCount++;
+      if (referenced.has(filePath)) {
+        usage.referencedBytes += stats.size;
+        usage.referencedCount++;
+      }
+    } catch (error) {
+      logger.main.warn("Failed to stat audio file", { file: name, error });
+    }
+  }
+  return usage;
+}
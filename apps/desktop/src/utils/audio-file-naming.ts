@@ -0,0 +1,119 @@
+import * as fs from "node:fs";
+import * as path from "node:path";
+import { logger } from "../main/logger";
+
+/**
+ * Filename template for saved recordings. Supported tokens:
+ * {date} 2024-03-05, {time} 14-30-05, {mode}, {app}, {words} (first words
+ * of the transcript), {id} (session id). {words} is left out of the default
+ * so dictated text doesn't show up in file listings unless asked for.
+ */
+export const DEFAULT_AUDIO_FILENAME_TEMPLATE = "{date}_{time}_{mode}";
+
+const SLUG_WORD_COUNT = 6;
+const MAX_BASENAME_LENGTH = 120;
+
+export interface AudioFileNameVariables {
+  sessionId: string;
+  recordedAt: Date;
+  modeName?: string;
+  appName?: string | null;
+  text?: string;
+}
+
+function pad(value: number): string {
+  return value.toString().padStart(2, "0");
+}
+
+function slugify(value: string): string {
+  return value
+    .normalize("NFKD")
+    .replace(/[\u0300-\u036f]/g, "")
+    .toLowerCase()
+    .replace(/[^\p{L}\p{N}]+/gu, "-")
+    .replace(/^-+|-+$/g, "");
+}
+
+function firstWordsSlug(text: string): string {
+  const words = text.split(/\s+/).filter(Boolean).slice(0, SLUG_WORD_COUNT);
+  return slugify(words.join(" "));
+}
+
+/**
+ * Expand a template into a filesystem-safe basename (without extension).
+ * Empty tokens are dropped along with their surrounding separators.
+ */
+export function renderAudioFileName(
+  template: string,
+  vars: AudioFileNameVariables,
+): string {
+  const d = vars.recordedAt;
+  const values: Record<string, string> = {
+    date: `${d.getFullYear()}-${pad(d.getMonth() + 1)}-${pad(d.getDate())}`,
+    time: `${pad(d.getHours())}-${pad(d.getMinutes())}-${pad(d.getSeconds())}`,
+    mode: slugify(vars.modeName ?? ""),
+    app: slugify(vars.appName ?? ""),
+    words: firstWordsSlug(vars.text ?? ""),
+    id: vars.sessionId,
+  };
+
+  const rendered = template
+    .replace(/\{(\w+)\}/g, (match, token: string) =>
+      token in values ? values[token] : match,
+    )
+    // Strip path separators and characters Windows rejects
+    .replace(/[<>:"/\\|?*\u0000-\u001f]/g, "")
+    // Collapse separators left behind by empty tokens
+    .replace(/([_\-. ])[_\-. ]+/g, "$1")
+    .replace(/^[_\-. ]+|[_\-. ]+$/g, "")
+    .slice(0, MAX_BASENAME_LENGTH)
+    .replace(/[_\-. ]+$/, "");
+
+  return rendered || `audio-${vars.sessionId}`;
+}
+
+/**
+ * Append -2, -3, ... until the path doesn't collide with an existing file.
+ */
+export async function resolveUniqueFilePath(
+  dir: string,
+  baseName: string,
+  extension: string,
+): Promise<string> {
+  let candidate = path.join(dir, `${baseName}${extension}`);
+  for (let suffix = 2; ; suffix++) {
+    try {
+      await fs.promises.access(candidate);
+    } catch {
+      return candidate;
+    }
+    candidate = path.join(dir, `${baseName}-${suffix}${extension}`);
+  }
+}
+
+/**
+ * Rename a finished recording according to the template.
+ * Returns the new path, or the original one if the rename failed.
+ */
+export async function applyAudioFileNameTemplate(
+  filePath: string,
+  template: string,
+  vars: AudioFileNameVariables,
+): Promise<string> {
+  const dir = path.dirname(filePath);
+  const extension = path.extname(filePath);
+  const baseName = renderAudioFileName(template, vars);
+
+  if (path.basename(filePath, extension) === baseName) {
+    return filePath;
+  }
+
+  try {
+    const target = await resolveUniqueFilePath(dir, baseName, extension);
+    await fs.promises.rename(filePath, target);
+    return target;
+  } catch (error) {
+    logger.audio.warn("Failed to rename audio file", { filePath, error });
+    return filePath;
+  }
+}
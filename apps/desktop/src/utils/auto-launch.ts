@@ -0,0 +1,81 @@
+import { app } from "electron";
+import * as fs from "node:fs";
+import * as os from "node:os";
+import * as path from "node:path";
+import { isLinux, isWindows } from "./platform";
+
+/**
+ * Start-at-login registration. macOS and Windows go through Electron's
+ * login item settings; Linux has no Electron API, so an XDG autostart
+ * entry is written instead.
+ */
+
+// Squirrel installs launch through Update.exe, which starts the current
+// version; registering the versioned exe would break after an update
+function windowsLoginItem(): { path: string; args: string[] } | undefined {
+  const updateExe = path.resolve(
+    path.dirname(process.execPath),
+    "..",
+    "Update.exe",
+  );
+  if (!fs.existsSync(updateExe)) {
+    return undefined;
+  }
+  return {
+    path: updateExe,
+    args: ["--processStart", `"${path.basename(process.execPath)}"`],
+  };
+}
+
+function linuxAutostartFile(): string {
+  const configHome =
+    process.env.XDG_CONFIG_HOME || path.join(os.homedir(), ".config");
+  return path.join(configHome, "autostart", `${app.getName()}.desktop`);
+}
+
+function linuxAutostartEntry(): string {
+  // AppImages run from a temporary mount; APPIMAGE is the stable path
+  const executable = process.env.APPIMAGE || process.execPath;
+  return [
+    "[Desktop Entry]",
+    "Type=Application",
+    `Name=${app.getName()}`,
+    `Exec="${executable}"`,
+    "Terminal=false",
+    "X-GNOME-Autostart-enabled=true",
+    "",
+  ].join("\n");
+}
+
+/**
+ * Whether the OS will start the app at login
+ */
+export function isAutoLaunchEnabled(): boolean {
+  if (isLinux()) {
+    return fs.existsSync(linuxAutostartFile());
+  }
+  const item = isWindows() ? windowsLoginItem() : undefined;
+  return app.getLoginItemSettings(item).openAtLogin;
+}
+
+export function setAutoLaunch(enabled: boolean): void {
+  if (isLinux()) {
+    // Don't register the development Electron binary
+    if (!app.isPackaged) return;
+    const file = linuxAutostartFile();
+    if (enabled) {
+      fs.mkdirSync(path.dirname(file), { recursive: true });
+      fs.writeFileSync(file, linuxAutostartEntry());
+    } else {
+      fs.rmSync(file, { force: true });
+    }
+    return;
+  }
+
+  const item = isWindows() ? windowsLoginItem() : undefined;
+  app.setLoginItemSettings({
+    openAtLogin: enabled,
+    openAsHidden: false,
+    ...item,
+  });
+}
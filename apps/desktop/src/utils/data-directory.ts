@@ -0,0 +1,65 @@
+import { app } from "electron";
+import * as fs from "node:fs";
+import * as path from "node:path";
+
+/**
+ * Where the database, downloaded models and recordings live. By default
+ * that's the user data folder (recordings go to the temp folder); the user
+ * can move them elsewhere, e.g. to an external drive. The chosen folder is
+ * recorded in a small file in the user data folder, since the database
+ * itself moves with it.
+ */
+
+interface DataLocation {
+  path: string;
+}
+
+const locationFile = () =>
+  path.join(app.getPath("userData"), "data-location.json");
+
+// Read once; changing the location restarts the app
+let customDirectory: string | null | undefined;
+
+/**
+ * Folder chosen by the user, or null when the default is used
+ */
+export function getCustomDataDirectory(): string | null {
+  if (customDirectory === undefined) {
+    try {
+      const location: DataLocation = JSON.parse(
+        fs.readFileSync(locationFile(), "utf8"),
+      );
+      customDirectory = location.path || null;
+    } catch {
+      customDirectory = null;
+    }
+  }
+  return customDirectory;
+}
+
+export function getDataDirectory(): string {
+  return getCustomDataDirectory() ?? app.getPath("userData");
+}
+
+export function getModelsDirectory(): string {
+  return path.join(getDataDirectory(), "models");
+}
+
+export function getRecordingsDirectory(): string {
+  const custom = getCustomDataDirectory();
+  return custom
+    ? path.join(custom, "recordings")
+    : path.join(app.getPath("temp"), "grizzo-audio");
+}
+
+/**
+ * Record a new location (null for the default). Takes effect on restart.
+ */
+export function setCustomDataDirectory(dir: string | null): void {
+  if (dir === null) {
+    fs.rmSync(locationFile(), { force: true });
+  } else {
+    const location: DataLocation = { path: dir };
+    fs.writeFileSync(locationFile(), JSON.stringify(location, null, 2));
+  }
+}
@@ -0,0 +1,144 @@
+import { app } from "electron";
+import * as fs from "node:fs";
+import * as path from "node:path";
+import { dbPath } from "../db";
+import { databaseKeyFile, removeDatabaseKeys } from "../db/encryption";
+import { checkpointDatabase } from "../db/maintenance";
+import { logger } from "../main/logger";
+
+/**
+ * Copies of the database taken before destructive operations such as
+ * resetting the app. Settings live in the database, so a backup is the
+ * database file plus its encryption key when it has one.
+ */
+
+export interface DatabaseBackup {
+  id: string;
+  createdAt: Date;
+  reason: string; // The operation that triggered the backup, e.g. "reset-app"
+  encrypted: boolean;
+  bytes: number;
+}
+
+interface BackupManifest {
+  createdAt: string;
+  reason: string;
+  encrypted: boolean;
+}
+
+// Older backups are deleted once there are more than this many
+const MAX_BACKUPS = 5;
+
+const MANIFEST_FILE = "backup.json";
+const DATABASE_FILE = "grizzo.db";
+const KEY_FILE = "grizzo.db.key";
+
+const backupDir = () => path.join(app.getPath("userData"), "backups");
+
+function readBackup(id: string): DatabaseBackup | null {
+  const dir = path.join(backupDir(), id);
+  try {
+    const manifest: BackupManifest = JSON.parse(
+      fs.readFileSync(path.join(dir, MANIFEST_FILE), "utf8"),
+    );
+    return {
+      id,
+      createdAt: new Date(manifest.createdAt),
+      reason: manifest.reason,
+      encrypted: manifest.encrypted,
+      bytes: fs.statSync(path.join(dir, DATABASE_FILE)).size,
+    };
+  } catch {
+    // Incomplete or foreign folder
+    return null;
+  }
+}
+
+/**
+ * Backups on disk, newest first
+ */
+export function listDatabaseBackups(): DatabaseBackup[] {
+  if (!fs.existsSync(backupDir())) {
+    return [];
+  }
+  return fs
+    .readdirSync(backupDir())
+    .map(readBackup)
+    .filter((backup): backup is DatabaseBackup => backup !== null)
+    .sort((a, b) => b.createdAt.getTime() - a.createdAt.getTime());
+}
+
+function pruneBackups(keep?: string) {
+  const prunable = listDatabaseBackups().filter((b) => b.id !== keep);
+  for (const backup of prunable.slice(MAX_BACKUPS)) {
+    fs.rmSync(path.join(backupDir(), backup.id), {
+      recursive: true,
+      force: true,
+    });
+  }
+}
+
+/**
+ * Copy the open database into a new timestamped backup folder. `keep` is
+ * a backup that mustn't be pruned to make room, e.g. one about to be
+ * restored.
+ */
+export async function createDatabaseBackup(
+  reason: string,
+  options: { keep?: string } = {},
+): Promise<DatabaseBackup> {
+  // Fold the WAL in so the database file alone is complete
+  await checkpointDatabase();
+
+  const createdAt = new Date();
+  const id = createdAt.toISOString().replace(/[:.]/g, "-");
+  const dir = path.join(backupDir(), id);
+  await fs.promises.mkdir(dir, { recursive: true });
+
+  await fs.promises.copyFile(dbPath, path.join(dir, DATABASE_FILE));
+  const encrypted = fs.existsSync(databaseKeyFile(dbPath));
+  if (encrypted) {
+    await fs.promises.copyFile(
+      databaseKeyFile(dbPath),
+      path.join(dir, KEY_FILE),
+    );
+  }
+  const manifest: BackupManifest = {
+    createdAt: createdAt.toISOString(),
+    reason,
+    encrypted,
+  };
+  await fs.promises.writeFile(
+    path.join(dir, MANIFEST_FILE),
+    JSON.stringify(manifest, null, 2),
+  );
+
+  pruneBackups(options.keep);
+  logger.db.info("Database backed up", { id, reason });
+  return readBackup(id)!;
+}
+
+/**
+ * Replace the database with a backup. The database must be closed first;
+ * the app has to restart to open the restored copy.
+ */
+export async function restoreDatabaseBackup(id: string): Promise<void> {
+  const backup = readBackup(path.basename(id));
+  if (!backup) {
+    throw new Error(`Backup not found: ${id}`);
+  }
+  const dir = path.join(backupDir(), backup.id);
+
+  for (const suffix of ["-wal", "-shm"]) {
+    await fs.promises.rm(`${dbPath}${suffix}`, { force: true });
+  }
+  removeDatabaseKeys(dbPath);
+  await fs.promises.copyFile(path.join(dir, DATABASE_FILE), dbPath);
+  if (backup.encrypted) {
+    await fs.promises.copyFile(
+      path.join(dir, KEY_FILE),
+      databaseKeyFile(dbPath),
+    );
+  }
+  logger.db.info("Database restored from backup", { id: backup.id });
+}
@@ -0,0 +1,158 @@
+import { getUserAgent } from "./http-client";
+
+/**
+ * Hugging Face Hub lookups for importing whisper.cpp models that aren't in
+ * the built-in list. Only ggml `.bin` files can be loaded by whisper.cpp,
+ * so repositories are listed by the ggml files they contain.
+ */
+
+const HUB_URL = "https://huggingface.co";
+
+// IDs of imported models, so they can't collide with built-in ones
+export const IMPORTED_MODEL_PREFIX = "hf:";
+
+const REPO_ID_PATTERN = /^[\w.-]+\/[\w.-]+$/;
+
+export interface HuggingFaceRepo {
+  repoId: string;
+  downloads: number;
+  likes: number;
+  lastModified: string | null;
+}
+
+export interface HuggingFaceModelFile {
+  repoId: string;
+  path: string;
+  sizeBytes: number;
+  sha256: string | null; // From Git LFS, when the file is stored there
+}
+
+export function isImportedModelId(modelId: string): boolean {
+  return modelId.startsWith(IMPORTED_MODEL_PREFIX);
+}
+
+export function importedModelId(repoId: string, filePath: string): string {
+  return `${IMPORTED_MODEL_PREFIX}${repoId}/${filePath}`;
+}
+
+export function parseImportedModelId(
+  modelId: string,
+): { repoId: string; filePath: string } | null {
+  if (!isImportedModelId(modelId)) return null;
+  const [owner, name, ...file] = modelId
+    .slice(IMPORTED_MODEL_PREFIX.length)
+    .split("/");
+  if (!owner || !name || file.length === 0) return null;
+  return { repoId: `${owner}/${name}`, filePath: file.join("/") };
+}
+
+export function huggingFaceFileUrl(repoId: string, filePath: string): string {
+  return `${HUB_URL}/${repoId}/resolve/main/${filePath
+    .split("/")
+    .map(encodeURIComponent)
+    .join("/")}`;
+}
+
+/**
+ * Where to fetch a model file from: each mirror in order, then the original
+ * URL unless `fallbackToOrigin` is false. Mirrors serve the same paths as
+ * huggingface.co, so only files hosted there are redirected. Only pass files
+ * whose checksum is verified after download; a mirror is trusted for nothing
+ * else.
+ */
+export function modelSourceUrls(
+  url: string,
+  sources?: { mirrors?: string[]; fallbackToOrigin?: boolean },
+): string[] {
+  const mirrors = sources?.mirrors ?? [];
+  if (mirrors.length === 0 || !url.startsWith(`${HUB_URL}/`)) {
+    return [url];
+  }
+
+  const path = url.slice(HUB_URL.length);
+  const mirrored = mirrors.map(
+    (mirror) => `${mirror.replace(/\/+$/, "")}${path}`,
+  );
+  return sources?.fallbackToOrigin === false ? mirrored : [...mirrored, url];
+}
+
+function assertRepoId(repoId: string) {
+  if (!REPO_ID_PATTERN.test(repoId)) {
+    throw new Error(`Invalid Hugging Face repository: ${repoId}`);
+  }
+}
+
+async function hubGet<T>(pathAndQuery: string): Promise<T> {
+  const response = await fetch(`${HUB_URL}/api/${pathAndQuery}`, {
+    headers: { "User-Agent": getUserAgent() },
+  });
+  if (!response.ok) {
+    throw new Error(
+      `Hugging Face request failed: ${response.status} ${response.statusText}`,
+    );
+  }
+  return (await response.json()) as T;
+}
+
+/**
+ * Repositories matching `query`, most downloaded first
+ */
+export async function searchHuggingFaceModels(
+  query: string,
+  limit = 20,
+): Promise<HuggingFaceRepo[]> {
+  const params = new URLSearchParams({
+    search: query.trim() || "whisper",
+    sort: "downloads",
+    direction: "-1",
+    limit: String(limit),
+  });
+  const models = await hubGet<
+    {
+      id: string;
+      downloads?: number;
+      likes?: number;
+      lastModified?: string;
+    }[]
+  >(`models?${params}`);
+
+  return models.map((model) => ({
+    repoId: model.id,
+    downloads: model.downloads ?? 0,
+    likes: model.likes ?? 0,
+    lastModified: model.lastModified ?? null,
+  }));
+}
+
+/**
+ * ggml model files in a repository's main branch
+ */
+export async function listHuggingFaceModelFiles(
+  repoId: string,
+): Promise<HuggingFaceModelFile[]> {
+  assertRepoId(repoId);
+  const entries = await hubGet<
+    {
+      type: string;
+      path: string;
+      size: number;
+      lfs?: { oid: string; size: number };
+    }[]
+  >(`models/${repoId}/tree/main?recursive=true`);
+
+  return entries
+    .filter((entry) => {
+      const name = entry.path.split("/").pop() ?? "";
+      return (
+        entry.type === "file" &&
+        name.startsWith("ggml") &&
+        name.endsWith(".bin")
+      );
+    })
+    .map((entry) => ({
+      repoId,
+      path: entry.path,
+      sizeBytes: entry.lfs?.size ?? entry.size,
+      sha256: entry.lfs?.oid ?? null,
+    }));
+}
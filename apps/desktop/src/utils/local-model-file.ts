@@ -0,0 +1,66 @@
+import * as fs from "node:fs";
+
+/**
+ * Whisper model files the user already has on disk. whisper.cpp loads
+ * ggml files; GGUF files are recognised only to explain why they can't
+ * be used.
+ */
+
+// IDs of models imported from a file, so they can't collide with others
+export const LOCAL_MODEL_PREFIX = "local:";
+
+// ggml's magic number 0x67676d6c, as stored little-endian
+const GGML_MAGIC = Buffer.from("lmgg", "ascii");
+const GGUF_MAGIC = Buffer.from("GGUF", "ascii");
+
+// Smaller than the tiny model, so certainly not a whisper model
+const MIN_MODEL_BYTES = 10 * 1024 * 1024;
+
+export function isLocalModelId(modelId: string): boolean {
+  return modelId.startsWith(LOCAL_MODEL_PREFIX);
+}
+
+export function localModelId(name: string): string {
+  const slug = name
+    .toLowerCase()
+    .replace(/[^a-z0-9.-]+/g, "-")
+    .replace(/^-+|-+$/g, "");
+  if (!slug) {
+    throw new Error("Enter a name for the model");
+  }
+  return `${LOCAL_MODEL_PREFIX}${slug}`;
+}
+
+/**
+ * Check that `filePath` looks like a model whisper.cpp can load; throws
+ * with a message for the user when it doesn't. Returns the file size.
+ */
+export async function validateLocalModelFile(
+  filePath: string,
+): Promise<number> {
+  const stats = await fs.promises.stat(filePath).catch(() => null);
+  if (!stats?.isFile()) {
+    throw new Error("The model file doesn't exist");
+  }
+  if (stats.size < MIN_MODEL_BYTES) {
+    throw new Error("The file is too small to be a Whisper model");
+  }
+
+  const header = Buffer.alloc(4);
+  const handle = await fs.promises.open(filePath, "r");
+  try {
+    await handle.read(header, 0, 4, 0);
+  } finally {
+    await handle.close();
+  }
+
+  if (header.equals(GGUF_MAGIC)) {
+    throw new Error(
+      "GGUF files can't be loaded by whisper.cpp. Choose a ggml .bin model.",
+    );
+  }
+  if (!header.equals(GGML_MAGIC)) {
+    throw new Error("The file isn't a ggml Whisper model");
+  }
+  return stats.size;
+}
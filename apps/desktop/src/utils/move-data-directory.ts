@@ -0,0 +1,144 @@
+import { createClient } from "@libsql/client";
+import { app } from "electron";
+import * as fs from "node:fs";
+import * as path from "node:path";
+import { closeDatabase, dbPath } from "../db";
+import { getDatabaseKey } from "../db/encryption";
+import { checkpointDatabase } from "../db/maintenance";
+import { logger } from "../main/logger";
+import {
+  getDataDirectory,
+  getModelsDirectory,
+  getRecordingsDirectory,
+  setCustomDataDirectory,
+} from "./data-directory";
+
+// The database, its WAL files, and its encryption key files
+const DATABASE_SUFFIXES = [
+  "",
+  "-wal",
+  "-shm",
+  ".key",
+  ".key.pending",
+  ".encryption-pending",
+];
+
+function isInside(child: string, parent: string): boolean {
+  const relative = path.relative(parent, child);
+  return !relative.startsWith("..") && !path.isAbsolute(relative);
+}
+
+/**
+ * Check that the data can be moved to `target`; throws with a message for
+ * the user when it can't
+ */
+export function validateDataDirectory(target: string): void {
+  if (!path.isAbsolute(target)) {
+    throw new Error("Choose an absolute folder path");
+  }
+  const current = getDataDirectory();
+  if (path.resolve(target) === path.resolve(current)) {
+    throw new Error("The data is already in this folder");
+  }
+  // A folder can't be copied into itself
+  if (
+    [getModelsDirectory(), getRecordingsDirectory()].some((dir) =>
+      isInside(target, dir),
+    )
+  ) {
+    throw new Error("The new folder can't be inside the app's data folders");
+  }
+  if (fs.existsSync(path.join(target, path.basename(dbPath)))) {
+    throw new Error("The folder already contains a Grizzo database");
+  }
+  fs.mkdirSync(target, { recursive: true });
+  fs.accessSync(target, fs.constants.W_OK);
+}
+
+// Point stored absolute paths at the moved files
+async function rewriteStoredPaths(
+  database: string,
+  moves: { from: string; to: string }[],
+) {
+  const client = createClient({
+    url: `file:${database}`,
+    encryptionKey: getDatabaseKey(database),
+  });
+  try {
+    for (const { from, to } of moves) {
+      for (const [table, column] of [
+        ["transcriptions", "audio_file"],
+        ["models", "local_path"],
+      ]) {
+        await client.execute({
+          sql: `UPDATE ${table} SET ${column} = ? || substr(${column}, length(?) + 1) WHERE substr(${column}, 1, length(?)) = ?`,
+          args: [to, from, from, from],
+        });
+      }
+    }
+  } finally {
+    client.close();
+  }
+}
+
+/**
+ * Move the database, models and recordings to `target` (null for the
+ * default location). Everything is copied first and the originals are
+ * only deleted once the copy is complete. The database is closed, so the
+ * app must restart afterwards, whether or not the move succeeded.
+ */
+export async function moveDataDirectory(target: string | null): Promise<void> {
+  const targetDir = target ?? app.getPath("userData");
+  validateDataDirectory(targetDir);
+
+  const moves = [
+    { from: getModelsDirectory(), to: path.join(targetDir, "models") },
+    {
+      from: getRecordingsDirectory(),
+      to: target
+        ? path.join(targetDir, "recordings")
+        : path.join(app.getPath("temp"), "grizzo-audio"),
+    },
+  ];
+  const targetDb = path.join(targetDir, path.basename(dbPath));
+  const copied: string[] = [];
+
+  logger.main.info("Moving data directory", {
+    from: getDataDirectory(),
+    to: targetDir,
+  });
+  try {
+    for (const { from, to } of moves) {
+      if (!fs.existsSync(from)) continue;
+      await fs.promises.cp(from, to, { recursive: true, errorOnExist: true });
+      copied.push(to);
+    }
+
+    await checkpointDatabase();
+    await closeDatabase();
+    for (const suffix of DATABASE_SUFFIXES) {
+      if (!fs.existsSync(`${dbPath}${suffix}`)) continue;
+      await fs.promises.copyFile(
+        `${dbPath}${suffix}`,
+        `${targetDb}${suffix}`,
+      );
+      copied.push(`${targetDb}${suffix}`);
+    }
+    await rewriteStoredPaths(targetDb, moves);
+    setCustomDataDirectory(target);
+  } catch (error) {
+    // Leave the originals in place and drop the partial copy
+    for (const file of copied) {
+      await fs.promises.rm(file, { recursive: true, force: true });
+    }
+    throw error;
+  }
+
+  for (const { from } of moves) {
+    await fs.promises.rm(from, { recursive: true, force: true });
+  }
+  for (const suffix of DATABASE_SUFFIXES) {
+    await fs.promises.rm(`${dbPath}${suffix}`, { force: true });
+  }
+  logger.main.info("Data directory moved", { to: targetDir });
+}
@@ -0,0 +1,103 @@
+import { app, session } from "electron";
+import type { AppSettingsData } from "../db/schema";
+import { logger } from "../main/logger";
+
+/**
+ * Proxy for outbound requests: transcription and formatting providers,
+ * model downloads and API key validation. The proxy is set on the default
+ * session, which covers the renderer; main-process fetch (used by the
+ * provider SDKs) is routed through the same session while a proxy is on.
+ */
+
+type ProxySettings = NonNullable<AppSettingsData["proxy"]>;
+
+interface ProxyCredentials {
+  username: string;
+  password: string;
+}
+
+const nodeFetch = globalThis.fetch;
+let credentials: ProxyCredentials | null = null;
+let loginHandlerRegistered = false;
+
+/**
+ * fetch over Chromium's network stack, so requests follow the session's
+ * proxy. Response bodies stream with backpressure like Node's fetch.
+ */
+function sessionFetch(
+  input: RequestInfo | URL,
+  init?: RequestInit,
+): Promise<Response> {
+  return session.defaultSession.fetch(
+    input instanceof URL ? input.href : input,
+    init,
+  );
+}
+
+// Proxy challenges, from the renderer and from sessionFetch alike
+function registerLoginHandler() {
+  if (loginHandlerRegistered) return;
+  loginHandlerRegistered = true;
+  app.on("login", (event, _webContents, _details, authInfo, callback) => {
+    if (authInfo.isProxy && credentials) {
+      event.preventDefault();
+      callback(credentials.username, credentials.password);
+    }
+  });
+}
+
+/**
+ * Split a proxy URL into Chromium proxy rules and any credentials in it
+ */
+function parseProxyUrl(url: string): {
+  proxyRules: string;
+  credentials: ProxyCredentials | null;
+} {
+  const parsed = new URL(url);
+  return {
+    proxyRules: `${parsed.protocol}//${parsed.host}`,
+    credentials: parsed.username
+      ? {
+          username: decodeURIComponent(parsed.username),
+          password: decodeURIComponent(parsed.password),
+        }
+      : null,
+  };
+}
+
+/**
+ * Point outbound requests at the configured proxy, or back to the system
+ * settings when it's off
+ */
+export async function applyProxySettings(
+  settings: ProxySettings | undefined,
+): Promise<void> {
+  if (!settings?.enabled || !settings.url) {
+    credentials = null;
+    globalThis.fetch = nodeFetch;
+    await session.defaultSession.setProxy({ mode: "system" });
+    await session.defaultSession.closeAllConnections();
+    return;
+  }
+
+  const parsed = parseProxyUrl(settings.url);
+  credentials = settings.username
+    ? { username: settings.username, password: settings.password ?? "" }
+    : parsed.credentials;
+  registerLoginHandler();
+
+  await session.defaultSession.setProxy({
+    mode: "fixed_servers",
+    proxyRules: parsed.proxyRules,
+    proxyBypassRules: (settings.bypass ?? []).join(","),
+  });
+  // Don't reuse connections opened before the change
+  await session.defaultSession.closeAllConnections();
+  globalThis.fetch = sessionFetch;
+
+  logger.main.info("Proxy enabled", {
+    proxy: parsed.proxyRules,
+    bypass: settings.bypass ?? [],
+    authenticated: credentials !== null,
+  });
+}
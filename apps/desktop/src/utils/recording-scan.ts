@@ -0,0 +1,121 @@
+import * as fs from "node:fs";
+import * as path from "node:path";
+import { logger } from "../main/logger";
+import { getRecordingsDirectory } from "./data-directory";
+import {
+  clearTranscriptionAudioFiles,
+  getTranscriptionAudioFiles,
+} from "../db/transcriptions";
+
+// Recordings younger than this may belong to a session still being saved
+export const ORPHAN_AUDIO_MIN_AGE_MS = 60 * 60 * 1000;
+
+export interface OrphanRecording {
+  filePath: string;
+  sizeBytes: number;
+  modifiedAt: Date;
+}
+
+export interface MissingRecording {
+  transcriptionId: number;
+  audioFile: string;
+}
+
+export interface RecordingScanReport {
+  orphans: OrphanRecording[]; // Files no transcription points to
+  missing: MissingRecording[]; // Transcriptions pointing at absent files
+}
+
+/**
+ * Cross-reference the recordings directory against transcriptions.audio_file.
+ * Orphans younger than ORPHAN_AUDIO_MIN_AGE_MS are left out since their
+ * transcription may not be saved yet.
+ */
+export async function scanRecordings(): Promise<RecordingScanReport> {
+  const audioDir = getRecordingsDirectory();
+  const rows = await getTranscriptionAudioFiles();
+  const referenced = new Set(rows.map((row) => row.audioFile));
+
+  const orphans: OrphanRecording[] = [];
+  const present = new Set<string>();
+  const names = fs.existsSync(audioDir)
+    ? await fs.promises.readdir(audioDir)
+    : [];
+  const now = Date.now();
+  for (const name of names) {
+    if (!name.endsWith(".wav")) continue;
+    const filePath = path.join(audioDir, name);
+    present.add(filePath);
+    if (referenced.has(filePath)) continue;
+    try {
+      const stats = await fs.promises.stat(filePath);
+      if (now - stats.mtime.getTime() < ORPHAN_AUDIO_MIN_AGE_MS) continue;
+      orphans.push({
+        filePath,
+        sizeBytes: stats.size,
+        modifiedAt: stats.mtime,
+      });
+    } catch (error) {
+      logger.main.warn("Failed to stat recording", { file: name, error });
+    }
+  }
+
+  // Files outside the recordings directory are checked individually
+  const missing: MissingRecording[] = [];
+  for (const row of rows) {
+    const exists = row.audioFile.startsWith(audioDir + path.sep)
+      ? present.has(row.audioFile)
+      : fs.existsSync(row.audioFile);
+    if (!exists) {
+      missing.push({ transcriptionId: row.id, audioFile: row.audioFile });
+    }
+  }
+
+  return { orphans, missing };
+}
+
+/**
+ * Delete orphaned recordings and/or clear audio_file on transcriptions
+ * whose recording is gone, based on a fresh scan
+ */
+export async function cleanupRecordings(options: {
+  deleteOrphans: boolean;
+  clearMissing: boolean;
+}): Promise<{
+  orphansDeleted: number;
+  bytesFreed: number;
+  missingCleared: number;
+}> {
+  const report = await scanRecordings();
+  let orphansDeleted = 0;
+  let bytesFreed = 0;
+  let missingCleared = 0;
+
+  if (options.deleteOrphans) {
+    for (const orphan of report.orphans) {
+      try {
+        await fs.promises.unlink(orphan.filePath);
+        orphansDeleted++;
+        bytesFreed += orphan.sizeBytes;
+      } catch (error) {
+        logger.main.warn("Failed to delete orphaned recording", {
+          filePath: orphan.filePath,
+          error,
+        });
+      }
+    }
+  }
+
+  if (options.clearMissing && report.missing.length > 0) {
+    missingCleared = await clearTranscriptionAudioFiles(
+      report.missing.map((entry) => entry.transcriptionId),
+    );
+  }
+
+  logger.main.info("Recording cleanup finished", {
+    orphansDeleted,
+    bytesFreed,
+    missingCleared,
+  });
+  return { orphansDeleted, bytesFreed, missingCleared };
+}
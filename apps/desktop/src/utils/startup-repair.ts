@@ -0,0 +1,153 @@
+import * as fs from "node:fs";
+import * as path from "node:path";
+import { logger } from "../main/logger";
+import { scanRecordings } from "./recording-scan";
+import { AVAILABLE_MODELS } from "../constants/models";
+import type { SettingsService } from "../services/settings-service";
+import type { ModelService } from "../services/model-service";
+import type { FormatterConfig } from "../types/formatter";
+import type { ModeConfig } from "../db/schema";
+
+export interface StartupRepairReport {
+  activeModeReset: boolean;
+  formatterModelsCleared: number;
+  modeSpeechModelsCleared: number;
+  orphanAudioFilesDeleted: number;
+}
+
+/**
+ * Clear formatter model IDs that no longer resolve to a synced language
+ * model (e.g. the provider was removed). Formatting is turned off when the
+ * primary model is gone so the UI doesn't show it enabled with no model.
+ */
+function repairFormatterConfig(
+  config: FormatterConfig,
+  languageModelIds: Set<string>,
+): FormatterConfig | null {
+  const primaryMissing =
+    !!config.modelId && !languageModelIds.has(config.modelId);
+  const fallbackMissing =
+    !!config.fallbackModelId && !languageModelIds.has(config.fallbackModelId);
+  if (!primaryMissing && !fallbackMissing) {
+    return null;
+  }
+  return {
+    ...config,
+    enabled: primaryMissing ? false : config.enabled,
+    modelId: primaryMissing ? undefined : config.modelId,
+    fallbackModelId: fallbackMissing ? undefined : config.fallbackModelId,
+  };
+}
+
+async function repairSettings(
+  settingsService: SettingsService,
+  modelService: ModelService,
+  report: StartupRepairReport,
+): Promise<void> {
+  const languageModelIds = new Set(
+    (await modelService.getSyncedProviderModels())
+      .filter((model) => model.type === "language")
+      .map((model) => model.id),
+  );
+  const downloadedModels = await modelService.getValidDownloadedModels();
+  const isUsableSpeechModel = (modelId: string) =>
+    AVAILABLE_MODELS.find((m) => m.id === modelId)?.setup === "api" ||
+    !!downloadedModels[modelId];
+
+  // Global formatter selection
+  const formatterConfig = await settingsService.getFormatterConfig();
+  const repairedFormatter =
+    formatterConfig && repairFormatterConfig(formatterConfig, languageModelIds);
+  if (repairedFormatter) {
+    await settingsService.setFormatterConfig(repairedFormatter);
+    report.formatterModelsCleared++;
+  }
+
+  // Modes: dangling active mode, per-mode formatter and speech models
+  const settings = await settingsService.getAllSettings();
+  if (!settings.modes || settings.modes.items.length === 0) {
+    return;
+  }
+  let changed = false;
+  const items = settings.modes.items.map((mode): ModeConfig => {
+    let next = mode;
+    const repaired = repairFormatterConfig(
+      mode.formatterConfig,
+      languageModelIds,
+    );
+    if (repaired) {
+      next = { ...next, formatterConfig: repaired };
+      report.formatterModelsCleared++;
+    }
+    if (mode.speechModelId && !isUsableSpeechModel(mode.speechModelId)) {
+      next = { ...next, speechModelId: undefined };
+      report.modeSpeechModelsCleared++;
+    }
+    changed ||= next !== mode;
+    return next;
+  });
+
+  let activeModeId = settings.modes.activeModeId;
+  if (!items.some((mode) => mode.id === activeModeId)) {
+    activeModeId = (items.find((mode) => mode.isDefault) ?? items[0]).id;
+    report.activeModeReset = true;
+    changed = true;
+  }
+
+  if (changed) {
+    await settingsService.updateSettings({ modes: { items, activeModeId } });
+  }
+}
+
+/**
+ * Delete recordings that no transcription points to (e.g. the app quit
+ * between writing the WAV and saving the transcription).
+ */
+async function deleteOrphanAudioFiles(
+  report: StartupRepairReport,
+): Promise<void> {
+  const { orphans } = await scanRecordings();
+  for (const orphan of orphans) {
+    try {
+      await fs.promises.unlink(orphan.filePath);
+      report.orphanAudioFilesDeleted++;
+    } catch (error) {
+      logger.main.warn("Failed to delete orphaned audio file", {
+        file: path.basename(orphan.filePath),
+        error,
+      });
+    }
+  }
+}
+
+/**
+ * Detect and fix settings that point at things which no longer exist.
+ * Runs once at launch after the model service has synced; never throws.
+ * The selected speech model is repaired by ModelService.initialize itself.
+ */
+export async function runStartupRepair(
+  settingsService: SettingsService,
+  modelService: ModelService,
+): Promise<StartupRepairReport> {
+  const report: StartupRepairReport = {
+    activeModeReset: false,
+    formatterModelsCleared: 0,
+    modeSpeechModelsCleared: 0,
+    orphanAudioFilesDeleted: 0,
+  };
+
+  try {
+    await repairSettings(settingsService, modelService, report);
+  } catch (error) {
+    logger.main.error("Startup repair of settings failed", { error });
+  }
+
+  try {
+    await deleteOrphanAudioFiles(report);
+  } catch (error) {
+    logger.main.error("Startup cleanup of orphaned audio failed", { error });
+  }
+
+  logger.main.info("Startup repair finished", report);
+  return report;
+}
@@ -0,0 +1,76 @@
+import * as fs from "node:fs";
+import * as path from "node:path";
+import { dbPath } from "../db";
+import { getTableStats, type TableStats } from "../db/maintenance";
+import { logPath, logger } from "../main/logger";
+import { getModelsDirectory, getRecordingsDirectory } from "./data-directory";
+
+export interface DirectoryUsage {
+  path: string;
+  bytes: number;
+  fileCount: number;
+}
+
+export interface StorageBreakdown {
+  database: {
+    path: string;
+    bytes: number; // Database file plus its WAL and shared-memory files
+    walBytes: number;
+    tables: TableStats[];
+  };
+  recordings: DirectoryUsage;
+  models: DirectoryUsage;
+  logs: DirectoryUsage;
+}
+
+async function fileSize(filePath: string): Promise<number> {
+  try {
+    return (await fs.promises.stat(filePath)).size;
+  } catch {
+    return 0;
+  }
+}
+
+// Total size of the files under a directory; missing directories are empty
+async function directoryUsage(dir: string): Promise<DirectoryUsage> {
+  const usage: DirectoryUsage = { path: dir, bytes: 0, fileCount: 0 };
+  if (!fs.existsSync(dir)) {
+    return usage;
+  }
+
+  const entries = await fs.promises.readdir(dir, {
+    recursive: true,
+    withFileTypes: true,
+  });
+  for (const entry of entries) {
+    if (!entry.isFile()) continue;
+    const filePath = path.join(entry.parentPath, entry.name);
+    try {
+      usage.bytes += (await fs.promises.stat(filePath)).size;
+      usage.fileCount++;
+    } catch (error) {
+      logger.main.warn("Failed to stat file", { file: filePath, error });
+    }
+  }
+  return usage;
+}
+
+/**
+ * Disk space used by the app, for the storage panel in settings
+ */
+export async function getStorageBreakdown(): Promise<StorageBreakdown> {
+  const walBytes = await fileSize(`${dbPath}-wal`);
+
+  return {
+    database: {
+      path: dbPath,
+      bytes:
+        (await fileSize(dbPath)) + walBytes + (await fileSize(`${dbPath}-shm`)),
+      walBytes,
+      tables: await getTableStats(),
+    },
+    recordings: await directoryUsage(getRecordingsDirectory()),
+    models: await directoryUsage(getModelsDirectory()),
+    logs: await directoryUsage(path.dirname(logPath)),
+  };
+}
@@ -0,0 +1,133 @@
+import * as si from "systeminformation";
+import { logger } from "../main/logger";
+
+export interface SystemInfo {
+  // Hardware
+  cpu_model: string;
+  cpu_cores: number;
+  cpu_threads: number;
+  cpu_speed_ghz: number;
+  memory_total_gb: number;
+
+  // OS
+  os_platform: string;
+  os_distro: string;
+  os_release: string;
+  os_arch: string;
+
+  // Graphics (the most capable GPU when there are several)
+  gpu_model: string;
+  gpu_vendor: string;
+  gpu_vram_mb: number; // 0 when unknown or shared with the CPU
+  gpu_discrete: boolean;
+  gpu_metal: boolean;
+
+  // System
+  manufacturer: string;
+  model: string;
+}
+
+type GraphicsController = si.Systeminformation.GraphicsControllerData;
+
+const DISCRETE_VENDOR_PATTERN = /nvidia|amd|advanced micro devices|\bati\b/i;
+
+// Dedicated memory in MB; nvidia-smi's figure is more reliable than WMI's
+function dedicatedVramMb(controller: GraphicsController): number {
+  if (controller.vramDynamic) return 0;
+  return controller.memoryTotal ?? controller.vram ?? 0;
+}
+
+function isDiscrete(controller: GraphicsController): boolean {
+  return (
+    DISCRETE_VENDOR_PATTERN.test(`${controller.vendor} ${controller.model}`) &&
+    dedicatedVramMb(controller) > 0
+  );
+}
+
+/**
+ * Windows laptops often list the integrated GPU first, so pick the discrete
+ * GPU with the most memory instead of the first controller
+ */
+function pickPrimaryGpu(
+  controllers: GraphicsController[],
+): GraphicsController | undefined {
+  return [...controllers].sort(
+    (a, b) =>
+      Number(isDiscrete(b)) - Number(isDiscrete(a)) ||
+      dedicatedVramMb(b) - dedicatedVramMb(a),
+  )[0];
+}
+
+async function collectSystemInfo(): Promise<SystemInfo> {
+  try {
+    const [cpu, mem, osInfo, graphics, system] = await Promise.all([
+      si.cpu(),
+      si.mem(),
+      si.osInfo(),
+      si.graphics(),
+      si.system(),
+    ]);
+    const gpu = pickPrimaryGpu(graphics.controllers);
+
+    return {
+      // Hardware
+      cpu_model: `${cpu.manufacturer} ${cpu.brand}`.trim(),
+      cpu_cores: cpu.physicalCores,
+      cpu_threads: cpu.cores,
+      cpu_speed_ghz: cpu.speed,
+      memory_total_gb: Math.round(mem.total / 1073741824),
+
+      // OS
+      os_platform: osInfo.platform,
+      os_distro: osInfo.distro,
+      os_release: osInfo.release,
+      os_arch: osInfo.arch,
+
+      // Graphics
+      gpu_model: gpu?.model || "Unknown",
+      gpu_vendor: gpu?.vendor || "Unknown",
+      gpu_vram_mb: gpu ? dedicatedVramMb(gpu) : 0,
+      gpu_discrete: gpu ? isDiscrete(gpu) : false,
+      // Every Apple Silicon Mac supports Metal; Intel Macs report a version
+      gpu_metal:
+        process.platform === "darwin" &&
+        (process.arch === "arm64" ||
+          graphics.controllers.some((c) => !!c.metalVersion)),
+
+      // System
+      manufacturer: system.manufacturer || "Unknown",
+      model: system.model || "Unknown",
+    };
+  } catch (error) {
+    logger.main.error("Failed to collect system info:", error);
+    // Return minimal info on error
+    return {
+      cpu_model: "Unknown",
+      cpu_cores: 0,
+      cpu_threads: 0,
+      cpu_speed_ghz: 0,
+      memory_total_gb: 0,
+      os_platform: process.platform,
+      os_distro: "Unknown",
+      os_release: "Unknown",
+      os_arch: process.arch,
+      gpu_model: "Unknown",
+      gpu_vendor: "Unknown",
+      gpu_vram_mb: 0,
+      gpu_discrete: false,
+      gpu_metal: process.platform === "darwin" && process.arch === "arm64",
+      manufacturer: "Unknown",
+      model: "Unknown",
+    };
+  }
+}
+
+let systemInfo: Promise<SystemInfo> | null = null;
+
+/**
+ * Hardware and OS details, collected once per run
+ */
+export function getSystemInfo(): Promise<SystemInfo> {
+  systemInfo ??= collectSystemInfo();
+  return systemInfo;
+}
@@ -0,0 +1,60 @@
+import type { Transcription } from "../db/schema";
+
+export type TranscriptionExportFormat = "csv" | "json";
+
+const EXPORT_COLUMNS = [
+  "id",
+  "timestamp",
+  "text",
+  "duration",
+  "speechModel",
+  "formattingModel",
+  "language",
+] as const;
+
+type ExportRow = Pick<Transcription, (typeof EXPORT_COLUMNS)[number]>;
+
+function toExportRecord(row: ExportRow) {
+  return {
+    id: row.id,
+    timestamp: row.timestamp.toISOString(),
+    text: row.text,
+    duration: row.duration,
+    speechModel: row.speechModel,
+    formattingModel: row.formattingModel,
+    language: row.language,
+  };
+}
+
+// RFC 4180: quote fields containing separators, quotes or line breaks.
+// Text that a spreadsheet would read as a formula gets a leading apostrophe.
+function csvField(value: string | number | null): string {
+  if (value === null) return "";
+  const text = String(value);
+  const escaped = text.replace(/"/g, '""');
+  if (typeof value === "string" && /^[=+\-@\t\r]/.test(text)) {
+    return `"'${escaped}"`;
+  }
+  return /[",\r\n]/.test(text) ? `"${escaped}"` : text;
+}
+
+/**
+ * Serialize transcriptions for export. CSV starts with a byte order mark so
+ * spreadsheet apps detect UTF-8.
+ */
+export function serializeTranscriptions(
+  rows: ExportRow[],
+  format: TranscriptionExportFormat,
+): string {
+  const records = rows.map(toExportRecord);
+  if (format === "json") {
+    return JSON.stringify(records, null, 2);
+  }
+  const lines = [
+    EXPORT_COLUMNS.join(","),
+    ...records.map((record) =>
+      EXPORT_COLUMNS.map((column) => csvField(record[column])).join(","),
+    ),
+  ];
+  return `\uFEFF${lines.join("\r\n")}\r\n`;
+}
@@ -374,6 +374,13 @@ const mockNativeImage = {
   createFromDataURL: vi.fn(() => ({})),
 };
 
+// Mock safeStorage; "encrypts" by copying the text into a buffer
+const mockSafeStorage = {
+  isEncryptionAvailable: vi.fn(() => true),
+  encryptString: vi.fn((text: string) => Buffer.from(text)),
+  decryptString: vi.fn((buffer: Buffer) => buffer.toString()),
+};
+
 export function createElectronMocks() {
   return {
     app: mockApp,
@@ -389,6 +396,7 @@ export function createElectronMocks() {
     globalShortcut: mockGlobalShortcut,
     clipboard: mockClipboard,
     nativeImage: mockNativeImage,
+    safeStorage: mockSafeStorage,
   };
 }
 
@@ -31,6 +31,8 @@ export async function initializeTestApp(
   vi.doMock("@db", () => ({
     db: testDb.db,
     dbPath: testDb.dbPath,
+    withTransaction: (fn: (tx: any) => Promise<unknown>) =>
+      testDb.db.transaction(fn),
     initializeDatabase: vi.fn().mockResolvedValue(undefined),
     closeDatabase: vi.fn().mockResolvedValue(undefined),
   }));
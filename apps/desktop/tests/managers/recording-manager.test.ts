@@ -0,0 +1,69 @@
+import { describe, it, expect, vi } from "vitest";
+import {
+  RecordingManager,
+  DEFAULT_WATCHDOG_TIMEOUT,
+  FINALIZE_WATCHDOG_TIMEOUT,
+} from "@main/managers/recording-manager";
+
+// A manager left in STOPPING for `elapsedMs`, optionally still finalizing
+function createStoppingSession(elapsedMs: number, finalizing: boolean) {
+  const transcriptionService = { cancelStreamingSession: vi.fn() };
+  const serviceManager = { getService: vi.fn(() => transcriptionService) };
+  const manager = new RecordingManager(serviceManager as any);
+  const startedAt = Date.now() - elapsedMs;
+  Object.assign(manager as any, {
+    recordingState: "stopping",
+    currentSessionId: "session-1",
+    finalizingSessionId: finalizing ? "session-1" : null,
+    finalizingStartedAt: finalizing ? startedAt : 0,
+    stateEnteredAt: startedAt,
+    audioChunks: [new Float32Array(512)],
+    terminationCode: finalizing ? null : "error",
+  });
+  const recovered = vi.fn();
+  manager.on("recording-recovered", recovered);
+  const checkWatchdog = () => (manager as any).checkWatchdog();
+  return { manager, transcriptionService, recovered, checkWatchdog };
+}
+
+describe("Recording watchdog", () => {
+  it("leaves a session alone while it's finalizing past the timeout", async () => {
+    const { manager, transcriptionService, recovered, checkWatchdog } =
+      createStoppingSession(DEFAULT_WATCHDOG_TIMEOUT + 30_000, true);
+
+    await checkWatchdog();
+
+    expect(manager.getState()).toBe("stopping");
+    expect(transcriptionService.cancelStreamingSession).not.toHaveBeenCalled();
+    expect(recovered).not.toHaveBeenCalled();
+  });
+
+  it("recovers a finalization that outlasts its own budget", async () => {
+    const { manager, transcriptionService, recovered, checkWatchdog } =
+      createStoppingSession(FINALIZE_WATCHDOG_TIMEOUT + 30_000, true);
+
+    await checkWatchdog();
+
+    expect(manager.getState()).toBe("idle");
+    expect(transcriptionService.cancelStreamingSession).toHaveBeenCalledWith(
+      "session-1",
+    );
+    expect(recovered).toHaveBeenCalledWith({
+      sessionId: "session-1",
+      state: "stopping",
+      action: "discarded",
+    });
+  });
+
+  it("recovers a stopping session that isn't finalizing", async () => {
+    const { manager, recovered, checkWatchdog } = createStoppingSession(
+      DEFAULT_WATCHDOG_TIMEOUT + 30_000,
+      false,
+    );
+
+    await checkWatchdog();
+
+    expect(manager.getState()).toBe("idle");
+    expect(recovered).toHaveBeenCalledOnce();
+  });
+});
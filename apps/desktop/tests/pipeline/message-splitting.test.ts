@@ -0,0 +1,91 @@
+import { describe, it, expect } from "vitest";
+import { splitIntoMessages } from "@/pipeline/processing/message-splitting";
+
+describe("splitIntoMessages", () => {
+  it.each([
+    {
+      name: "text that fits stays one message",
+      text: "  Short note.  ",
+      maxLength: 50,
+      expected: ["Short note."],
+    },
+    {
+      name: "blank text gives no messages",
+      text: "   \n ",
+      maxLength: 50,
+      expected: [],
+    },
+    {
+      name: "paragraph breaks become message boundaries",
+      text: "First paragraph here.\n\nSecond one.",
+      maxLength: 30,
+      expected: ["First paragraph here.", "Second one."],
+    },
+    {
+      name: "paragraphs are never merged",
+      text: "One. Two. Three.\n \nFour.",
+      maxLength: 12,
+      expected: ["One. Two.", "Three.", "Four."],
+    },
+    {
+      name: "splits at sentence boundaries",
+      text: "Hi there. How are you? I'm fine.",
+      maxLength: 20,
+      expected: ["Hi there.", "How are you?", "I'm fine."],
+    },
+    {
+      name: "splits after question and exclamation marks",
+      text: "Is it done? Yes! Ship it.",
+      maxLength: 12,
+      expected: ["Is it done?", "Yes!", "Ship it."],
+    },
+    {
+      name: "packs CJK sentences without a space",
+      text: "今日は晴れです。明日は雨です。週末は雪かもしれません。",
+      maxLength: 16,
+      expected: ["今日は晴れです。明日は雨です。", "週末は雪かもしれません。"],
+    },
+    {
+      name: "splits CJK at the full stop",
+      text: "今日は晴れです。明日は雨です。",
+      maxLength: 8,
+      expected: ["今日は晴れです。", "明日は雨です。"],
+    },
+    {
+      name: "packs CJK after a Latin sentence",
+      text: "Tokyo is sunny. 今日は晴れです。明日は雨です。",
+      maxLength: 20,
+      expected: ["Tokyo is sunny.", "今日は晴れです。明日は雨です。"],
+    },
+    {
+      name: "cuts a CJK sentence over the limit",
+      text: "週末は雪かもしれません。",
+      maxLength: 6,
+      expected: ["週末は雪かも", "しれません。"],
+    },
+    {
+      name: "wraps a sentence over the limit at words",
+      text: "the quick brown fox jumps over the lazy dog",
+      maxLength: 15,
+      expected: ["the quick brown", "fox jumps over", "the lazy dog"],
+    },
+    {
+      name: "cuts a word over the limit",
+      text: "see https://example.com/a/very/long/path now",
+      maxLength: 12,
+      expected: ["see", "https://exam", "ple.com/a/ve", "ry/long/path", "now"],
+    },
+    {
+      name: "cuts a word at exactly the limit",
+      text: "abcdefghijklmnopqrstuvwxy",
+      maxLength: 10,
+      expected: ["abcdefghij", "klmnopqrst", "uvwxy"],
+    },
+  ])("$name", ({ text, maxLength, expected }) => {
+    const messages = splitIntoMessages(text, maxLength);
+    expect(messages).toEqual(expected);
+    for (const message of messages) {
+      expect(message.length).toBeLessThanOrEqual(maxLength);
+    }
+  });
+});
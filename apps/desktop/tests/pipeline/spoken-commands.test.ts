@@ -0,0 +1,73 @@
+import { describe, it, expect } from "vitest";
+import { applySpokenCommands } from "@/pipeline/processing/spoken-commands";
+
+describe("applySpokenCommands", () => {
+  it.each([
+    // English
+    ["en", "Hello comma how are you question mark", "Hello, how are you?"],
+    ["en", "See you tomorrow period", "See you tomorrow."],
+    ["en", "Wait for it period. Then go", "Wait for it. Then go"],
+    [
+      "en",
+      "Dear Anna, new line thanks for the notes period",
+      "Dear Anna,\nThanks for the notes.",
+    ],
+    [
+      "en",
+      "He said open quote hi close quote and left",
+      'He said "hi" and left',
+    ],
+    ["en", "First sentence. Second sentence. delete that", "First sentence."],
+    // Spanish
+    ["es", "Hola coma qué tal signo de interrogación", "Hola, qué tal?"],
+    ["es", "Uno punto y coma dos punto", "Uno; dos."],
+    [
+      "es",
+      "Primera línea nueva línea segunda línea",
+      "Primera línea\nSegunda línea",
+    ],
+    // French
+    ["fr", "Bonjour virgule ça va point d'interrogation", "Bonjour, ça va?"],
+    ["fr", "Merci point. À bientôt", "Merci. À bientôt"],
+    ["fr", "Liste deux points pommes", "Liste: pommes"],
+    // German
+    ["de", "Hallo Komma wie geht's Fragezeichen", "Hallo, wie geht's?"],
+    ["de", "Danke Punkt Bis morgen", "Danke. Bis morgen"],
+    ["de", "Erste Zeile neue Zeile zweite Zeile", "Erste Zeile\nZweite Zeile"],
+  ])("%s: %j", (language, input, expected) => {
+    expect(applySpokenCommands(input, language)).toBe(expected);
+  });
+
+  it.each([
+    ["en", "The trial period ends on Friday."],
+    ["en", "What is the period of the orbit?"],
+    ["en", "The grace period, however, is short."],
+    ["en", "At that point we stopped."],
+    ["en", "Let me point out one thing."],
+    ["es", "Estoy a punto de salir."],
+    ["es", "Llegamos a las tres en punto."],
+    ["es", "El punto es que no vino."],
+    ["fr", "Je suis sur le point de partir."],
+    ["fr", "C'est un point important."],
+    ["fr", "Merci point à bientôt"],
+    ["de", "Der Punkt ist wichtig."],
+    ["de", "Wir treffen uns um Punkt acht."],
+    ["de", "Das ist der Punkt."],
+  ])("%s: keeps %j as prose", (language, input) => {
+    expect(applySpokenCommands(input, language)).toBe(input);
+  });
+
+  it("capitalizes a prose command word that starts a new line", () => {
+    expect(
+      applySpokenCommands("Notes new line period of review is over", "en"),
+    ).toBe("Notes\nPeriod of review is over");
+    expect(
+      applySpokenCommands("Notas nueva línea punto de partida", "es"),
+    ).toBe("Notas\nPunto de partida");
+  });
+
+  it("uses English commands for languages without their own", () => {
+    expect(applySpokenCommands("Hello comma there", "ja")).toBe("Hello, there");
+    expect(applySpokenCommands("Hallo period", "de")).toBe("Hallo period");
+  });
+});
@@ -0,0 +1,163 @@
+import { describe, it, expect } from "vitest";
+import { normalizeText } from "@/pipeline/processing/text-normalization";
+import type { NormalizationConfig } from "@/types/normalization";
+
+const OFF: NormalizationConfig = {
+  enabled: true,
+  numbers: "off",
+  dateFormat: "off",
+  currency: false,
+};
+
+describe("normalizeText", () => {
+  describe("numbers: digits", () => {
+    const config = { ...OFF, numbers: "digits" as const };
+
+    it.each([
+      ["We need twenty five chairs.", "We need 25 chairs."],
+      ["three hundred forty two cups", "342 cups"],
+      ["He scored two million five hundred thousand.", "He scored 2,500,000."],
+      ["a hundred people came", "100 people came"],
+      ["It shipped in nineteen ninety nine.", "It shipped in 1999."],
+      ["about three point five times more", "about 3.5 times more"],
+      ["Growth was twelve percent.", "Growth was 12%."],
+      ["Ten and twenty", "10 and 20"],
+    ])("%j → %j", (input, expected) => {
+      expect(normalizeText(input, config)).toBe(expected);
+    });
+  });
+
+  describe("numbers: words", () => {
+    const config = { ...OFF, numbers: "words" as const };
+
+    it.each([
+      ["I have 3 cats and 2 dogs.", "I have three cats and two dogs."],
+      ["Step 0 comes first", "Step zero comes first"],
+    ])("%j → %j", (input, expected) => {
+      expect(normalizeText(input, config)).toBe(expected);
+    });
+  });
+
+  describe("dates", () => {
+    it.each([
+      {
+        dateFormat: "long",
+        input: "Meet on March the fifth, twenty twenty four.",
+        expected: "Meet on March 5, 2024.",
+      },
+      {
+        dateFormat: "long",
+        input: "the twenty first of June",
+        expected: "June 21",
+      },
+      {
+        dateFormat: "iso",
+        input: "Due March 5th 2024.",
+        expected: "Due 2024-03-05.",
+      },
+      // ISO has no form without a year
+      { dateFormat: "iso", input: "Due March 5th.", expected: "Due March 5." },
+      {
+        dateFormat: "us",
+        input: "Due March 5th 2024.",
+        expected: "Due 03/05/2024.",
+      },
+      { dateFormat: "us", input: "Due March fifth.", expected: "Due 03/05." },
+      {
+        dateFormat: "eu",
+        input: "Due the fifth of March, 2024.",
+        expected: "Due 05/03/2024.",
+      },
+      { dateFormat: "eu", input: "Due March 5th.", expected: "Due 05/03." },
+    ] as const)("$dateFormat: $input", ({ dateFormat, input, expected }) => {
+      expect(normalizeText(input, { ...OFF, dateFormat })).toBe(expected);
+    });
+  });
+
+  describe("currency", () => {
+    const config = { ...OFF, currency: true };
+
+    it.each([
+      ["It costs five dollars and fifty cents.", "It costs $5.50."],
+      ["Lend me twenty bucks.", "Lend me $20."],
+      ["It's 3.5 euros.", "It's €3.50."],
+      ["That's two thousand pounds.", "That's £2000."],
+      ["It was a hundred and one dollars.", "It was $101."],
+    ])("%j → %j", (input, expected) => {
+      expect(normalizeText(input, config)).toBe(expected);
+    });
+  });
+
+  describe("leaves non-matches untouched", () => {
+    const digits = { ...OFF, numbers: "digits" as const };
+    const words = { ...OFF, numbers: "words" as const };
+    const dates = { ...OFF, dateFormat: "iso" as const };
+    const currency = { ...OFF, currency: true };
+    const all: NormalizationConfig = {
+      enabled: true,
+      numbers: "digits",
+      dateFormat: "iso",
+      currency: true,
+    };
+
+    it.each([
+      {
+        name: "single digits stay spelled out",
+        config: digits,
+        input: "I have one question and three ideas.",
+      },
+      {
+        name: "'point' as a verb",
+        config: digits,
+        input: "Let me point out one thing.",
+      },
+      {
+        name: "numbers that aren't single digits",
+        config: words,
+        input: "Version 2.5 costs $5, 12 items, 7% off, at 3:30 on 4/5.",
+      },
+      {
+        name: "'may' and 'second' as words",
+        config: dates,
+        input: "You may second that.",
+      },
+      {
+        name: "'march' as a verb",
+        config: dates,
+        input: "March forward, soldiers.",
+      },
+      {
+        name: "an impossible day",
+        config: dates,
+        input: "It's March thirty second.",
+      },
+      {
+        name: "'pounds' as a verb",
+        config: currency,
+        input: "He pounds the table, dollars aside.",
+      },
+      {
+        name: "more than 99 cents",
+        config: currency,
+        input: "five dollars and two hundred cents",
+      },
+      {
+        name: "everything off",
+        config: OFF,
+        input: "twenty five dollars on March 5th",
+      },
+      {
+        name: "normalization disabled",
+        config: { ...all, enabled: false },
+        input: "twenty five dollars on March 5th",
+      },
+    ])("$name", ({ config, input }) => {
+      expect(normalizeText(input, config)).toBe(input);
+    });
+
+    it("skips languages other than English", () => {
+      expect(normalizeText("twenty five", digits, "de")).toBe("twenty five");
+      expect(normalizeText("twenty five", digits, "en-US")).toBe("25");
+    });
+  });
+});
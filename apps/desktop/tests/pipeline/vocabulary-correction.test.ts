@@ -0,0 +1,109 @@
+import { describe, it, expect } from "vitest";
+import {
+  applyVocabularyCorrection,
+} from "@/pipeline/processing/vocabulary-correction";
+
+const vocabulary = ["John", "Kubernetes"];
+
+describe("applyVocabularyCorrection", () => {
+  it.each([
+    {
+      name: "swaps a misheard word for a term",
+      original: "Ask Jon about it.",
+      corrected: "Ask John about it.",
+      text: "Ask John about it.",
+      corrections: 1,
+    },
+    {
+      name: "keeps punctuation around a swapped term",
+      original: "Talk to jon.",
+      corrected: "Talk to John.",
+      text: "Talk to John.",
+      corrections: 1,
+    },
+    {
+      name: "turns several misheard words into one term",
+      original: "We run it on cooper netties, mostly.",
+      corrected: "We run it on Kubernetes, mostly.",
+      text: "We run it on Kubernetes, mostly.",
+      corrections: 1,
+    },
+    {
+      name: "counts each corrected span",
+      original: "Jon moved us to cooper netties.",
+      corrected: "John moved us to Kubernetes.",
+      text: "John moved us to Kubernetes.",
+      corrections: 2,
+    },
+    {
+      name: "returns the original when nothing changed",
+      original: "Ask John about it.",
+      corrected: "Ask John about it.",
+      text: "Ask John about it.",
+      corrections: 0,
+    },
+    {
+      name: "keeps the original's whitespace and line breaks",
+      original: "  Ask jon\n\nabout  it. ",
+      corrected: "Ask John about it.",
+      text: "  Ask John\n\nabout  it. ",
+      corrections: 1,
+    },
+    {
+      name: "keeps the line break after a multi-word span",
+      original: "Deploy to cooper netties\nthen check the logs",
+      corrected: "Deploy to Kubernetes then check the logs",
+      text: "Deploy to Kubernetes\nthen check the logs",
+      corrections: 1,
+    },
+  ])("$name", ({ original, corrected, text, corrections }) => {
+    const result = applyVocabularyCorrection(original, corrected, vocabulary);
+    expect(result).toEqual({ text, corrections });
+  });
+
+  it.each([
+    {
+      name: "punctuation changed along with a term",
+      original: "Talk to jon.",
+      corrected: "Talk to John!",
+    },
+    {
+      name: "punctuation changed on its own",
+      original: "Hello there.",
+      corrected: "Hello there!",
+    },
+    {
+      name: "an extra word next to a term",
+      original: "Ask Jon about it.",
+      corrected: "Ask John really about it.",
+    },
+    {
+      name: "an inserted word",
+      original: "Ask John about it.",
+      corrected: "Please Ask John about it.",
+    },
+    {
+      name: "a removed word",
+      original: "Ask John about it now.",
+      corrected: "Ask John about it.",
+    },
+    {
+      name: "a replacement that isn't a vocabulary term",
+      original: "Ask Jon about it.",
+      corrected: "Ask Jonathan about it.",
+    },
+    {
+      name: "capitalization changed",
+      original: "ask John about it.",
+      corrected: "Ask John about it.",
+    },
+    {
+      name: "too many words turned into a term",
+      original: "We use the cooper net is thing daily.",
+      corrected: "We use Kubernetes daily.",
+    },
+  ])("rejects $name", ({ original, corrected }) => {
+    const result = applyVocabularyCorrection(original, corrected, vocabulary);
+    expect(result).toBeNull();
+  });
+});
@@ -0,0 +1,94 @@
+import { describe, it, expect, beforeEach, afterEach, vi } from "vitest";
+import { createTestDatabase, type TestDatabase } from "../helpers/test-db";
+import { initializeTestServices } from "../helpers/test-app";
+import { setTestDatabase } from "../setup";
+import { TranscriptionService } from "@services/transcription-service";
+import { getDictationStats } from "@db/stats";
+import { getModelUsageStats } from "@db/model-usage";
+
+const SAMPLE_RATE = 16000;
+
+describe("Dictation stats", () => {
+  let testDb: TestDatabase;
+  let serviceManager: any;
+  let cleanup: () => Promise<void>;
+  let transcriptionService: TranscriptionService;
+
+  beforeEach(async () => {
+    testDb = await createTestDatabase({ name: "dictation-stats-test" });
+    setTestDatabase(testDb.db);
+    const result = await initializeTestServices(testDb);
+    serviceManager = result.serviceManager;
+    cleanup = result.cleanup;
+
+    // A real service with the speech model stubbed out, so dictations are
+    // saved the way the app saves them
+    const modelService = serviceManager.getService("modelService");
+    vi.spyOn(modelService, "getSelectedModel").mockResolvedValue(
+      "whisper-base",
+    );
+    transcriptionService = new TranscriptionService(
+      modelService,
+      null as any,
+      serviceManager.getService("settingsService"),
+      serviceManager.getService("telemetryService"),
+      null,
+      null,
+    );
+    vi.spyOn(transcriptionService as any, "selectProvider").mockResolvedValue({
+      name: "test",
+      transcribe: vi.fn().mockResolvedValue(""),
+      flush: vi
+        .fn()
+        .mockResolvedValue("one two three four five six seven eight nine ten"),
+      reset: vi.fn(),
+    });
+  });
+
+  afterEach(async () => {
+    vi.restoreAllMocks();
+    if (cleanup) {
+      await cleanup();
+    }
+    if (testDb) {
+      await testDb.close();
+    }
+  });
+
+  // Stream `seconds` of silence in one-second chunks, then finalize
+  async function dictate(sessionId: string, seconds: number) {
+    for (let i = 0; i < seconds; i++) {
+      await transcriptionService.processStreamingChunk({
+        sessionId,
+        audioChunk: new Float32Array(SAMPLE_RATE),
+      });
+    }
+    return transcriptionService.finalizeSession({ sessionId });
+  }
+
+  it("computes speaking rate from saved dictations", async () => {
+    await dictate("session-1", 30);
+
+    const stats = await getDictationStats({});
+
+    expect(stats.totals.transcriptions).toBe(1);
+    expect(stats.totals.words).toBe(10);
+    expect(stats.totals.durationSeconds).toBeCloseTo(30);
+    expect(stats.totals.averageWpm).toBeCloseTo(20);
+  });
+
+  it("adds each recording's length to its speech model's usage", async () => {
+    await dictate("session-1", 30);
+    await dictate("session-2", 12);
+
+    // Usage is recorded in the background
+    await vi.waitFor(async () => {
+      const [usage] = await getModelUsageStats("speech");
+      expect(usage).toMatchObject({
+        modelId: "whisper-base",
+        transcriptionCount: 2,
+      });
+      expect(usage.audioSeconds).toBeCloseTo(42);
+    });
+  });
+});
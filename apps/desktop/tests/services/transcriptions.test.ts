@@ -7,6 +7,8 @@ import {
 } from "../helpers/fixtures";
 import { initializeTestServices } from "../helpers/test-app";
 import { setTestDatabase } from "../setup";
+import * as schema from "@db/schema";
+import { backfillTranscriptionPreviews } from "@db/transcriptions";
 
 describe("Transcriptions Service", () => {
   let testDb: TestDatabase;
@@ -35,10 +37,11 @@ describe("Transcriptions Service", () => {
     });
 
     it("should return all transcriptions", async () => {
-      const transcriptions = await trpcCaller.transcriptions.getTranscriptions({
-        limit: 10,
-        offset: 0,
-      });
+      const { items: transcriptions } =
+        await trpcCaller.transcriptions.getTranscriptions({
+          limit: 10,
+          offset: 0,
+        });
 
       expect(transcriptions).toHaveLength(sampleTranscriptions.length);
       expect(transcriptions[0]).toHaveProperty("id");
@@ -47,22 +50,50 @@ describe("Transcriptions Service", () => {
     });
 
     it("should respect limit parameter", async () => {
-      const transcriptions = await trpcCaller.transcriptions.getTranscriptions({
+      const page = await trpcCaller.transcriptions.getTranscriptions({
         limit: 2,
         offset: 0,
       });
 
-      expect(transcriptions).toHaveLength(2);
+      expect(page.items).toHaveLength(2);
+      expect(page.total).toBe(sampleTranscriptions.length);
+      expect(page.hasMore).toBe(true);
+    });
+
+    it("should report no more pages on the last page", async () => {
+      const page = await trpcCaller.transcriptions.getTranscriptions({
+        limit: 2,
+        offset: 2,
+      });
+
+      expect(page.items).toHaveLength(1);
+      expect(page.total).toBe(sampleTranscriptions.length);
+      expect(page.hasMore).toBe(false);
+      expect(page.nextCursor).toBeUndefined();
+    });
+
+    it("should page through every row with a cursor", async () => {
+      const first = await trpcCaller.transcriptions.getTranscriptions({
+        limit: 2,
+      });
+      const second = await trpcCaller.transcriptions.getTranscriptions({
+        limit: 2,
+        cursor: first.nextCursor,
+      });
+
+      const ids = [...first.items, ...second.items].map((t: any) => t.id);
+      expect(new Set(ids).size).toBe(sampleTranscriptions.length);
+      expect(second.hasMore).toBe(false);
     });
 
     it("should respect offset parameter", async () => {
-      const allTranscriptions =
+      const { items: allTranscriptions } =
         await trpcCaller.transcriptions.getTranscriptions({
           limit: 10,
           offset: 0,
         });
 
-      const offsetTranscriptions =
+      const { items: offsetTranscriptions } =
         await trpcCaller.transcriptions.getTranscriptions({
           limit: 10,
           offset: 1,
@@ -71,6 +102,87 @@ describe("Transcriptions Service", () => {
       expect(offsetTranscriptions).toHaveLength(allTranscriptions.length - 1);
       expect(offsetTranscriptions[0].id).not.toBe(allTranscriptions[0].id);
     });
+
+    it("should return only the requested fields", async () => {
+      const { items: transcriptions } =
+        await trpcCaller.transcriptions.getTranscriptions({
+          limit: 10,
+          fields: ["preview", "rawText", "timestamp"],
+        });
+
+      expect(transcriptions).toHaveLength(sampleTranscriptions.length);
+      expect(Object.keys(transcriptions[0]).sort()).toEqual([
+        "id",
+        "preview",
+        "rawText",
+        "timestamp",
+      ]);
+    });
+
+    it("should backfill previews of rows saved without one", async () => {
+      const [row] = await testDb.db
+        .insert(schema.transcriptions)
+        .values({ text: "  First sentence here. Second one follows." })
+        .returning();
+      expect(row.preview).toBeNull();
+
+      const filled = await backfillTranscriptionPreviews();
+
+      expect(filled).toBe(sampleTranscriptions.length + 1);
+      const { items } = await trpcCaller.transcriptions.getTranscriptions({
+        limit: 10,
+        fields: ["preview"],
+      });
+      expect(items.find((item: any) => item.id === row.id)?.preview).toBe(
+        "First sentence here.",
+      );
+      expect(await backfillTranscriptionPreviews()).toBe(0);
+    });
+
+    it("should filter by speech model and language", async () => {
+      const page = await trpcCaller.transcriptions.getTranscriptions({
+        speechModel: "whisper-large",
+        language: "en",
+      });
+
+      expect(page.total).toBe(1);
+      expect(page.items[0].speechModel).toBe("whisper-large");
+    });
+
+    it("should filter by timestamp range", async () => {
+      const future = new Date(Date.now() + 60 * 60 * 1000);
+      const upcoming = await trpcCaller.transcriptions.getTranscriptions({
+        from: future,
+      });
+      const past = await trpcCaller.transcriptions.getTranscriptions({
+        to: future,
+      });
+
+      expect(upcoming.items).toHaveLength(0);
+      expect(upcoming.total).toBe(0);
+      expect(past.total).toBe(sampleTranscriptions.length);
+    });
+
+    it("should filter by the app dictated into", async () => {
+      const [slack] = await testDb.db
+        .insert(schema.transcriptions)
+        .values({
+          text: "Sent from Slack",
+          targetAppBundleId: "com.tinyspeck.slackmacgap",
+          targetAppName: "Slack",
+        })
+        .returning();
+
+      const page = await trpcCaller.transcriptions.getTranscriptions({
+        targetAppBundleId: "com.tinyspeck.slackmacgap",
+      });
+      const apps = await trpcCaller.transcriptions.getTargetApps();
+
+      expect(page.items.map((t: any) => t.id)).toEqual([slack.id]);
+      expect(apps).toEqual([
+        { bundleId: "com.tinyspeck.slackmacgap", name: "Slack", count: 1 },
+      ]);
+    });
   });
 
   describe("Get Transcription by ID", () => {
@@ -85,10 +197,11 @@ describe("Transcriptions Service", () => {
     });
 
     it("should return transcription by id", async () => {
-      const transcriptions = await trpcCaller.transcriptions.getTranscriptions({
-        limit: 1,
-        offset: 0,
-      });
+      const { items: transcriptions } =
+        await trpcCaller.transcriptions.getTranscriptions({
+          limit: 1,
+          offset: 0,
+        });
 
       const transcription =
         await trpcCaller.transcriptions.getTranscriptionById({
@@ -100,6 +213,25 @@ describe("Transcriptions Service", () => {
       expect(transcription.text).toBe(transcriptions[0].text);
     });
 
+    it("should return both the raw and the formatted text", async () => {
+      const created = await trpcCaller.transcriptions.createTranscription({
+        text: "Meet Kate at 3 p.m.",
+        rawText: "meet kate at three pm",
+      });
+      await trpcCaller.transcriptions.updateTranscription({
+        id: created.id,
+        data: { text: "Meet Kate at 3 p.m. on Friday." },
+      });
+
+      const transcription =
+        await trpcCaller.transcriptions.getTranscriptionById({
+          id: created.id,
+        });
+
+      expect(transcription.text).toBe("Meet Kate at 3 p.m. on Friday.");
+      expect(transcription.rawText).toBe("meet kate at three pm");
+    });
+
     it("should return null for non-existent id", async () => {
       const result = await trpcCaller.transcriptions.getTranscriptionById({
         id: 99999,
@@ -120,24 +252,82 @@ describe("Transcriptions Service", () => {
     });
 
     it("should delete transcription by id", async () => {
-      const transcriptions = await trpcCaller.transcriptions.getTranscriptions({
-        limit: 10,
-        offset: 0,
-      });
+      const { items: transcriptions } =
+        await trpcCaller.transcriptions.getTranscriptions({
+          limit: 10,
+          offset: 0,
+        });
 
       const initialCount = transcriptions.length;
       const idToDelete = transcriptions[0].id;
 
       await trpcCaller.transcriptions.deleteTranscription({ id: idToDelete });
 
-      const afterDelete = await trpcCaller.transcriptions.getTranscriptions({
-        limit: 10,
-        offset: 0,
-      });
+      const { items: afterDelete } =
+        await trpcCaller.transcriptions.getTranscriptions({
+          limit: 10,
+          offset: 0,
+        });
 
       expect(afterDelete).toHaveLength(initialCount - 1);
       expect(afterDelete.find((t: any) => t.id === idToDelete)).toBeUndefined();
     });
+
+    it("should delete several transcriptions at once", async () => {
+      const { items: transcriptions } =
+        await trpcCaller.transcriptions.getTranscriptions({ limit: 10 });
+      const ids = transcriptions.slice(0, 2).map((t: any) => t.id);
+
+      const result = await trpcCaller.transcriptions.bulkDelete({ ids });
+      const count = await trpcCaller.transcriptions.getTranscriptionsCount({});
+
+      expect(result.deleted).toBe(2);
+      expect(count).toBe(sampleTranscriptions.length - 2);
+    });
+
+    it("should purge transcriptions beyond the retention limit", async () => {
+      const { items: before } =
+        await trpcCaller.transcriptions.getTranscriptions({ limit: 10 });
+      await serviceManager
+        .getService("settingsService")
+        .setHistorySettings({ maxItems: 1 });
+
+      const report = await serviceManager
+        .getService("retentionService")
+        .enforce();
+      const { items: after } =
+        await trpcCaller.transcriptions.getTranscriptions({ limit: 10 });
+
+      expect(report.transcriptionsDeleted).toBe(
+        sampleTranscriptions.length - 1,
+      );
+      expect(after.map((t: any) => t.id)).toEqual([before[0].id]);
+    });
+
+    it("should drop expired recordings but keep the text", async () => {
+      const { items } = await trpcCaller.transcriptions.getTranscriptions({
+        limit: 1,
+      });
+      await trpcCaller.transcriptions.updateTranscription({
+        id: items[0].id,
+        data: {
+          timestamp: new Date(Date.now() - 40 * 24 * 60 * 60 * 1000),
+          audioFile: "/tmp/grizzo-audio/old.wav",
+        },
+      });
+      await serviceManager
+        .getService("settingsService")
+        .setHistorySettings({ audioRetentionDays: 30 });
+
+      await serviceManager.getService("retentionService").enforce();
+      const transcription =
+        await trpcCaller.transcriptions.getTranscriptionById({
+          id: items[0].id,
+        });
+
+      expect(transcription.text).toBe(items[0].text);
+      expect(transcription.audioFile).toBeNull();
+    });
   });
 
   describe("Search Transcriptions", () => {
@@ -163,6 +353,97 @@ describe("Transcriptions Service", () => {
       });
     });
 
+    it("should rank the best match first and highlight hits", async () => {
+      const results = await trpcCaller.transcriptions.searchTranscriptions({
+        searchTerm: "comprehensive testing",
+        limit: 10,
+      });
+
+      expect(results).toHaveLength(1);
+      expect(results[0].text).toBe(
+        "A third transcription for comprehensive testing",
+      );
+      expect(results[0].snippet).toContain("<mark>comprehensive</mark>");
+    });
+
+    it("should find transcriptions edited after creation", async () => {
+      const [first] = await trpcCaller.transcriptions.searchTranscriptions({
+        searchTerm: "another",
+      });
+      await trpcCaller.transcriptions.updateTranscription({
+        id: first.id,
+        data: { text: "Rewritten with a kubernetes mention" },
+      });
+
+      const stale = await trpcCaller.transcriptions.searchTranscriptions({
+        searchTerm: "another",
+      });
+      const fresh = await trpcCaller.transcriptions.searchTranscriptions({
+        searchTerm: "kube",
+      });
+
+      expect(stale).toHaveLength(0);
+      expect(fresh.map((result: any) => result.id)).toEqual([first.id]);
+    });
+
+    it("should treat search syntax characters as plain text", async () => {
+      const results = await trpcCaller.transcriptions.searchTranscriptions({
+        searchTerm: 'test" OR (',
+        limit: 10,
+      });
+
+      expect(results.length).toBeGreaterThan(0);
+    });
+
+    it("should find part of a Chinese or Japanese dictation", async () => {
+      const [japanese] = await testDb.db
+        .insert(schema.transcriptions)
+        .values({ text: "明日は東京駅で待ち合わせしましょう" })
+        .returning();
+
+      const short = await trpcCaller.transcriptions.searchTranscriptions({
+        searchTerm: "東京",
+      });
+      const long = await trpcCaller.transcriptions.searchTranscriptions({
+        searchTerm: "東京駅で",
+      });
+
+      expect(short.map((result: any) => result.id)).toEqual([japanese.id]);
+      expect(short[0].snippet).toContain("<mark>東京</mark>");
+      expect(long.map((result: any) => result.id)).toEqual([japanese.id]);
+    });
+
+    it("should match words regardless of accents", async () => {
+      const [accented] = await testDb.db
+        .insert(schema.transcriptions)
+        .values({ text: "Meet me at the café near the façade" })
+        .returning();
+
+      for (const searchTerm of ["cafe", "facade", "café"]) {
+        const results = await trpcCaller.transcriptions.searchTranscriptions({
+          searchTerm,
+        });
+        expect(results.map((result: any) => result.id)).toEqual([
+          accented.id,
+        ]);
+      }
+    });
+
+    it("should escape HTML in snippets", async () => {
+      await testDb.db
+        .insert(schema.transcriptions)
+        .values({ text: 'Paste <img src=x onerror="alert(1)"> markup here' });
+
+      for (const searchTerm of ["markup", "x"]) {
+        const results = await trpcCaller.transcriptions.searchTranscriptions({
+          searchTerm,
+        });
+
+        expect(results[0].snippet).not.toContain("<img");
+        expect(results[0].snippet).toContain("&#60;img");
+      }
+    });
+
     it("should return empty array for no matches", async () => {
       const results = await trpcCaller.transcriptions.searchTranscriptions({
         searchTerm: "nonexistentquerystring",
@@ -184,13 +465,15 @@ describe("Transcriptions Service", () => {
       cleanup = result.cleanup;
     });
 
-    it("should return empty array for empty database", async () => {
-      const transcriptions = await trpcCaller.transcriptions.getTranscriptions({
+    it("should return an empty page for empty database", async () => {
+      const page = await trpcCaller.transcriptions.getTranscriptions({
         limit: 10,
         offset: 0,
       });
 
-      expect(transcriptions).toHaveLength(0);
+      expect(page.items).toHaveLength(0);
+      expect(page.total).toBe(0);
+      expect(page.hasMore).toBe(false);
     });
 
     it("should handle search on empty database", async () => {
@@ -221,4 +504,290 @@ describe("Transcriptions Service", () => {
       expect(count).toBeGreaterThan(0);
     });
   });
+
+  describe("Favorites and Tags", () => {
+    beforeEach(async () => {
+      testDb = await createTestDatabase({ name: "favorites-tags-test" });
+      setTestDatabase(testDb.db);
+      await seedDatabase(testDb, "withTranscriptions");
+      const result = await initializeTestServices(testDb);
+      serviceManager = result.serviceManager;
+      trpcCaller = result.trpcCaller;
+      cleanup = result.cleanup;
+    });
+
+    it("should filter by favorite", async () => {
+      const { items } = await trpcCaller.transcriptions.getTranscriptions({
+        limit: 10,
+      });
+      await trpcCaller.transcriptions.setFavorite({
+        id: items[1].id,
+        isFavorite: true,
+      });
+
+      const favorites = await trpcCaller.transcriptions.getTranscriptions({
+        favorite: true,
+      });
+
+      expect(favorites.items.map((t: any) => t.id)).toEqual([items[1].id]);
+    });
+
+    it("should tag transcriptions and filter by tag", async () => {
+      const { items } = await trpcCaller.transcriptions.getTranscriptions({
+        limit: 10,
+      });
+      const tags = await trpcCaller.transcriptions.setTags({
+        id: items[0].id,
+        tags: ["work", " ideas ", "work"],
+      });
+      await trpcCaller.transcriptions.setTags({
+        id: items[2].id,
+        tags: ["work"],
+      });
+
+      const tagged = await trpcCaller.transcriptions.getTranscriptions({
+        tag: "ideas",
+      });
+      const transcription =
+        await trpcCaller.transcriptions.getTranscriptionById({
+          id: items[0].id,
+        });
+      const allTags = await trpcCaller.transcriptions.getTags();
+
+      expect(tags).toEqual(["ideas", "work"]);
+      expect(tagged.items.map((t: any) => t.id)).toEqual([items[0].id]);
+      expect(transcription.tags).toEqual(["ideas", "work"]);
+      expect(allTags).toEqual([
+        { tag: "ideas", count: 1 },
+        { tag: "work", count: 2 },
+      ]);
+    });
+
+    it("should drop tags with their transcription", async () => {
+      const { items } = await trpcCaller.transcriptions.getTranscriptions({
+        limit: 1,
+      });
+      await trpcCaller.transcriptions.setTags({
+        id: items[0].id,
+        tags: ["work"],
+      });
+
+      await trpcCaller.transcriptions.deleteTranscription({ id: items[0].id });
+
+      expect(await trpcCaller.transcriptions.getTags()).toEqual([]);
+    });
+  });
+
+  describe("Revisions", () => {
+    beforeEach(async () => {
+      testDb = await createTestDatabase({ name: "revisions-test" });
+      setTestDatabase(testDb.db);
+      await seedDatabase(testDb, "withTranscriptions");
+      const result = await initializeTestServices(testDb);
+      serviceManager = result.serviceManager;
+      trpcCaller = result.trpcCaller;
+      cleanup = result.cleanup;
+    });
+
+    it("should keep the previous text when the text changes", async () => {
+      const { items } = await trpcCaller.transcriptions.getTranscriptions({
+        limit: 1,
+      });
+      await trpcCaller.transcriptions.updateTranscription({
+        id: items[0].id,
+        data: { text: "First correction" },
+      });
+      await trpcCaller.transcriptions.updateTranscription({
+        id: items[0].id,
+        data: { language: "de" },
+      });
+
+      const revisions = await trpcCaller.transcriptions.getRevisions({
+        id: items[0].id,
+      });
+
+      expect(revisions.map((r: any) => r.text)).toEqual([items[0].text]);
+    });
+
+    it("should revert to a revision without losing the current text", async () => {
+      const { items } = await trpcCaller.transcriptions.getTranscriptions({
+        limit: 1,
+      });
+      await trpcCaller.transcriptions.updateTranscription({
+        id: items[0].id,
+        data: { text: "First correction" },
+      });
+      const [original] = await trpcCaller.transcriptions.getRevisions({
+        id: items[0].id,
+      });
+
+      const reverted = await trpcCaller.transcriptions.revertToRevision({
+        revisionId: original.id,
+      });
+      const revisions = await trpcCaller.transcriptions.getRevisions({
+        id: items[0].id,
+      });
+
+      expect(reverted.text).toBe(items[0].text);
+      expect(revisions.map((r: any) => r.text)).toEqual([
+        "First correction",
+        items[0].text,
+      ]);
+    });
+  });
+
+  describe("Dictation Sessions", () => {
+    beforeEach(async () => {
+      testDb = await createTestDatabase({ name: "sessions-test" });
+      setTestDatabase(testDb.db);
+      await seedDatabase(testDb, "empty");
+      const result = await initializeTestServices(testDb);
+      serviceManager = result.serviceManager;
+      trpcCaller = result.trpcCaller;
+      cleanup = result.cleanup;
+    });
+
+    it("should group dictations made in quick succession", async () => {
+      // Whole seconds, as stored
+      const start = new Date(Math.floor(Date.now() / 1000) * 1000 - 3600_000);
+      const at = (minutes: number) =>
+        new Date(start.getTime() + minutes * 60 * 1000);
+      const first = await trpcCaller.transcriptions.createTranscription({
+        text: "First snippet",
+        timestamp: at(0),
+      });
+      await trpcCaller.transcriptions.createTranscription({
+        text: "Second snippet",
+        timestamp: at(2),
+      });
+      const later = await trpcCaller.transcriptions.createTranscription({
+        text: "After a break",
+        timestamp: at(20),
+      });
+
+      const sessions = await trpcCaller.transcriptions.getSessions({});
+      const firstSession = await trpcCaller.transcriptions.getTranscriptions({
+        sessionId: first.id,
+      });
+
+      expect(
+        sessions.map((session: any) => [session.sessionId, session.count]),
+      ).toEqual([
+        [later.id, 1],
+        [first.id, 2],
+      ]);
+      expect(sessions[1].startedAt).toEqual(at(0));
+      expect(sessions[1].endedAt).toEqual(at(2));
+      expect(firstSession.total).toBe(2);
+    });
+  });
+
+  describe("Dictation Stats", () => {
+    beforeEach(async () => {
+      testDb = await createTestDatabase({ name: "stats-test" });
+      setTestDatabase(testDb.db);
+      await seedDatabase(testDb, "withTranscriptions");
+      const result = await initializeTestServices(testDb);
+      serviceManager = result.serviceManager;
+      trpcCaller = result.trpcCaller;
+      cleanup = result.cleanup;
+    });
+
+    it("should aggregate words, rate and breakdowns", async () => {
+      const stats = await trpcCaller.stats.getDictationStats();
+
+      expect(stats.totals.transcriptions).toBe(sampleTranscriptions.length);
+      expect(stats.totals.words).toBe(17);
+      expect(stats.totals.durationSeconds).toBe(19);
+      expect(stats.totals.averageWpm).toBeCloseTo(17 / (19 / 60));
+      expect(stats.streaks).toEqual({ current: 1, longest: 1 });
+      expect(stats.byModel).toEqual([
+        { speechModel: "whisper-base", transcriptions: 2, words: 11 },
+        { speechModel: "whisper-large", transcriptions: 1, words: 6 },
+      ]);
+      expect(stats.byLanguage).toEqual([
+        { language: "en", transcriptions: 3, words: 17 },
+      ]);
+    });
+  });
+
+  describe("Review Queue", () => {
+    let flaggedId: number;
+
+    beforeEach(async () => {
+      testDb = await createTestDatabase({ name: "review-queue-test" });
+      setTestDatabase(testDb.db);
+      await seedDatabase(testDb, "withTranscriptions");
+      const [flagged] = await testDb.db
+        .insert(schema.transcriptions)
+        .values({
+          text: "thank you for watching",
+          language: "en",
+          duration: 12,
+          needsReview: true,
+        })
+        .returning();
+      flaggedId = flagged.id;
+      const result = await initializeTestServices(testDb);
+      serviceManager = result.serviceManager;
+      trpcCaller = result.trpcCaller;
+      cleanup = result.cleanup;
+    });
+
+    it("should return only flagged transcriptions", async () => {
+      const queue = await trpcCaller.transcriptions.getReviewQueue({});
+
+      expect(queue).toHaveLength(1);
+      expect(queue[0].id).toBe(flaggedId);
+      expect(await trpcCaller.transcriptions.getReviewQueueCount()).toBe(1);
+    });
+
+    it("should clear the flag when approved", async () => {
+      await trpcCaller.transcriptions.approveTranscription({ id: flaggedId });
+
+      const queue = await trpcCaller.transcriptions.getReviewQueue({});
+      expect(queue).toHaveLength(0);
+    });
+
+    it("should clear the flag when the text is edited", async () => {
+      const updated = await trpcCaller.transcriptions.updateTranscription({
+        id: flaggedId,
+        data: { text: "Corrected text" },
+      });
+
+      expect(updated.needsReview).toBe(false);
+      expect(await trpcCaller.transcriptions.getReviewQueueCount()).toBe(0);
+    });
+  });
+
+  describe("Preview", () => {
+    beforeEach(async () => {
+      testDb = await createTestDatabase({ name: "preview-test" });
+      setTestDatabase(testDb.db);
+      const result = await initializeTestServices(testDb);
+      serviceManager = result.serviceManager;
+      trpcCaller = result.trpcCaller;
+      cleanup = result.cleanup;
+    });
+
+    it("should store the first sentence as preview", async () => {
+      const created = await trpcCaller.transcriptions.createTranscription({
+        text: "Meeting notes for Monday. We agreed to ship on Friday.",
+      });
+
+      expect(created.preview).toBe("Meeting notes for Monday.");
+    });
+
+    it("should refresh the preview when the text changes", async () => {
+      const created = await trpcCaller.transcriptions.createTranscription({
+        text: "First draft.",
+      });
+      const updated = await trpcCaller.transcriptions.updateTranscription({
+        id: created.id,
+        data: { text: "Second draft! With more detail." },
+      });
+
+      expect(updated.preview).toBe("Second draft!");
+    });
+  });
 });
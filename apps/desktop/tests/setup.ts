@@ -33,6 +33,8 @@ vi.mock("@db", () => ({
   get dbPath() {
     return "/test/db/path";
   },
+  withTransaction: (fn: (tx: any) => Promise<unknown>) =>
+    getTestDatabase().transaction(fn),
   initializeDatabase: vi.fn().mockResolvedValue(undefined),
   closeDatabase: vi.fn().mockResolvedValue(undefined),
 }));
@@ -0,0 +1,56 @@
+import { describe, it, expect } from "vitest";
+import { serializeTranscriptions } from "@utils/transcription-export";
+
+function csvTextCell(text: string): string {
+  const csv = serializeTranscriptions(
+    [
+      {
+        id: 1,
+        timestamp: new Date("2024-03-05T10:00:00.000Z"),
+        text,
+        duration: 12,
+        speechModel: null,
+        formattingModel: null,
+        language: "en",
+      },
+    ],
+    "csv",
+  );
+  const [, row] = csv.split("\r\n");
+  const prefix = "1,2024-03-05T10:00:00.000Z,";
+  return row.slice(prefix.length, row.lastIndexOf(",12,"));
+}
+
+describe("serializeTranscriptions", () => {
+  it.each([
+    ["plain text", "Hello there", "Hello there"],
+    ["a comma", "Hello, there", '"Hello, there"'],
+    ["quotes", 'She said "hi"', '"She said ""hi"""'],
+    ["a formula", "=SUM(A1:A2)", `"'=SUM(A1:A2)"`],
+    ["a leading plus", "+1 555 0100", `"'+1 555 0100"`],
+    ["a leading minus", "-2 degrees", `"'-2 degrees"`],
+    ["a leading at sign", "@channel hello", `"'@channel hello"`],
+    ["a leading tab", "\tindented", `"'\tindented"`],
+    ["a formula with quotes", '=HYPERLINK("x")', `"'=HYPERLINK(""x"")"`],
+  ])("writes %s as a safe CSV cell", (_name, text, expected) => {
+    expect(csvTextCell(text)).toBe(expected);
+  });
+
+  it("keeps text unchanged in JSON", () => {
+    const json = serializeTranscriptions(
+      [
+        {
+          id: 1,
+          timestamp: new Date("2024-03-05T10:00:00.000Z"),
+          text: "=SUM(A1:A2)",
+          duration: null,
+          speechModel: null,
+          formattingModel: null,
+          language: null,
+        },
+      ],
+      "json",
+    );
+    expect(JSON.parse(json)[0].text).toBe("=SUM(A1:A2)");
+  });
+});
@@ -446,10 +446,20 @@ class AccessibilityService {
         return true
     }
 
-    // Pastes the given text into the active application
-    public func pasteText(transcript: String) -> Bool {
+    // Pastes the given text into the active application, optionally deleting
+    // `replaceLength` characters before the cursor first (voice edits) and
+    // pressing Return afterwards (`submit`, for chat apps)
+    public func pasteText(transcript: String, replaceLength: Int = 0, submit: Bool = false) -> Bool {
         logToStderr("[AccessibilityService] Attempting to paste transcript: \(transcript).")
 
+        if replaceLength > 0 && !deleteBackward(count: replaceLength) {
+            return false
+        }
+
+        if transcript.isEmpty {
+            return true
+        }
+
         let pasteboard = NSPasteboard.general
         pasteboard.clearContents()
         let success = pasteboard.setString(transcript, forType: .string)
@@ -487,6 +497,39 @@ class AccessibilityService {
 
         logToStderr("[AccessibilityService] Paste keyboard events posted.")
 
+        if submit {
+            guard
+                let returnDown = CGEvent(keyboardEventSource: source, virtualKey: VK_RETURN, keyDown: true),
+                let returnUp = CGEvent(keyboardEventSource: source, virtualKey: VK_RETURN, keyDown: false)
+            else {
+                logToStderr("[AccessibilityService] Failed to create CGEvent for return.")
+                return false
+            }
+            returnDown.post(tap: loc)
+            returnUp.post(tap: loc)
+        }
+
+        return true
+    }
+
+    // Simulates `count` backspace presses in the active application
+    private func deleteBackward(count: Int) -> Bool {
+        let source = CGEventSource(stateID: .hidSystemState)
+        let loc: CGEventTapLocation = .cgSessionEventTap
+
+        for _ in 0..<count {
+            guard
+                let down = CGEvent(keyboardEventSource: source, virtualKey: VK_DELETE, keyDown: true),
+                let up = CGEvent(keyboardEventSource: source, virtualKey: VK_DELETE, keyDown: false)
+            else {
+                logToStderr("[AccessibilityService] Failed to create CGEvent for delete.")
+                return false
+            }
+            down.post(tap: loc)
+            up.post(tap: loc)
+        }
+
+        logToStderr("[AccessibilityService] Posted \(count) delete keyboard events.")
         return true
     }
 
@@ -78,7 +78,10 @@ class IOBridge: NSObject {
                 logToStderr("[IOBridge] Decoded pasteParams.transcript for ID: \(request.id)")
 
                 // Call the actual paste function (to be implemented in AccessibilityService or similar)
-                let success = accessibilityService.pasteText(transcript: pasteParams.transcript)
+                let success = accessibilityService.pasteText(
+                    transcript: pasteParams.transcript,
+                    replaceLength: pasteParams.replaceLength ?? 0,
+                    submit: pasteParams.submit ?? false)
 
                 // Corrected to use generated Swift model name from models.swift
                 let resultPayload = PasteTextResultSchema(
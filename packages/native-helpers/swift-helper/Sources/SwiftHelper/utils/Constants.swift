@@ -72,6 +72,12 @@ let EXTRACTION_TIMEOUT_MS: Double = 600.0
 /// Virtual key code for 'V' key
 let VK_V: CGKeyCode = 9
 
+/// Virtual key code for Delete (backspace)
+let VK_DELETE: CGKeyCode = 51
+
+/// Virtual key code for Return
+let VK_RETURN: CGKeyCode = 36
+
 /// Virtual key code for Command key
 let VK_COMMAND: CGKeyCode = 55
 
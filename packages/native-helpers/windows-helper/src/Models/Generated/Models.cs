@@ -259,6 +259,14 @@ namespace WindowsHelper.Models
 
     public partial class PasteTextParams
     {
+        [JsonIgnore(Condition = JsonIgnoreCondition.WhenWritingNull)]
+        [JsonPropertyName("replaceLength")]
+        public long? ReplaceLength { get; set; }
+
+        [JsonIgnore(Condition = JsonIgnoreCondition.WhenWritingNull)]
+        [JsonPropertyName("submit")]
+        public bool? Submit { get; set; }
+
         [JsonPropertyName("transcript")]
         public string Transcript { get; set; }
     }
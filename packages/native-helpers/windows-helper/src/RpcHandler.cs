@@ -242,7 +242,11 @@ namespace WindowsHelper
 
                 if (parameters != null)
                 {
-                    var success = accessibilityService.PasteText(parameters.Transcript, out var errorMessage);
+                    var success = accessibilityService.PasteText(
+                        parameters.Transcript,
+                        (int)(parameters.ReplaceLength ?? 0),
+                        parameters.Submit ?? false,
+                        out var errorMessage);
                     return new RpcResponse
                     {
                         Id = request.Id.ToString(),
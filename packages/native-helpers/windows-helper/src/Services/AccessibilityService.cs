@@ -13,6 +13,8 @@ namespace WindowsHelper.Services
 
         private const byte VK_CONTROL = 0x11;
         private const byte VK_V = 0x56;
+        private const byte VK_BACK = 0x08;
+        private const byte VK_RETURN = 0x0D;
         private const uint KEYEVENTF_KEYUP = 0x0002;
         #endregion
 
@@ -35,13 +37,25 @@ namespace WindowsHelper.Services
             return AccessibilityContextService.GetAccessibilityContext(editableOnly);
         }
 
-        public bool PasteText(string text, out string? errorMessage)
+        public bool PasteText(string text, int replaceLength, bool submit, out string? errorMessage)
         {
             errorMessage = null;
 
             try
             {
-                LogToStderr($"PasteText called with text length: {text.Length}");
+                LogToStderr($"PasteText called with text length: {text.Length}, replaceLength: {replaceLength}, submit: {submit}");
+
+                // Delete previously pasted characters first (voice edits)
+                for (var i = 0; i < replaceLength; i++)
+                {
+                    keybd_event(VK_BACK, 0, 0, UIntPtr.Zero);
+                    keybd_event(VK_BACK, 0, KEYEVENTF_KEYUP, UIntPtr.Zero);
+                }
+
+                if (string.IsNullOrEmpty(text))
+                {
+                    return true;
+                }
 
                 // Save original clipboard content
                 var savedContent = clipboardService.Save();
@@ -67,6 +81,13 @@ namespace WindowsHelper.Services
                 // Wait for paste to complete before restoring
                 Thread.Sleep(200);
 
+                // Send the message in chat apps
+                if (submit)
+                {
+                    keybd_event(VK_RETURN, 0, 0, UIntPtr.Zero);
+                    keybd_event(VK_RETURN, 0, KEYEVENTF_KEYUP, UIntPtr.Zero);
+                }
+
                 // Restore original clipboard synchronously and report errors
                 var restoreError = clipboardService.RestoreSync(savedContent, newSeq);
                 if (restoreError != null)
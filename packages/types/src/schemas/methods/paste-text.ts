@@ -3,6 +3,8 @@ import { z } from "zod";
 // Request params
 export const PasteTextParamsSchema = z.object({
   transcript: z.string(),
+  replaceLength: z.number().int().nonnegative().optional(), // Characters before the cursor to delete first
+  submit: z.boolean().optional(), // Press Return after pasting (sends the message in chat apps)
 });
 export type PasteTextParams = z.infer<typeof PasteTextParamsSchema>;
 
@@ -0,0 +1,74 @@
+import * as zlib from "node:zlib";
+
+/**
+ * Storage format of a row in `yjs_updates.update_data`.
+ * Legacy rows (written before the `format` column existed) are base64 text.
+ */
+export enum UpdateFormat {
+  Base64 = 0,
+  Raw = 1,
+  Zstd = 2,
+}
+
+export type UpdateCompression = "zstd" | "none";
+
+// zstd landed in node:zlib in Node 22.15; older typings don't declare it
+type ZstdZlib = {
+  zstdCompressSync?: (data: Uint8Array) => Buffer;
+  zstdDecompressSync?: (data: Uint8Array) => Buffer;
+};
+
+const zstd = zlib as unknown as ZstdZlib;
+
+export const zstdAvailable =
+  typeof zstd.zstdCompressSync === "function" &&
+  typeof zstd.zstdDecompressSync === "function";
+
+// Tiny updates (single keystrokes) don't shrink, so store them as-is
+const MIN_COMPRESS_BYTES = 256;
+
+export function encodeUpdate(
+  update: Uint8Array,
+  compression: UpdateCompression,
+): { data: Uint8Array; format: UpdateFormat } {
+  if (
+    compression === "zstd" &&
+    zstdAvailable &&
+    update.byteLength >= MIN_COMPRESS_BYTES
+  ) {
+    const compressed = zstd.zstdCompressSync!(update);
+    if (compressed.byteLength < update.byteLength) {
+      return { data: new Uint8Array(compressed), format: UpdateFormat.Zstd };
+    }
+  }
+  return { data: update, format: UpdateFormat.Raw };
+}
+
+function toBytes(value: unknown): Uint8Array {
+  if (value instanceof Uint8Array) {
+    return value;
+  }
+  if (value instanceof ArrayBuffer) {
+    return new Uint8Array(value);
+  }
+  if (typeof value === "string") {
+    const binaryString = atob(value);
+    const bytes = new Uint8Array(binaryString.length);
+    for (let i = 0; i < binaryString.length; i++) {
+      bytes[i] = binaryString.charCodeAt(i);
+    }
+    return bytes;
+  }
+  throw new Error(`Unsupported update_data type: ${typeof value}`);
+}
+
+export function decodeUpdate(value: unknown, format: UpdateFormat): Uint8Array {
+  const bytes = toBytes(value);
+  if (format === UpdateFormat.Zstd) {
+    if (!zstdAvailable) {
+      throw new Error("zstd-compressed update found but zstd is unavailable");
+    }
+    return new Uint8Array(zstd.zstdDecompressSync!(bytes));
+  }
+  return bytes;
+}
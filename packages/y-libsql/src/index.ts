@@ -1,10 +1,183 @@
 import * as Y from "yjs";
 import { Client, createClient } from "@libsql/client";
+import {
+  UpdateFormat,
+  decodeUpdate,
+  encodeUpdate,
+  zstdAvailable,
+  type UpdateCompression,
+} from "./codec";
 
 export interface LibSQLPersistenceOptions {
   url?: string;
   authToken?: string;
   client?: Client;
+  /** Compression for stored updates (default "zstd" when the runtime supports it) */
+  compression?: UpdateCompression;
+}
+
+export interface CompactionThresholds {
+  /** Compact once a document has at least this many stored updates (default 50) */
+  minUpdates?: number;
+  /** ...or once its stored updates take at least this many bytes (default 256 KiB) */
+  minBytes?: number;
+  /** Leave documents alone while they were edited this recently (default 5 minutes) */
+  quietPeriodMs?: number;
+}
+
+export interface DocumentStats {
+  updateCount: number;
+  totalBytes: number;
+  lastUpdateAt: Date | null;
+}
+
+export interface CompactionStats {
+  documentsChecked: number;
+  documentsCompacted: number;
+  updatesRemoved: number;
+  bytesBefore: number;
+  bytesAfter: number;
+}
+
+const DEFAULT_THRESHOLDS: Required<CompactionThresholds> = {
+  minUpdates: 50,
+  minBytes: 256 * 1024,
+  quietPeriodMs: 5 * 60 * 1000,
+};
+
+function needsCompaction(
+  stats: DocumentStats,
+  thresholds: CompactionThresholds,
+  now: number,
+): boolean {
+  const { minUpdates, minBytes, quietPeriodMs } = {
+    ...DEFAULT_THRESHOLDS,
+    ...thresholds,
+  };
+  // A single row is already as compact as it gets
+  if (stats.updateCount < 2) return false;
+  if (
+    stats.lastUpdateAt &&
+    now - stats.lastUpdateAt.getTime() < quietPeriodMs
+  ) {
+    return false;
+  }
+  return stats.updateCount >= minUpdates || stats.totalBytes >= minBytes;
+}
+
+function rowToStats(row: Record<string, unknown> | undefined): DocumentStats {
+  const last = row?.last_update_at;
+  return {
+    updateCount: Number(row?.update_count ?? 0),
+    totalBytes: Number(row?.total_bytes ?? 0),
+    lastUpdateAt: last == null ? null : new Date(Number(last) * 1000),
+  };
+}
+
+const STATS_COLUMNS = `COUNT(*) AS update_count,
+  COALESCE(SUM(LENGTH(update_data)), 0) AS total_bytes,
+  MAX(created_at) AS last_update_at`;
+
+/**
+ * Compact every document in the database whose stored updates cross the
+ * thresholds, without loading them into a provider. Rows written while a
+ * document is being compacted are kept.
+ */
+export async function compactAllDocuments(
+  client: Client,
+  thresholds: CompactionThresholds = {},
+  compression: UpdateCompression = "zstd",
+): Promise<CompactionStats> {
+  const stats: CompactionStats = {
+    documentsChecked: 0,
+    documentsCompacted: 0,
+    updatesRemoved: 0,
+    bytesBefore: 0,
+    bytesAfter: 0,
+  };
+  const now = Date.now();
+  const docs = await client.execute(
+    `SELECT doc_name, ${STATS_COLUMNS} FROM yjs_updates GROUP BY doc_name`,
+  );
+
+  for (const row of docs.rows) {
+    stats.documentsChecked++;
+    const docStats = rowToStats(row);
+    if (!needsCompaction(docStats, thresholds, now)) continue;
+
+    const docName = row.doc_name as string;
+    const updates = await client.execute({
+      sql: "SELECT id, update_data, format FROM yjs_updates WHERE doc_name = ? ORDER BY id",
+      args: [docName],
+    });
+    if (updates.rows.length < 2) continue;
+
+    const doc = new Y.Doc();
+    let maxId = 0;
+    let bytesBefore = 0;
+    for (const update of updates.rows) {
+      const format = Number(update.format) as UpdateFormat;
+      Y.applyUpdate(doc, decodeUpdate(update.update_data, format));
+      maxId = Math.max(maxId, Number(update.id));
+      bytesBefore += toByteLength(update.update_data);
+    }
+    const { data, format } = encodeUpdate(
+      Y.encodeStateAsUpdate(doc),
+      compression,
+    );
+    doc.destroy();
+
+    await client.batch(
+      [
+        {
+          sql: "DELETE FROM yjs_updates WHERE doc_name = ? AND id <= ?",
+          args: [docName, maxId],
+        },
+        {
+          sql: "INSERT INTO yjs_updates (doc_name, update_data, format) VALUES (?, ?, ?)",
+          args: [docName, data, format],
+        },
+      ],
+      "write",
+    );
+
+    stats.documentsCompacted++;
+    stats.updatesRemoved += updates.rows.length - 1;
+    stats.bytesBefore += bytesBefore;
+    stats.bytesAfter += data.byteLength;
+  }
+
+  return stats;
+}
+
+/**
+ * Everything stored for a document that a client with the given state
+ * vector is missing, as a single update. Works on the stored rows directly,
+ * so a backend can answer sync requests without keeping documents loaded.
+ * Pass no state vector to get the whole document.
+ */
+export async function getMissingUpdate(
+  client: Client,
+  docName: string,
+  stateVector?: Uint8Array,
+): Promise<Uint8Array> {
+  const result = await client.execute({
+    sql: "SELECT update_data, format FROM yjs_updates WHERE doc_name = ? ORDER BY id",
+    args: [docName],
+  });
+  const merged = Y.mergeUpdates(
+    result.rows.map((row) =>
+      decodeUpdate(row.update_data, Number(row.format) as UpdateFormat),
+    ),
+  );
+  return stateVector ? Y.diffUpdate(merged, stateVector) : merged;
+}
+
+function toByteLength(value: unknown): number {
+  if (value instanceof ArrayBuffer || value instanceof Uint8Array) {
+    return value.byteLength;
+  }
+  return typeof value === "string" ? value.length : 0;
 }
 
 export class LibSQLPersistence {
@@ -17,6 +190,7 @@ export class LibSQLPersistence {
   private _resolveSynced!: () => void;
   private _storeUpdateHandler: (update: Uint8Array, origin: any) => void;
   private meta: Map<string, any> = new Map();
+  private compression: UpdateCompression;
 
   constructor(
     docName: string,
@@ -25,6 +199,7 @@ export class LibSQLPersistence {
   ) {
     this.doc = ydoc;
     this.docName = docName;
+    this.compression = options.compression ?? "zstd";
 
     // Initialize client
     if (options.client) {
@@ -80,10 +255,22 @@ export class LibSQLPersistence {
         id INTEGER PRIMARY KEY AUTOINCREMENT,
         doc_name TEXT NOT NULL,
         update_data BLOB NOT NULL,
-        created_at INTEGER DEFAULT (unixepoch()),
-        INDEX idx_doc_name (doc_name)
+        format INTEGER NOT NULL DEFAULT ${UpdateFormat.Base64},
+        created_at INTEGER DEFAULT (unixepoch())
       )
     `);
+    await this.client.execute(
+      "CREATE INDEX IF NOT EXISTS idx_doc_name ON yjs_updates (doc_name)",
+    );
+
+    // Tables created before compression support lack the format column;
+    // their rows are base64 text and get rewritten on the next load
+    const columns = await this.client.execute("PRAGMA table_info(yjs_updates)");
+    if (!columns.rows.some((row) => row.name === "format")) {
+      await this.client.execute(
+        `ALTER TABLE yjs_updates ADD COLUMN format INTEGER NOT NULL DEFAULT ${UpdateFormat.Base64}`,
+      );
+    }
 
     // Create metadata table
     await this.client.execute(`
@@ -99,34 +286,33 @@ export class LibSQLPersistence {
   private async _loadUpdates() {
     // Fetch all updates for this document
     const result = await this.client.execute({
-      sql: "SELECT update_data FROM yjs_updates WHERE doc_name = ? ORDER BY id",
+      sql: "SELECT update_data, format FROM yjs_updates WHERE doc_name = ? ORDER BY id",
       args: [this.docName],
     });
 
+    let hasLegacyRows = false;
     if (result.rows.length > 0) {
       // Apply updates to the document
       Y.transact(
         this.doc,
         () => {
           for (const row of result.rows) {
-            const updateData = row.update_data;
-            if (updateData instanceof ArrayBuffer) {
-              Y.applyUpdate(this.doc, new Uint8Array(updateData), this);
-            } else if (typeof updateData === "string") {
-              // Handle base64 encoded data
-              const binaryString = atob(updateData);
-              const bytes = new Uint8Array(binaryString.length);
-              for (let i = 0; i < binaryString.length; i++) {
-                bytes[i] = binaryString.charCodeAt(i);
-              }
-              Y.applyUpdate(this.doc, bytes, this);
+            const format = Number(row.format) as UpdateFormat;
+            if (format === UpdateFormat.Base64) {
+              hasLegacyRows = true;
             }
+            Y.applyUpdate(this.doc, decodeUpdate(row.update_data, format), this);
           }
         },
         this,
       );
     }
 
+    // Lazily migrate base64 rows: fold them into one compressed update
+    if (hasLegacyRows && this.compression === "zstd" && zstdAvailable) {
+      await this.compactUpdates();
+    }
+
     // Load metadata
     const metaResult = await this.client.execute({
       sql: "SELECT key, value FROM yjs_metadata WHERE doc_name = ?",
@@ -151,12 +337,11 @@ export class LibSQLPersistence {
     }
 
     try {
-      // Convert Uint8Array to base64 for storage
-      const base64Update = btoa(String.fromCharCode(...update));
+      const { data, format } = encodeUpdate(update, this.compression);
 
       await this.client.execute({
-        sql: "INSERT INTO yjs_updates (doc_name, update_data) VALUES (?, ?)",
-        args: [this.docName, base64Update],
+        sql: "INSERT INTO yjs_updates (doc_name, update_data, format) VALUES (?, ?, ?)",
+        args: [this.docName, data, format],
       });
     } catch (error) {
       console.error("Failed to store update:", error);
@@ -206,22 +391,61 @@ export class LibSQLPersistence {
     this.meta.clear();
   }
 
-  async compactUpdates(): Promise<void> {
-    // Get the current state as a single update
-    const stateUpdate = Y.encodeStateAsUpdate(this.doc);
+  /**
+   * The part of this document a client with the given state vector is
+   * missing; send the client's Y.encodeStateVector(doc) and apply the
+   * result instead of replaying every stored update
+   */
+  async getMissingUpdate(stateVector?: Uint8Array): Promise<Uint8Array> {
+    await this.whenSynced;
+    return Y.encodeStateAsUpdate(this.doc, stateVector);
+  }
 
-    // Clear old updates
-    await this.client.execute({
-      sql: "DELETE FROM yjs_updates WHERE doc_name = ?",
+  /**
+   * Number and size of the update rows stored for this document
+   */
+  async getStats(): Promise<DocumentStats> {
+    const result = await this.client.execute({
+      sql: `SELECT ${STATS_COLUMNS} FROM yjs_updates WHERE doc_name = ?`,
       args: [this.docName],
     });
+    return rowToStats(result.rows[0]);
+  }
 
-    // Store the compacted update
-    const base64Update = btoa(String.fromCharCode(...stateUpdate));
-    await this.client.execute({
-      sql: "INSERT INTO yjs_updates (doc_name, update_data) VALUES (?, ?)",
-      args: [this.docName, base64Update],
-    });
+  /**
+   * Compact only when the stored updates cross the thresholds and the
+   * document hasn't been edited recently. Returns whether it compacted.
+   */
+  async compactIfNeeded(
+    thresholds: CompactionThresholds = {},
+  ): Promise<boolean> {
+    await this.whenSynced;
+    if (!needsCompaction(await this.getStats(), thresholds, Date.now())) {
+      return false;
+    }
+    await this.compactUpdates();
+    return true;
+  }
+
+  async compactUpdates(): Promise<void> {
+    // Get the current state as a single update
+    const stateUpdate = Y.encodeStateAsUpdate(this.doc);
+    const { data, format } = encodeUpdate(stateUpdate, this.compression);
+
+    // Replace old updates with the compacted one atomically
+    await this.client.batch(
+      [
+        {
+          sql: "DELETE FROM yjs_updates WHERE doc_name = ?",
+          args: [this.docName],
+        },
+        {
+          sql: "INSERT INTO yjs_updates (doc_name, update_data, format) VALUES (?, ?, ?)",
+          args: [this.docName, data, format],
+        },
+      ],
+      "write",
+    );
   }
 
   destroy(): void {
@@ -238,3 +462,4 @@ export class LibSQLPersistence {
 
 // Export for convenience
 export { Y };
+export { UpdateFormat, type UpdateCompression } from "./codec";